@@ -0,0 +1,129 @@
+//! Lenient parsing, which recovers from invalid content instead of failing.
+//!
+//! Office applications open documents with out-of-range or unknown values without complaining, so a strict parser
+//! rejects plenty of documents found in the wild. Parsers called with a lenient `ParseContext` skip invalid attribute
+//! values and elements they can't parse instead of failing, and report each of them as a `Diagnostic`. Skipped
+//! attributes fall back to their schema default. Both skipped attributes and elements end up in the `UnknownContent`
+//! of the model they belong to, so they're still written back unchanged.
+//!
+//! An error is only returned if the root element itself can't be parsed, or the document is not well-formed.
+//!
+//! # Example
+//!
+//! ```
+//! use msoffice_shared::{diagnostics::Severity, drawingml::colors::Color, xml::XmlNode, xsdtypes::XsdType};
+//! use std::str::FromStr;
+//!
+//! let xml = r#"<a:srgbClr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" val="FF0000">
+//!     <a:lumMod val="lots"/><a:alpha val="50000"/>
+//! </a:srgbClr>"#;
+//! let xml_node = XmlNode::from_str(xml).unwrap();
+//! assert!(Color::from_xml_element(&xml_node).is_err());
+//!
+//! let parsed = Color::from_xml_element_lenient(&xml_node).unwrap();
+//! assert_eq!(parsed.diagnostics.len(), 1);
+//! assert_eq!(parsed.diagnostics[0].severity, Severity::Error);
+//! match parsed.value {
+//!     Color::SRgbColor(color) => assert_eq!(color.color_transforms.len(), 1),
+//!     _ => unreachable!(),
+//! }
+//! ```
+
+use crate::{
+    error::{Error, Result},
+    xml::location::{ElementPath, SourceLocation},
+    xsdtypes::ParseContext,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// Severity of a `Diagnostic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// An invalid attribute value was skipped. The attribute falls back to its default value.
+    Warning,
+    /// An element was skipped, along with everything it contains.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A problem found and recovered from during lenient parsing.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The error a strict parser would have returned
+    pub error: Error,
+}
+
+impl Diagnostic {
+    /// Location of the invalid content, if it's known
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.error.location()
+    }
+
+    /// Path of the invalid content, if it's known
+    pub fn path(&self) -> Option<&ElementPath> {
+        self.error.path()
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.error)
+    }
+}
+
+/// A value parsed in lenient mode, along with the problems found in it.
+#[derive(Debug)]
+pub struct Parsed<T> {
+    pub value: T,
+    /// Recovered problems in document order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<T> Parsed<T> {
+    /// Returns true if the value was parsed without any problems
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+/// Recovers from an invalid attribute value in lenient mode by reporting it as a warning and returning None, so the
+/// attribute is treated as if it was missing.
+pub(crate) fn recover_attribute<T>(result: Result<T>, context: &mut ParseContext) -> Result<Option<T>> {
+    recover(result, Severity::Warning, context)
+}
+
+/// Recovers from a failed parse of an element in lenient mode by reporting it as an error and returning None, so the
+/// element is skipped.
+pub(crate) fn recover_element<T>(result: Result<T>, context: &mut ParseContext) -> Result<Option<T>> {
+    recover(result, Severity::Error, context)
+}
+
+/// Errors of documents that are not well-formed and I/O errors are never recovered from.
+fn recover<T>(result: Result<T>, severity: Severity, context: &mut ParseContext) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err @ Error::Xml(_)) | Err(err @ Error::Io(_)) | Err(err @ Error::Zip(_)) => Err(err),
+        Err(error) => match context.diagnostics.as_mut() {
+            Some(diagnostics) => {
+                diagnostics.push(Diagnostic { severity, error });
+                Ok(None)
+            }
+            None => Err(error),
+        },
+    }
+}
@@ -1,16 +1,113 @@
-use crate::xml::XmlNode;
+use crate::diagnostics::recover_element;
+use crate::error::{
+    InvalidAttributeValueError, InvalidTextValueError, MissingAttributeError, MissingChildNodeError, ParseDateTimeError,
+};
+use crate::xml::{format_xml_bool, ns, parse_xml_bool, zip_file_to_xsd_type, XmlNode};
+use crate::xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdType};
 use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
     io::{Read, Seek},
     str::FromStr,
 };
+use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
 use zip::read::ZipFile;
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+pub use crate::error::Result;
+pub use time::OffsetDateTime;
+
+/// A heading of the document parts listed in `AppInfo::titles_of_parts`, e.g. the "Slide Titles" heading of the
+/// slides of a presentation. The parts of each heading follow the parts of the previous heading.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeadingPair {
+    pub name: String,
+    /// Number of parts under this heading
+    pub count: i32,
+}
 
+/// The extended properties of a package, stored in `docProps/app.xml`.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct AppInfo {
+    pub template: Option<String>,
+    pub manager: Option<String>,
+    pub company: Option<String>,
+    pub pages: Option<i32>,
+    pub words: Option<i32>,
+    pub characters: Option<i32>,
+    pub characters_with_spaces: Option<i32>,
+    pub lines: Option<i32>,
+    pub paragraphs: Option<i32>,
+    pub slides: Option<i32>,
+    pub notes: Option<i32>,
+    pub hidden_slides: Option<i32>,
+    pub mm_clips: Option<i32>,
+    /// Total editing time in minutes
+    pub total_time: Option<i32>,
+    pub presentation_format: Option<String>,
+    pub scale_crop: Option<bool>,
+    pub links_up_to_date: Option<bool>,
+    pub shared_doc: Option<bool>,
+    pub hyperlink_base: Option<String>,
+    pub hyperlinks_changed: Option<bool>,
+    pub heading_pairs: Vec<HeadingPair>,
+    pub titles_of_parts: Vec<String>,
     pub app_name: Option<String>,
     pub app_version: Option<String>,
+    pub doc_security: Option<i32>,
+}
+
+impl XsdType for AppInfo {
+    /// Parses the `Properties` root element of an extended properties part. In lenient mode invalid numbers, bools
+    /// and vectors are reported and skipped.
+    fn from_xml_element_with_context(root: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        root.child_nodes
+            .iter()
+            .try_fold(Default::default(), |mut instance: Self, child_node| {
+                let text = || child_node.text.as_ref().cloned();
+                let int = |context: &mut ParseContext| {
+                    recover_element(parse_trimmed_text::<i32>(child_node), context).map(Option::flatten)
+                };
+                let bool = |context: &mut ParseContext| {
+                    recover_element(parse_bool_text(child_node), context).map(Option::flatten)
+                };
+                match child_node.local_name_in(ns::EXTENDED_PROPERTIES).unwrap_or_default() {
+                    "Template" => instance.template = text(),
+                    "Manager" => instance.manager = text(),
+                    "Company" => instance.company = text(),
+                    "Pages" => instance.pages = int(context)?,
+                    "Words" => instance.words = int(context)?,
+                    "Characters" => instance.characters = int(context)?,
+                    "CharactersWithSpaces" => instance.characters_with_spaces = int(context)?,
+                    "Lines" => instance.lines = int(context)?,
+                    "Paragraphs" => instance.paragraphs = int(context)?,
+                    "Slides" => instance.slides = int(context)?,
+                    "Notes" => instance.notes = int(context)?,
+                    "HiddenSlides" => instance.hidden_slides = int(context)?,
+                    "MMClips" => instance.mm_clips = int(context)?,
+                    "TotalTime" => instance.total_time = int(context)?,
+                    "PresentationFormat" => instance.presentation_format = text(),
+                    "ScaleCrop" => instance.scale_crop = bool(context)?,
+                    "LinksUpToDate" => instance.links_up_to_date = bool(context)?,
+                    "SharedDoc" => instance.shared_doc = bool(context)?,
+                    "HyperlinkBase" => instance.hyperlink_base = text(),
+                    "HyperlinksChanged" => instance.hyperlinks_changed = bool(context)?,
+                    "HeadingPairs" => {
+                        instance.heading_pairs =
+                            recover_element(parse_heading_pairs(child_node), context)?.unwrap_or_default()
+                    }
+                    "TitlesOfParts" => {
+                        instance.titles_of_parts =
+                            recover_element(parse_titles_of_parts(child_node), context)?.unwrap_or_default()
+                    }
+                    "Application" => instance.app_name = text(),
+                    "AppVersion" => instance.app_version = text(),
+                    "DocSecurity" => instance.doc_security = int(context)?,
+                    _ => (),
+                }
+
+                Ok(instance)
+            })
+    }
 }
 
 impl AppInfo {
@@ -23,32 +120,155 @@ impl AppInfo {
     }
 
     pub fn from_zip_file(zip_file: &mut ZipFile) -> Result<Self> {
-        let mut xml_string = String::new();
-        zip_file.read_to_string(&mut xml_string)?;
-        let root = XmlNode::from_str(&xml_string)?;
+        zip_file_to_xsd_type(zip_file, &mut ParseContext::new())
+    }
+}
 
-        Ok(root
-            .child_nodes
-            .iter()
-            .fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "Application" => instance.app_name = child_node.text.as_ref().cloned(),
-                    "AppVersion" => instance.app_version = child_node.text.as_ref().cloned(),
-                    _ => (),
-                }
+impl ToXmlElement for AppInfo {
+    /// Converts the properties to the root element of an extended properties part (`docProps/app.xml`), which is
+    /// named `Properties`
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace(node_name, ns::EXTENDED_PROPERTIES);
+        node.attributes
+            .insert(String::from("xmlns:vt"), String::from(ns::DOC_PROPS_VTYPES));
+
+        let int = |value: Option<i32>| value.map(|value| value.to_string());
+        let bool = |value: Option<bool>| value.map(|value| String::from(format_xml_bool(value)));
+        let text_elements = vec![
+            ("Template", self.template.clone()),
+            ("Manager", self.manager.clone()),
+            ("Company", self.company.clone()),
+            ("TotalTime", int(self.total_time)),
+            ("Pages", int(self.pages)),
+            ("Words", int(self.words)),
+            ("Characters", int(self.characters)),
+            ("CharactersWithSpaces", int(self.characters_with_spaces)),
+            ("Lines", int(self.lines)),
+            ("Application", self.app_name.clone()),
+            ("DocSecurity", int(self.doc_security)),
+            ("PresentationFormat", self.presentation_format.clone()),
+            ("Paragraphs", int(self.paragraphs)),
+            ("Slides", int(self.slides)),
+            ("Notes", int(self.notes)),
+            ("HiddenSlides", int(self.hidden_slides)),
+            ("MMClips", int(self.mm_clips)),
+            ("ScaleCrop", bool(self.scale_crop)),
+        ];
+        node.child_nodes.extend(
+            text_elements
+                .into_iter()
+                .filter_map(|(name, text)| text.map(|text| text_element(name, ns::EXTENDED_PROPERTIES, &text))),
+        );
 
-                instance
-            }))
+        if !self.heading_pairs.is_empty() {
+            let variants = self.heading_pairs.iter().flat_map(|heading_pair| {
+                vec![
+                    variant_element(text_element("vt:lpstr", ns::DOC_PROPS_VTYPES, &heading_pair.name)),
+                    variant_element(text_element(
+                        "vt:i4",
+                        ns::DOC_PROPS_VTYPES,
+                        &heading_pair.count.to_string(),
+                    )),
+                ]
+            });
+            let mut heading_pairs_node = XmlNode::new_with_namespace("HeadingPairs", ns::EXTENDED_PROPERTIES);
+            heading_pairs_node
+                .child_nodes
+                .push(vector_element("variant", variants.collect()));
+            node.child_nodes.push(heading_pairs_node);
+        }
+
+        if !self.titles_of_parts.is_empty() {
+            let titles = self
+                .titles_of_parts
+                .iter()
+                .map(|title| text_element("vt:lpstr", ns::DOC_PROPS_VTYPES, title));
+            let mut titles_of_parts_node = XmlNode::new_with_namespace("TitlesOfParts", ns::EXTENDED_PROPERTIES);
+            titles_of_parts_node
+                .child_nodes
+                .push(vector_element("lpstr", titles.collect()));
+            node.child_nodes.push(titles_of_parts_node);
+        }
+
+        let text_elements = vec![
+            ("LinksUpToDate", bool(self.links_up_to_date)),
+            ("SharedDoc", bool(self.shared_doc)),
+            ("HyperlinkBase", self.hyperlink_base.clone()),
+            ("HyperlinksChanged", bool(self.hyperlinks_changed)),
+            ("AppVersion", self.app_version.clone()),
+        ];
+        node.child_nodes.extend(
+            text_elements
+                .into_iter()
+                .filter_map(|(name, text)| text.map(|text| text_element(name, ns::EXTENDED_PROPERTIES, &text))),
+        );
+
+        node
     }
 }
+
+/// The core properties of a package, stored in `docProps/core.xml`.
+///
+/// `created` and `modified` are W3CDTF dates, while `last_printed` is an `xsd:dateTime`, which is a W3CDTF date-time
+/// with seconds. All of them are parsed as `W3cdtfDateTime`s, so they're written back in the form they were read.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Core {
     pub title: Option<String>,
+    pub subject: Option<String>,
     pub creator: Option<String>,
+    pub keywords: Option<String>,
+    pub description: Option<String>,
     pub last_modified_by: Option<String>,
     pub revision: Option<i32>,
-    pub created_time: Option<String>,  // TODO: maybe store as some DateTime struct?
-    pub modified_time: Option<String>, // TODO: maybe store as some DateTime struct?
+    pub last_printed: Option<W3cdtfDateTime>,
+    pub created_time: Option<W3cdtfDateTime>,
+    pub modified_time: Option<W3cdtfDateTime>,
+    pub category: Option<String>,
+    pub content_status: Option<String>,
+    pub language: Option<String>,
+    pub identifier: Option<String>,
+    pub version: Option<String>,
+}
+
+impl XsdType for Core {
+    /// Parses the `cp:coreProperties` root element of a core properties part. In lenient mode an invalid revision or
+    /// date is reported and skipped.
+    fn from_xml_element_with_context(root: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        root.child_nodes
+            .iter()
+            .try_fold(Default::default(), |mut instance: Self, child_node| {
+                let text = || child_node.text.as_ref().cloned();
+                let is_in = |namespace| child_node.is_in_namespace(namespace);
+                match child_node.local_name() {
+                    "title" if is_in(ns::DUBLIN_CORE) => instance.title = text(),
+                    "subject" if is_in(ns::DUBLIN_CORE) => instance.subject = text(),
+                    "creator" if is_in(ns::DUBLIN_CORE) => instance.creator = text(),
+                    "keywords" if is_in(ns::CORE_PROPERTIES) => instance.keywords = text(),
+                    "description" if is_in(ns::DUBLIN_CORE) => instance.description = text(),
+                    "lastModifiedBy" if is_in(ns::CORE_PROPERTIES) => instance.last_modified_by = text(),
+                    "revision" if is_in(ns::CORE_PROPERTIES) => {
+                        instance.revision = recover_element(parse_trimmed_text(child_node), context)?.flatten()
+                    }
+                    "lastPrinted" if is_in(ns::CORE_PROPERTIES) => {
+                        instance.last_printed = recover_element(parse_date_time(child_node), context)?.flatten()
+                    }
+                    "created" if is_in(ns::DUBLIN_CORE_TERMS) => {
+                        instance.created_time = recover_element(parse_date_time(child_node), context)?.flatten()
+                    }
+                    "modified" if is_in(ns::DUBLIN_CORE_TERMS) => {
+                        instance.modified_time = recover_element(parse_date_time(child_node), context)?.flatten()
+                    }
+                    "category" if is_in(ns::CORE_PROPERTIES) => instance.category = text(),
+                    "contentStatus" if is_in(ns::CORE_PROPERTIES) => instance.content_status = text(),
+                    "language" if is_in(ns::DUBLIN_CORE) => instance.language = text(),
+                    "identifier" if is_in(ns::DUBLIN_CORE) => instance.identifier = text(),
+                    "version" if is_in(ns::CORE_PROPERTIES) => instance.version = text(),
+                    _ => (),
+                }
+
+                Ok(instance)
+            })
+    }
 }
 
 impl Core {
@@ -61,24 +281,1068 @@ impl Core {
     }
 
     pub fn from_zip_file(zip_file: &mut ZipFile) -> Result<Self> {
-        let mut xml_string = String::new();
-        zip_file.read_to_string(&mut xml_string)?;
-        let root = XmlNode::from_str(&xml_string)?;
+        zip_file_to_xsd_type(zip_file, &mut ParseContext::new())
+    }
+}
 
-        root.child_nodes
+impl ToXmlElement for Core {
+    /// Converts the properties to the root element of a core properties part (`docProps/core.xml`), which is named
+    /// `cp:coreProperties`
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace(node_name, ns::CORE_PROPERTIES);
+        for (prefix, namespace) in &[
+            ("cp", ns::CORE_PROPERTIES),
+            ("dc", ns::DUBLIN_CORE),
+            ("dcterms", ns::DUBLIN_CORE_TERMS),
+            ("dcmitype", ns::DUBLIN_CORE_TYPE),
+            ("xsi", ns::XSI),
+        ] {
+            node.attributes
+                .insert(format!("xmlns:{}", prefix), String::from(*namespace));
+        }
+
+        let text_elements = vec![
+            ("dc:title", ns::DUBLIN_CORE, self.title.clone()),
+            ("dc:subject", ns::DUBLIN_CORE, self.subject.clone()),
+            ("dc:creator", ns::DUBLIN_CORE, self.creator.clone()),
+            ("cp:keywords", ns::CORE_PROPERTIES, self.keywords.clone()),
+            ("dc:description", ns::DUBLIN_CORE, self.description.clone()),
+            ("cp:lastModifiedBy", ns::CORE_PROPERTIES, self.last_modified_by.clone()),
+            (
+                "cp:revision",
+                ns::CORE_PROPERTIES,
+                self.revision.map(|revision| revision.to_string()),
+            ),
+            (
+                "cp:lastPrinted",
+                ns::CORE_PROPERTIES,
+                self.last_printed.as_ref().map(ToString::to_string),
+            ),
+        ];
+        node.child_nodes.extend(
+            text_elements
+                .into_iter()
+                .filter_map(|(name, namespace, text)| text.map(|text| text_element(name, namespace, &text))),
+        );
+
+        node.child_nodes.extend(
+            self.created_time
+                .as_ref()
+                .map(|created_time| w3cdtf_element("dcterms:created", created_time)),
+        );
+        node.child_nodes.extend(
+            self.modified_time
+                .as_ref()
+                .map(|modified_time| w3cdtf_element("dcterms:modified", modified_time)),
+        );
+
+        let text_elements = vec![
+            ("cp:category", ns::CORE_PROPERTIES, &self.category),
+            ("cp:contentStatus", ns::CORE_PROPERTIES, &self.content_status),
+            ("dc:language", ns::DUBLIN_CORE, &self.language),
+            ("dc:identifier", ns::DUBLIN_CORE, &self.identifier),
+            ("cp:version", ns::CORE_PROPERTIES, &self.version),
+        ];
+        node.child_nodes.extend(
+            text_elements
+                .into_iter()
+                .filter_map(|(name, namespace, text)| text.as_ref().map(|text| text_element(name, namespace, text))),
+        );
+
+        node
+    }
+}
+
+/// The format identifier of user-defined custom properties, the only one Office uses
+pub const CUSTOM_PROPERTY_FMTID: &str = "{D5CDD505-2E9C-101B-9397-08002B2CF9AE}";
+
+/// The smallest property identifier of a custom property. Identifiers 0 and 1 are reserved.
+pub const MIN_CUSTOM_PROPERTY_PID: i32 = 2;
+
+/// The typed value of a custom property, stored as one of the variant types of `docPropsVTypes`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomPropertyValue {
+    /// `vt:lpwstr`, the type Office uses for text
+    LpwStr(String),
+    LpStr(String),
+    BStr(String),
+    I1(i8),
+    I2(i16),
+    /// `vt:i4`, the type Office uses for numbers without a fraction
+    I4(i32),
+    I8(i64),
+    Int(i32),
+    UI1(u8),
+    UI2(u16),
+    UI4(u32),
+    UI8(u64),
+    UInt(u32),
+    R4(f32),
+    /// `vt:r8`, the type Office uses for numbers with a fraction
+    R8(f64),
+    Bool(bool),
+    /// `vt:filetime`, the type Office uses for dates
+    FileTime(OffsetDateTime),
+    /// A value of a variant type that's not modelled by this crate (e.g. a vector or a blob), kept as it's read
+    Other(Box<XmlNode>),
+}
+
+impl XsdType for CustomPropertyValue {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let text = || xml_node.text.clone().unwrap_or_default();
+        let invalid =
+            |err: Box<dyn ::std::error::Error + Send + Sync>| InvalidTextValueError::new(xml_node, text(), err);
+
+        macro_rules! number {
+            ($variant:ident) => {
+                CustomPropertyValue::$variant(text().trim().parse().map_err(|err| invalid(Box::new(err)))?)
+            };
+        }
+
+        let value = match xml_node.local_name_in(ns::DOC_PROPS_VTYPES).unwrap_or_default() {
+            "lpwstr" => CustomPropertyValue::LpwStr(text()),
+            "lpstr" => CustomPropertyValue::LpStr(text()),
+            "bstr" => CustomPropertyValue::BStr(text()),
+            "i1" => number!(I1),
+            "i2" => number!(I2),
+            "i4" => number!(I4),
+            "i8" => number!(I8),
+            "int" => number!(Int),
+            "ui1" => number!(UI1),
+            "ui2" => number!(UI2),
+            "ui4" => number!(UI4),
+            "ui8" => number!(UI8),
+            "uint" => number!(UInt),
+            "r4" => number!(R4),
+            "r8" => number!(R8),
+            "bool" => CustomPropertyValue::Bool(parse_xml_bool(text().trim()).map_err(|err| invalid(Box::new(err)))?),
+            "filetime" => CustomPropertyValue::FileTime(
+                text()
+                    .parse::<W3cdtfDateTime>()
+                    .map_err(|err| invalid(Box::new(err)))?
+                    .date_time,
+            ),
+            _ => CustomPropertyValue::Other(Box::new(xml_node.clone())),
+        };
+
+        Ok(value)
+    }
+}
+
+impl ToXmlChoice for CustomPropertyValue {
+    fn to_xml_element(&self) -> XmlNode {
+        let (name, text) = match self {
+            CustomPropertyValue::LpwStr(value) => ("vt:lpwstr", value.clone()),
+            CustomPropertyValue::LpStr(value) => ("vt:lpstr", value.clone()),
+            CustomPropertyValue::BStr(value) => ("vt:bstr", value.clone()),
+            CustomPropertyValue::I1(value) => ("vt:i1", value.to_string()),
+            CustomPropertyValue::I2(value) => ("vt:i2", value.to_string()),
+            CustomPropertyValue::I4(value) => ("vt:i4", value.to_string()),
+            CustomPropertyValue::I8(value) => ("vt:i8", value.to_string()),
+            CustomPropertyValue::Int(value) => ("vt:int", value.to_string()),
+            CustomPropertyValue::UI1(value) => ("vt:ui1", value.to_string()),
+            CustomPropertyValue::UI2(value) => ("vt:ui2", value.to_string()),
+            CustomPropertyValue::UI4(value) => ("vt:ui4", value.to_string()),
+            CustomPropertyValue::UI8(value) => ("vt:ui8", value.to_string()),
+            CustomPropertyValue::UInt(value) => ("vt:uint", value.to_string()),
+            CustomPropertyValue::R4(value) => ("vt:r4", format_xsd_float(*value)),
+            CustomPropertyValue::R8(value) => ("vt:r8", format_xsd_float(*value)),
+            CustomPropertyValue::Bool(value) => ("vt:bool", String::from(format_xml_bool(*value))),
+            CustomPropertyValue::FileTime(value) => ("vt:filetime", W3cdtfDateTime::from(*value).to_string()),
+            CustomPropertyValue::Other(xml_node) => return (**xml_node).clone(),
+        };
+
+        text_element(name, ns::DOC_PROPS_VTYPES, &text)
+    }
+}
+
+// Formats a float the way xsd:float and xsd:double spell the infinities, which Rust writes as "inf"
+fn format_xsd_float<T: Into<f64> + Display + Copy>(value: T) -> String {
+    match value.into() {
+        value if value == f64::INFINITY => String::from("INF"),
+        value if value == f64::NEG_INFINITY => String::from("-INF"),
+        _ => value.to_string(),
+    }
+}
+
+impl From<&str> for CustomPropertyValue {
+    fn from(value: &str) -> Self {
+        CustomPropertyValue::LpwStr(String::from(value))
+    }
+}
+
+impl From<String> for CustomPropertyValue {
+    fn from(value: String) -> Self {
+        CustomPropertyValue::LpwStr(value)
+    }
+}
+
+impl From<i32> for CustomPropertyValue {
+    fn from(value: i32) -> Self {
+        CustomPropertyValue::I4(value)
+    }
+}
+
+impl From<f64> for CustomPropertyValue {
+    fn from(value: f64) -> Self {
+        CustomPropertyValue::R8(value)
+    }
+}
+
+impl From<bool> for CustomPropertyValue {
+    fn from(value: bool) -> Self {
+        CustomPropertyValue::Bool(value)
+    }
+}
+
+impl From<OffsetDateTime> for CustomPropertyValue {
+    fn from(value: OffsetDateTime) -> Self {
+        CustomPropertyValue::FileTime(value)
+    }
+}
+
+/// A user-defined property of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomProperty {
+    /// Format identifier of the property, a GUID. Custom properties added by this crate use `CUSTOM_PROPERTY_FMTID`.
+    pub fmtid: String,
+    /// Property identifier, unique within the custom properties of the document
+    pub pid: i32,
+    pub name: Option<String>,
+    /// Name of a bookmark (or range) of the document the value of the property is linked to
+    pub link_target: Option<String>,
+    pub value: CustomPropertyValue,
+}
+
+impl XsdType for CustomProperty {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let fmtid = xml_node
+            .attributes
+            .get("fmtid")
+            .cloned()
+            .ok_or_else(|| MissingAttributeError::at(xml_node, "fmtid"))?;
+        let pid: i32 = xml_node.parse_required_attribute("pid")?;
+        if pid < MIN_CUSTOM_PROPERTY_PID {
+            return Err(InvalidAttributeValueError::new(
+                xml_node,
+                "pid",
+                pid.to_string(),
+                "property identifiers below 2 are reserved",
+            )
+            .into());
+        }
+
+        let name = xml_node.attributes.get("name").cloned();
+        let link_target = xml_node.attributes.get("linkTarget").cloned();
+        let value = xml_node
+            .child_nodes
+            .first()
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "lpwstr"))
+            .map_err(Into::into)
+            .and_then(|child_node| CustomPropertyValue::from_xml_element_with_context(child_node, context))?;
+
+        Ok(Self {
+            fmtid,
+            pid,
+            name,
+            link_target,
+            value,
+        })
+    }
+}
+
+impl ToXmlElement for CustomProperty {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace(node_name, ns::CUSTOM_PROPERTIES);
+        node.attributes.insert(String::from("fmtid"), self.fmtid.clone());
+        node.attributes.insert(String::from("pid"), self.pid.to_string());
+        if let Some(name) = &self.name {
+            node.attributes.insert(String::from("name"), name.clone());
+        }
+
+        if let Some(link_target) = &self.link_target {
+            node.attributes.insert(String::from("linkTarget"), link_target.clone());
+        }
+
+        node.child_nodes.push(self.value.to_xml_element());
+        node
+    }
+}
+
+/// The custom properties of a package, stored in `docProps/custom.xml`.
+///
+/// Properties are looked up by name, which is compared case-insensitively like Office does. New properties get the
+/// next free property identifier, starting at `MIN_CUSTOM_PROPERTY_PID`.
+///
+/// ```
+/// use msoffice_shared::docprops::{CustomProperties, CustomPropertyValue};
+///
+/// let mut custom_properties = CustomProperties::new();
+/// custom_properties.set("Classification", "Internal");
+/// custom_properties.set("Project code", 4711);
+/// assert_eq!(custom_properties.get("classification").unwrap().pid, 2);
+///
+/// custom_properties.set("Classification", "Confidential");
+/// assert_eq!(
+///     custom_properties.value("Classification"),
+///     Some(&CustomPropertyValue::LpwStr(String::from("Confidential")))
+/// );
+/// assert!(custom_properties.remove("Project code").is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CustomProperties {
+    properties: Vec<CustomProperty>,
+}
+
+impl XsdType for CustomProperties {
+    /// Parses the `Properties` root element of a custom properties part. In lenient mode invalid properties are
+    /// reported and skipped.
+    fn from_xml_element_with_context(root: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut properties = Vec::new();
+        for child_node in root
+            .child_nodes
             .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "title" => instance.title = child_node.text.as_ref().cloned(),
-                    "creator" => instance.creator = child_node.text.as_ref().cloned(),
-                    "lastModifiedBy" => instance.last_modified_by = child_node.text.as_ref().cloned(),
-                    "revision" => instance.revision = child_node.text.as_ref().map(|s| s.parse()).transpose()?,
-                    "created" => instance.created_time = child_node.text.as_ref().cloned(),
-                    "modified" => instance.modified_time = child_node.text.as_ref().cloned(),
-                    _ => (),
+            .filter(|child_node| child_node.is(ns::CUSTOM_PROPERTIES, "property"))
+        {
+            properties.extend(recover_element(
+                CustomProperty::from_xml_element_with_context(child_node, context),
+                context,
+            )?);
+        }
+
+        Ok(Self { properties })
+    }
+}
+
+impl CustomProperties {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_zip<R>(zipper: &mut zip::ZipArchive<R>) -> Result<Self>
+    where
+        R: Read + Seek,
+    {
+        let mut custom_xml_file = zipper.by_name("docProps/custom.xml")?;
+        Self::from_zip_file(&mut custom_xml_file)
+    }
+
+    pub fn from_zip_file(zip_file: &mut ZipFile) -> Result<Self> {
+        zip_file_to_xsd_type(zip_file, &mut ParseContext::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CustomProperty> {
+        self.properties.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CustomProperty> {
+        self.properties.iter().find(|property| has_name(property, name))
+    }
+
+    pub fn value(&self, name: &str) -> Option<&CustomPropertyValue> {
+        self.get(name).map(|property| &property.value)
+    }
+
+    /// Returns the property identifier the next added property will get: one more than the largest identifier in
+    /// use, but at least `MIN_CUSTOM_PROPERTY_PID`. If the largest identifier is `i32::MAX`, the smallest free
+    /// identifier is returned instead.
+    pub fn next_pid(&self) -> i32 {
+        let max_pid = self.properties.iter().map(|property| property.pid).max();
+        match max_pid {
+            None => MIN_CUSTOM_PROPERTY_PID,
+            Some(max_pid) => match max_pid.checked_add(1) {
+                Some(pid) => pid.max(MIN_CUSTOM_PROPERTY_PID),
+                None => {
+                    let mut pids: Vec<i32> = self.properties.iter().map(|property| property.pid).collect();
+                    pids.sort_unstable();
+                    // There can't be as many properties as identifiers, so there's always one that's free
+                    let mut first_free = MIN_CUSTOM_PROPERTY_PID;
+                    for pid in pids {
+                        if pid == first_free {
+                            first_free += 1;
+                        } else if pid > first_free {
+                            break;
+                        }
+                    }
+
+                    first_free
                 }
+            },
+        }
+    }
 
-                Ok(instance)
-            })
+    /// Sets the value of the property `name`. An existing property keeps its identifiers and link target, otherwise
+    /// a new property is added with the next free property identifier. Returns the previous value.
+    pub fn set<T, U>(&mut self, name: T, value: U) -> Option<CustomPropertyValue>
+    where
+        T: AsRef<str>,
+        U: Into<CustomPropertyValue>,
+    {
+        let name = name.as_ref();
+        let value = value.into();
+        if let Some(property) = self.properties.iter_mut().find(|property| has_name(property, name)) {
+            return Some(::std::mem::replace(&mut property.value, value));
+        }
+
+        let pid = self.next_pid();
+        self.properties.push(CustomProperty {
+            fmtid: String::from(CUSTOM_PROPERTY_FMTID),
+            pid,
+            name: Some(String::from(name)),
+            link_target: None,
+            value,
+        });
+
+        None
+    }
+
+    /// Removes the property `name`. The property identifiers of the other properties don't change.
+    pub fn remove(&mut self, name: &str) -> Option<CustomProperty> {
+        let idx = self.properties.iter().position(|property| has_name(property, name))?;
+        Some(self.properties.remove(idx))
+    }
+}
+
+impl ToXmlElement for CustomProperties {
+    /// Converts the properties to the root element of a custom properties part (`docProps/custom.xml`), which is
+    /// named `Properties`
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace(node_name, ns::CUSTOM_PROPERTIES);
+        node.attributes
+            .insert(String::from("xmlns:vt"), String::from(ns::DOC_PROPS_VTYPES));
+        node.child_nodes.extend(
+            self.properties
+                .iter()
+                .map(|property| property.to_xml_element("property")),
+        );
+        node
+    }
+}
+
+// Compares the names case-insensitively without allocating
+fn has_name(property: &CustomProperty, name: &str) -> bool {
+    property.name.as_ref().is_some_and(|property_name| {
+        property_name
+            .chars()
+            .flat_map(char::to_lowercase)
+            .eq(name.chars().flat_map(char::to_lowercase))
+    })
+}
+
+/// The components a W3CDTF date-time is written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum W3cdtfPrecision {
+    /// `YYYY`
+    Year,
+    /// `YYYY-MM`
+    Month,
+    /// `YYYY-MM-DD`
+    Day,
+    /// `YYYY-MM-DDThh:mmTZD`
+    Minute,
+    /// `YYYY-MM-DDThh:mm:ssTZD`, with fractions of a second if they're not zero. This is how Office writes dates.
+    Second,
+}
+
+/// A W3CDTF date-time, e.g. `2019-05-03T10:15:00Z`, along with the form it was written in.
+///
+/// The reduced precision forms denote the start of the period, e.g. `2019-05` is `2019-05-01T00:00:00Z`. Date-times
+/// without a time zone designator aren't valid W3CDTF, but they're accepted and taken to be in UTC. The precision and
+/// the missing time zone designator are kept, so the value is written back the way it was read. Fractions of a second
+/// are truncated to nanoseconds.
+///
+/// ```
+/// use msoffice_shared::docprops::{W3cdtfDateTime, W3cdtfPrecision};
+///
+/// let date_time: W3cdtfDateTime = "2019-05-03T12:15:30.25+02:00".parse().unwrap();
+/// assert_eq!(date_time.date_time.unix_timestamp(), 1_556_878_530);
+/// assert_eq!(date_time.to_string(), "2019-05-03T12:15:30.25+02:00");
+///
+/// let month: W3cdtfDateTime = "2019-05".parse().unwrap();
+/// assert_eq!(month.precision, W3cdtfPrecision::Month);
+/// assert_eq!(month.to_string(), "2019-05");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct W3cdtfDateTime {
+    pub date_time: OffsetDateTime,
+    pub precision: W3cdtfPrecision,
+    /// Whether the time is followed by a time zone designator. Always false for the forms without a time.
+    pub has_time_zone: bool,
+}
+
+impl From<OffsetDateTime> for W3cdtfDateTime {
+    /// Creates a complete date-time with seconds and a time zone designator
+    fn from(date_time: OffsetDateTime) -> Self {
+        Self {
+            date_time,
+            precision: W3cdtfPrecision::Second,
+            has_time_zone: true,
+        }
+    }
+}
+
+impl FromStr for W3cdtfDateTime {
+    type Err = ParseDateTimeError;
+
+    fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+        parse_w3cdtf(value.trim()).ok_or_else(|| ParseDateTimeError::new(value))
+    }
+}
+
+impl Display for W3cdtfDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let date_time = &self.date_time;
+        write!(f, "{:04}", date_time.year())?;
+        if self.precision >= W3cdtfPrecision::Month {
+            write!(f, "-{:02}", u8::from(date_time.month()))?;
+        }
+
+        if self.precision >= W3cdtfPrecision::Day {
+            write!(f, "-{:02}", date_time.day())?;
+        }
+
+        if self.precision < W3cdtfPrecision::Minute {
+            return Ok(());
+        }
+
+        write!(f, "T{:02}:{:02}", date_time.hour(), date_time.minute())?;
+        if self.precision >= W3cdtfPrecision::Second {
+            write!(f, ":{:02}", date_time.second())?;
+            if date_time.nanosecond() != 0 {
+                let fraction = format!("{:09}", date_time.nanosecond());
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+        }
+
+        let offset = date_time.offset();
+        if !self.has_time_zone {
+            Ok(())
+        } else if offset.is_utc() {
+            f.write_str("Z")
+        } else {
+            let sign = if offset.is_negative() { '-' } else { '+' };
+            write!(
+                f,
+                "{}{:02}:{:02}",
+                sign,
+                offset.whole_hours().abs(),
+                offset.minutes_past_hour().abs()
+            )
+        }
+    }
+}
+
+// Returns None if `value` is not in one of the forms of W3CDTF
+fn parse_w3cdtf(value: &str) -> Option<W3cdtfDateTime> {
+    let (date, time) = match value.find('T') {
+        Some(idx) => (&value[..idx], Some(&value[idx + 1..])),
+        None => (value, None),
+    };
+
+    let mut date_components = date.split('-');
+    let year = parse_digits(date_components.next()?, 4)?;
+    let month = match date_components.next() {
+        Some(month) => Some(parse_digits(month, 2)?),
+        None => None,
+    };
+    let day = match date_components.next() {
+        Some(day) => Some(parse_digits(day, 2)?),
+        None => None,
+    };
+    if date_components.next().is_some() {
+        return None;
+    }
+
+    let (precision, time, offset) = match time {
+        None => {
+            let precision = match (month, day) {
+                (None, _) => W3cdtfPrecision::Year,
+                (Some(_), None) => W3cdtfPrecision::Month,
+                (Some(_), Some(_)) => W3cdtfPrecision::Day,
+            };
+            (precision, Time::MIDNIGHT, None)
+        }
+        Some(time) => {
+            // A time can only follow a complete date
+            day?;
+            let (time, offset) = split_time_zone(time)?;
+            let mut time_components = time.split(':');
+            let hour = parse_digits(time_components.next()?, 2)?;
+            let minute = parse_digits(time_components.next()?, 2)?;
+            let (precision, second, nanosecond) = match time_components.next() {
+                Some(second) => {
+                    let (second, nanosecond) = match second.find('.') {
+                        Some(idx) => (&second[..idx], parse_fraction(&second[idx + 1..])?),
+                        None => (second, 0),
+                    };
+                    (W3cdtfPrecision::Second, parse_digits(second, 2)?, nanosecond)
+                }
+                None => (W3cdtfPrecision::Minute, 0, 0),
+            };
+            if time_components.next().is_some() {
+                return None;
+            }
+
+            let time = Time::from_hms_nano(hour as u8, minute as u8, second as u8, nanosecond).ok()?;
+            (precision, time, offset)
+        }
+    };
+
+    let month = Month::try_from(month.unwrap_or(1) as u8).ok()?;
+    let date = Date::from_calendar_date(year as i32, month, day.unwrap_or(1) as u8).ok()?;
+    Some(W3cdtfDateTime {
+        date_time: PrimitiveDateTime::new(date, time).assume_offset(offset.unwrap_or(UtcOffset::UTC)),
+        precision,
+        has_time_zone: offset.is_some(),
+    })
+}
+
+// Splits the time zone designator (`Z`, `+hh:mm` or `-hh:mm`) off the end of `time` if it has one
+fn split_time_zone(time: &str) -> Option<(&str, Option<UtcOffset>)> {
+    if let Some(time) = time.strip_suffix('Z') {
+        return Some((time, Some(UtcOffset::UTC)));
+    }
+
+    match time.rfind(['+', '-']) {
+        Some(idx) => {
+            let (hours, minutes) = time[idx + 1..].split_once(':')?;
+            let sign = if time[idx..].starts_with('-') { -1 } else { 1 };
+            let hours = sign * parse_digits(hours, 2)? as i8;
+            let minutes = sign * parse_digits(minutes, 2)? as i8;
+            let offset = UtcOffset::from_hms(hours, minutes, 0).ok()?;
+            Some((&time[..idx], Some(offset)))
+        }
+        None => Some((time, None)),
+    }
+}
+
+// Parses a number of exactly `len` digits
+fn parse_digits(value: &str, len: usize) -> Option<u32> {
+    if value.len() == len && value.bytes().all(|byte| byte.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+// Parses the digits of a fraction of a second as nanoseconds, ignoring the digits after the 9th
+fn parse_fraction(fraction: &str) -> Option<u32> {
+    if fraction.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits = &fraction.as_bytes()[..fraction.len().min(9)];
+    let value = digits
+        .iter()
+        .fold(0, |value, digit| value * 10 + u32::from(digit - b'0'));
+    Some(value * 10u32.pow(9 - digits.len() as u32))
+}
+
+fn parse_trimmed_text<T>(node: &XmlNode) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn ::std::error::Error + Send + Sync>>,
+{
+    node.text
+        .as_ref()
+        .map(|text| {
+            text.trim()
+                .parse()
+                .map_err(|err| InvalidTextValueError::new(node, text.as_str(), err).into())
+        })
+        .transpose()
+}
+
+fn parse_bool_text(node: &XmlNode) -> Result<Option<bool>> {
+    node.text
+        .as_ref()
+        .map(|text| {
+            parse_xml_bool(text.trim()).map_err(|err| InvalidTextValueError::new(node, text.as_str(), err).into())
+        })
+        .transpose()
+}
+
+fn vector_node(node: &XmlNode) -> Result<&XmlNode> {
+    node.child_nodes
+        .iter()
+        .find(|child_node| child_node.is(ns::DOC_PROPS_VTYPES, "vector"))
+        .ok_or_else(|| MissingChildNodeError::at(node, "vector").into())
+}
+
+// Heading pairs are stored as a vector of variants, holding the name and the part count of each heading in turn
+fn parse_heading_pairs(node: &XmlNode) -> Result<Vec<HeadingPair>> {
+    let vector = vector_node(node)?;
+    let mut values = vector
+        .child_nodes
+        .iter()
+        .filter(|child_node| child_node.is(ns::DOC_PROPS_VTYPES, "variant"))
+        .map(|variant| {
+            variant
+                .child_nodes
+                .first()
+                .ok_or_else(|| MissingChildNodeError::at(variant, "lpstr"))
+        });
+
+    let mut heading_pairs = Vec::new();
+    while let Some(name) = values.next() {
+        let name = name?;
+        let count = values
+            .next()
+            .ok_or_else(|| MissingChildNodeError::at(vector, "variant"))??;
+        heading_pairs.push(HeadingPair {
+            name: name.text.clone().unwrap_or_default(),
+            count: parse_trimmed_text(count)?.ok_or_else(|| MissingChildNodeError::at(count, "i4"))?,
+        });
+    }
+
+    Ok(heading_pairs)
+}
+
+fn parse_titles_of_parts(node: &XmlNode) -> Result<Vec<String>> {
+    Ok(vector_node(node)?
+        .child_nodes
+        .iter()
+        .map(|title| title.text.clone().unwrap_or_default())
+        .collect())
+}
+
+fn vector_element(base_type: &str, values: Vec<XmlNode>) -> XmlNode {
+    let mut node = XmlNode::new_with_namespace("vt:vector", ns::DOC_PROPS_VTYPES);
+    node.attributes.insert(String::from("size"), values.len().to_string());
+    node.attributes
+        .insert(String::from("baseType"), String::from(base_type));
+    node.child_nodes = values;
+    node
+}
+
+fn variant_element(value: XmlNode) -> XmlNode {
+    let mut node = XmlNode::new_with_namespace("vt:variant", ns::DOC_PROPS_VTYPES);
+    node.child_nodes.push(value);
+    node
+}
+
+fn parse_date_time(node: &XmlNode) -> Result<Option<W3cdtfDateTime>> {
+    parse_trimmed_text(node)
+}
+
+fn text_element(name: &str, namespace: &str, text: &str) -> XmlNode {
+    let mut node = XmlNode::new_with_namespace(name, namespace);
+    node.text = Some(String::from(text));
+    node
+}
+
+// Dates of the core properties are typed as W3CDTF with xsi:type, as required by the schema
+fn w3cdtf_element(name: &str, date_time: &W3cdtfDateTime) -> XmlNode {
+    let mut node = text_element(name, ns::DUBLIN_CORE_TERMS, &date_time.to_string());
+    node.attributes
+        .insert(String::from("xsi:type"), String::from("dcterms:W3CDTF"));
+    node.attribute_namespaces
+        .insert(String::from("xsi:type"), String::from(ns::XSI));
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, xml::writer::WriteOptions};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_doc_props_round_trip() {
+        let core = Core {
+            title: Some(String::from("Quarterly <results>")),
+            creator: Some(String::from("Author")),
+            revision: Some(4),
+            created_time: Some("2019-05-03T10:15:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        let written = core
+            .to_xml_element("cp:coreProperties")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        assert!(
+            written.contains(r#"<dcterms:created xsi:type="dcterms:W3CDTF">2019-05-03T10:15:00Z</dcterms:created>"#)
+        );
+        assert_eq!(
+            Core::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap(),
+            core
+        );
+
+        let app_info = AppInfo {
+            app_name: Some(String::from("Microsoft Office PowerPoint")),
+            app_version: Some(String::from("16.0000")),
+            ..Default::default()
+        };
+        let written = app_info
+            .to_xml_element("Properties")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        assert_eq!(
+            AppInfo::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap(),
+            app_info
+        );
+    }
+
+    #[test]
+    fn test_core_properties() {
+        let xml = r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+            <dc:title>Title</dc:title>
+            <dc:subject>Subject</dc:subject>
+            <cp:keywords>one; two</cp:keywords>
+            <dc:description>Description</dc:description>
+            <cp:revision> 12 </cp:revision>
+            <cp:lastPrinted>2019-05-02T08:00:00</cp:lastPrinted>
+            <dcterms:created xsi:type="dcterms:W3CDTF">2019-05-01T09:30:00Z</dcterms:created>
+            <dcterms:modified xsi:type="dcterms:W3CDTF">2019-05-03T12:15:30.5-05:30</dcterms:modified>
+            <cp:category>Category</cp:category>
+            <cp:contentStatus>Final</cp:contentStatus>
+            <dc:language>en-US</dc:language>
+            <dc:identifier>urn:isbn:0451450523</dc:identifier>
+            <cp:version>1.2</cp:version>
+        </cp:coreProperties>"#;
+        let core = Core::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(core.keywords.as_deref(), Some("one; two"));
+        assert_eq!(core.revision, Some(12));
+        assert_eq!(core.content_status.as_deref(), Some("Final"));
+        assert_eq!(core.version.as_deref(), Some("1.2"));
+        let last_printed = core.last_printed.unwrap();
+        assert_eq!(last_printed.date_time.offset(), UtcOffset::UTC);
+        assert!(!last_printed.has_time_zone);
+        let modified = core.modified_time.unwrap();
+        assert_eq!(modified.date_time.offset(), UtcOffset::from_hms(-5, -30, 0).unwrap());
+        assert_eq!(modified.date_time.nanosecond(), 500_000_000);
+        assert_eq!(modified.to_string(), "2019-05-03T12:15:30.5-05:30");
+
+        let written = core
+            .to_xml_element("cp:coreProperties")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        assert!(written.contains("<cp:lastPrinted>2019-05-02T08:00:00</cp:lastPrinted>"));
+        assert_eq!(
+            Core::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap(),
+            core
+        );
+
+        let invalid = xml
+            .replace("<cp:revision> 12 </cp:revision>", "<cp:revision>draft</cp:revision>")
+            .replace("2019-05-01T09:30:00Z", "2019-02-30");
+        let invalid = XmlNode::from_str(&invalid).unwrap();
+        match Core::from_xml_element(&invalid) {
+            Err(Error::InvalidTextValue(err)) => assert_eq!(err.value, "draft"),
+            _ => panic!("invalid revision should be rejected"),
+        }
+
+        let parsed = Core::from_xml_element_lenient(&invalid).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 2);
+        assert_eq!(parsed.value.revision, None);
+        assert_eq!(parsed.value.created_time, None);
+        assert_eq!(parsed.value.title.as_deref(), Some("Title"));
+
+        // Elements are matched by namespace, so a title of another vocabulary doesn't override the Dublin Core one
+        let foreign = xml.replace(
+            "<dc:subject>",
+            r#"<x:title xmlns:x="urn:x">Other</x:title><cp:title>Other</cp:title><dc:subject>"#,
+        );
+        let core = Core::from_xml_element(&XmlNode::from_str(&foreign).unwrap()).unwrap();
+        assert_eq!(core.title.as_deref(), Some("Title"));
+
+        for (value, precision) in &[
+            ("2019", W3cdtfPrecision::Year),
+            ("2019-05-03", W3cdtfPrecision::Day),
+            ("2019-05-03T10:15+01:00", W3cdtfPrecision::Minute),
+            ("2019-05-03T10:15:00.123456789Z", W3cdtfPrecision::Second),
+        ] {
+            let date_time = value.parse::<W3cdtfDateTime>().unwrap();
+            assert_eq!(date_time.precision, *precision);
+            assert_eq!(date_time.to_string(), *value);
+        }
+
+        for invalid_date in &[
+            "2019-13",
+            "2019-05-03T25:00Z",
+            "2019-05-03T10:00+2:00",
+            "05/03/2019",
+            "2019-05T10:00Z",
+            "2019-05-03T10:00:00.Z",
+            "+019-05-03",
+        ] {
+            assert!(invalid_date.parse::<W3cdtfDateTime>().is_err(), "{}", invalid_date);
+        }
+    }
+
+    #[test]
+    fn test_app_properties() {
+        let xml = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+            <TotalTime>42</TotalTime><Words>128</Words><Application>Microsoft Office PowerPoint</Application>
+            <PresentationFormat>Widescreen</PresentationFormat><Paragraphs>12</Paragraphs><Slides>2</Slides>
+            <Notes>1</Notes><HiddenSlides>0</HiddenSlides><MMClips>0</MMClips><ScaleCrop>false</ScaleCrop>
+            <HeadingPairs>
+                <vt:vector size="4" baseType="variant">
+                    <vt:variant><vt:lpstr>Theme</vt:lpstr></vt:variant><vt:variant><vt:i4>1</vt:i4></vt:variant>
+                    <vt:variant><vt:lpstr>Slide Titles</vt:lpstr></vt:variant><vt:variant><vt:i4>2</vt:i4></vt:variant>
+                </vt:vector>
+            </HeadingPairs>
+            <TitlesOfParts>
+                <vt:vector size="3" baseType="lpstr">
+                    <vt:lpstr>Office Theme</vt:lpstr><vt:lpstr>Introduction</vt:lpstr><vt:lpstr>Summary</vt:lpstr>
+                </vt:vector>
+            </TitlesOfParts>
+            <Company>Contoso</Company><LinksUpToDate>false</LinksUpToDate><SharedDoc>0</SharedDoc>
+            <HyperlinksChanged>false</HyperlinksChanged><AppVersion>16.0000</AppVersion>
+        </Properties>"#;
+        let app_info = AppInfo::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(app_info.total_time, Some(42));
+        assert_eq!(app_info.slides, Some(2));
+        assert_eq!(app_info.shared_doc, Some(false));
+        assert_eq!(app_info.company.as_deref(), Some("Contoso"));
+        assert_eq!(
+            app_info.heading_pairs,
+            vec![
+                HeadingPair {
+                    name: String::from("Theme"),
+                    count: 1
+                },
+                HeadingPair {
+                    name: String::from("Slide Titles"),
+                    count: 2
+                },
+            ]
+        );
+        assert_eq!(
+            app_info.titles_of_parts,
+            vec!["Office Theme", "Introduction", "Summary"]
+        );
+
+        let written = app_info
+            .to_xml_element("Properties")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        assert!(written.contains(r#"<vt:vector baseType="variant" size="4">"#));
+        assert_eq!(
+            AppInfo::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap(),
+            app_info
+        );
+
+        let invalid = XmlNode::from_str(&xml.replace("<Slides>2</Slides>", "<Slides>two</Slides>")).unwrap();
+        assert!(AppInfo::from_xml_element(&invalid).is_err());
+        let parsed = AppInfo::from_xml_element_lenient(&invalid).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(parsed.value.slides, None);
+        assert_eq!(parsed.value.titles_of_parts.len(), 3);
+    }
+
+    #[test]
+    fn test_custom_properties() {
+        let xml = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+            <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Classification"><vt:lpwstr>Internal</vt:lpwstr></property>
+            <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="5" name="Budget"><vt:r8>1250.5</vt:r8></property>
+            <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Reviewed"><vt:bool>true</vt:bool></property>
+            <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="4" name="Due"><vt:filetime>2019-06-01T00:00:00Z</vt:filetime></property>
+            <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="6" name="Tags"><vt:vector size="1" baseType="lpwstr"><vt:lpwstr>a</vt:lpwstr></vt:vector></property>
+        </Properties>"#;
+        let mut custom_properties = CustomProperties::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(custom_properties.len(), 5);
+        assert_eq!(
+            custom_properties.value("budget"),
+            Some(&CustomPropertyValue::R8(1250.5))
+        );
+        assert_eq!(
+            custom_properties.value("Reviewed"),
+            Some(&CustomPropertyValue::Bool(true))
+        );
+        assert_eq!(
+            custom_properties.value("Due"),
+            Some(&CustomPropertyValue::FileTime(
+                "2019-06-01".parse::<W3cdtfDateTime>().unwrap().date_time
+            ))
+        );
+        match custom_properties.value("Tags") {
+            Some(CustomPropertyValue::Other(xml_node)) => assert_eq!(xml_node.local_name(), "vector"),
+            _ => panic!("unknown variant types should be kept"),
+        }
+
+        let mut written = Vec::new();
+        custom_properties
+            .write_xml("Properties", &mut written, &WriteOptions::compact())
+            .unwrap();
+        assert_eq!(
+            CustomProperties::from_xml_element(&XmlNode::from_str(::std::str::from_utf8(&written).unwrap()).unwrap())
+                .unwrap(),
+            custom_properties
+        );
+
+        assert_eq!(custom_properties.next_pid(), 7);
+        assert_eq!(
+            custom_properties.set("Classification", "Confidential"),
+            Some(CustomPropertyValue::LpwStr(String::from("Internal")))
+        );
+        assert_eq!(custom_properties.get("Classification").unwrap().pid, 2);
+        assert_eq!(custom_properties.set("Project", 4711), None);
+        assert_eq!(custom_properties.get("Project").unwrap().pid, 7);
+        assert_eq!(
+            custom_properties.remove("Project").unwrap().value,
+            CustomPropertyValue::I4(4711)
+        );
+        assert!(custom_properties.remove("Project").is_none());
+        assert_eq!(custom_properties.len(), 5);
+
+        let mut empty = CustomProperties::new();
+        assert_eq!(empty.next_pid(), MIN_CUSTOM_PROPERTY_PID);
+        empty.set("Ärger", true);
+        assert_eq!(empty.get("äRGER").unwrap().pid, 2);
+
+        let overflowing = XmlNode::from_str(&xml.replace(r#"pid="5""#, r#"pid="2147483647""#)).unwrap();
+        let mut overflowing = CustomProperties::from_xml_element(&overflowing).unwrap();
+        assert_eq!(overflowing.next_pid(), 5);
+        overflowing.set("Project", 4711);
+        assert_eq!(overflowing.next_pid(), 7);
+
+        let foreign = xml.replace(
+            "</Properties>",
+            r#"<x:property xmlns:x="urn:x" fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="7" name="Other"><vt:i4>1</vt:i4></x:property></Properties>"#,
+        );
+        let foreign = CustomProperties::from_xml_element(&XmlNode::from_str(&foreign).unwrap()).unwrap();
+        assert!(foreign.get("Other").is_none());
+
+        let reserved = XmlNode::from_str(&xml.replace(r#"pid="5""#, r#"pid="-1""#)).unwrap();
+        assert!(CustomProperties::from_xml_element(&reserved).is_err());
+
+        let invalid = XmlNode::from_str(&xml.replace("1250.5", "lots")).unwrap();
+        assert!(CustomProperties::from_xml_element(&invalid).is_err());
+        let parsed = CustomProperties::from_xml_element_lenient(&invalid).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert!(parsed.value.get("Budget").is_none());
+    }
+
+    #[test]
+    fn test_custom_property_float_round_trip() {
+        let values = vec![
+            (CustomPropertyValue::R8(f64::INFINITY), "INF"),
+            (CustomPropertyValue::R8(f64::NEG_INFINITY), "-INF"),
+            (CustomPropertyValue::R4(f32::INFINITY), "INF"),
+            (CustomPropertyValue::R4(1.1), "1.1"),
+            (CustomPropertyValue::R8(-0.25), "-0.25"),
+        ];
+
+        for (value, text) in values {
+            let written = value.to_xml_element();
+            assert_eq!(written.text.as_deref(), Some(text));
+            assert_eq!(CustomPropertyValue::from_xml_element(&written).unwrap(), value);
+        }
+
+        let written = CustomPropertyValue::R8(f64::NAN).to_xml_element();
+        assert_eq!(written.text.as_deref(), Some("NaN"));
+        match CustomPropertyValue::from_xml_element(&written).unwrap() {
+            CustomPropertyValue::R8(value) => assert!(value.is_nan()),
+            value => panic!("unexpected value {:?}", value),
+        }
     }
 }
@@ -1,11 +1,12 @@
 use crate::{
+    drawingml::util::XmlNodeExt,
     error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
     relationship::RelationshipId,
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AudioCD {
@@ -42,25 +43,44 @@ pub struct AudioCD {
     /// In the above example, the audioCD sound element shown specifies for a portion of audio spanning from 2
     /// seconds into the first track to 1 minute, 5 seconds into the third track.
     pub end_time: AudioCDTime,
+
+    pub unknown: UnknownContent,
 }
 
-impl AudioCD {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AudioCD {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut start_time = None;
         let mut end_time = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "st" => start_time = Some(AudioCDTime::from_xml_element(child_node)?),
-                "end" => end_time = Some(AudioCDTime::from_xml_element(child_node)?),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "st" => start_time = Some(AudioCDTime::from_xml_element_with_context(child_node, context)?),
+                "end" => end_time = Some(AudioCDTime::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let start_time = start_time.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "st"))?;
-        let end_time = end_time.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "end"))?;
+        let start_time = start_time.ok_or_else(|| MissingChildNodeError::at(xml_node, "st"))?;
+        let end_time = end_time.ok_or_else(|| MissingChildNodeError::at(xml_node, "end"))?;
+
+        unknown.push_all_attributes(xml_node);
+
+        Ok(Self {
+            start_time,
+            end_time,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { start_time, end_time })
+impl ToXmlElement for AudioCD {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.start_time.to_xml_element("a:st"));
+        node.child_nodes.push(self.end_time.to_xml_element("a:end"));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -89,24 +109,43 @@ pub struct AudioFile {
     /// * mpeg ISO
     /// * IEC 11172-3
     pub content_type: Option<String>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AudioFile {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AudioFile {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut link = None;
         let mut content_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match xml_node.relationship_attr_name(attr).as_ref() {
                 "r:link" => link = Some(value.clone()),
                 "contentType" => content_type = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let link = link.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:link"))?;
+        let link = link.ok_or_else(|| MissingAttributeError::at(xml_node, "r:link"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self {
+            link,
+            content_type,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { link, content_type })
+impl ToXmlElement for AudioFile {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("r:link", &self.link);
+        node.set_optional_attribute("contentType", self.content_type.as_ref());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -120,24 +159,46 @@ pub struct AudioCDTime {
     ///
     /// Defaults to 0
     pub time: Option<u32>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AudioCDTime {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AudioCDTime {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut track = None;
         let mut time = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "track" => track = Some(value.parse()?),
-                "time" => time = Some(value.parse()?),
-                _ => (),
+                "track" => track = Some(xml_node.parse_attribute_value(attr, value)?),
+                "time" => {
+                    time = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let track = track.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "track"))?;
+        let track = track.ok_or_else(|| MissingAttributeError::at(xml_node, "track"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { track, time, unknown })
+    }
+}
 
-        Ok(Self { track, time })
+impl ToXmlElement for AudioCDTime {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("track", self.track);
+        node.set_optional_attribute("time", self.time);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -146,17 +207,36 @@ pub struct QuickTimeFile {
     /// Specifies the identification information for a linked object. This attribute is used to
     /// specify the location of an object that does not reside within this file.
     pub link: RelationshipId,
+
+    pub unknown: UnknownContent,
 }
 
-impl QuickTimeFile {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let link = xml_node
-            .attributes
-            .get("r:link")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:link"))?
-            .clone();
+impl XsdType for QuickTimeFile {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut link = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match xml_node.relationship_attr_name(attr).as_ref() {
+                "r:link" => link = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        let link = link.ok_or_else(|| MissingAttributeError::at(xml_node, "r:link"))?;
 
-        Ok(Self { link })
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { link, unknown })
+    }
+}
+
+impl ToXmlElement for QuickTimeFile {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("r:link", &self.link);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -182,24 +262,43 @@ pub struct VideoFile {
     /// If this attribute is omitted, application should attempt to determine the content type by
     /// reading the contents of the relationship’s target.
     pub content_type: Option<String>,
+
+    pub unknown: UnknownContent,
 }
 
-impl VideoFile {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for VideoFile {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut link = None;
         let mut content_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match xml_node.relationship_attr_name(attr).as_ref() {
                 "r:link" => link = Some(value.clone()),
                 "contentType" => content_type = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let link = link.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:link"))?;
+        let link = link.ok_or_else(|| MissingAttributeError::at(xml_node, "r:link"))?;
 
-        Ok(Self { link, content_type })
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self {
+            link,
+            content_type,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for VideoFile {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("r:link", &self.link);
+        node.set_optional_attribute("contentType", self.content_type.as_ref());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -214,24 +313,42 @@ pub struct EmbeddedWAVAudioFile {
     /// attached sound should the user need to identify the sound among others within the UI.
     pub name: Option<String>,
     //pub built_in: Option<bool>, // false
+    pub unknown: UnknownContent,
 }
 
-impl EmbeddedWAVAudioFile {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for EmbeddedWAVAudioFile {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut embed_rel_id = None;
         let mut name = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match xml_node.relationship_attr_name(attr).as_ref() {
                 "r:embed" => embed_rel_id = Some(value.clone()),
                 "name" => name = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let embed_rel_id = embed_rel_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:embed"))?;
+        let embed_rel_id = embed_rel_id.ok_or_else(|| MissingAttributeError::at(xml_node, "r:embed"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self {
+            embed_rel_id,
+            name,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { embed_rel_id, name })
+impl ToXmlElement for EmbeddedWAVAudioFile {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("r:embed", &self.embed_rel_id);
+        node.set_optional_attribute("name", self.name.as_ref());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -269,7 +386,7 @@ pub enum Media {
     /// case, is used to refer to this audioCD element from within the timing node list. For this example we see that the
     /// audio for this CD starts playing at the 0 second mark on the first track and ends on the 1 minute 5 second mark
     /// of the third track.
-    AudioCd(AudioCD),
+    AudioCd(Box<AudioCD>),
 
     /// This element specifies the existence of an audio WAV file. This element is specified within the non-visual
     /// properties of an object. The audio shall be attached to an object as this is how it is represented within the
@@ -393,14 +510,24 @@ pub enum Media {
 }
 
 impl XsdType for Media {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "audioCd" => Ok(Media::AudioCd(AudioCD::from_xml_element(xml_node)?)),
-            "wavAudioFile" => Ok(Media::WavAudioFile(EmbeddedWAVAudioFile::from_xml_element(xml_node)?)),
-            "audioFile" => Ok(Media::AudioFile(AudioFile::from_xml_element(xml_node)?)),
-            "videoFile" => Ok(Media::VideoFile(VideoFile::from_xml_element(xml_node)?)),
-            "quickTimeFile" => Ok(Media::QuickTimeFile(QuickTimeFile::from_xml_element(xml_node)?)),
-            _ => Err(Box::new(NotGroupMemberError::new(xml_node.name.clone(), "EG_Media"))),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "audioCd" => Ok(Media::AudioCd(Box::new(AudioCD::from_xml_element_with_context(
+                xml_node, context,
+            )?))),
+            "wavAudioFile" => Ok(Media::WavAudioFile(
+                EmbeddedWAVAudioFile::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "audioFile" => Ok(Media::AudioFile(AudioFile::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "videoFile" => Ok(Media::VideoFile(VideoFile::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "quickTimeFile" => Ok(Media::QuickTimeFile(QuickTimeFile::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_Media").into()),
         }
     }
 }
@@ -410,9 +537,21 @@ impl XsdChoice for Media {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "audioCd" | "wavAudioFile" | "audioFile" | "videoFile" | "quickTimeFile" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "audioCd" | "wavAudioFile" | "audioFile" | "videoFile" | "quickTimeFile"
+        )
+    }
+}
+
+impl ToXmlChoice for Media {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Media::AudioCd(media) => media.to_xml_element("a:audioCd"),
+            Media::WavAudioFile(media) => media.to_xml_element("a:wavAudioFile"),
+            Media::AudioFile(media) => media.to_xml_element("a:audioFile"),
+            Media::VideoFile(media) => media.to_xml_element("a:videoFile"),
+            Media::QuickTimeFile(media) => media.to_xml_element("a:quickTimeFile"),
         }
     }
 }
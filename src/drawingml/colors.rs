@@ -1,19 +1,18 @@
 use super::{
     sharedstylesheet::ColorMapping,
     simpletypes::{
-        parse_hex_color_rgb, Angle, FixedPercentage, HexColorRGB, Percentage, PositiveFixedAngle,
+        format_hex_color_rgb, parse_hex_color_rgb, Angle, FixedPercentage, HexColorRGB, Percentage, PositiveFixedAngle,
         PositiveFixedPercentage, PositivePercentage, PresetColorVal, SchemeColorVal, SystemColorVal,
     },
-    util::XmlNodeExt,
+    util::{dml_element, dml_val_element, XmlNodeExt},
 };
 use crate::{
-    error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    error::{InvalidAttributeValueError, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    xml::{unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq)]
@@ -434,8 +433,8 @@ pub enum ColorTransform {
 }
 
 impl XsdType for ColorTransform {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<ColorTransform> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<ColorTransform> {
+        match xml_node.dml_local_name() {
             "tint" => Ok(ColorTransform::Tint(xml_node.parse_val_attribute()?)),
             "shade" => Ok(ColorTransform::Shade(xml_node.parse_val_attribute()?)),
             "comp" => Ok(ColorTransform::Complement),
@@ -464,18 +463,78 @@ impl XsdType for ColorTransform {
             "blueMod" => Ok(ColorTransform::BlueModulate(xml_node.parse_val_attribute()?)),
             "gamma" => Ok(ColorTransform::Gamma),
             "invGamma" => Ok(ColorTransform::InverseGamma),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_ColorTransform").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_ColorTransform").into()),
         }
     }
 }
 
 impl XsdChoice for ColorTransform {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "tint" | "shade" | "comp" | "inv" | "gray" | "alpha" | "alphaOff" | "alphaMod" | "hue" | "hueOff"
-            | "hueMod" | "sat" | "satOff" | "satMod" | "lum" | "lumOff" | "lumMod" | "red" | "redOff" | "redMod"
-            | "green" | "greenOff" | "greenMod" | "blue" | "blueOff" | "blueMod" | "gamma" | "invGamma" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "tint"
+                | "shade"
+                | "comp"
+                | "inv"
+                | "gray"
+                | "alpha"
+                | "alphaOff"
+                | "alphaMod"
+                | "hue"
+                | "hueOff"
+                | "hueMod"
+                | "sat"
+                | "satOff"
+                | "satMod"
+                | "lum"
+                | "lumOff"
+                | "lumMod"
+                | "red"
+                | "redOff"
+                | "redMod"
+                | "green"
+                | "greenOff"
+                | "greenMod"
+                | "blue"
+                | "blueOff"
+                | "blueMod"
+                | "gamma"
+                | "invGamma"
+        )
+    }
+}
+
+impl ToXmlChoice for ColorTransform {
+    fn to_xml_element(&self) -> XmlNode {
+        match *self {
+            ColorTransform::Tint(val) => dml_val_element("tint", val),
+            ColorTransform::Shade(val) => dml_val_element("shade", val),
+            ColorTransform::Complement => dml_element("comp"),
+            ColorTransform::Inverse => dml_element("inv"),
+            ColorTransform::Grayscale => dml_element("gray"),
+            ColorTransform::Alpha(val) => dml_val_element("alpha", val),
+            ColorTransform::AlphaOffset(val) => dml_val_element("alphaOff", val),
+            ColorTransform::AlphaModulate(val) => dml_val_element("alphaMod", val),
+            ColorTransform::Hue(val) => dml_val_element("hue", val),
+            ColorTransform::HueOffset(val) => dml_val_element("hueOff", val),
+            ColorTransform::HueModulate(val) => dml_val_element("hueMod", val),
+            ColorTransform::Saturation(val) => dml_val_element("sat", val),
+            ColorTransform::SaturationOffset(val) => dml_val_element("satOff", val),
+            ColorTransform::SaturationModulate(val) => dml_val_element("satMod", val),
+            ColorTransform::Luminance(val) => dml_val_element("lum", val),
+            ColorTransform::LuminanceOffset(val) => dml_val_element("lumOff", val),
+            ColorTransform::LuminanceModulate(val) => dml_val_element("lumMod", val),
+            ColorTransform::Red(val) => dml_val_element("red", val),
+            ColorTransform::RedOffset(val) => dml_val_element("redOff", val),
+            ColorTransform::RedModulate(val) => dml_val_element("redMod", val),
+            ColorTransform::Green(val) => dml_val_element("green", val),
+            ColorTransform::GreenOffset(val) => dml_val_element("greenOff", val),
+            ColorTransform::GreenModulate(val) => dml_val_element("greenMod", val),
+            ColorTransform::Blue(val) => dml_val_element("blue", val),
+            ColorTransform::BlueOffset(val) => dml_val_element("blueOff", val),
+            ColorTransform::BlueModulate(val) => dml_val_element("blueMod", val),
+            ColorTransform::Gamma => dml_element("gamma"),
+            ColorTransform::InverseGamma => dml_element("invGamma"),
         }
     }
 }
@@ -493,71 +552,111 @@ pub struct ScRgbColor {
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ScRgbColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<ScRgbColor> {
+impl XsdType for ScRgbColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_r = None;
         let mut opt_g = None;
         let mut opt_b = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "r" => opt_r = Some(value.parse()?),
-                "g" => opt_g = Some(value.parse()?),
-                "b" => opt_b = Some(value.parse()?),
-                _ => (),
+                "r" => opt_r = Some(xml_node.parse_attribute_value(attr, value)?),
+                "g" => opt_g = Some(xml_node.parse_attribute_value(attr, value)?),
+                "b" => opt_b = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let r = opt_r.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r"))?;
-        let g = opt_g.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "g"))?;
-        let b = opt_b.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "b"))?;
+        let r = opt_r.ok_or_else(|| MissingAttributeError::at(xml_node, "r"))?;
+        let g = opt_g.ok_or_else(|| MissingAttributeError::at(xml_node, "g"))?;
+        let b = opt_b.ok_or_else(|| MissingAttributeError::at(xml_node, "b"))?;
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             r,
             g,
             b,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ScRgbColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("r", self.r);
+        node.set_attribute("g", self.g);
+        node.set_attribute("b", self.b);
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SRgbColor {
     pub value: u32,
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl SRgbColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<SRgbColor> {
-        let value = xml_node
-            .attributes
-            .get("val")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingAttributeError::new(xml_node.name.clone(), "val")))
-            .and_then(|value| u32::from_str_radix(value, 16).map_err(Box::from))?;
+impl XsdType for SRgbColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut opt_val = None;
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "val" => {
+                    opt_val = Some(
+                        u32::from_str_radix(value, 16)
+                            .map_err(|err| InvalidAttributeValueError::new(xml_node, attr, value.as_str(), err))?,
+                    )
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        let value = opt_val.ok_or_else(|| MissingAttributeError::at(xml_node, "val"))?;
+
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             value,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for SRgbColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_val_attribute(format!("{:06X}", self.value));
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct HslColor {
     /// Specifies the angular value describing the wavelength. Expressed in 1/6000ths of a
@@ -575,42 +674,58 @@ pub struct HslColor {
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl HslColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<HslColor> {
+impl XsdType for HslColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_h = None;
         let mut opt_s = None;
         let mut opt_l = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "hue" => opt_h = Some(value.parse::<PositiveFixedAngle>()?),
-                "sat" => opt_s = Some(value.parse()?),
-                "lum" => opt_l = Some(value.parse()?),
-                _ => (),
+                "hue" => opt_h = Some(xml_node.parse_attribute_value::<PositiveFixedAngle>(attr, value)?),
+                "sat" => opt_s = Some(xml_node.parse_attribute_value(attr, value)?),
+                "lum" => opt_l = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let hue = opt_h.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "hue"))?;
-        let saturation = opt_s.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "sat"))?;
-        let luminance = opt_l.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "lum"))?;
+        let hue = opt_h.ok_or_else(|| MissingAttributeError::at(xml_node, "hue"))?;
+        let saturation = opt_s.ok_or_else(|| MissingAttributeError::at(xml_node, "sat"))?;
+        let luminance = opt_l.ok_or_else(|| MissingAttributeError::at(xml_node, "lum"))?;
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             hue,
             saturation,
             luminance,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for HslColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("hue", self.hue);
+        node.set_attribute("sat", self.saturation);
+        node.set_attribute("lum", self.luminance);
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SystemColor {
     /// Specifies the system color value.
@@ -621,91 +736,156 @@ pub struct SystemColor {
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl SystemColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<SystemColor> {
+impl XsdType for SystemColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_val = None;
         let mut last_color = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "val" => opt_val = Some(value.parse()?),
-                "lastClr" => last_color = Some(parse_hex_color_rgb(value)?),
-                _ => (),
+                "val" => opt_val = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                "lastClr" => {
+                    last_color = unknown.recover_attribute(
+                        parse_hex_color_rgb(value)
+                            .map_err(|err| InvalidAttributeValueError::new(xml_node, attr, value.as_str(), err).into()),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let value = opt_val.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "val"))?;
+        let value = opt_val.ok_or_else(|| MissingAttributeError::at(xml_node, "val"))?;
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             value,
             last_color,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for SystemColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_val_attribute(&self.value);
+        node.set_optional_attribute("lastClr", self.last_color.as_ref().map(format_hex_color_rgb));
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PresetColor {
     pub value: PresetColorVal,
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PresetColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<PresetColor> {
-        let value = xml_node.parse_val_attribute()?;
+impl XsdType for PresetColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut opt_val = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "val" => opt_val = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        let value = opt_val.ok_or_else(|| MissingAttributeError::at(xml_node, "val"))?;
+
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             value,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PresetColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_val_attribute(&self.value);
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SchemeColor {
     pub value: SchemeColorVal,
 
     /// Color transforms to apply to this color
     pub color_transforms: Vec<ColorTransform>,
+
+    pub unknown: UnknownContent,
 }
 
-impl SchemeColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<SchemeColor> {
-        let value = xml_node
-            .attributes
-            .get("val")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "val"))?
-            .parse()?;
+impl XsdType for SchemeColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut opt_val = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "val" => opt_val = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        let value = opt_val.ok_or_else(|| MissingAttributeError::at(xml_node, "val"))?;
 
-        let color_transforms = xml_node
-            .child_nodes
-            .iter()
-            .filter_map(ColorTransform::try_from_xml_element)
-            .collect::<Result<Vec<_>>>()?;
+        let mut color_transforms = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            color_transforms.extend(unknown.parse_choice_element(position, child_node, context)?);
+        }
 
         Ok(Self {
             value,
             color_transforms,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for SchemeColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_val_attribute(&self.value);
+        node.child_nodes
+            .extend(self.color_transforms.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Color {
     /// This element specifies a color using the red, green, blue RGB color model. Each component, red, green, and blue
@@ -795,24 +975,49 @@ pub enum Color {
 }
 
 impl XsdType for Color {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Color> {
-        match xml_node.local_name() {
-            "scrgbClr" => Ok(Color::ScRgbColor(ScRgbColor::from_xml_element(xml_node)?)),
-            "srgbClr" => Ok(Color::SRgbColor(SRgbColor::from_xml_element(xml_node)?)),
-            "hslClr" => Ok(Color::HslColor(HslColor::from_xml_element(xml_node)?)),
-            "sysClr" => Ok(Color::SystemColor(SystemColor::from_xml_element(xml_node)?)),
-            "schemeClr" => Ok(Color::SchemeColor(SchemeColor::from_xml_element(xml_node)?)),
-            "prstClr" => Ok(Color::PresetColor(PresetColor::from_xml_element(xml_node)?)),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_ColorChoice").into()),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Color> {
+        match xml_node.dml_local_name() {
+            "scrgbClr" => Ok(Color::ScRgbColor(ScRgbColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "srgbClr" => Ok(Color::SRgbColor(SRgbColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "hslClr" => Ok(Color::HslColor(HslColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "sysClr" => Ok(Color::SystemColor(SystemColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "schemeClr" => Ok(Color::SchemeColor(SchemeColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "prstClr" => Ok(Color::PresetColor(PresetColor::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_ColorChoice").into()),
         }
     }
 }
 
 impl XsdChoice for Color {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "scrgbClr" | "srgbClr" | "hslClr" | "sysClr" | "schemeClr" | "prstClr" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "scrgbClr" | "srgbClr" | "hslClr" | "sysClr" | "schemeClr" | "prstClr"
+        )
+    }
+}
+
+impl ToXmlChoice for Color {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Color::ScRgbColor(color) => color.to_xml_element("a:scrgbClr"),
+            Color::SRgbColor(color) => color.to_xml_element("a:srgbClr"),
+            Color::HslColor(color) => color.to_xml_element("a:hslClr"),
+            Color::SystemColor(color) => color.to_xml_element("a:sysClr"),
+            Color::SchemeColor(color) => color.to_xml_element("a:schemeClr"),
+            Color::PresetColor(color) => color.to_xml_element("a:prstClr"),
         }
     }
 }
@@ -826,19 +1031,45 @@ pub struct CustomColor {
 
     /// The color represented by this custom color.
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl CustomColor {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let name = xml_node.attributes.get("name").cloned();
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| Color::is_choice_member(child_node.local_name()))
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice")))
-            .and_then(Color::from_xml_element)?;
+impl XsdType for CustomColor {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut name = None;
+        let mut color = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "name" => name = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_ColorChoice"))?;
 
-        Ok(Self { name, color })
+        Ok(Self { name, color, unknown })
+    }
+}
+
+impl ToXmlElement for CustomColor {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("name", self.name.as_ref());
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -863,22 +1094,28 @@ pub enum ColorMappingOverride {
 }
 
 impl XsdType for ColorMappingOverride {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "masterClrMapping" => Ok(ColorMappingOverride::UseMaster),
             "overrideClrMapping" => Ok(ColorMappingOverride::Override(Box::new(
-                ColorMapping::from_xml_element(xml_node)?,
+                ColorMapping::from_xml_element_with_context(xml_node, context)?,
             ))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "CT_ColorMappingOverride").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "CT_ColorMappingOverride").into()),
         }
     }
 }
 
 impl XsdChoice for ColorMappingOverride {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "masterClrMapping" | "overrideClrMapping" => true,
-            _ => false,
+        matches!(name.as_ref(), "masterClrMapping" | "overrideClrMapping")
+    }
+}
+
+impl ToXmlChoice for ColorMappingOverride {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            ColorMappingOverride::UseMaster => dml_element("masterClrMapping"),
+            ColorMappingOverride::Override(mapping) => mapping.to_xml_element("a:overrideClrMapping"),
         }
     }
 }
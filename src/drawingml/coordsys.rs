@@ -1,10 +1,12 @@
 use crate::drawingml::simpletypes::{Angle, Coordinate, PositiveCoordinate};
+use crate::drawingml::util::XmlNodeExt;
 use crate::error::MissingAttributeError;
-use crate::xml::{parse_xml_bool, XmlNode};
+use crate::xml::{format_xml_bool, unknown::UnknownContent, XmlNode};
+use crate::xsdtypes::{ParseContext, ToXmlElement, XsdType};
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Point2D {
     /// Specifies a coordinate on the x-axis. The origin point for this coordinate shall be specified
     /// by the parent XML element.
@@ -13,33 +15,54 @@ pub struct Point2D {
     /// Specifies a coordinate on the x-axis. The origin point for this coordinate shall be specified
     /// by the parent XML element.
     pub y: Coordinate,
-}
 
-impl Point2D {
-    pub fn new(x: Coordinate, y: Coordinate) -> Self {
-        Self { x, y }
-    }
+    pub unknown: UnknownContent,
+}
 
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Point2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut x = None;
         let mut y = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "x" => x = Some(value.parse()?),
-                "y" => y = Some(value.parse()?),
-                _ => (),
+                "x" => x = Some(xml_node.parse_attribute_value(attr, value)?),
+                "y" => y = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let x = x.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "x"))?;
-        let y = y.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "y"))?;
+        let x = x.ok_or_else(|| MissingAttributeError::at(xml_node, "x"))?;
+        let y = y.ok_or_else(|| MissingAttributeError::at(xml_node, "y"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { x, y, unknown })
+    }
+}
+
+impl Point2D {
+    pub fn new(x: Coordinate, y: Coordinate) -> Self {
+        Self {
+            x,
+            y,
+            unknown: Default::default(),
+        }
+    }
+}
 
-        Ok(Self { x, y })
+impl ToXmlElement for Point2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("x", self.x);
+        node.set_attribute("y", self.y);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PositiveSize2D {
     /// Specifies the length of the extents rectangle in EMUs. This rectangle shall dictate the size
     /// of the object as displayed (the result of any scaling to the original object).
@@ -47,33 +70,68 @@ pub struct PositiveSize2D {
     /// Specifies the width of the extents rectangle in EMUs. This rectangle shall dictate the size
     /// of the object as displayed (the result of any scaling to the original object).
     pub height: PositiveCoordinate,
-}
 
-impl PositiveSize2D {
-    pub fn new(width: PositiveCoordinate, height: PositiveCoordinate) -> Self {
-        Self { width, height }
-    }
+    pub unknown: UnknownContent,
+}
 
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for PositiveSize2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_width = None;
         let mut opt_height = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "cx" => opt_width = Some(value.parse::<PositiveCoordinate>()?),
-                "cy" => opt_height = Some(value.parse::<PositiveCoordinate>()?),
-                _ => (),
+                "cx" => {
+                    opt_width = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<PositiveCoordinate>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "cy" => {
+                    opt_height = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<PositiveCoordinate>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let width = opt_width.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "cx"))?;
-        let height = opt_height.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "cy"))?;
+        let width = opt_width.ok_or_else(|| MissingAttributeError::at(xml_node, "cx"))?;
+        let height = opt_height.ok_or_else(|| MissingAttributeError::at(xml_node, "cy"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { width, height, unknown })
+    }
+}
+
+impl PositiveSize2D {
+    pub fn new(width: PositiveCoordinate, height: PositiveCoordinate) -> Self {
+        Self {
+            width,
+            height,
+            unknown: Default::default(),
+        }
+    }
+}
 
-        Ok(Self { width, height })
+impl ToXmlElement for PositiveSize2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("cx", self.width);
+        node.set_attribute("cy", self.height);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
-#[derive(Default, Debug, Copy, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct Transform2D {
     /// Specifies the rotation of the Graphic Frame. The units for which this attribute is specified
     /// in reside within the simple type definition referenced below.
@@ -95,19 +153,21 @@ pub struct Transform2D {
 
     /// This element specifies the size of the bounding box enclosing the referenced object.
     pub extents: Option<PositiveSize2D>,
+
+    pub unknown: UnknownContent,
 }
 
-impl Transform2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Transform2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (key, value)| {
                 match key.as_str() {
-                    "rot" => instance.rotate_angle = Some(value.parse()?),
-                    "flipH" => instance.flip_horizontal = Some(parse_xml_bool(value)?),
-                    "flipV" => instance.flip_vertical = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "rot" => instance.rotate_angle = Some(xml_node.parse_attribute_value(key, value)?),
+                    "flipH" => instance.flip_horizontal = Some(xml_node.parse_bool_attribute_value(key, value)?),
+                    "flipV" => instance.flip_vertical = Some(xml_node.parse_bool_attribute_value(key, value)?),
+                    _ => instance.unknown.push_attribute(xml_node, key),
                 }
 
                 Ok(instance)
@@ -116,11 +176,26 @@ impl Transform2D {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
-                            "off" => instance.offset = Some(Point2D::from_xml_element(child_node)?),
-                            "ext" => instance.extents = Some(PositiveSize2D::from_xml_element(child_node)?),
-                            _ => (),
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
+                            "off" => {
+                                instance.offset = instance.unknown.recover_element(
+                                    Point2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "ext" => {
+                                instance.extents = instance.unknown.recover_element(
+                                    PositiveSize2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -129,7 +204,22 @@ impl Transform2D {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone, PartialEq)]
+impl ToXmlElement for Transform2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rot", self.rotate_angle);
+        node.set_optional_attribute("flipH", self.flip_horizontal.map(format_xml_bool));
+        node.set_optional_attribute("flipV", self.flip_vertical.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.offset.as_ref().map(|offset| offset.to_xml_element("a:off")));
+        node.child_nodes
+            .extend(self.extents.as_ref().map(|extents| extents.to_xml_element("a:ext")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct GroupTransform2D {
     /// Rotation. Specifies the clockwise rotation of a group in 1/64000 of a degree.
     ///
@@ -162,19 +252,42 @@ pub struct GroupTransform2D {
     /// This element specifies the size dimensions of the child extents rectangle and is used for calculations of grouping,
     /// scaling, and rotation behavior of shapes placed within a group.
     pub child_extents: Option<PositiveSize2D>,
+
+    pub unknown: UnknownContent,
 }
 
-impl GroupTransform2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GroupTransform2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "rot" => instance.rotate_angle = Some(value.parse()?),
-                    "flipH" => instance.flip_horizontal = Some(parse_xml_bool(value)?),
-                    "flipV" => instance.flip_vertical = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "rot" => {
+                        instance.rotate_angle = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "flipH" => {
+                        instance.flip_horizontal = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "flipV" => {
+                        instance.flip_vertical = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -183,13 +296,42 @@ impl GroupTransform2D {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
-                            "off" => instance.offset = Some(Point2D::from_xml_element(child_node)?),
-                            "ext" => instance.extents = Some(PositiveSize2D::from_xml_element(child_node)?),
-                            "chOff" => instance.child_offset = Some(Point2D::from_xml_element(child_node)?),
-                            "chExt" => instance.child_extents = Some(PositiveSize2D::from_xml_element(child_node)?),
-                            _ => (),
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
+                            "off" => {
+                                instance.offset = instance.unknown.recover_element(
+                                    Point2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "ext" => {
+                                instance.extents = instance.unknown.recover_element(
+                                    PositiveSize2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "chOff" => {
+                                instance.child_offset = instance.unknown.recover_element(
+                                    Point2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "chExt" => {
+                                instance.child_extents = instance.unknown.recover_element(
+                                    PositiveSize2D::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -197,3 +339,28 @@ impl GroupTransform2D {
             })
     }
 }
+
+impl ToXmlElement for GroupTransform2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rot", self.rotate_angle);
+        node.set_optional_attribute("flipH", self.flip_horizontal.map(format_xml_bool));
+        node.set_optional_attribute("flipV", self.flip_vertical.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.offset.as_ref().map(|offset| offset.to_xml_element("a:off")));
+        node.child_nodes
+            .extend(self.extents.as_ref().map(|extents| extents.to_xml_element("a:ext")));
+        node.child_nodes.extend(
+            self.child_offset
+                .as_ref()
+                .map(|offset| offset.to_xml_element("a:chOff")),
+        );
+        node.child_nodes.extend(
+            self.child_extents
+                .as_ref()
+                .map(|extents| extents.to_xml_element("a:chExt")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
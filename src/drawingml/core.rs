@@ -1,6 +1,7 @@
 use super::{
     audiovideo::EmbeddedWAVAudioFile,
     coordsys::{GroupTransform2D, Transform2D},
+    extensions::OfficeArtExtensionList,
     shapedefs::Geometry,
     shapeprops::{
         EffectProperties, FillProperties, LineDashProperties, LineEndProperties, LineFillProperties, LineJoinProperties,
@@ -11,16 +12,16 @@ use super::{
     },
     styles::{FontReference, StyleMatrixReference},
     text::{bodyformatting::TextBodyProperties, bullet::TextListStyle, paragraphs::TextParagraph},
+    util::XmlNodeExt,
 };
 use crate::{
     error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
     relationship::RelationshipId,
-    xml::{parse_xml_bool, XmlNode},
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{format_xml_bool, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AnimationGraphicalObjectBuildProperties {
@@ -60,18 +61,15 @@ pub enum AnimationGraphicalObjectBuildProperties {
 }
 
 impl XsdType for AnimationGraphicalObjectBuildProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "bldDgm" => Ok(AnimationGraphicalObjectBuildProperties::BuildDiagram(
-                AnimationDgmBuildProperties::from_xml_element(xml_node)?,
+                AnimationDgmBuildProperties::from_xml_element_with_context(xml_node, context)?,
             )),
             "bldChart" => Ok(AnimationGraphicalObjectBuildProperties::BuildChart(
-                AnimationChartBuildProperties::from_xml_element(xml_node)?,
+                AnimationChartBuildProperties::from_xml_element_with_context(xml_node, context)?,
             )),
-            _ => Err(Box::new(NotGroupMemberError::new(
-                xml_node.name.clone(),
-                "CT_AnimationGraphicalObjectBuildProperties",
-            ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "CT_AnimationGraphicalObjectBuildProperties").into()),
         }
     }
 }
@@ -81,9 +79,15 @@ impl XsdChoice for AnimationGraphicalObjectBuildProperties {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "bldDgm" | "bldChart" => true,
-            _ => false,
+        matches!(name.as_ref(), "bldDgm" | "bldChart")
+    }
+}
+
+impl ToXmlChoice for AnimationGraphicalObjectBuildProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            AnimationGraphicalObjectBuildProperties::BuildDiagram(props) => props.to_xml_element("a:bldDgm"),
+            AnimationGraphicalObjectBuildProperties::BuildChart(props) => props.to_xml_element("a:bldChart"),
         }
     }
 }
@@ -101,22 +105,52 @@ pub struct AnimationDgmBuildProperties {
     ///
     /// Defaults to false
     pub reverse: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AnimationDgmBuildProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AnimationDgmBuildProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "bld" => instance.build_type = Some(value.parse()?),
-                    "rev" => instance.reverse = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "bld" => {
+                        instance.build_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rev" => {
+                        instance.reverse = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for AnimationDgmBuildProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("bld", self.build_type);
+        node.set_optional_attribute("rev", self.reverse.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -136,22 +170,52 @@ pub struct AnimationChartBuildProperties {
     ///
     /// An example of background elements are grid lines and the chart legend.
     pub animate_bg: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AnimationChartBuildProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AnimationChartBuildProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "bld" => instance.build_type = Some(value.parse()?),
-                    "animBg" => instance.animate_bg = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "bld" => {
+                        instance.build_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "animBg" => {
+                        instance.animate_bg = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for AnimationChartBuildProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("bld", self.build_type);
+        node.set_optional_attribute("animBg", self.animate_bg.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -167,18 +231,15 @@ pub enum AnimationElementChoice {
 }
 
 impl XsdType for AnimationElementChoice {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "dgm" => Ok(AnimationElementChoice::Diagram(AnimationDgmElement::from_xml_element(
-                xml_node,
-            )?)),
-            "chart" => Ok(AnimationElementChoice::Chart(AnimationChartElement::from_xml_element(
-                xml_node,
-            )?)),
-            _ => Err(Box::new(NotGroupMemberError::new(
-                xml_node.name.clone(),
-                "CT_AnimationElementChoice",
-            ))),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "dgm" => Ok(AnimationElementChoice::Diagram(
+                AnimationDgmElement::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "chart" => Ok(AnimationElementChoice::Chart(
+                AnimationChartElement::from_xml_element_with_context(xml_node, context)?,
+            )),
+            _ => Err(NotGroupMemberError::at(xml_node, "CT_AnimationElementChoice").into()),
         }
     }
 }
@@ -188,9 +249,15 @@ impl XsdChoice for AnimationElementChoice {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "dgm" | "chart" => true,
-            _ => false,
+        matches!(name.as_ref(), "dgm" | "chart")
+    }
+}
+
+impl ToXmlChoice for AnimationElementChoice {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            AnimationElementChoice::Diagram(element) => element.to_xml_element("a:dgm"),
+            AnimationElementChoice::Chart(element) => element.to_xml_element("a:chart"),
         }
     }
 }
@@ -208,22 +275,45 @@ pub struct AnimationDgmElement {
     ///
     /// Defaults to DgmBuildStep::Shape
     pub build_step: Option<DgmBuildStep>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AnimationDgmElement {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AnimationDgmElement {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
                     "id" => instance.id = Some(value.clone()),
-                    "bldStep" => instance.build_step = Some(value.parse()?),
-                    _ => (),
+                    "bldStep" => {
+                        instance.build_step = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for AnimationDgmElement {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("id", self.id.as_ref());
+        node.set_optional_attribute("bldStep", self.build_step);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -244,33 +334,64 @@ pub struct AnimationChartElement {
     /// be built as one object meaning it is animated as a single graphic. Alternatively the chart
     /// can be animated, or built as separate pieces.
     pub build_step: ChartBuildStep,
+
+    pub unknown: UnknownContent,
 }
 
-impl AnimationChartElement {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AnimationChartElement {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut series_index = None;
         let mut category_index = None;
         let mut build_step = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_ref() {
-                "seriesIdx" => series_index = Some(value.parse()?),
-                "categoryIdx" => category_index = Some(value.parse()?),
-                "bldStep" => build_step = Some(value.parse()?),
-                _ => (),
+                "seriesIdx" => {
+                    series_index = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "categoryIdx" => {
+                    category_index = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "bldStep" => build_step = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let build_step = build_step.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "bldStep"))?;
+        let build_step = build_step.ok_or_else(|| MissingAttributeError::at(xml_node, "bldStep"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             series_index,
             category_index,
             build_step,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for AnimationChartElement {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("seriesIdx", self.series_index);
+        node.set_optional_attribute("categoryIdx", self.category_index);
+        node.set_attribute("bldStep", self.build_step);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct NonVisualConnectorProperties {
     /// This element specifies all locking properties for a connection shape. These properties inform the generating
@@ -284,23 +405,69 @@ pub struct NonVisualConnectorProperties {
     /// This element specifies the ending connection that should be made by the corresponding connector shape. This
     /// connects the end tail of the connector to the final destination shape.
     pub end_connection: Option<Connection>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualConnectorProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "cxnSpLocks" => instance.connector_locks = Some(ConnectorLocking::from_xml_element(child_node)?),
-                    "stCxn" => instance.start_connection = Some(Connection::from_xml_element(child_node)?),
-                    "endCxn" => instance.end_connection = Some(Connection::from_xml_element(child_node)?),
-                    _ => (),
+impl XsdType for NonVisualConnectorProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        instance.unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "cxnSpLocks" => {
+                    instance.connector_locks = instance.unknown.recover_element(
+                        ConnectorLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "stCxn" => {
+                    instance.start_connection = instance.unknown.recover_element(
+                        Connection::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
                 }
+                "endCxn" => {
+                    instance.end_connection = instance.unknown.recover_element(
+                        Connection::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for NonVisualConnectorProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.extend(
+            self.connector_locks
+                .as_ref()
+                .map(|locks| locks.to_xml_element("a:cxnSpLocks")),
+        );
+        node.child_nodes.extend(
+            self.start_connection
+                .as_ref()
+                .map(|connection| connection.to_xml_element("a:stCxn")),
+        );
+        node.child_nodes.extend(
+            self.end_connection
+                .as_ref()
+                .map(|connection| connection.to_xml_element("a:endCxn")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -309,30 +476,78 @@ pub struct NonVisualGraphicFrameProperties {
     /// This element specifies all locking properties for a graphic frame. These properties inform the generating
     /// application about specific properties that have been previously locked and thus should not be changed.
     pub graphic_frame_locks: Option<GraphicalObjectFrameLocking>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualGraphicFrameProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let graphic_frame_locks = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "graphicFrameLocks")
-            .map(GraphicalObjectFrameLocking::from_xml_element)
-            .transpose()?;
+impl XsdType for NonVisualGraphicFrameProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut graphic_frame_locks = None;
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "graphicFrameLocks" => {
+                    graphic_frame_locks = unknown.recover_element(
+                        GraphicalObjectFrameLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { graphic_frame_locks })
+        Ok(Self {
+            graphic_frame_locks,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for NonVisualGraphicFrameProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.extend(
+            self.graphic_frame_locks
+                .as_ref()
+                .map(|locks| locks.to_xml_element("a:graphicFrameLocks")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContentPartLocking {
     pub locking: Locking,
+
+    pub unknown: UnknownContent,
 }
 
-impl ContentPartLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let locking = Locking::from_xml_element(xml_node)?;
-        Ok(Self { locking })
+impl XsdType for ContentPartLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let locking = xml_node
+            .attributes
+            .iter()
+            .try_fold(Locking::default(), |locking, attr| {
+                locking.try_update_from_xml_attribute(xml_node, attr, &mut unknown, context)
+            })?;
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { locking, unknown })
+    }
+}
+
+impl ToXmlElement for ContentPartLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        self.locking.set_xml_attributes(&mut node);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -340,38 +555,101 @@ impl ContentPartLocking {
 pub struct NonVisualContentPartProperties {
     pub locking: Option<ContentPartLocking>,
     pub is_comment: Option<bool>, // default=true
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualContentPartProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let is_comment = xml_node.attributes.get("isComment").map(parse_xml_bool).transpose()?;
+impl XsdType for NonVisualContentPartProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut is_comment = None;
+        let mut locking = None;
 
-        let locking = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "cpLocks")
-            .map(ContentPartLocking::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "isComment" => {
+                    is_comment = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "cpLocks" => {
+                    locking = unknown.recover_element(
+                        ContentPartLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { locking, is_comment })
+        Ok(Self {
+            locking,
+            is_comment,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for NonVisualContentPartProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("isComment", self.is_comment.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.locking.as_ref().map(|locking| locking.to_xml_element("a:cpLocks")));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct NonVisualGroupDrawingShapeProps {
     pub locks: Option<GroupLocking>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualGroupDrawingShapeProps {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let locks = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "grpSpLocks")
-            .map(GroupLocking::from_xml_element)
-            .transpose()?;
+impl XsdType for NonVisualGroupDrawingShapeProps {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut locks = None;
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "grpSpLocks" => {
+                    locks = unknown.recover_element(
+                        GroupLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { locks })
+        Ok(Self { locks, unknown })
+    }
+}
+
+impl ToXmlElement for NonVisualGroupDrawingShapeProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.locks.as_ref().map(|locks| locks.to_xml_element("a:grpSpLocks")));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -395,30 +673,66 @@ pub struct NonVisualPictureProperties {
     /// resized from its current (smaller) size.
     pub prefer_relative_resize: Option<bool>,
     pub picture_locks: Option<PictureLocking>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualPictureProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let prefer_relative_resize = xml_node
-            .attributes
-            .get("preferRelativeResize")
-            .map(parse_xml_bool)
-            .transpose()?;
+impl XsdType for NonVisualPictureProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut prefer_relative_resize = None;
+        let mut picture_locks = None;
 
-        let picture_locks = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "picLocks")
-            .map(PictureLocking::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "preferRelativeResize" => {
+                    prefer_relative_resize = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "picLocks" => {
+                    picture_locks = unknown.recover_element(
+                        PictureLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
         Ok(Self {
             prefer_relative_resize,
             picture_locks,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for NonVisualPictureProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("preferRelativeResize", self.prefer_relative_resize.map(format_xml_bool));
+        node.child_nodes.extend(
+            self.picture_locks
+                .as_ref()
+                .map(|locks| locks.to_xml_element("a:picLocks")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct NonVisualDrawingShapeProps {
     pub shape_locks: Option<ShapeLocking>,
@@ -429,26 +743,63 @@ pub struct NonVisualDrawingShapeProps {
     ///
     /// Defaults to false
     pub is_text_box: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualDrawingShapeProps {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let is_text_box = xml_node.attributes.get("txBox").map(parse_xml_bool).transpose()?;
+impl XsdType for NonVisualDrawingShapeProps {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut is_text_box = None;
+        let mut shape_locks = None;
 
-        let shape_locks = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "spLocks")
-            .map(ShapeLocking::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "txBox" => {
+                    is_text_box = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "spLocks" => {
+                    shape_locks = unknown.recover_element(
+                        ShapeLocking::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
         Ok(Self {
             is_text_box,
             shape_locks,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for NonVisualDrawingShapeProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("txBox", self.is_text_box.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.shape_locks.as_ref().map(|locks| locks.to_xml_element("a:spLocks")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// ```xml example
 /// <docPr id="1" name="Object name" descr="Some description" title="Title of the object">
 ///     <a:hlinkClick r:id="rId2" tooltip="Some Sample Text"/>
@@ -544,10 +895,16 @@ pub struct NonVisualDrawingProps {
     /// mouse of the user hovers over the object. When this action is activated then additional attributes can be used to
     /// specify other tasks that should be performed along with the action.
     pub hyperlink_hover: Option<Box<Hyperlink>>,
+
+    /// Future extensions, e.g. whether the object is decorative.
+    pub extension_list: Option<OfficeArtExtensionList>,
+
+    pub unknown: UnknownContent,
 }
 
-impl NonVisualDrawingProps {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for NonVisualDrawingProps {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_id = None;
         let mut opt_name = None;
         let mut description = None;
@@ -555,28 +912,62 @@ impl NonVisualDrawingProps {
         let mut title = None;
         let mut hyperlink_click = None;
         let mut hyperlink_hover = None;
+        let mut extension_list = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "id" => opt_id = Some(value.parse()?),
+                "id" => opt_id = Some(xml_node.parse_attribute_value(attr, value)?),
                 "name" => opt_name = Some(value.clone()),
                 "descr" => description = Some(value.clone()),
-                "hidden" => hidden = Some(parse_xml_bool(value)?),
+                "hidden" => {
+                    hidden = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
                 "title" => title = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "hlinkClick" => hyperlink_click = Some(Box::new(Hyperlink::from_xml_element(child_node)?)),
-                "hlinkHover" => hyperlink_hover = Some(Box::new(Hyperlink::from_xml_element(child_node)?)),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "hlinkClick" => {
+                    hyperlink_click = unknown
+                        .recover_element(
+                            Hyperlink::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "hlinkHover" => {
+                    hyperlink_hover = unknown
+                        .recover_element(
+                            Hyperlink::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "extLst" => {
+                    extension_list = unknown.recover_element(
+                        OfficeArtExtensionList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let id = opt_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?;
-        let name = opt_name.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?;
+        let id = opt_id.ok_or_else(|| MissingAttributeError::at(xml_node, "id"))?;
+        let name = opt_name.ok_or_else(|| MissingAttributeError::at(xml_node, "name"))?;
 
         Ok(Self {
             id,
@@ -586,10 +977,40 @@ impl NonVisualDrawingProps {
             title,
             hyperlink_click,
             hyperlink_hover,
+            extension_list,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for NonVisualDrawingProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("id", self.id);
+        node.set_attribute("name", &self.name);
+        node.set_optional_attribute("descr", self.description.as_ref());
+        node.set_optional_attribute("hidden", self.hidden.map(format_xml_bool));
+        node.set_optional_attribute("title", self.title.as_ref());
+        node.child_nodes.extend(
+            self.hyperlink_click
+                .as_ref()
+                .map(|hyperlink| hyperlink.to_xml_element("a:hlinkClick")),
+        );
+        node.child_nodes.extend(
+            self.hyperlink_hover
+                .as_ref()
+                .map(|hyperlink| hyperlink.to_xml_element("a:hlinkHover")),
+        );
+        node.child_nodes.extend(
+            self.extension_list
+                .as_ref()
+                .map(|extension_list| extension_list.to_xml_element("a:extLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Locking {
     /// Specifies that the generating application should not allow shape grouping for the
@@ -664,33 +1085,130 @@ pub struct Locking {
     pub no_change_shape_type: Option<bool>,
 }
 
-impl Locking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Locking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
-            .try_fold(Default::default(), Self::try_update_from_xml_attribute)
+            .try_fold(Default::default(), |instance: Self, attr| {
+                instance.try_update_from_xml_attribute(xml_node, attr, &mut UnknownContent::default(), context)
+            })
     }
+}
 
-    pub fn try_update_from_xml_attribute(mut self, (attr, value): (&String, &String)) -> Result<Self> {
+impl Locking {
+    /// Updates the locking from an attribute of `xml_node`, so that elements extending `CT_Locking` with their own
+    /// attributes can be parsed in a single pass. Attributes that aren't part of `CT_Locking` are recorded to `unknown`.
+    pub fn try_update_from_xml_attribute(
+        mut self,
+        xml_node: &XmlNode,
+        (attr, value): (&String, &String),
+        unknown: &mut UnknownContent,
+        context: &mut ParseContext,
+    ) -> Result<Self> {
         match attr.as_ref() {
-            "noGrp" => self.no_grouping = Some(parse_xml_bool(value)?),
-            "noSelect" => self.no_select = Some(parse_xml_bool(value)?),
-            "noRot" => self.no_rotate = Some(parse_xml_bool(value)?),
-            "noChangeAspect" => self.no_change_aspect_ratio = Some(parse_xml_bool(value)?),
-            "noMove" => self.no_move = Some(parse_xml_bool(value)?),
-            "noResize" => self.no_resize = Some(parse_xml_bool(value)?),
-            "noEditPoints" => self.no_edit_points = Some(parse_xml_bool(value)?),
-            "noAdjustHandles" => self.no_adjust_handles = Some(parse_xml_bool(value)?),
-            "noChangeArrowheads" => self.no_change_arrowheads = Some(parse_xml_bool(value)?),
-            "noChangeShapeType" => self.no_change_shape_type = Some(parse_xml_bool(value)?),
-            _ => (),
+            "noGrp" => {
+                self.no_grouping = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noSelect" => {
+                self.no_select = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noRot" => {
+                self.no_rotate = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noChangeAspect" => {
+                self.no_change_aspect_ratio = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noMove" => {
+                self.no_move = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noResize" => {
+                self.no_resize = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noEditPoints" => {
+                self.no_edit_points = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noAdjustHandles" => {
+                self.no_adjust_handles = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noChangeArrowheads" => {
+                self.no_change_arrowheads = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            "noChangeShapeType" => {
+                self.no_change_shape_type = unknown.recover_attribute(
+                    xml_node.parse_bool_attribute_value(attr, value),
+                    xml_node,
+                    attr,
+                    context,
+                )?
+            }
+            _ => unknown.push_attribute(xml_node, attr),
         }
 
         Ok(self)
     }
 }
 
+impl Locking {
+    pub fn set_xml_attributes(&self, xml_node: &mut XmlNode) {
+        xml_node.set_optional_attribute("noGrp", self.no_grouping.map(format_xml_bool));
+        xml_node.set_optional_attribute("noSelect", self.no_select.map(format_xml_bool));
+        xml_node.set_optional_attribute("noRot", self.no_rotate.map(format_xml_bool));
+        xml_node.set_optional_attribute("noChangeAspect", self.no_change_aspect_ratio.map(format_xml_bool));
+        xml_node.set_optional_attribute("noMove", self.no_move.map(format_xml_bool));
+        xml_node.set_optional_attribute("noResize", self.no_resize.map(format_xml_bool));
+        xml_node.set_optional_attribute("noEditPoints", self.no_edit_points.map(format_xml_bool));
+        xml_node.set_optional_attribute("noAdjustHandles", self.no_adjust_handles.map(format_xml_bool));
+        xml_node.set_optional_attribute("noChangeArrowheads", self.no_change_arrowheads.map(format_xml_bool));
+        xml_node.set_optional_attribute("noChangeShapeType", self.no_change_shape_type.map(format_xml_bool));
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct ShapeLocking {
     pub locking: Locking,
@@ -701,21 +1219,51 @@ pub struct ShapeLocking {
     ///
     /// Defaults to false
     pub no_text_edit: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ShapeLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for ShapeLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "noTextEdit" => instance.no_text_edit = Some(parse_xml_bool(value)?),
-                    _ => instance.locking = instance.locking.try_update_from_xml_attribute((attr, value))?,
+                    "noTextEdit" => {
+                        instance.no_text_edit = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => {
+                        instance.locking = instance.locking.try_update_from_xml_attribute(
+                            xml_node,
+                            (attr, value),
+                            &mut instance.unknown,
+                            context,
+                        )?
+                    }
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for ShapeLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        self.locking.set_xml_attributes(&mut node);
+        node.set_optional_attribute("noTextEdit", self.no_text_edit.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -768,27 +1316,97 @@ pub struct GroupLocking {
     ///
     /// Defaults to false
     pub no_resize: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl GroupLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GroupLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "noGrp" => instance.no_grouping = Some(parse_xml_bool(value)?),
-                    "noUngrp" => instance.no_ungrouping = Some(parse_xml_bool(value)?),
-                    "noSelect" => instance.no_select = Some(parse_xml_bool(value)?),
-                    "noRot" => instance.no_rotate = Some(parse_xml_bool(value)?),
-                    "noChangeAspect" => instance.no_change_aspect_ratio = Some(parse_xml_bool(value)?),
-                    "noMove" => instance.no_move = Some(parse_xml_bool(value)?),
-                    "noResize" => instance.no_resize = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "noGrp" => {
+                        instance.no_grouping = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noUngrp" => {
+                        instance.no_ungrouping = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noSelect" => {
+                        instance.no_select = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noRot" => {
+                        instance.no_rotate = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noChangeAspect" => {
+                        instance.no_change_aspect_ratio = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noMove" => {
+                        instance.no_move = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noResize" => {
+                        instance.no_resize = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for GroupLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("noGrp", self.no_grouping.map(format_xml_bool));
+        node.set_optional_attribute("noUngrp", self.no_ungrouping.map(format_xml_bool));
+        node.set_optional_attribute("noSelect", self.no_select.map(format_xml_bool));
+        node.set_optional_attribute("noRot", self.no_rotate.map(format_xml_bool));
+        node.set_optional_attribute("noChangeAspect", self.no_change_aspect_ratio.map(format_xml_bool));
+        node.set_optional_attribute("noMove", self.no_move.map(format_xml_bool));
+        node.set_optional_attribute("noResize", self.no_resize.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -842,38 +1460,119 @@ pub struct GraphicalObjectFrameLocking {
     ///
     /// Defaults to false
     pub no_resize: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl GraphicalObjectFrameLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GraphicalObjectFrameLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "noGrp" => instance.no_grouping = Some(parse_xml_bool(value)?),
-                    "noDrilldown" => instance.no_drilldown = Some(parse_xml_bool(value)?),
-                    "noSelect" => instance.no_select = Some(parse_xml_bool(value)?),
-                    "noChangeAspect" => instance.no_change_aspect = Some(parse_xml_bool(value)?),
-                    "noMove" => instance.no_move = Some(parse_xml_bool(value)?),
-                    "noResize" => instance.no_resize = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "noGrp" => {
+                        instance.no_grouping = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noDrilldown" => {
+                        instance.no_drilldown = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noSelect" => {
+                        instance.no_select = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noChangeAspect" => {
+                        instance.no_change_aspect = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noMove" => {
+                        instance.no_move = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noResize" => {
+                        instance.no_resize = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for GraphicalObjectFrameLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("noGrp", self.no_grouping.map(format_xml_bool));
+        node.set_optional_attribute("noDrilldown", self.no_drilldown.map(format_xml_bool));
+        node.set_optional_attribute("noSelect", self.no_select.map(format_xml_bool));
+        node.set_optional_attribute("noChangeAspect", self.no_change_aspect.map(format_xml_bool));
+        node.set_optional_attribute("noMove", self.no_move.map(format_xml_bool));
+        node.set_optional_attribute("noResize", self.no_resize.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct ConnectorLocking {
     pub locking: Locking,
+
+    pub unknown: UnknownContent,
+}
+
+impl XsdType for ConnectorLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let locking = xml_node
+            .attributes
+            .iter()
+            .try_fold(Locking::default(), |locking, attr| {
+                locking.try_update_from_xml_attribute(xml_node, attr, &mut unknown, context)
+            })?;
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { locking, unknown })
+    }
 }
 
-impl ConnectorLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let locking = Locking::from_xml_element(xml_node)?;
-        Ok(Self { locking })
+impl ToXmlElement for ConnectorLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        self.locking.set_xml_attributes(&mut node);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -886,21 +1585,51 @@ pub struct PictureLocking {
     ///
     /// Defaults to false
     pub no_crop: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PictureLocking {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for PictureLocking {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "noCrop" => instance.no_crop = Some(parse_xml_bool(value)?),
-                    _ => instance.locking = instance.locking.try_update_from_xml_attribute((attr, value))?,
+                    "noCrop" => {
+                        instance.no_crop = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => {
+                        instance.locking = instance.locking.try_update_from_xml_attribute(
+                            xml_node,
+                            (attr, value),
+                            &mut instance.unknown,
+                            context,
+                        )?
+                    }
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for PictureLocking {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        self.locking.set_xml_attributes(&mut node);
+        node.set_optional_attribute("noCrop", self.no_crop.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -913,25 +1642,44 @@ pub struct Connection {
     /// there are many connection sites on a shape and it shall be specified which connection
     /// site the corresponding connector shape should connect to.
     pub shape_index: u32,
+
+    pub unknown: UnknownContent,
 }
 
-impl Connection {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Connection {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut id = None;
         let mut shape_index = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "id" => id = Some(value.parse()?),
-                "idx" => shape_index = Some(value.parse()?),
-                _ => (),
+                "id" => id = Some(xml_node.parse_attribute_value(attr, value)?),
+                "idx" => shape_index = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let id = id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?;
-        let shape_index = shape_index.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "idx"))?;
+        let id = id.ok_or_else(|| MissingAttributeError::at(xml_node, "id"))?;
+        let shape_index = shape_index.ok_or_else(|| MissingAttributeError::at(xml_node, "idx"))?;
+
+        unknown.push_all_elements(xml_node);
 
-        Ok(Self { id, shape_index })
+        Ok(Self {
+            id,
+            shape_index,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for Connection {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("id", self.id);
+        node.set_attribute("idx", self.shape_index);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -945,18 +1693,37 @@ pub struct GraphicalObject {
     /// Depending on the kind of graphical object used not every generating application that supports the
     /// OOXML framework has the ability to render the graphical object.
     pub graphic_data: GraphicalObjectData,
+
+    pub unknown: UnknownContent,
 }
 
-impl GraphicalObject {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let graphic_data = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "graphicData")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "graphicData")))
-            .and_then(GraphicalObjectData::from_xml_element)?;
+impl XsdType for GraphicalObject {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut graphic_data = None;
+        unknown.push_all_attributes(xml_node);
 
-        Ok(Self { graphic_data })
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "graphicData" => {
+                    graphic_data = Some(GraphicalObjectData::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let graphic_data = graphic_data.ok_or_else(|| MissingChildNodeError::at(xml_node, "graphicData"))?;
+
+        Ok(Self { graphic_data, unknown })
+    }
+}
+
+impl ToXmlElement for GraphicalObject {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.graphic_data.to_xml_element("a:graphicData"));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -968,17 +1735,37 @@ pub struct GraphicalObjectData {
     /// this tag. The URI is used to identify the correct 'server' that can process the contents of
     /// this tag.
     pub uri: String,
+
+    pub unknown: UnknownContent,
 }
 
-impl GraphicalObjectData {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let uri = xml_node
-            .attributes
-            .get("uri")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingAttributeError::new(xml_node.name.clone(), "uri")))?
-            .clone();
+impl XsdType for GraphicalObjectData {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut uri = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "uri" => uri = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        // The content of the graphic data is defined by the uri, so it's kept as is
+        unknown.push_all_elements(xml_node);
 
-        Ok(Self { uri })
+        let uri = uri.ok_or_else(|| MissingAttributeError::at(xml_node, "uri"))?;
+
+        Ok(Self { uri, unknown })
+    }
+}
+
+impl ToXmlElement for GraphicalObjectData {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("uri", &self.uri);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1008,36 +1795,79 @@ pub struct GroupShapeProperties {
     pub effect_properties: Option<EffectProperties>,
     // TODO implement
     //pub scene_3d: Option<Scene3D>,
+    pub unknown: UnknownContent,
 }
 
-impl GroupShapeProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let black_and_white_mode = xml_node
-            .attributes
-            .get("bwMode")
-            .map(|value| value.parse())
-            .transpose()?;
-
-        xml_node.child_nodes.iter().try_fold(
-            Self {
-                black_and_white_mode,
-                ..Default::default()
-            },
-            |mut instance, child_node| {
-                match child_node.local_name() {
-                    "xfrm" => instance.transform = Some(Box::new(GroupTransform2D::from_xml_element(child_node)?)),
-                    child_name if FillProperties::is_choice_member(child_name) => {
-                        instance.fill_properties = Some(FillProperties::from_xml_element(child_node)?)
-                    }
-                    child_name if EffectProperties::is_choice_member(child_name) => {
-                        instance.effect_properties = Some(EffectProperties::from_xml_element(child_node)?)
-                    }
-                    _ => (),
+impl XsdType for GroupShapeProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "bwMode" => {
+                    instance.black_and_white_mode = instance.unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
                 }
+                _ => instance.unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-                Ok(instance)
-            },
-        )
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "xfrm" => {
+                    instance.transform = instance
+                        .unknown
+                        .recover_element(
+                            GroupTransform2D::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                child_name if FillProperties::is_choice_member(child_name) => {
+                    instance.fill_properties = instance.unknown.recover_element(
+                        FillProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                child_name if EffectProperties::is_choice_member(child_name) => {
+                    instance.effect_properties = instance.unknown.recover_element(
+                        EffectProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for GroupShapeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("bwMode", self.black_and_white_mode);
+        node.child_nodes.extend(
+            self.transform
+                .as_ref()
+                .map(|transform| transform.to_xml_element("a:xfrm")),
+        );
+        node.child_nodes
+            .extend(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.effect_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1078,20 +1908,50 @@ pub struct LineProperties {
 
     /// This element specifies decorations which can be added to the tail of a line.
     pub tail_end: Option<LineEndProperties>,
+
+    pub unknown: UnknownContent,
 }
 
-impl LineProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<LineProperties> {
+impl XsdType for LineProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "w" => instance.width = Some(value.parse()?),
-                    "cap" => instance.cap = Some(value.parse()?),
-                    "cmpd" => instance.compound = Some(value.parse()?),
-                    "algn" => instance.pen_alignment = Some(value.parse()?),
-                    _ => (),
+                    "w" => {
+                        instance.width = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "cap" => {
+                        instance.cap = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "cmpd" => {
+                        instance.compound = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "algn" => {
+                        instance.pen_alignment = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -1100,20 +1960,50 @@ impl LineProperties {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
-                            "headEnd" => instance.head_end = Some(LineEndProperties::from_xml_element(child_node)?),
-                            "tailEnd" => instance.tail_end = Some(LineEndProperties::from_xml_element(child_node)?),
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
+                            "headEnd" => {
+                                instance.head_end = instance.unknown.recover_element(
+                                    LineEndProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "tailEnd" => {
+                                instance.tail_end = instance.unknown.recover_element(
+                                    LineEndProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
                             child_name if LineFillProperties::is_choice_member(child_name) => {
-                                instance.fill_properties = Some(LineFillProperties::from_xml_element(child_node)?)
+                                instance.fill_properties = instance.unknown.recover_element(
+                                    LineFillProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
                             }
                             child_name if LineDashProperties::is_choice_member(child_name) => {
-                                instance.dash_properties = Some(LineDashProperties::from_xml_element(child_node)?)
+                                instance.dash_properties = instance.unknown.recover_element(
+                                    LineDashProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
                             }
                             child_name if LineJoinProperties::is_choice_member(child_name) => {
-                                instance.join_properties = Some(LineJoinProperties::from_xml_element(child_node)?)
+                                instance.join_properties = instance.unknown.recover_element(
+                                    LineJoinProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
                             }
-                            _ => (),
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -1122,6 +2012,34 @@ impl LineProperties {
     }
 }
 
+impl ToXmlElement for LineProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("w", self.width);
+        node.set_optional_attribute("cap", self.cap);
+        node.set_optional_attribute("cmpd", self.compound);
+        node.set_optional_attribute("algn", self.pen_alignment);
+        node.child_nodes
+            .extend(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.dash_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.join_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.extend(
+            self.head_end
+                .as_ref()
+                .map(|head_end| head_end.to_xml_element("a:headEnd")),
+        );
+        node.child_nodes.extend(
+            self.tail_end
+                .as_ref()
+                .map(|tail_end| tail_end.to_xml_element("a:tailEnd")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct ShapeProperties {
     /// Specifies that the picture should be rendered using only black and white coloring. That is
@@ -1154,40 +2072,118 @@ pub struct ShapeProperties {
     // TODO implement
     //pub scene_3d: Option<Scene3D>,
     //pub shape_3d: Option<Shape3D>,
+    /// Future extensions, e.g. the fill and outline of the shape that's only rendered by older applications.
+    pub extension_list: Option<OfficeArtExtensionList>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ShapeProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let black_and_white_mode = xml_node
-            .attributes
-            .get("bwMode")
-            .map(|value| value.parse())
-            .transpose()?;
-
-        xml_node.child_nodes.iter().try_fold(
-            Self {
-                black_and_white_mode,
-                ..Default::default()
-            },
-            |mut instance, child_node| {
-                match child_node.local_name() {
-                    "xfrm" => instance.transform = Some(Box::new(Transform2D::from_xml_element(child_node)?)),
-                    "ln" => instance.line_properties = Some(Box::new(LineProperties::from_xml_element(child_node)?)),
-                    child_name if Geometry::is_choice_member(child_name) => {
-                        instance.geometry = Some(Geometry::from_xml_element(child_node)?)
-                    }
-                    child_name if FillProperties::is_choice_member(child_name) => {
-                        instance.fill_properties = Some(FillProperties::from_xml_element(child_node)?)
-                    }
-                    child_name if EffectProperties::is_choice_member(child_name) => {
-                        instance.effect_properties = Some(EffectProperties::from_xml_element(child_node)?)
-                    }
-                    _ => (),
+impl XsdType for ShapeProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "bwMode" => {
+                    instance.black_and_white_mode = instance.unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
                 }
+                _ => instance.unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-                Ok(instance)
-            },
-        )
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "xfrm" => {
+                    instance.transform = instance
+                        .unknown
+                        .recover_element(
+                            Transform2D::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "ln" => {
+                    instance.line_properties = instance
+                        .unknown
+                        .recover_element(
+                            LineProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "extLst" => {
+                    instance.extension_list = instance.unknown.recover_element(
+                        OfficeArtExtensionList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                child_name if Geometry::is_choice_member(child_name) => {
+                    instance.geometry = instance.unknown.recover_element(
+                        Geometry::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                child_name if FillProperties::is_choice_member(child_name) => {
+                    instance.fill_properties = instance.unknown.recover_element(
+                        FillProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                child_name if EffectProperties::is_choice_member(child_name) => {
+                    instance.effect_properties = instance.unknown.recover_element(
+                        EffectProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for ShapeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("bwMode", self.black_and_white_mode);
+        node.child_nodes.extend(
+            self.transform
+                .as_ref()
+                .map(|transform| transform.to_xml_element("a:xfrm")),
+        );
+        node.child_nodes
+            .extend(self.geometry.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.line_properties.as_ref().map(|line| line.to_xml_element("a:ln")));
+        node.child_nodes
+            .extend(self.effect_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.extend(
+            self.extension_list
+                .as_ref()
+                .map(|extension_list| extension_list.to_xml_element("a:extLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1213,43 +2209,70 @@ pub struct ShapeStyle {
     /// </fontRef>
     /// ```
     pub font_reference: FontReference,
+
+    pub unknown: UnknownContent,
 }
 
-impl ShapeStyle {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for ShapeStyle {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut line_reference = None;
         let mut fill_reference = None;
         let mut effect_reference = None;
         let mut font_reference = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "lnRef" => line_reference = Some(StyleMatrixReference::from_xml_element(child_node)?),
-                "fillRef" => fill_reference = Some(StyleMatrixReference::from_xml_element(child_node)?),
-                "effectRef" => effect_reference = Some(StyleMatrixReference::from_xml_element(child_node)?),
-                "fontRef" => font_reference = Some(FontReference::from_xml_element(child_node)?),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "lnRef" => {
+                    line_reference = Some(StyleMatrixReference::from_xml_element_with_context(
+                        child_node, context,
+                    )?)
+                }
+                "fillRef" => {
+                    fill_reference = Some(StyleMatrixReference::from_xml_element_with_context(
+                        child_node, context,
+                    )?)
+                }
+                "effectRef" => {
+                    effect_reference = Some(StyleMatrixReference::from_xml_element_with_context(
+                        child_node, context,
+                    )?)
+                }
+                "fontRef" => font_reference = Some(FontReference::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let line_reference =
-            line_reference.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "lnRef"))?;
-        let fill_reference =
-            fill_reference.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "fillRef"))?;
-        let effect_reference =
-            effect_reference.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "effectRef"))?;
-        let font_reference =
-            font_reference.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "fontRef"))?;
+        let line_reference = line_reference.ok_or_else(|| MissingChildNodeError::at(xml_node, "lnRef"))?;
+        let fill_reference = fill_reference.ok_or_else(|| MissingChildNodeError::at(xml_node, "fillRef"))?;
+        let effect_reference = effect_reference.ok_or_else(|| MissingChildNodeError::at(xml_node, "effectRef"))?;
+        let font_reference = font_reference.ok_or_else(|| MissingChildNodeError::at(xml_node, "fontRef"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             line_reference,
             fill_reference,
             effect_reference,
             font_reference,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ShapeStyle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.line_reference.to_xml_element("a:lnRef"));
+        node.child_nodes.push(self.fill_reference.to_xml_element("a:fillRef"));
+        node.child_nodes
+            .push(self.effect_reference.to_xml_element("a:effectRef"));
+        node.child_nodes.push(self.font_reference.to_xml_element("a:fontRef"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextBody {
     /// Specifies the properties of this text body.
@@ -1289,34 +2312,73 @@ pub struct TextBody {
     /// </p:txBody>
     /// ```
     pub paragraph_array: Vec<TextParagraph>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextBody {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TextBody {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut body_properties = None;
         let mut list_style = None;
         let mut paragraph_array = Vec::new();
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "bodyPr" => body_properties = Some(Box::new(TextBodyProperties::from_xml_element(child_node)?)),
-                "lstStyle" => list_style = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
-                "p" => paragraph_array.push(TextParagraph::from_xml_element(child_node)?),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "bodyPr" => {
+                    body_properties = Some(Box::new(TextBodyProperties::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                "lstStyle" => {
+                    list_style = unknown
+                        .recover_element(
+                            TextListStyle::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "p" => paragraph_array.extend(unknown.recover_element(
+                    TextParagraph::from_xml_element_with_context(child_node, context),
+                    position,
+                    child_node,
+                    context,
+                )?),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let body_properties =
-            body_properties.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "bodyPr"))?;
+        let body_properties = body_properties.ok_or_else(|| MissingChildNodeError::at(xml_node, "bodyPr"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             body_properties,
             list_style,
             paragraph_array,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for TextBody {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.body_properties.to_xml_element("a:bodyPr"));
+        node.child_nodes
+            .extend(self.list_style.as_ref().map(|style| style.to_xml_element("a:lstStyle")));
+        node.child_nodes.extend(
+            self.paragraph_array
+                .iter()
+                .map(|paragraph| paragraph.to_xml_element("a:p")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Hyperlink {
     /// Specifies the relationship id that when looked up in this slides relationship file contains
@@ -1363,37 +2425,108 @@ pub struct Hyperlink {
     /// Defaults to false
     pub end_sound: Option<bool>,
     pub sound: Option<EmbeddedWAVAudioFile>,
+
+    pub unknown: UnknownContent,
 }
 
-impl Hyperlink {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let sound = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "snd")
-            .map(EmbeddedWAVAudioFile::from_xml_element)
-            .transpose()?;
-
-        xml_node.attributes.iter().try_fold(
-            Self {
-                sound,
-                ..Default::default()
-            },
-            |mut instance, (attr, value)| {
-                match attr.as_str() {
-                    "r:id" => instance.relationship_id = Some(value.clone()),
-                    "invalidUrl" => instance.invalid_url = Some(value.clone()),
-                    "action" => instance.action = Some(value.clone()),
-                    "tgtFrame" => instance.target_frame = Some(value.clone()),
-                    "tooltip" => instance.tooltip = Some(value.clone()),
-                    "history" => instance.history = Some(parse_xml_bool(value)?),
-                    "highlightClick" => instance.highlight_click = Some(parse_xml_bool(value)?),
-                    "endSnd" => instance.end_sound = Some(parse_xml_bool(value)?),
-                    _ => (),
+impl XsdType for Hyperlink {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match xml_node.relationship_attr_name(attr).as_ref() {
+                "r:id" => instance.relationship_id = Some(value.clone()),
+                "invalidUrl" => instance.invalid_url = Some(value.clone()),
+                "action" => instance.action = Some(value.clone()),
+                "tgtFrame" => instance.target_frame = Some(value.clone()),
+                "tooltip" => instance.tooltip = Some(value.clone()),
+                "history" => {
+                    instance.history = instance.unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "highlightClick" => {
+                    instance.highlight_click = instance.unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
                 }
+                "endSnd" => {
+                    instance.end_sound = instance.unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => instance.unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-                Ok(instance)
-            },
-        )
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "snd" => {
+                    instance.sound = instance.unknown.recover_element(
+                        EmbeddedWAVAudioFile::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for Hyperlink {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("r:id", self.relationship_id.as_ref());
+        node.set_optional_attribute("invalidUrl", self.invalid_url.as_ref());
+        node.set_optional_attribute("action", self.action.as_ref());
+        node.set_optional_attribute("tgtFrame", self.target_frame.as_ref());
+        node.set_optional_attribute("tooltip", self.tooltip.as_ref());
+        node.set_optional_attribute("history", self.history.map(format_xml_bool));
+        node.set_optional_attribute("highlightClick", self.highlight_click.map(format_xml_bool));
+        node.set_optional_attribute("endSnd", self.end_sound.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.sound.as_ref().map(|sound| sound.to_xml_element("a:snd")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_text_body_lenient() {
+        let xml = r#"<p:txBody xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+            xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+            <a:bodyPr/>
+            <a:p><a:r><a:rPr lang="en-US"/></a:r><a:r><a:t>Text</a:t></a:r></a:p>
+        </p:txBody>"#;
+        let xml_node = XmlNode::from_str(xml).unwrap();
+        assert!(TextBody::from_xml_element(&xml_node).is_err());
+
+        let parsed = TextBody::from_xml_element_lenient(&xml_node).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.diagnostics[0].path().map(ToString::to_string).as_deref(),
+            Some("p:txBody/a:p/a:r")
+        );
+        let paragraph = &parsed.value.paragraph_array[0];
+        assert_eq!(paragraph.text_run_list.len(), 1);
+        assert_eq!(paragraph.to_xml_element("a:p").child_nodes.len(), 2);
     }
 }
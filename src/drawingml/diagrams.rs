@@ -1,35 +1,63 @@
 use super::{
     core::LineProperties,
     shapeprops::{EffectProperties, FillProperties},
+    util::XmlNodeExt,
 };
 use crate::{
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct BackgroundFormatting {
     pub fill: Option<FillProperties>,
     pub effect: Option<EffectProperties>,
+
+    pub unknown: UnknownContent,
 }
 
-impl BackgroundFormatting {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                let node_name = child_node.local_name();
-                if FillProperties::is_choice_member(node_name) {
-                    instance.fill = Some(FillProperties::from_xml_element(child_node)?);
-                } else if EffectProperties::is_choice_member(node_name) {
-                    instance.effect = Some(EffectProperties::from_xml_element(child_node)?);
+impl XsdType for BackgroundFormatting {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        instance.unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                node_name if FillProperties::is_choice_member(node_name) => {
+                    instance.fill = instance.unknown.recover_element(
+                        FillProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                node_name if EffectProperties::is_choice_member(node_name) => {
+                    instance.effect = instance.unknown.recover_element(
+                        EffectProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
                 }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for BackgroundFormatting {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.fill.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.effect.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -37,72 +65,111 @@ impl BackgroundFormatting {
 pub struct WholeE2oFormatting {
     pub line: Option<LineProperties>,
     pub effect: Option<EffectProperties>,
+
+    pub unknown: UnknownContent,
 }
 
-impl WholeE2oFormatting {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "ln" => instance.line = Some(LineProperties::from_xml_element(child_node)?),
-                    node_name if EffectProperties::is_choice_member(node_name) => {
-                        instance.effect = Some(EffectProperties::from_xml_element(child_node)?)
-                    }
-                    _ => (),
+impl XsdType for WholeE2oFormatting {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        instance.unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "ln" => {
+                    instance.line = instance.unknown.recover_element(
+                        LineProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                node_name if EffectProperties::is_choice_member(node_name) => {
+                    instance.effect = instance.unknown.recover_element(
+                        EffectProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
                 }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for WholeE2oFormatting {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.line.as_ref().map(|line| line.to_xml_element("a:ln")));
+        node.child_nodes
+            .extend(self.effect.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xml::ns;
     use std::str::FromStr;
 
     impl BackgroundFormatting {
         pub fn test_xml(node_name: &'static str) -> String {
-            format!(r#"<{node_name}><noFill /></{node_name}>"#, node_name = node_name)
+            format!(r#"<{node_name}><a:noFill /></{node_name}>"#, node_name = node_name)
         }
 
         pub fn test_instance() -> Self {
             Self {
                 fill: Some(FillProperties::NoFill),
                 effect: None,
+                unknown: Default::default(),
             }
         }
     }
 
     #[test]
     pub fn test_background_formatting_from_xml() {
-        let xml = BackgroundFormatting::test_xml("backgroundFormatting");
+        let xml = format!(
+            r#"<root xmlns:a="{}">{}</root>"#,
+            ns::DRAWINGML,
+            BackgroundFormatting::test_xml("backgroundFormatting")
+        );
+        let xml_node = XmlNode::from_str(xml.as_str()).unwrap();
         assert_eq!(
-            BackgroundFormatting::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            BackgroundFormatting::from_xml_element(&xml_node.child_nodes[0]).unwrap(),
             BackgroundFormatting::test_instance(),
         );
     }
 
     impl WholeE2oFormatting {
         pub fn test_xml(node_name: &'static str) -> String {
-            format!(r#"<{node_name}><ln /></{node_name}>"#, node_name = node_name)
+            format!(r#"<{node_name}><a:ln /></{node_name}>"#, node_name = node_name)
         }
 
         pub fn test_instance() -> Self {
             Self {
                 line: Some(Default::default()),
                 effect: None,
+                unknown: Default::default(),
             }
         }
     }
 
     #[test]
     pub fn test_whole_e2o_formatting_from_xml() {
-        let xml = WholeE2oFormatting::test_xml("wholeE2oFormatting");
+        let xml = format!(
+            r#"<root xmlns:a="{}">{}</root>"#,
+            ns::DRAWINGML,
+            WholeE2oFormatting::test_xml("wholeE2oFormatting")
+        );
+        let xml_node = XmlNode::from_str(xml.as_str()).unwrap();
         assert_eq!(
-            WholeE2oFormatting::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            WholeE2oFormatting::from_xml_element(&xml_node.child_nodes[0]).unwrap(),
             WholeE2oFormatting::test_instance(),
         );
     }
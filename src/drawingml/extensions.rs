@@ -0,0 +1,744 @@
+//! Office Art extension lists (`a:extLst`).
+//!
+//! Later versions of Office store their additions to DrawingML in `a:ext` elements identified by a GUID `uri`. The
+//! extensions this crate knows about are parsed to typed values by the `ExtensionRegistry` of the `ParseContext`,
+//! every other extension is kept as the raw `a:ext` element, so it's written back unchanged. Parsers of other
+//! extensions can be registered to the registry, their content is stored as an `ExtensionContent` trait object.
+//!
+//! Typed extensions keep whatever their `a:ext` element and its content element hold besides the typed value in an
+//! `ExtensionUnknownContent`, so they're written back unchanged as well.
+
+use super::{
+    core::LineProperties,
+    shapeprops::FillProperties,
+    util::{dml_element, XmlNodeExt},
+};
+use crate::{
+    diagnostics::recover_element,
+    error::{MissingAttributeError, MissingChildNodeError, Result},
+    relationship::RelationshipId,
+    xml::{format_xml_bool, ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdType},
+};
+use std::{any::Any, collections::HashMap, fmt::Debug};
+
+/// URIs of the extensions parsed by the default `ExtensionRegistry`.
+pub mod uri {
+    /// `a14:hiddenFill`: fill of a shape that's hidden by Office 2010 and later, but rendered by older versions
+    pub const HIDDEN_FILL: &str = "{909E8E84-426E-40DD-AFC4-6F175D3DCCD1}";
+    /// `a14:hiddenLine`: outline of a shape that's hidden by Office 2010 and later, but rendered by older versions
+    pub const HIDDEN_LINE: &str = "{91240B29-F687-4F45-9708-019B960494DF}";
+    /// `a14:useLocalDpi`: whether a picture is stored in its original resolution
+    pub const USE_LOCAL_DPI: &str = "{28A0092B-C50C-407E-A947-70E740481C1C}";
+    /// `a14:imgProps`: image editing properties of a picture, e.g. artistic effects
+    pub const IMAGE_PROPERTIES: &str = "{BEBA8EAE-BF5A-486C-A8C5-ECC9F3942E4B}";
+    /// `adec:decorative`: marks an object as decorative, so it's skipped by screen readers
+    pub const DECORATIVE: &str = "{C183D7F6-B498-43B3-948B-1728B52AA6E4}";
+}
+
+/// Parses an `a:ext` element to an `Extension`. Parsers of extensions unknown to this crate return
+/// `Extension::Custom`.
+pub type ExtensionParser = fn(&XmlNode, &mut ParseContext) -> Result<Extension>;
+
+/// Maps extension URIs to the parsers of their content. URIs are compared case insensitively.
+///
+/// The registry used while parsing is set with `ParseContext::with_extension_registry`. The default registry has a
+/// parser for each URI in `uri`.
+#[derive(Debug, Clone)]
+pub struct ExtensionRegistry {
+    parsers: HashMap<String, ExtensionParser>,
+}
+
+impl Default for ExtensionRegistry {
+    /// Creates a registry with a parser for each URI in `uri`.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(uri::HIDDEN_FILL, parse_hidden_fill);
+        registry.register(uri::HIDDEN_LINE, parse_hidden_line);
+        registry.register(uri::USE_LOCAL_DPI, parse_use_local_dpi);
+        registry.register(uri::IMAGE_PROPERTIES, parse_image_properties);
+        registry.register(uri::DECORATIVE, parse_decorative);
+        registry
+    }
+}
+
+impl ExtensionRegistry {
+    /// Creates an empty registry, which keeps every extension as `Extension::Unknown`.
+    pub fn new() -> Self {
+        Self {
+            parsers: HashMap::new(),
+        }
+    }
+
+    /// Registers the parser of an extension URI, replacing the previously registered parser if there's any.
+    pub fn register<T: AsRef<str>>(&mut self, uri: T, parser: ExtensionParser) {
+        self.parsers.insert(uri.as_ref().to_uppercase(), parser);
+    }
+
+    pub fn unregister<T: AsRef<str>>(&mut self, uri: T) -> Option<ExtensionParser> {
+        self.parsers.remove(&uri.as_ref().to_uppercase())
+    }
+
+    pub fn is_registered<T: AsRef<str>>(&self, uri: T) -> bool {
+        self.parsers.contains_key(&uri.as_ref().to_uppercase())
+    }
+
+    /// Returns the parser registered for an extension URI
+    pub fn get<T: AsRef<str>>(&self, uri: T) -> Option<ExtensionParser> {
+        self.parsers.get(&uri.as_ref().to_uppercase()).copied()
+    }
+}
+
+/// The typed content of an extension parsed by a parser registered by the user of the crate, e.g. an extension
+/// defined by a newer version of Office that this crate doesn't know about yet.
+///
+/// It's implemented for every type that implements `ToXmlChoice`, `Clone`, `PartialEq` and `Debug`. The content is
+/// written back as the only child of the `a:ext` element. Use `downcast_ref` to get the concrete type back.
+pub trait ExtensionContent: Debug + Send + Sync {
+    /// Converts the content to the child of the `a:ext` element
+    fn to_content_element(&self) -> XmlNode;
+
+    fn clone_box(&self) -> Box<dyn ExtensionContent>;
+
+    fn eq_content(&self, other: &dyn ExtensionContent) -> bool;
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T> ExtensionContent for T
+where
+    T: ToXmlChoice + Clone + PartialEq + Debug + Send + Sync + 'static,
+{
+    fn to_content_element(&self) -> XmlNode {
+        ToXmlChoice::to_xml_element(self)
+    }
+
+    fn clone_box(&self) -> Box<dyn ExtensionContent> {
+        Box::new(self.clone())
+    }
+
+    fn eq_content(&self, other: &dyn ExtensionContent) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn ExtensionContent {
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+impl Clone for Box<dyn ExtensionContent> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for dyn ExtensionContent {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_content(other)
+    }
+}
+
+/// Content of an extension that isn't described by its typed value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtensionUnknownContent {
+    /// The `uri` attribute of the `a:ext` element as it was read, so it's written back in the same form (e.g. in lower
+    /// case). None to write the URI returned by `Extension::uri`.
+    pub uri: Option<String>,
+    /// Unknown content of the `a:ext` element, i.e. everything but its `uri` attribute and the content element
+    pub ext: UnknownContent,
+    /// Unknown content of the content element (e.g. `a14:hiddenFill`). Typed values that have an `UnknownContent` of
+    /// their own (e.g. `LineProperties`) keep it there instead.
+    pub content: UnknownContent,
+}
+
+/// The content of an `a:ext` element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Extension {
+    HiddenFill {
+        fill_properties: FillProperties,
+        unknown: ExtensionUnknownContent,
+    },
+    HiddenLine {
+        line_properties: Box<LineProperties>,
+        unknown: ExtensionUnknownContent,
+    },
+    UseLocalDpi {
+        value: bool,
+        unknown: ExtensionUnknownContent,
+    },
+    ImageProperties {
+        image_properties: ImageProperties,
+        unknown: ExtensionUnknownContent,
+    },
+    Decorative {
+        value: bool,
+        unknown: ExtensionUnknownContent,
+    },
+    /// An extension parsed by a parser registered by the user of the crate
+    Custom {
+        uri: String,
+        content: Box<dyn ExtensionContent>,
+        unknown: ExtensionUnknownContent,
+    },
+    /// An extension without a registered parser, kept as the whole `a:ext` element. In lenient mode extensions that
+    /// fail to parse, or don't have a `uri` attribute, are kept this way too.
+    Unknown(XmlNode),
+}
+
+impl Extension {
+    /// Creates an `Extension::Custom` without any unknown content. A parser can set the unknown content of the
+    /// extension with `with_unknown`.
+    pub fn custom<T: Into<String>, C: ExtensionContent + 'static>(uri: T, content: C) -> Self {
+        Extension::Custom {
+            uri: uri.into(),
+            content: Box::new(content),
+            unknown: Default::default(),
+        }
+    }
+
+    /// Replaces the unknown content of a typed extension. `Extension::Unknown` is returned unchanged.
+    pub fn with_unknown(mut self, unknown_content: ExtensionUnknownContent) -> Self {
+        match &mut self {
+            Extension::HiddenFill { unknown, .. }
+            | Extension::HiddenLine { unknown, .. }
+            | Extension::UseLocalDpi { unknown, .. }
+            | Extension::ImageProperties { unknown, .. }
+            | Extension::Decorative { unknown, .. }
+            | Extension::Custom { unknown, .. } => *unknown = unknown_content,
+            Extension::Unknown(_) => (),
+        }
+
+        self
+    }
+
+    /// Returns the URI identifying the extension. None if an unknown extension is missing its `uri` attribute.
+    pub fn uri(&self) -> Option<&str> {
+        match self {
+            Extension::HiddenFill { .. } => Some(uri::HIDDEN_FILL),
+            Extension::HiddenLine { .. } => Some(uri::HIDDEN_LINE),
+            Extension::UseLocalDpi { .. } => Some(uri::USE_LOCAL_DPI),
+            Extension::ImageProperties { .. } => Some(uri::IMAGE_PROPERTIES),
+            Extension::Decorative { .. } => Some(uri::DECORATIVE),
+            Extension::Custom { uri, .. } => Some(uri),
+            Extension::Unknown(xml_node) => xml_node.attributes.get("uri").map(String::as_str),
+        }
+    }
+}
+
+impl ToXmlChoice for Extension {
+    fn to_xml_element(&self) -> XmlNode {
+        let (mut content, unknown) = match self {
+            Extension::HiddenFill {
+                fill_properties,
+                unknown,
+            } => {
+                let mut node = XmlNode::new_with_conventional_namespace("a14:hiddenFill");
+                node.child_nodes.push(fill_properties.to_xml_element());
+                (node, unknown)
+            }
+            Extension::HiddenLine {
+                line_properties,
+                unknown,
+            } => (line_properties.to_xml_element("a14:hiddenLine"), unknown),
+            Extension::UseLocalDpi { value, unknown } => {
+                let mut node = XmlNode::new_with_conventional_namespace("a14:useLocalDpi");
+                node.set_val_attribute(format_xml_bool(*value));
+                (node, unknown)
+            }
+            Extension::ImageProperties {
+                image_properties,
+                unknown,
+            } => (image_properties.to_xml_element("a14:imgProps"), unknown),
+            Extension::Decorative { value, unknown } => {
+                let mut node = XmlNode::new_with_conventional_namespace("adec:decorative");
+                node.set_val_attribute(format_xml_bool(*value));
+                (node, unknown)
+            }
+            Extension::Custom { content, unknown, .. } => (content.to_content_element(), unknown),
+            Extension::Unknown(xml_node) => return xml_node.clone(),
+        };
+        unknown.content.apply_to(&mut content);
+
+        let mut node = dml_element("ext");
+        node.set_attribute("uri", unknown.uri.as_deref().or_else(|| self.uri()).unwrap_or_default());
+        node.child_nodes.push(content);
+        unknown.ext.apply_to(&mut node);
+        node
+    }
+}
+
+/// A list of extensions (`a:extLst`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OfficeArtExtensionList {
+    pub extensions: Vec<Extension>,
+
+    pub unknown: UnknownContent,
+}
+
+impl XsdType for OfficeArtExtensionList {
+    /// Parses each extension with the parser registered for its URI in the `ExtensionRegistry` of `context`. An
+    /// extension without a parser is kept as `Extension::Unknown`, just like one that fails to parse in lenient mode.
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        unknown.push_all_attributes(xml_node);
+
+        let mut extensions = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if !child_node.is(ns::DRAWINGML, "ext") {
+                unknown.push_element(position, child_node);
+                continue;
+            }
+
+            let uri = recover_element(
+                child_node
+                    .attributes
+                    .get("uri")
+                    .ok_or_else(|| MissingAttributeError::at(child_node, "uri").into()),
+                context,
+            )?;
+
+            let extension = match uri.and_then(|uri| context.extension_registry().get(uri)) {
+                Some(parser) => recover_element(parser(child_node, context), context)?,
+                None => None,
+            };
+
+            extensions.push(extension.unwrap_or_else(|| Extension::Unknown(child_node.clone())));
+        }
+
+        Ok(Self { extensions, unknown })
+    }
+}
+
+impl OfficeArtExtensionList {
+    /// Returns the first extension with the given URI
+    pub fn find<T: AsRef<str>>(&self, uri: T) -> Option<&Extension> {
+        self.extensions.iter().find(|extension| {
+            extension
+                .uri()
+                .map(|ext_uri| ext_uri.eq_ignore_ascii_case(uri.as_ref()))
+                == Some(true)
+        })
+    }
+}
+
+impl ToXmlElement for OfficeArtExtensionList {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.extensions.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+/// Image editing properties of a picture (`a14:imgProps`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageProperties {
+    pub image_layer: Option<ImageLayer>,
+
+    pub unknown: UnknownContent,
+}
+
+impl XsdType for ImageProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut image_layer = None;
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.local_name_in(ns::DRAWINGML_2010) {
+                Some("imgLayer") => {
+                    image_layer = unknown.recover_element(
+                        ImageLayer::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self { image_layer, unknown })
+    }
+}
+
+impl ToXmlElement for ImageProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.extend(
+            self.image_layer
+                .as_ref()
+                .map(|layer| layer.to_xml_element("a14:imgLayer")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+/// The original image of an edited picture along with the effects applied to it (`a14:imgLayer`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageLayer {
+    /// Relationship id of the unedited image
+    pub embed_rel_id: Option<RelationshipId>,
+
+    /// The `a14:imgEffect` elements in the order they're applied
+    pub effects: Vec<XmlNode>,
+
+    pub unknown: UnknownContent,
+}
+
+impl XsdType for ImageLayer {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut embed_rel_id = None;
+        let mut effects = Vec::new();
+
+        for (attr, value) in &xml_node.attributes {
+            match xml_node.relationship_attr_name(attr).as_ref() {
+                "r:embed" => embed_rel_id = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.local_name_in(ns::DRAWINGML_2010) {
+                Some("imgEffect") => effects.push(child_node.clone()),
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self {
+            embed_rel_id,
+            effects,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for ImageLayer {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("r:embed", self.embed_rel_id.as_ref());
+        node.child_nodes.extend(self.effects.iter().cloned());
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+// Returns the child of an `a:ext` element with the given name in the namespace of the extension, along with the `uri`
+// and the unknown content of the `a:ext` element
+fn extension_content<'a>(
+    xml_node: &'a XmlNode,
+    namespace: &str,
+    local_name: &'static str,
+) -> Result<(&'a XmlNode, ExtensionUnknownContent)> {
+    let position = xml_node
+        .child_nodes
+        .iter()
+        .position(|child_node| child_node.is(namespace, local_name))
+        .ok_or_else(|| MissingChildNodeError::at(xml_node, local_name))?;
+
+    let mut unknown = UnknownContent::default();
+    for attr in xml_node.attributes.keys().filter(|attr| *attr != "uri") {
+        unknown.push_attribute(xml_node, attr);
+    }
+
+    for (other_position, child_node) in xml_node.child_nodes.iter().enumerate() {
+        if other_position != position {
+            unknown.push_element(other_position, child_node);
+        }
+    }
+
+    let unknown = ExtensionUnknownContent {
+        uri: xml_node.attributes.get("uri").cloned(),
+        ext: unknown,
+        content: Default::default(),
+    };
+    Ok((&xml_node.child_nodes[position], unknown))
+}
+
+fn parse_hidden_fill(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Extension> {
+    let (hidden_fill, mut unknown) = extension_content(xml_node, ns::DRAWINGML_2010, "hiddenFill")?;
+    unknown.content.push_all_attributes(hidden_fill);
+
+    let mut fill_properties = None;
+    for (position, child_node) in hidden_fill.child_nodes.iter().enumerate() {
+        match fill_properties {
+            None => fill_properties = unknown.content.parse_choice_element(position, child_node, context)?,
+            Some(_) => unknown.content.push_element(position, child_node),
+        }
+    }
+
+    Ok(Extension::HiddenFill {
+        fill_properties: fill_properties.ok_or_else(|| MissingChildNodeError::at(hidden_fill, "EG_FillProperties"))?,
+        unknown,
+    })
+}
+
+fn parse_hidden_line(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Extension> {
+    let (hidden_line, unknown) = extension_content(xml_node, ns::DRAWINGML_2010, "hiddenLine")?;
+    Ok(Extension::HiddenLine {
+        line_properties: Box::new(LineProperties::from_xml_element_with_context(hidden_line, context)?),
+        unknown,
+    })
+}
+
+fn parse_use_local_dpi(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Extension> {
+    let (value, unknown) = parse_bool_extension(xml_node, ns::DRAWINGML_2010, "useLocalDpi", context)?;
+    Ok(Extension::UseLocalDpi { value, unknown })
+}
+
+fn parse_image_properties(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Extension> {
+    let (image_properties, unknown) = extension_content(xml_node, ns::DRAWINGML_2010, "imgProps")?;
+    Ok(Extension::ImageProperties {
+        image_properties: ImageProperties::from_xml_element_with_context(image_properties, context)?,
+        unknown,
+    })
+}
+
+fn parse_decorative(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Extension> {
+    let (value, unknown) = parse_bool_extension(xml_node, ns::DRAWINGML_DECORATIVE, "decorative", context)?;
+    Ok(Extension::Decorative { value, unknown })
+}
+
+// Parses an extension whose content element holds a single bool `val` attribute, which defaults to true
+fn parse_bool_extension(
+    xml_node: &XmlNode,
+    namespace: &str,
+    local_name: &'static str,
+    context: &mut ParseContext,
+) -> Result<(bool, ExtensionUnknownContent)> {
+    let (content, mut unknown) = extension_content(xml_node, namespace, local_name)?;
+    let mut value = None;
+
+    for (attr, attr_value) in &content.attributes {
+        match attr.as_str() {
+            "val" => {
+                value = unknown.content.recover_attribute(
+                    content.parse_bool_attribute_value(attr, attr_value),
+                    content,
+                    attr,
+                    context,
+                )?
+            }
+            _ => unknown.content.push_attribute(content, attr),
+        }
+    }
+
+    unknown.content.push_all_elements(content);
+    Ok((value.unwrap_or(true), unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawingml::shapeprops::Blip;
+    use std::str::FromStr;
+
+    const EXTENSION_LIST_XML: &str = r#"<a:extLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+        <a:ext uri="{909E8E84-426E-40DD-AFC4-6F175D3DCCD1}">
+            <a14:hiddenFill xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main"><a:noFill/></a14:hiddenFill>
+        </a:ext>
+        <a:ext uri="{28a0092b-c50c-407e-a947-70e740481c1c}">
+            <a14:useLocalDpi xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main" val="0"/>
+        </a:ext>
+        <a:ext uri="{C183D7F6-B498-43B3-948B-1728B52AA6E4}">
+            <adec:decorative xmlns:adec="http://schemas.microsoft.com/office/drawing/2017/decorative" val="1"/>
+        </a:ext>
+        <a:ext uri="{FF2B5EF4-FFF2-40B4-BE49-F238E27FC236}">
+            <a16:creationId xmlns:a16="http://schemas.microsoft.com/office/drawing/2014/main" id="{1}"/>
+        </a:ext>
+    </a:extLst>"#;
+
+    #[test]
+    fn test_extension_list() {
+        let xml_node = XmlNode::from_str(EXTENSION_LIST_XML).unwrap();
+        let extension_list = OfficeArtExtensionList::from_xml_element(&xml_node).unwrap();
+
+        assert!(matches!(
+            &extension_list.extensions[0],
+            Extension::HiddenFill {
+                fill_properties: FillProperties::NoFill,
+                ..
+            }
+        ));
+        assert!(matches!(
+            extension_list.find(uri::USE_LOCAL_DPI),
+            Some(Extension::UseLocalDpi { value: false, .. })
+        ));
+        assert!(matches!(
+            &extension_list.extensions[2],
+            Extension::Decorative { value: true, .. }
+        ));
+        match &extension_list.extensions[3] {
+            Extension::Unknown(node) => assert_eq!(node, &xml_node.child_nodes[3]),
+            extension => panic!("unexpected extension {:?}", extension),
+        }
+
+        let written = extension_list.to_xml_element("a:extLst");
+        assert_eq!(
+            OfficeArtExtensionList::from_xml_element(&written).unwrap(),
+            extension_list
+        );
+        assert!(written.child_nodes[0].child_nodes[0].is(ns::DRAWINGML_2010, "hiddenFill"));
+        // The URI is written back in the form it was read
+        assert_eq!(
+            written.child_nodes[1].attributes["uri"],
+            "{28a0092b-c50c-407e-a947-70e740481c1c}"
+        );
+
+        let mut registry = ExtensionRegistry::default();
+        registry.unregister(uri::HIDDEN_FILL);
+        registry.register(CREATION_ID_URI, parse_creation_id);
+        let mut context = ParseContext::new().with_extension_registry(registry);
+        let extension_list = OfficeArtExtensionList::from_xml_element_with_context(&xml_node, &mut context).unwrap();
+        assert_eq!(
+            extension_list.extensions[0],
+            Extension::Unknown(xml_node.child_nodes[0].clone())
+        );
+        match &extension_list.extensions[3] {
+            Extension::Custom { uri, content, .. } => {
+                assert_eq!(uri, CREATION_ID_URI);
+                assert_eq!(
+                    content.downcast_ref::<CreationId>(),
+                    Some(&CreationId(String::from("{1}")))
+                );
+            }
+            extension => panic!("unexpected extension {:?}", extension),
+        }
+
+        let written = extension_list.to_xml_element("a:extLst");
+        assert_eq!(
+            written.child_nodes[3].child_nodes[0]
+                .attributes
+                .get("id")
+                .map(String::as_str),
+            Some("{1}")
+        );
+        assert_eq!(
+            OfficeArtExtensionList::from_xml_element_with_context(&written, &mut context).unwrap(),
+            extension_list
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct CreationId(String);
+
+    impl ToXmlChoice for CreationId {
+        fn to_xml_element(&self) -> XmlNode {
+            let mut node = XmlNode::new_with_namespace("a16:creationId", CREATION_ID_NAMESPACE);
+            node.attributes.insert(String::from("id"), self.0.clone());
+            node
+        }
+    }
+
+    const CREATION_ID_URI: &str = "{FF2B5EF4-FFF2-40B4-BE49-F238E27FC236}";
+    const CREATION_ID_NAMESPACE: &str = "http://schemas.microsoft.com/office/drawing/2014/main";
+
+    fn parse_creation_id(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Extension> {
+        let (creation_id, _) = extension_content(xml_node, CREATION_ID_NAMESPACE, "creationId")?;
+        let id = creation_id
+            .attributes
+            .get("id")
+            .ok_or_else(|| MissingAttributeError::at(creation_id, "id"))?;
+        Ok(Extension::custom(CREATION_ID_URI, CreationId(id.clone())))
+    }
+
+    #[test]
+    fn test_registry_is_used_for_nested_extension_lists() {
+        let xml_node = XmlNode::from_str(
+            r#"<a:blip xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:extLst>
+                <a:ext uri="{FF2B5EF4-FFF2-40B4-BE49-F238E27FC236}">
+                    <a16:creationId xmlns:a16="http://schemas.microsoft.com/office/drawing/2014/main" id="{2}"/>
+                </a:ext>
+            </a:extLst></a:blip>"#,
+        )
+        .unwrap();
+
+        let mut registry = ExtensionRegistry::new();
+        registry.register(CREATION_ID_URI, parse_creation_id);
+        let mut context = ParseContext::new().with_extension_registry(registry);
+        let blip = Blip::from_xml_element_with_context(&xml_node, &mut context).unwrap();
+        let extension = blip.extension_list.as_ref().unwrap().find(CREATION_ID_URI).unwrap();
+        match extension {
+            Extension::Custom { content, .. } => assert_eq!(
+                content.downcast_ref::<CreationId>(),
+                Some(&CreationId(String::from("{2}")))
+            ),
+            extension => panic!("unexpected extension {:?}", extension),
+        }
+    }
+
+    #[test]
+    fn test_typed_extensions_round_trip() {
+        let xml_node = XmlNode::from_str(
+            r#"<a:extLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:x="urn:x"
+                xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main">
+                <a:ext uri="{909E8E84-426E-40DD-AFC4-6F175D3DCCD1}" x:a="1">
+                    <x:before/><a14:hiddenFill x:b="2"><a:noFill/><x:after/></a14:hiddenFill>
+                </a:ext>
+                <a:ext uri="{28A0092B-C50C-407E-A947-70E740481C1C}"><a14:useLocalDpi val="0" x:c="3"/><x:ext/></a:ext>
+            </a:extLst>"#,
+        )
+        .unwrap();
+
+        let extension_list = OfficeArtExtensionList::from_xml_element(&xml_node).unwrap();
+        match &extension_list.extensions[0] {
+            Extension::HiddenFill {
+                fill_properties,
+                unknown,
+            } => {
+                assert_eq!(fill_properties, &FillProperties::NoFill);
+                assert_eq!(unknown.ext.elements[0].node.name, "x:before");
+                assert_eq!(unknown.content.elements[0].node.name, "x:after");
+            }
+            extension => panic!("unexpected extension {:?}", extension),
+        }
+
+        let written = extension_list.to_xml_element("a:extLst");
+        assert_eq!(written, xml_node);
+    }
+
+    #[test]
+    fn test_extension_without_uri() {
+        let xml_node = XmlNode::from_str(
+            r#"<a:extLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:ext><x/></a:ext></a:extLst>"#,
+        )
+        .unwrap();
+        assert!(OfficeArtExtensionList::from_xml_element(&xml_node).is_err());
+
+        let parsed = OfficeArtExtensionList::from_xml_element_lenient(&xml_node).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.value.extensions,
+            vec![Extension::Unknown(xml_node.child_nodes[0].clone())]
+        );
+    }
+
+    #[test]
+    fn test_invalid_extension_lenient() {
+        let xml_node = XmlNode::from_str(
+            r#"<a:extLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <a:ext uri="{909E8E84-426E-40DD-AFC4-6F175D3DCCD1}">
+                    <a14:hiddenFill xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main"/>
+                </a:ext>
+            </a:extLst>"#,
+        )
+        .unwrap();
+        assert!(OfficeArtExtensionList::from_xml_element(&xml_node).is_err());
+
+        let parsed = OfficeArtExtensionList::from_xml_element_lenient(&xml_node).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.value.extensions,
+            vec![Extension::Unknown(xml_node.child_nodes[0].clone())]
+        );
+    }
+}
@@ -3,6 +3,7 @@ pub mod colors;
 pub mod coordsys;
 pub mod core;
 pub mod diagrams;
+pub mod extensions;
 pub mod picture;
 pub mod shapedefs;
 pub mod shapeprops;
@@ -11,3 +12,4 @@ pub mod simpletypes;
 pub mod styles;
 pub mod text;
 pub mod util;
+pub mod validation;
@@ -2,78 +2,145 @@ use super::{
     core::{NonVisualDrawingProps, NonVisualPictureProperties, ShapeProperties},
     shapeprops::BlipFillProperties,
 };
-use crate::{error::MissingChildNodeError, xml::XmlNode};
+use crate::{
+    error::MissingChildNodeError,
+    xml::{ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlElement, XsdType},
+};
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PictureNonVisual {
     pub non_visual_drawing_props: NonVisualDrawingProps,
     pub non_visual_picture_props: NonVisualPictureProperties,
+
+    pub unknown: UnknownContent,
 }
 
-impl PictureNonVisual {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for PictureNonVisual {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut non_visual_drawing_props = None;
         let mut non_visual_picture_props = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "cNvPr" => non_visual_drawing_props = Some(NonVisualDrawingProps::from_xml_element(child_node)?),
+        let namespace = xml_node.namespace().unwrap_or(ns::DRAWINGML_PICTURE);
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.local_name_in(namespace).unwrap_or_default() {
+                "cNvPr" => {
+                    non_visual_drawing_props = Some(NonVisualDrawingProps::from_xml_element_with_context(
+                        child_node, context,
+                    )?)
+                }
                 "cNvPicPr" => {
-                    non_visual_picture_props = Some(NonVisualPictureProperties::from_xml_element(child_node)?)
+                    non_visual_picture_props = unknown.recover_element(
+                        NonVisualPictureProperties::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
                 }
-                _ => (),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
         let non_visual_drawing_props =
-            non_visual_drawing_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cNvPr"))?;
+            non_visual_drawing_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "cNvPr"))?;
 
         let non_visual_picture_props =
-            non_visual_picture_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cNvPicPr"))?;
+            non_visual_picture_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "cNvPicPr"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             non_visual_drawing_props,
             non_visual_picture_props,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PictureNonVisual {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        let child_name = |local_name| prefixed_child_name(node_name, local_name);
+        node.child_nodes
+            .push(self.non_visual_drawing_props.to_xml_element(&child_name("cNvPr")));
+        node.child_nodes
+            .push(self.non_visual_picture_props.to_xml_element(&child_name("cNvPicPr")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Picture {
     pub non_visual_props: PictureNonVisual,
     pub blip_fill_props: BlipFillProperties,
     pub shape_props: ShapeProperties,
+
+    pub unknown: UnknownContent,
 }
 
-impl Picture {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Picture {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut non_visual_props = None;
         let mut blip_fill_props = None;
         let mut shape_props = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "nvPicPr" => non_visual_props = Some(PictureNonVisual::from_xml_element(child_node)?),
-                "blipFill" => blip_fill_props = Some(BlipFillProperties::from_xml_element(child_node)?),
-                "spPr" => shape_props = Some(ShapeProperties::from_xml_element(child_node)?),
-                _ => (),
+        // The picture vocabulary is reused by other markup languages (e.g. p:pic), so children are expected to be in
+        // the same namespace as the parent
+        let namespace = xml_node.namespace().unwrap_or(ns::DRAWINGML_PICTURE);
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.local_name_in(namespace).unwrap_or_default() {
+                "nvPicPr" => {
+                    non_visual_props = Some(PictureNonVisual::from_xml_element_with_context(child_node, context)?)
+                }
+                "blipFill" => {
+                    blip_fill_props = Some(BlipFillProperties::from_xml_element_with_context(child_node, context)?)
+                }
+                "spPr" => shape_props = Some(ShapeProperties::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let non_visual_props =
-            non_visual_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "nvPicPr"))?;
+        let non_visual_props = non_visual_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "nvPicPr"))?;
+
+        let blip_fill_props = blip_fill_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "blipFill"))?;
 
-        let blip_fill_props =
-            blip_fill_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "blipFill"))?;
+        let shape_props = shape_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "spPr"))?;
 
-        let shape_props = shape_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "spPr"))?;
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             non_visual_props,
             blip_fill_props,
             shape_props,
+            unknown,
         })
     }
 }
+
+impl ToXmlElement for Picture {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        let child_name = |local_name| prefixed_child_name(node_name, local_name);
+        node.child_nodes
+            .push(self.non_visual_props.to_xml_element(&child_name("nvPicPr")));
+        node.child_nodes
+            .push(self.blip_fill_props.to_xml_element(&child_name("blipFill")));
+        node.child_nodes
+            .push(self.shape_props.to_xml_element(&child_name("spPr")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+// Children are written with the same prefix as the parent, mirroring how they're matched while parsing
+fn prefixed_child_name(parent_name: &str, local_name: &str) -> String {
+    match parent_name.find(':') {
+        Some(idx) => format!("{}{}", &parent_name[..=idx], local_name),
+        None => String::from(local_name),
+    }
+}
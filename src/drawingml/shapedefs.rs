@@ -1,15 +1,18 @@
 use crate::{
-    drawingml::simpletypes::{
-        AdjAngle, AdjCoordinate, GeomGuideFormula, GeomGuideName, PathFillMode, PositiveCoordinate, ShapeType,
-        TextShapeType,
+    diagnostics::recover_element,
+    drawingml::{
+        simpletypes::{
+            AdjAngle, AdjCoordinate, GeomGuideFormula, GeomGuideName, PathFillMode, PositiveCoordinate, ShapeType,
+            TextShapeType,
+        },
+        util::{dml_element, XmlNodeExt},
     },
-    error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
-    xml::{parse_xml_bool, XmlNode},
-    xsdtypes::{XsdChoice, XsdType},
+    error::{Error, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    xml::{format_xml_bool, ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GeomRect {
@@ -36,10 +39,13 @@ pub struct GeomRect {
     /// system. The width and height for this coordinate system are specified within the ext
     /// transform element.
     pub bottom: AdjCoordinate,
+
+    pub unknown: UnknownContent,
 }
 
-impl GeomRect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GeomRect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut left = None;
         let mut top = None;
         let mut right = None;
@@ -47,28 +53,43 @@ impl GeomRect {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "l" => left = Some(value.parse()?),
-                "t" => top = Some(value.parse()?),
-                "r" => right = Some(value.parse()?),
-                "b" => bottom = Some(value.parse()?),
-                _ => (),
+                "l" => left = Some(xml_node.parse_attribute_value(attr, value)?),
+                "t" => top = Some(xml_node.parse_attribute_value(attr, value)?),
+                "r" => right = Some(xml_node.parse_attribute_value(attr, value)?),
+                "b" => bottom = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let left = left.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "l"))?;
-        let top = top.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "t"))?;
-        let right = right.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r"))?;
-        let bottom = bottom.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "b"))?;
+        let left = left.ok_or_else(|| MissingAttributeError::at(xml_node, "l"))?;
+        let top = top.ok_or_else(|| MissingAttributeError::at(xml_node, "t"))?;
+        let right = right.ok_or_else(|| MissingAttributeError::at(xml_node, "r"))?;
+        let bottom = bottom.ok_or_else(|| MissingAttributeError::at(xml_node, "b"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             left,
             top,
             right,
             bottom,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for GeomRect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("l", &self.left);
+        node.set_attribute("t", &self.top);
+        node.set_attribute("r", &self.right);
+        node.set_attribute("b", &self.bottom);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PolarAdjustHandle {
     /// Specifies the name of the guide that is updated with the adjustment radius from this
@@ -99,10 +120,13 @@ pub struct PolarAdjustHandle {
     /// That is the maxAng and minAng are equal.
     pub max_angle: Option<AdjAngle>,
     pub position: AdjPoint2D,
+
+    pub unknown: UnknownContent,
 }
 
-impl PolarAdjustHandle {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for PolarAdjustHandle {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut guide_reference_radial = None;
         let mut guide_reference_angle = None;
         let mut min_radial = None;
@@ -114,20 +138,52 @@ impl PolarAdjustHandle {
             match attr.as_str() {
                 "gdRefR" => guide_reference_radial = Some(value.clone()),
                 "gdRefAng" => guide_reference_angle = Some(value.clone()),
-                "minR" => min_radial = Some(value.parse()?),
-                "maxR" => max_radial = Some(value.parse()?),
-                "minAng" => min_angle = Some(value.parse()?),
-                "maxAng" => max_angle = Some(value.parse()?),
-                _ => (),
+                "minR" => {
+                    min_radial = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "maxR" => {
+                    max_radial = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "minAng" => {
+                    min_angle = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "maxAng" => {
+                    max_angle = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let position = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "pos")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "pos")))
-            .and_then(AdjPoint2D::from_xml_element)?;
+        let mut position = None;
+
+        for (child_position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "pos" => position = Some(AdjPoint2D::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(child_position, child_node),
+            }
+        }
+
+        let position = position.ok_or_else(|| MissingChildNodeError::at(xml_node, "pos"))?;
 
         Ok(Self {
             guide_reference_radial,
@@ -137,10 +193,26 @@ impl PolarAdjustHandle {
             min_angle,
             max_angle,
             position,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PolarAdjustHandle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("gdRefR", self.guide_reference_radial.as_ref());
+        node.set_optional_attribute("minR", self.min_radial.as_ref());
+        node.set_optional_attribute("maxR", self.max_radial.as_ref());
+        node.set_optional_attribute("gdRefAng", self.guide_reference_angle.as_ref());
+        node.set_optional_attribute("minAng", self.min_angle.as_ref());
+        node.set_optional_attribute("maxAng", self.max_angle.as_ref());
+        node.child_nodes.push(self.position.to_xml_element("a:pos"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct XYAdjustHandle {
     /// Specifies the name of the guide that is updated with the adjustment x position from this
@@ -219,10 +291,13 @@ pub struct XYAdjustHandle {
     /// </a:custGeom>
     /// ```
     pub position: AdjPoint2D,
+
+    pub unknown: UnknownContent,
 }
 
-impl XYAdjustHandle {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for XYAdjustHandle {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut guide_reference_x = None;
         let mut guide_reference_y = None;
         let mut min_x = None;
@@ -234,20 +309,52 @@ impl XYAdjustHandle {
             match attr.as_str() {
                 "gdRefX" => guide_reference_x = Some(value.clone()),
                 "gdRefY" => guide_reference_y = Some(value.clone()),
-                "minX" => min_x = Some(value.parse()?),
-                "maxX" => max_x = Some(value.parse()?),
-                "minY" => min_y = Some(value.parse()?),
-                "maxY" => max_y = Some(value.parse()?),
-                _ => (),
+                "minX" => {
+                    min_x = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "maxX" => {
+                    max_x = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "minY" => {
+                    min_y = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "maxY" => {
+                    max_y = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let position = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "pos")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "pos")))
-            .and_then(AdjPoint2D::from_xml_element)?;
+        let mut position = None;
+
+        for (child_position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "pos" => position = Some(AdjPoint2D::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(child_position, child_node),
+            }
+        }
+
+        let position = position.ok_or_else(|| MissingChildNodeError::at(xml_node, "pos"))?;
 
         Ok(Self {
             guide_reference_x,
@@ -257,10 +364,26 @@ impl XYAdjustHandle {
             min_y,
             max_y,
             position,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for XYAdjustHandle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("gdRefX", self.guide_reference_x.as_ref());
+        node.set_optional_attribute("minX", self.min_x.as_ref());
+        node.set_optional_attribute("maxX", self.max_x.as_ref());
+        node.set_optional_attribute("gdRefY", self.guide_reference_y.as_ref());
+        node.set_optional_attribute("minY", self.min_y.as_ref());
+        node.set_optional_attribute("maxY", self.max_y.as_ref());
+        node.child_nodes.push(self.position.to_xml_element("a:pos"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AdjustHandle {
     /// This element specifies an XY-based adjust handle for a custom shape. The position of this adjust handle is
@@ -277,22 +400,30 @@ pub enum AdjustHandle {
 }
 
 impl XsdType for AdjustHandle {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "ahXY" => Ok(AdjustHandle::XY(Box::new(XYAdjustHandle::from_xml_element(xml_node)?))),
-            "ahPolar" => Ok(AdjustHandle::Polar(Box::new(PolarAdjustHandle::from_xml_element(
-                xml_node,
-            )?))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "AdjustHandle").into()),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "ahXY" => Ok(AdjustHandle::XY(Box::new(
+                XYAdjustHandle::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            "ahPolar" => Ok(AdjustHandle::Polar(Box::new(
+                PolarAdjustHandle::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "AdjustHandle").into()),
         }
     }
 }
 
 impl XsdChoice for AdjustHandle {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "ahXY" | "ahPolar" => true,
-            _ => false,
+        matches!(name.as_ref(), "ahXY" | "ahPolar")
+    }
+}
+
+impl ToXmlChoice for AdjustHandle {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            AdjustHandle::XY(handle) => handle.to_xml_element("a:ahXY"),
+            AdjustHandle::Polar(handle) => handle.to_xml_element("a:ahPolar"),
         }
     }
 }
@@ -364,25 +495,40 @@ pub struct AdjPoint2D {
     /// bounding box. Because the units for within this coordinate space are determined by the
     /// path width and height an exact measurement unit cannot be specified here.
     pub y: AdjCoordinate,
+
+    pub unknown: UnknownContent,
 }
 
-impl AdjPoint2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for AdjPoint2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut x = None;
         let mut y = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "x" => x = Some(value.parse()?),
-                "y" => y = Some(value.parse()?),
-                _ => (),
+                "x" => x = Some(xml_node.parse_attribute_value(attr, value)?),
+                "y" => y = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let x = x.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "x"))?;
-        let y = y.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "y"))?;
+        let x = x.ok_or_else(|| MissingAttributeError::at(xml_node, "x"))?;
+        let y = y.ok_or_else(|| MissingAttributeError::at(xml_node, "y"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { x, y, unknown })
+    }
+}
 
-        Ok(Self { x, y })
+impl ToXmlElement for AdjPoint2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("x", &self.x);
+        node.set_attribute("y", &self.y);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -408,10 +554,13 @@ pub struct Path2DArcTo {
     /// supposed cicle path the arc is extended. The extension from the start angle is always in
     /// the clockwise direction around the supposed circle.
     pub swing_angle: AdjAngle,
+
+    pub unknown: UnknownContent,
 }
 
-impl Path2DArcTo {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Path2DArcTo {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut width_radius = None;
         let mut height_radius = None;
         let mut start_angle = None;
@@ -419,28 +568,43 @@ impl Path2DArcTo {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "wR" => width_radius = Some(value.parse()?),
-                "hR" => height_radius = Some(value.parse()?),
-                "stAng" => start_angle = Some(value.parse()?),
-                "swAng" => swing_angle = Some(value.parse()?),
-                _ => (),
+                "wR" => width_radius = Some(xml_node.parse_attribute_value(attr, value)?),
+                "hR" => height_radius = Some(xml_node.parse_attribute_value(attr, value)?),
+                "stAng" => start_angle = Some(xml_node.parse_attribute_value(attr, value)?),
+                "swAng" => swing_angle = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let width_radius = width_radius.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "wR"))?;
-        let height_radius = height_radius.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "hR"))?;
-        let start_angle = start_angle.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "stAng"))?;
-        let swing_angle = swing_angle.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "swAng"))?;
+        let width_radius = width_radius.ok_or_else(|| MissingAttributeError::at(xml_node, "wR"))?;
+        let height_radius = height_radius.ok_or_else(|| MissingAttributeError::at(xml_node, "hR"))?;
+        let start_angle = start_angle.ok_or_else(|| MissingAttributeError::at(xml_node, "stAng"))?;
+        let swing_angle = swing_angle.ok_or_else(|| MissingAttributeError::at(xml_node, "swAng"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             width_radius,
             height_radius,
             start_angle,
             swing_angle,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for Path2DArcTo {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("wR", &self.width_radius);
+        node.set_attribute("hR", &self.height_radius);
+        node.set_attribute("stAng", &self.start_angle);
+        node.set_attribute("swAng", &self.swing_angle);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies a creation path consisting of a series of moves, lines and curves that when combined
 /// forms a geometric shape. This element is only utilized if a custom geometry is specified.
 ///
@@ -514,37 +678,89 @@ pub struct Path2D {
     /// Defaults to true
     pub extrusion_ok: Option<bool>,
     pub commands: Vec<Path2DCommand>,
+
+    pub unknown: UnknownContent,
 }
 
-impl Path2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Path2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "w" => instance.width = Some(value.parse()?),
-                    "h" => instance.height = Some(value.parse()?),
-                    "fill" => instance.fill_mode = Some(value.parse()?),
-                    "stroke" => instance.stroke = Some(parse_xml_bool(value)?),
-                    "extrusionOk" => instance.extrusion_ok = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "w" => {
+                        instance.width = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "h" => {
+                        instance.height = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "fill" => {
+                        instance.fill_mode = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "stroke" => {
+                        instance.stroke = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "extrusionOk" => {
+                        instance.extrusion_ok = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
             .and_then(|mut instance| {
-                instance.commands = xml_node
-                    .child_nodes
-                    .iter()
-                    .filter_map(Path2DCommand::try_from_xml_element)
-                    .collect::<Result<Vec<_>>>()?;
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    instance
+                        .commands
+                        .extend(instance.unknown.parse_choice_element(position, child_node, context)?);
+                }
 
                 Ok(instance)
             })
     }
 }
 
+impl ToXmlElement for Path2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("w", self.width);
+        node.set_optional_attribute("h", self.height);
+        node.set_optional_attribute("fill", self.fill_mode);
+        node.set_optional_attribute("stroke", self.stroke.map(format_xml_bool));
+        node.set_optional_attribute("extrusionOk", self.extrusion_ok.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.commands.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies the precense of a shape guide that is used to govern the geometry of the specified shape.
 /// A shape guide consists of a formula and a name that the result of the formula is assigned to. Recognized
 /// formulas are listed with the fmla attribute documentation for this element.
@@ -636,14 +852,14 @@ pub struct GeomGuide {
     ///     Arguments: 3 (fmla="?: x y z")
     ///
     ///     Usage: "?: x y z" = if (x > 0), then y = value of this guide,  
-    ///     else z = value of this guide
+    ///   else z = value of this guide
     ///
     /// * **('abs') - Absolute Value Formula**
     ///
     ///     Arguments: 1 (fmla="abs x")
     ///
     ///     Usage: "abs x" = if (x < 0), then (-1) * x = value of this guide  
-    ///     else x = value of this guide
+    ///   else x = value of this guide
     ///
     /// * **('at2') - ArcTan Formula**
     ///
@@ -668,14 +884,14 @@ pub struct GeomGuide {
     ///     Arguments: 2 (fmla="max x y")
     ///
     ///     Usage: "max x y" = if (x > y), then x = value of this guide  
-    ///     else y = value of this guide
+    ///   else y = value of this guide
     ///
     /// * **('min') - Minimum Value Formula**
     ///
     ///     Arguments: 2 (fmla="min x y")
     ///
     ///     Usage: "min x y" = if (x < y), then x = value of this guide  
-    ///     else y = value of this guide
+    ///   else y = value of this guide
     ///
     /// * **('mod') - Modulo Formula**
     ///
@@ -688,8 +904,8 @@ pub struct GeomGuide {
     ///     Arguments: 3 (fmla="pin x y z")
     ///
     ///     Usage: "pin x y z" = if (y < x), then x = value of this guide  
-    ///     else if (y > z), then z = value of this guide  
-    ///     else y = value of this guide
+    ///   else if (y > z), then z = value of this guide  
+    ///   else y = value of this guide
     ///
     /// * **('sat2') - Sine ArcTan Formula**
     ///
@@ -727,10 +943,13 @@ pub struct GeomGuide {
     /// only be used within the avLst as an adjust value for the shape. This however is not
     /// strictly enforced.
     pub formula: GeomGuideFormula,
+
+    pub unknown: UnknownContent,
 }
 
-impl GeomGuide {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GeomGuide {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut name = None;
         let mut formula = None;
 
@@ -738,13 +957,25 @@ impl GeomGuide {
             match attr.as_str() {
                 "name" => name = Some(value.clone()),
                 "fmla" => formula = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let name = name.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?;
-        let formula = formula.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "fmla"))?;
-        Ok(Self { name, formula })
+        let name = name.ok_or_else(|| MissingAttributeError::at(xml_node, "name"))?;
+        let formula = formula.ok_or_else(|| MissingAttributeError::at(xml_node, "fmla"))?;
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { name, formula, unknown })
+    }
+}
+
+impl ToXmlElement for GeomGuide {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("name", &self.name);
+        node.set_attribute("fmla", &self.formula);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -856,30 +1087,32 @@ pub enum Path2DCommand {
 }
 
 impl XsdType for Path2DCommand {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let get_point_at = |index| {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let get_point_at = |index, context: &mut ParseContext| {
             xml_node
                 .child_nodes
                 .get(index)
-                .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "pt")))
-                .and_then(AdjPoint2D::from_xml_element)
+                .ok_or_else(|| Error::from(MissingChildNodeError::at(xml_node, "pt")))
+                .and_then(|child_node| AdjPoint2D::from_xml_element_with_context(child_node, context))
         };
 
-        match xml_node.local_name() {
+        match xml_node.dml_local_name() {
             "close" => Ok(Path2DCommand::Close),
-            "moveTo" => Ok(Path2DCommand::LineTo(get_point_at(0)?)),
-            "lnTo" => Ok(Path2DCommand::LineTo(get_point_at(0)?)),
-            "arcTo" => Ok(Path2DCommand::ArcTo(Path2DArcTo::from_xml_element(xml_node)?)),
-            "quadBezTo" => Ok(Path2DCommand::QuadBezierTo(get_point_at(0)?, get_point_at(1)?)),
+            "moveTo" => Ok(Path2DCommand::MoveTo(get_point_at(0, context)?)),
+            "lnTo" => Ok(Path2DCommand::LineTo(get_point_at(0, context)?)),
+            "arcTo" => Ok(Path2DCommand::ArcTo(Path2DArcTo::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "quadBezTo" => Ok(Path2DCommand::QuadBezierTo(
+                get_point_at(0, context)?,
+                get_point_at(1, context)?,
+            )),
             "cubicBezTo" => Ok(Path2DCommand::CubicBezTo(
-                get_point_at(0)?,
-                get_point_at(1)?,
-                get_point_at(2)?,
+                get_point_at(0, context)?,
+                get_point_at(1, context)?,
+                get_point_at(2, context)?,
             )),
-            _ => Err(Box::new(NotGroupMemberError::new(
-                xml_node.name.clone(),
-                "EG_Path2DCommand",
-            ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_Path2DCommand").into()),
         }
     }
 }
@@ -889,9 +1122,29 @@ impl XsdChoice for Path2DCommand {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "close" | "moveTo" | "lnTo" | "arcTo" | "quadBezTo" | "cubicBezTo" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "close" | "moveTo" | "lnTo" | "arcTo" | "quadBezTo" | "cubicBezTo"
+        )
+    }
+}
+
+impl ToXmlChoice for Path2DCommand {
+    fn to_xml_element(&self) -> XmlNode {
+        let with_points = |name, points: &[&AdjPoint2D]| {
+            let mut node = dml_element(name);
+            node.child_nodes
+                .extend(points.iter().map(|point| point.to_xml_element("a:pt")));
+            node
+        };
+
+        match self {
+            Path2DCommand::Close => dml_element("close"),
+            Path2DCommand::MoveTo(point) => with_points("moveTo", &[point]),
+            Path2DCommand::LineTo(point) => with_points("lnTo", &[point]),
+            Path2DCommand::ArcTo(arc) => arc.to_xml_element("a:arcTo"),
+            Path2DCommand::QuadBezierTo(p1, p2) => with_points("quadBezTo", &[p1, p2]),
+            Path2DCommand::CubicBezTo(p1, p2, p3) => with_points("cubicBezTo", &[p1, p2, p3]),
         }
     }
 }
@@ -899,19 +1152,30 @@ impl XsdChoice for Path2DCommand {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct GeomGuideList(pub Vec<GeomGuide>);
 
-impl GeomGuideList {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GeomGuideList {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         Ok(Self(
             xml_node
                 .child_nodes
                 .iter()
-                .filter(|child_node| child_node.local_name() == "gd")
-                .map(GeomGuide::from_xml_element)
+                .filter(|child_node| child_node.is(ns::DRAWINGML, "gd"))
+                .filter_map(|child_node| {
+                    recover_element(GeomGuide::from_xml_element_with_context(child_node, context), context).transpose()
+                })
                 .collect::<Result<Vec<_>>>()?,
         ))
     }
 }
 
+impl ToXmlElement for GeomGuideList {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.0.iter().map(|guide| guide.to_xml_element("a:gd")));
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CustomGeometry2D {
     /// This element specifies the adjust values that are applied to the specified shape. An adjust value is simply a guide
@@ -1027,50 +1291,128 @@ pub struct CustomGeometry2D {
     /// is encountered at the end of the previous creation path then this joining line should not be rendered by the
     /// generating application. The rendering should resume with the first line or curve on the new creation path.
     pub path_list: Vec<Path2D>,
+
+    pub unknown: UnknownContent,
 }
 
-impl CustomGeometry2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "avLst" => instance.adjust_value_list = Some(GeomGuideList::from_xml_element(child_node)?),
-                    "gdLst" => instance.guide_list = Some(GeomGuideList::from_xml_element(child_node)?),
-                    "ahLst" => {
-                        instance.adjust_handle_list = Some(
-                            child_node
-                                .child_nodes
-                                .iter()
-                                .filter_map(AdjustHandle::try_from_xml_element)
-                                .collect::<Result<Vec<_>>>()?,
-                        )
-                    }
-                    "cxnLst" => {
-                        instance.connection_site_list = Some(
-                            child_node
-                                .child_nodes
-                                .iter()
-                                .filter(|cxn_node| cxn_node.local_name() == "cxn")
-                                .map(ConnectionSite::from_xml_element)
-                                .collect::<Result<Vec<_>>>()?,
-                        )
-                    }
-                    "rect" => instance.rect = Some(Box::new(GeomRect::from_xml_element(child_node)?)),
-                    "pathLst" => {
-                        instance.path_list = child_node
+impl XsdType for CustomGeometry2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        instance.unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "avLst" => {
+                    instance.adjust_value_list = instance.unknown.recover_element(
+                        GeomGuideList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "gdLst" => {
+                    instance.guide_list = instance.unknown.recover_element(
+                        GeomGuideList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "ahLst" => {
+                    instance.adjust_handle_list = Some(
+                        child_node
                             .child_nodes
                             .iter()
-                            .filter(|path_node| path_node.local_name() == "path")
-                            .map(Path2D::from_xml_element)
-                            .collect::<Result<Vec<_>>>()?
-                    }
-                    _ => (),
+                            .filter_map(|child_node| {
+                                AdjustHandle::try_from_xml_element_with_context(child_node, context)
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    )
                 }
+                "cxnLst" => {
+                    instance.connection_site_list = Some(
+                        child_node
+                            .child_nodes
+                            .iter()
+                            .filter(|cxn_node| cxn_node.is(ns::DRAWINGML, "cxn"))
+                            .filter_map(|child_node| {
+                                recover_element(
+                                    ConnectionSite::from_xml_element_with_context(child_node, context),
+                                    context,
+                                )
+                                .transpose()
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+                "rect" => {
+                    instance.rect = instance
+                        .unknown
+                        .recover_element(
+                            GeomRect::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "pathLst" => {
+                    instance.path_list = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|path_node| path_node.is(ns::DRAWINGML, "path"))
+                        .filter_map(|child_node| {
+                            recover_element(Path2D::from_xml_element_with_context(child_node, context), context)
+                                .transpose()
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                }
+                _ => instance.unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(instance)
+    }
+}
+
+impl ToXmlElement for CustomGeometry2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.extend(
+            self.adjust_value_list
+                .as_ref()
+                .map(|list| list.to_xml_element("a:avLst")),
+        );
+        node.child_nodes
+            .extend(self.guide_list.as_ref().map(|list| list.to_xml_element("a:gdLst")));
+
+        if let Some(adjust_handle_list) = &self.adjust_handle_list {
+            let mut list_node = dml_element("ahLst");
+            list_node
+                .child_nodes
+                .extend(adjust_handle_list.iter().map(ToXmlChoice::to_xml_element));
+            node.child_nodes.push(list_node);
+        }
+
+        if let Some(connection_site_list) = &self.connection_site_list {
+            let mut list_node = dml_element("cxnLst");
+            list_node
+                .child_nodes
+                .extend(connection_site_list.iter().map(|site| site.to_xml_element("a:cxn")));
+            node.child_nodes.push(list_node);
+        }
+
+        node.child_nodes
+            .extend(self.rect.as_ref().map(|rect| rect.to_xml_element("a:rect")));
+
+        let mut path_list_node = dml_element("pathLst");
+        path_list_node
+            .child_nodes
+            .extend(self.path_list.iter().map(|path| path.to_xml_element("a:path")));
+        node.child_nodes.push(path_list_node);
+
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1104,30 +1446,61 @@ pub struct PresetGeometry2D {
     /// utilized here is the sun shape.
     pub preset: ShapeType,
     pub adjust_value_list: Option<GeomGuideList>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PresetGeometry2D {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let preset = xml_node
-            .attributes
-            .get("prst")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "prst"))?
-            .parse()?;
+impl XsdType for PresetGeometry2D {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut preset = None;
+        let mut adjust_value_list = None;
 
-        let adjust_value_list = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "avLst")
-            .map(GeomGuideList::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "prst" => preset = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "avLst" => {
+                    adjust_value_list = unknown.recover_element(
+                        GeomGuideList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let preset = preset.ok_or_else(|| MissingAttributeError::at(xml_node, "prst"))?;
 
         Ok(Self {
             preset,
             adjust_value_list,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PresetGeometry2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("prst", &self.preset);
+        node.child_nodes.extend(
+            self.adjust_value_list
+                .as_ref()
+                .map(|list| list.to_xml_element("a:avLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Geometry {
     /// This element specifies the existence of a custom geometric shape. This shape consists of a series of lines and
@@ -1194,24 +1567,30 @@ pub enum Geometry {
 }
 
 impl XsdType for Geometry {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "custGeom" => Ok(Geometry::Custom(Box::new(CustomGeometry2D::from_xml_element(
-                xml_node,
-            )?))),
-            "prstGeom" => Ok(Geometry::Preset(Box::new(PresetGeometry2D::from_xml_element(
-                xml_node,
-            )?))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_Geometry").into()),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "custGeom" => Ok(Geometry::Custom(Box::new(
+                CustomGeometry2D::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            "prstGeom" => Ok(Geometry::Preset(Box::new(
+                PresetGeometry2D::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_Geometry").into()),
         }
     }
 }
 
 impl XsdChoice for Geometry {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "custGeom" | "prstGeom" => true,
-            _ => false,
+        matches!(name.as_ref(), "custGeom" | "prstGeom")
+    }
+}
+
+impl ToXmlChoice for Geometry {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Geometry::Custom(geometry) => geometry.to_xml_element("a:custGeom"),
+            Geometry::Preset(geometry) => geometry.to_xml_element("a:prstGeom"),
         }
     }
 }
@@ -1248,30 +1627,61 @@ pub struct PresetTextShape {
 
     /// The list of adjust values used to represent this preset text shape.
     pub adjust_value_list: Option<GeomGuideList>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PresetTextShape {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let preset = xml_node
-            .attributes
-            .get("prst")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "prst"))?
-            .parse()?;
+impl XsdType for PresetTextShape {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut preset = None;
+        let mut adjust_value_list = None;
 
-        let adjust_value_list = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "avLst")
-            .map(GeomGuideList::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "prst" => preset = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "avLst" => {
+                    adjust_value_list = unknown.recover_element(
+                        GeomGuideList::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let preset = preset.ok_or_else(|| MissingAttributeError::at(xml_node, "prst"))?;
 
         Ok(Self {
             preset,
             adjust_value_list,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PresetTextShape {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("prst", &self.preset);
+        node.child_nodes.extend(
+            self.adjust_value_list
+                .as_ref()
+                .map(|list| list.to_xml_element("a:avLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies the existence of a connection site on a custom shape. A connection site allows a cxnSp to
 /// be attached to this shape. This connection is maintained when the shape is repositioned within the document. It
 /// should be noted that this connection is placed within the shape bounding box using the transform coordinate
@@ -1331,23 +1741,101 @@ pub struct ConnectionSite {
     /// any overlap with the shape.
     pub angle: AdjAngle,
     pub position: AdjPoint2D,
+
+    pub unknown: UnknownContent,
 }
 
-impl ConnectionSite {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let angle = xml_node
-            .attributes
-            .get("ang")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "ang"))?
-            .parse()?;
+impl XsdType for ConnectionSite {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut angle = None;
+        let mut position = None;
 
-        let position = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "pos")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "pos")))
-            .and_then(AdjPoint2D::from_xml_element)?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "ang" => angle = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (child_position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "pos" => position = Some(AdjPoint2D::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(child_position, child_node),
+            }
+        }
+
+        let angle = angle.ok_or_else(|| MissingAttributeError::at(xml_node, "ang"))?;
+        let position = position.ok_or_else(|| MissingChildNodeError::at(xml_node, "pos"))?;
+
+        Ok(Self {
+            angle,
+            position,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for ConnectionSite {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("ang", &self.angle);
+        node.child_nodes.push(self.position.to_xml_element("a:pos"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_unknown_shape_type() {
+        let xml_node = XmlNode::from_str(r#"<a:prstGeom prst="futureShape"><a:avLst/></a:prstGeom>"#).unwrap();
+        assert!(PresetGeometry2D::from_xml_element(&xml_node).is_err());
+
+        let mut context = ParseContext::new().with_unknown_values_accepted(true);
+        let geometry = PresetGeometry2D::from_xml_element_with_context(&xml_node, &mut context).unwrap();
+        assert_eq!(geometry.preset, ShapeType::Unknown(String::from("futureShape")));
+        assert_eq!(
+            geometry
+                .to_xml_element("a:prstGeom")
+                .attributes
+                .get("prst")
+                .map(String::as_str),
+            Some("futureShape")
+        );
+        assert!(PresetGeometry2D::from_xml_element(&xml_node).is_err());
+
+        let mut context = ParseContext::lenient().with_unknown_values_accepted(true);
+        PresetGeometry2D::from_xml_element_with_context(&xml_node, &mut context).unwrap();
+        assert!(context.diagnostics().is_empty());
+
+        assert_eq!(ShapeType::Rect.to_string(), "rect");
+        assert!("Unknown".parse::<ShapeType>().is_err());
+    }
 
-        Ok(Self { angle, position })
+    #[test]
+    fn test_unknown_text_shape_type() {
+        let xml_node = XmlNode::from_str(r#"<a:prstTxWarp prst="textFutureWarp"/>"#).unwrap();
+        assert!(PresetTextShape::from_xml_element(&xml_node).is_err());
+
+        let mut context = ParseContext::new().with_unknown_values_accepted(true);
+        let text_shape = PresetTextShape::from_xml_element_with_context(&xml_node, &mut context).unwrap();
+        assert_eq!(
+            text_shape.preset,
+            TextShapeType::Unknown(String::from("textFutureWarp"))
+        );
+        assert_eq!(
+            text_shape
+                .to_xml_element("a:prstTxWarp")
+                .attributes
+                .get("prst")
+                .map(String::as_str),
+            Some("textFutureWarp")
+        );
+        assert_eq!(TextShapeType::ArchUp.to_string(), "textArchUp");
     }
 }
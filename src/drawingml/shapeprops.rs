@@ -1,22 +1,24 @@
 use crate::{
+    diagnostics::recover_element,
     drawingml::{
         colors::Color,
+        extensions::OfficeArtExtensionList,
         simpletypes::{
             BlendMode, BlipCompression, Coordinate, EffectContainerType, FixedAngle, FixedPercentage, LineEndLength,
             LineEndType, LineEndWidth, PathShadeType, Percentage, PositiveCoordinate, PositiveFixedAngle,
             PositiveFixedPercentage, PositivePercentage, PresetLineDashVal, PresetPatternVal, PresetShadowVal,
             RectAlignment, TileFlipMode,
         },
+        util::{dml_element, dml_val_element, XmlNodeExt},
     },
-    error::{LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    error::{Error, LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
     relationship::RelationshipId,
-    xml::{parse_xml_bool, XmlNode},
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{format_xml_bool, ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 use log::trace;
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct RelativeRect {
@@ -31,24 +33,70 @@ pub struct RelativeRect {
 
     /// Specifies the bottom edge of the rectangle.
     pub bottom: Option<Percentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl RelativeRect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<RelativeRect> {
+impl XsdType for RelativeRect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "l" => instance.left = Some(value.parse()?),
-                    "t" => instance.top = Some(value.parse()?),
-                    "r" => instance.right = Some(value.parse()?),
-                    "b" => instance.bottom = Some(value.parse()?),
-                    _ => (),
+                    "l" => {
+                        instance.left = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "t" => {
+                        instance.top = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "r" => {
+                        instance.right = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "b" => {
+                        instance.bottom = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for RelativeRect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("l", self.left);
+        node.set_optional_attribute("t", self.top);
+        node.set_optional_attribute("r", self.right);
+        node.set_optional_attribute("b", self.bottom);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -60,17 +108,36 @@ impl RelativeRect {
 pub struct AlphaBiLevelEffect {
     // Specifies the threshold value for the alpha bi-level effect.
     pub threshold: PositiveFixedPercentage,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaBiLevelEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<AlphaBiLevelEffect> {
-        let threshold = xml_node
-            .attributes
-            .get("thresh")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "thresh"))?
-            .parse()?;
+impl XsdType for AlphaBiLevelEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut threshold = None;
 
-        Ok(Self { threshold })
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "thresh" => threshold = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
+
+        let threshold = threshold.ok_or_else(|| MissingAttributeError::at(xml_node, "thresh"))?;
+
+        Ok(Self { threshold, unknown })
+    }
+}
+
+impl ToXmlElement for AlphaBiLevelEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("thresh", self.threshold);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -80,17 +147,42 @@ impl AlphaBiLevelEffect {
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct AlphaInverseEffect {
     pub color: Option<Color>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaInverseEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<AlphaInverseEffect> {
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?;
+impl XsdType for AlphaInverseEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut color = None;
+
+        unknown.push_all_attributes(xml_node);
 
-        Ok(Self { color })
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if Color::is_choice_member(local_name) => {
+                    color = unknown.recover_element(
+                        Color::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self { color, unknown })
+    }
+}
+
+impl ToXmlElement for AlphaInverseEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.color.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -101,18 +193,36 @@ impl AlphaInverseEffect {
 #[derive(Debug, Clone, PartialEq)]
 pub struct AlphaModulateEffect {
     pub container: EffectContainer,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaModulateEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<AlphaModulateEffect> {
-        let container = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "cont")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "container")))
-            .and_then(EffectContainer::from_xml_element)?;
+impl XsdType for AlphaModulateEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut container = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "cont" => container = Some(EffectContainer::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let container = container.ok_or_else(|| MissingChildNodeError::at(xml_node, "cont"))?;
+
+        Ok(Self { container, unknown })
+    }
+}
 
-        Ok(Self { container })
+impl ToXmlElement for AlphaModulateEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.container.to_xml_element("a:cont"));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -125,13 +235,41 @@ pub struct AlphaModulateFixedEffect {
     ///
     /// Defaults to 100000
     pub amount: Option<PositivePercentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaModulateFixedEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let amount = xml_node.attributes.get("amt").map(|value| value.parse()).transpose()?;
+impl XsdType for AlphaModulateFixedEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut amount = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "amt" => {
+                    amount = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { amount, unknown })
+    }
+}
 
-        Ok(Self { amount })
+impl ToXmlElement for AlphaModulateFixedEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("amt", self.amount);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -143,13 +281,41 @@ impl AlphaModulateFixedEffect {
 pub struct AlphaOutsetEffect {
     /// Specifies the radius of outset/inset.
     pub radius: Option<Coordinate>,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaOutsetEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let radius = xml_node.attributes.get("rad").map(|value| value.parse()).transpose()?;
+impl XsdType for AlphaOutsetEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut radius = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "rad" => {
+                    radius = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self { radius, unknown })
+    }
+}
 
-        Ok(Self { radius })
+impl ToXmlElement for AlphaOutsetEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rad", self.radius);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -160,17 +326,36 @@ impl AlphaOutsetEffect {
 pub struct AlphaReplaceEffect {
     /// Specifies the new opacity value.
     pub alpha: PositiveFixedPercentage,
+
+    pub unknown: UnknownContent,
 }
 
-impl AlphaReplaceEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let alpha = xml_node
-            .attributes
-            .get("a")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "a"))?
-            .parse()?;
+impl XsdType for AlphaReplaceEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut alpha = None;
 
-        Ok(Self { alpha })
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "a" => alpha = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
+
+        let alpha = alpha.ok_or_else(|| MissingAttributeError::at(xml_node, "a"))?;
+
+        Ok(Self { alpha, unknown })
+    }
+}
+
+impl ToXmlElement for AlphaReplaceEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("a", self.alpha);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -182,17 +367,36 @@ pub struct BiLevelEffect {
     /// Specifies the luminance threshold for the Bi-Level effect. Values greater than or equal to
     /// the threshold are set to white. Values lesser than the threshold are set to black.
     pub threshold: PositiveFixedPercentage,
+
+    pub unknown: UnknownContent,
 }
 
-impl BiLevelEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let threshold = xml_node
-            .attributes
-            .get("thresh")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "thresh"))?
-            .parse()?;
+impl XsdType for BiLevelEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut threshold = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "thresh" => threshold = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
 
-        Ok(Self { threshold })
+        let threshold = threshold.ok_or_else(|| MissingAttributeError::at(xml_node, "thresh"))?;
+
+        Ok(Self { threshold, unknown })
+    }
+}
+
+impl ToXmlElement for BiLevelEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("thresh", self.threshold);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -203,24 +407,48 @@ pub struct BlendEffect {
     /// Specifies how to blend the two effects.
     pub blend: BlendMode,
     pub container: EffectContainer,
+
+    pub unknown: UnknownContent,
 }
 
-impl BlendEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let blend = xml_node
-            .attributes
-            .get("blend")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "blend"))?
-            .parse()?;
+impl XsdType for BlendEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut blend = None;
+        let mut container = None;
 
-        let container = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "cont")
-            .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "cont")))
-            .and_then(EffectContainer::from_xml_element)?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "blend" => blend = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "cont" => container = Some(EffectContainer::from_xml_element_with_context(child_node, context)?),
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let blend = blend.ok_or_else(|| MissingAttributeError::at(xml_node, "blend"))?;
+        let container = container.ok_or_else(|| MissingChildNodeError::at(xml_node, "cont"))?;
+
+        Ok(Self {
+            blend,
+            container,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { blend, container })
+impl ToXmlElement for BlendEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("blend", &self.blend);
+        node.child_nodes.push(self.container.to_xml_element("a:cont"));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -244,22 +472,52 @@ pub struct BlurEffect {
     ///
     /// Defaults to true
     pub grow: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl BlurEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for BlurEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "rad" => instance.radius = Some(value.parse()?),
-                    "grow" => instance.grow = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "rad" => {
+                        instance.radius = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "grow" => {
+                        instance.grow = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for BlurEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rad", self.radius);
+        node.set_optional_attribute("grow", self.grow.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -279,62 +537,118 @@ pub struct ColorChangeEffect {
     /// This element specifies the color which replaces the clrFrom in a clrChange effect. This is the "target" or "to"
     /// color in the color change effect.
     pub color_to: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl ColorChangeEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let use_alpha = xml_node.attributes.get("useA").map(|value| value.parse()).transpose()?;
+impl XsdType for ColorChangeEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut use_alpha = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "useA" => {
+                    use_alpha = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
 
         let mut color_from = None;
         let mut color_to = None;
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
                 "clrFrom" => {
                     color_from = child_node
                         .child_nodes
                         .iter()
-                        .find_map(Color::try_from_xml_element)
+                        .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                         .transpose()?
                 }
                 "clrTo" => {
                     color_to = child_node
                         .child_nodes
                         .iter()
-                        .find_map(Color::try_from_xml_element)
+                        .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                         .transpose()?
                 }
-                _ => (),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let color_from = color_from.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrFrom"))?;
-        let color_to = color_to.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrTo"))?;
+        let color_from = color_from.ok_or_else(|| MissingChildNodeError::at(xml_node, "clrFrom"))?;
+        let color_to = color_to.ok_or_else(|| MissingChildNodeError::at(xml_node, "clrTo"))?;
 
         Ok(Self {
             use_alpha,
             color_from,
             color_to,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ColorChangeEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("useA", self.use_alpha.map(format_xml_bool));
+
+        let mut color_from_node = dml_element("clrFrom");
+        color_from_node.child_nodes.push(self.color_from.to_xml_element());
+        node.child_nodes.push(color_from_node);
+
+        let mut color_to_node = dml_element("clrTo");
+        color_to_node.child_nodes.push(self.color_to.to_xml_element());
+        node.child_nodes.push(color_to_node);
+
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies a solid color replacement value. All effect colors are changed to a fixed color. Alpha values
 /// are unaffected.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColorReplaceEffect {
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl ColorReplaceEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_Color"))?;
+impl XsdType for ColorReplaceEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut color = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_Color"))?;
+
+        Ok(Self { color, unknown })
+    }
+}
 
-        Ok(Self { color })
+impl ToXmlElement for ColorReplaceEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -347,22 +661,52 @@ pub struct LuminanceEffect {
 
     /// Specifies the percent to change the contrast.
     pub contrast: Option<FixedPercentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl LuminanceEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for LuminanceEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "bright" => instance.brightness = Some(value.parse()?),
-                    "contrast" => instance.contrast = Some(value.parse()?),
-                    _ => (),
+                    "bright" => {
+                        instance.brightness = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "contrast" => {
+                        instance.contrast = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for LuminanceEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("bright", self.brightness);
+        node.set_optional_attribute("contrast", self.contrast);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -372,45 +716,90 @@ impl LuminanceEffect {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DuotoneEffect {
     pub colors: [Color; 2],
+
+    pub unknown: UnknownContent,
 }
 
-impl DuotoneEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let mut iterator = xml_node.child_nodes.iter().filter_map(Color::try_from_xml_element);
+impl XsdType for DuotoneEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut colors = Vec::new();
+        unknown.push_all_attributes(xml_node);
 
-        let color1 = iterator
-            .next()
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_Color"))?;
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if colors.len() < 2 && Color::is_choice_member(local_name) => {
+                    colors.push(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        let color2 = iterator
+        let mut colors = colors.into_iter();
+        let color1 = colors
+            .next()
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_Color"))?;
+        let color2 = colors
             .next()
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_Color"))?;
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_Color"))?;
 
-        // TODO(dam4rus): Check if node contains more than 2 color?
         Ok(Self {
             colors: [color1, color2],
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for DuotoneEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.colors.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies a fill which is one of blipFill, gradFill, grpFill, noFill, pattFill or solidFill.
 #[derive(Debug, Clone, PartialEq)]
 pub struct FillEffect {
     pub fill_properties: FillProperties,
+
+    pub unknown: UnknownContent,
 }
 
-impl FillEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let fill_properties = xml_node
-            .child_nodes
-            .iter()
-            .find_map(FillProperties::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_FillProperties"))?;
+impl XsdType for FillEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut fill_properties = None;
 
-        Ok(Self { fill_properties })
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if fill_properties.is_none() && FillProperties::is_choice_member(local_name) => {
+                    fill_properties = Some(FillProperties::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let fill_properties =
+            fill_properties.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_FillProperties"))?;
+
+        Ok(Self {
+            fill_properties,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for FillEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.fill_properties.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -421,24 +810,50 @@ pub struct FillOverlayEffect {
     /// Specifies how to blend the fill with the base effect.
     pub blend_mode: BlendMode,
     pub fill: FillProperties,
+
+    pub unknown: UnknownContent,
 }
 
-impl FillOverlayEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let blend_mode = xml_node
-            .attributes
-            .get("blend")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "blend"))?
-            .parse()?;
+impl XsdType for FillOverlayEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut blend_mode = None;
+        let mut fill = None;
 
-        let fill = xml_node
-            .child_nodes
-            .iter()
-            .find_map(FillProperties::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_FillProperties"))?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "blend" => blend_mode = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if fill.is_none() && FillProperties::is_choice_member(local_name) => {
+                    fill = Some(FillProperties::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { blend_mode, fill })
+        let blend_mode = blend_mode.ok_or_else(|| MissingAttributeError::at(xml_node, "blend"))?;
+        let fill = fill.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_FillProperties"))?;
+
+        Ok(Self {
+            blend_mode,
+            fill,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for FillOverlayEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("blend", &self.blend_mode);
+        node.child_nodes.push(self.fill.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -450,20 +865,52 @@ pub struct GlowEffect {
     /// Defaults to 0
     pub radius: Option<PositiveCoordinate>,
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl GlowEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let radius = xml_node.attributes.get("rad").map(|value| value.parse()).transpose()?;
+impl XsdType for GlowEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut radius = None;
+        let mut color = None;
 
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice"))?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "rad" => {
+                    radius = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_ColorChoice"))?;
+
+        Ok(Self { radius, color, unknown })
+    }
+}
 
-        Ok(Self { radius, color })
+impl ToXmlElement for GlowEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rad", self.radius);
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -485,23 +932,61 @@ pub struct HslEffect {
     ///
     /// Defaults to 0
     pub luminance: Option<FixedPercentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl HslEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for HslEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "hue" => instance.hue = Some(value.parse()?),
-                    "sat" => instance.saturation = Some(value.parse()?),
-                    "lum" => instance.luminance = Some(value.parse()?),
-                    _ => (),
+                    "hue" => {
+                        instance.hue = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sat" => {
+                        instance.saturation = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "lum" => {
+                        instance.luminance = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for HslEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("hue", self.hue);
+        node.set_optional_attribute("sat", self.saturation);
+        node.set_optional_attribute("lum", self.luminance);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -524,16 +1009,25 @@ pub struct InnerShadowEffect {
     /// Defaults to 0
     pub direction: Option<PositiveFixedAngle>,
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl InnerShadowEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice"))?;
+impl XsdType for InnerShadowEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut color = None;
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_ColorChoice"))?;
 
         let mut blur_radius = None;
         let mut distance = None;
@@ -541,10 +1035,31 @@ impl InnerShadowEffect {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "blurRad" => blur_radius = Some(value.parse()?),
-                "dist" => distance = Some(value.parse()?),
-                "dir" => direction = Some(value.parse()?),
-                _ => (),
+                "blurRad" => {
+                    blur_radius = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "dist" => {
+                    distance = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "dir" => {
+                    direction = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
@@ -553,10 +1068,23 @@ impl InnerShadowEffect {
             distance,
             direction,
             color,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for InnerShadowEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("blurRad", self.blur_radius);
+        node.set_optional_attribute("dist", self.distance);
+        node.set_optional_attribute("dir", self.direction);
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies an Outer Shadow Effect.
 #[derive(Debug, Clone, PartialEq)]
 pub struct OuterShadowEffect {
@@ -606,16 +1134,25 @@ pub struct OuterShadowEffect {
     /// Defaults to true
     pub rotate_with_shape: Option<bool>,
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl OuterShadowEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice"))?;
+impl XsdType for OuterShadowEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut color = None;
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_ColorChoice"))?;
 
         let mut blur_radius = None;
         let mut distance = None;
@@ -629,16 +1166,79 @@ impl OuterShadowEffect {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "blurRad" => blur_radius = Some(value.parse()?),
-                "dist" => distance = Some(value.parse()?),
-                "dir" => direction = Some(value.parse()?),
-                "sx" => scale_x = Some(value.parse()?),
-                "sy" => scale_y = Some(value.parse()?),
-                "kx" => skew_x = Some(value.parse()?),
-                "ky" => skew_y = Some(value.parse()?),
-                "algn" => alignment = Some(value.parse()?),
-                "rotWithShape" => rotate_with_shape = Some(parse_xml_bool(value)?),
-                _ => (),
+                "blurRad" => {
+                    blur_radius = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "dist" => {
+                    distance = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "dir" => {
+                    direction = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "sx" => {
+                    scale_x = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "sy" => {
+                    scale_y = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "kx" => {
+                    skew_x = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "ky" => {
+                    skew_y = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "algn" => {
+                    alignment = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "rotWithShape" => {
+                    rotate_with_shape = unknown.recover_attribute(
+                        xml_node.parse_bool_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
@@ -653,10 +1253,29 @@ impl OuterShadowEffect {
             alignment,
             rotate_with_shape,
             color,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for OuterShadowEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("blurRad", self.blur_radius);
+        node.set_optional_attribute("dist", self.distance);
+        node.set_optional_attribute("dir", self.direction);
+        node.set_optional_attribute("sx", self.scale_x);
+        node.set_optional_attribute("sy", self.scale_y);
+        node.set_optional_attribute("kx", self.skew_x);
+        node.set_optional_attribute("ky", self.skew_y);
+        node.set_optional_attribute("algn", self.alignment);
+        node.set_optional_attribute("rotWithShape", self.rotate_with_shape.map(format_xml_bool));
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies that a preset shadow is to be used. Each preset shadow is equivalent to a specific outer
 /// shadow effect. For each preset shadow, the color element, direction attribute, and distance attribute represent
 /// the color, direction, and distance parameters of the corresponding outer shadow. Additionally, the
@@ -677,16 +1296,25 @@ pub struct PresetShadowEffect {
     /// Defaults to 0
     pub direction: Option<PositiveFixedAngle>,
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl PresetShadowEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice"))?;
+impl XsdType for PresetShadowEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut color = None;
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_ColorChoice"))?;
 
         let mut preset = None;
         let mut distance = None;
@@ -694,24 +1322,51 @@ impl PresetShadowEffect {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "prst" => preset = Some(value.parse()?),
-                "dist" => distance = Some(value.parse()?),
-                "dir" => direction = Some(value.parse()?),
-                _ => (),
+                "prst" => preset = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                "dist" => {
+                    distance = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "dir" => {
+                    direction = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let preset = preset.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "prst"))?;
+        let preset = preset.ok_or_else(|| MissingAttributeError::at(xml_node, "prst"))?;
 
         Ok(Self {
             preset,
             distance,
             direction,
             color,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for PresetShadowEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("prst", &self.preset);
+        node.set_optional_attribute("dist", self.distance);
+        node.set_optional_attribute("dir", self.direction);
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies a reflection effect.
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct ReflectionEffect {
@@ -784,34 +1439,160 @@ pub struct ReflectionEffect {
     ///
     /// Defaults to true
     pub rotate_with_shape: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ReflectionEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for ReflectionEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "blurRad" => instance.blur_radius = Some(value.parse()?),
-                    "stA" => instance.start_opacity = Some(value.parse()?),
-                    "stPos" => instance.start_position = Some(value.parse()?),
-                    "endA" => instance.end_opacity = Some(value.parse()?),
-                    "endPos" => instance.end_position = Some(value.parse()?),
-                    "dist" => instance.distance = Some(value.parse()?),
-                    "dir" => instance.direction = Some(value.parse()?),
-                    "fadeDir" => instance.fade_direction = Some(value.parse()?),
-                    "sx" => instance.scale_x = Some(value.parse()?),
-                    "sy" => instance.scale_y = Some(value.parse()?),
-                    "kx" => instance.skew_x = Some(value.parse()?),
-                    "ky" => instance.skew_y = Some(value.parse()?),
-                    "algn" => instance.alignment = Some(value.parse()?),
-                    "rotWithShape" => instance.rotate_with_shape = Some(value.parse()?),
-                    _ => (),
+                    "blurRad" => {
+                        instance.blur_radius = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "stA" => {
+                        instance.start_opacity = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "stPos" => {
+                        instance.start_position = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "endA" => {
+                        instance.end_opacity = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "endPos" => {
+                        instance.end_position = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "dist" => {
+                        instance.distance = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "dir" => {
+                        instance.direction = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "fadeDir" => {
+                        instance.fade_direction = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sx" => {
+                        instance.scale_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sy" => {
+                        instance.scale_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "kx" => {
+                        instance.skew_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "ky" => {
+                        instance.skew_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "algn" => {
+                        instance.alignment = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rotWithShape" => {
+                        instance.rotate_with_shape = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for ReflectionEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("blurRad", self.blur_radius);
+        node.set_optional_attribute("stA", self.start_opacity);
+        node.set_optional_attribute("stPos", self.start_position);
+        node.set_optional_attribute("endA", self.end_opacity);
+        node.set_optional_attribute("endPos", self.end_position);
+        node.set_optional_attribute("dist", self.distance);
+        node.set_optional_attribute("dir", self.direction);
+        node.set_optional_attribute("fadeDir", self.fade_direction);
+        node.set_optional_attribute("sx", self.scale_x);
+        node.set_optional_attribute("sy", self.scale_y);
+        node.set_optional_attribute("kx", self.skew_x);
+        node.set_optional_attribute("ky", self.skew_y);
+        node.set_optional_attribute("algn", self.alignment);
+        node.set_optional_attribute("rotWithShape", self.rotate_with_shape.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -828,22 +1609,52 @@ pub struct RelativeOffsetEffect {
     ///
     /// Defaults to 0
     pub translate_y: Option<Percentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl RelativeOffsetEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for RelativeOffsetEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "tx" => instance.translate_x = Some(value.parse()?),
-                    "ty" => instance.translate_y = Some(value.parse()?),
-                    _ => (),
+                    "tx" => {
+                        instance.translate_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "ty" => {
+                        instance.translate_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for RelativeOffsetEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("tx", self.translate_x);
+        node.set_optional_attribute("ty", self.translate_y);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -852,17 +1663,36 @@ impl RelativeOffsetEffect {
 pub struct SoftEdgesEffect {
     /// Specifies the radius of blur to apply to the edges.
     pub radius: PositiveCoordinate,
+
+    pub unknown: UnknownContent,
 }
 
-impl SoftEdgesEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let radius = xml_node
-            .attributes
-            .get("rad")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "rad"))?
-            .parse()?;
+impl XsdType for SoftEdgesEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut radius = None;
 
-        Ok(Self { radius })
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "rad" => radius = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        unknown.push_all_elements(xml_node);
+
+        let radius = radius.ok_or_else(|| MissingAttributeError::at(xml_node, "rad"))?;
+
+        Ok(Self { radius, unknown })
+    }
+}
+
+impl ToXmlElement for SoftEdgesEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("rad", self.radius);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -878,22 +1708,52 @@ pub struct TintEffect {
     ///
     /// Defaults to 0
     pub amount: Option<FixedPercentage>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TintEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TintEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "hue" => instance.hue = Some(value.parse()?),
-                    "amt" => instance.amount = Some(value.parse()?),
-                    _ => (),
+                    "hue" => {
+                        instance.hue = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "amt" => {
+                        instance.amount = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for TintEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("hue", self.hue);
+        node.set_optional_attribute("amt", self.amount);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -938,26 +1798,88 @@ pub struct TransformEffect {
     ///
     /// Defaults to 0
     pub skew_y: Option<FixedAngle>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TransformEffect {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TransformEffect {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "sx" => instance.scale_x = Some(value.parse()?),
-                    "sy" => instance.scale_y = Some(value.parse()?),
-                    "kx" => instance.skew_x = Some(value.parse()?),
-                    "ky" => instance.skew_y = Some(value.parse()?),
-                    "tx" => instance.translate_x = Some(value.parse()?),
-                    "ty" => instance.translate_y = Some(value.parse()?),
-                    _ => (),
+                    "sx" => {
+                        instance.scale_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sy" => {
+                        instance.scale_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "kx" => {
+                        instance.skew_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "ky" => {
+                        instance.skew_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "tx" => {
+                        instance.translate_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "ty" => {
+                        instance.translate_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for TransformEffect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("sx", self.scale_x);
+        node.set_optional_attribute("sy", self.scale_y);
+        node.set_optional_attribute("kx", self.skew_x);
+        node.set_optional_attribute("ky", self.skew_y);
+        node.set_optional_attribute("tx", self.translate_x);
+        node.set_optional_attribute("ty", self.translate_y);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1019,50 +1941,98 @@ pub enum Effect {
 }
 
 impl XsdType for Effect {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "cont" => Ok(Effect::Container(EffectContainer::from_xml_element(xml_node)?)),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "cont" => Ok(Effect::Container(EffectContainer::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
             "effect" => {
                 let reference = xml_node
                     .attributes
                     .get("ref")
-                    .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "ref"))?
+                    .ok_or_else(|| MissingAttributeError::at(xml_node, "ref"))?
                     .clone();
                 Ok(Effect::EffectReference(reference))
             }
-            "alphaBiLevel" => Ok(Effect::AlphaBiLevel(AlphaBiLevelEffect::from_xml_element(xml_node)?)),
+            "alphaBiLevel" => Ok(Effect::AlphaBiLevel(AlphaBiLevelEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
             "alphaCeiling" => Ok(Effect::AlphaCeiling),
             "alphaFloor" => Ok(Effect::AlphaFloor),
-            "alphaInv" => Ok(Effect::AlphaInverse(AlphaInverseEffect::from_xml_element(xml_node)?)),
-            "alphaMod" => Ok(Effect::AlphaModulate(AlphaModulateEffect::from_xml_element(xml_node)?)),
-            "alphaModFix" => Ok(Effect::AlphaModulateFixed(AlphaModulateFixedEffect::from_xml_element(
-                xml_node,
+            "alphaInv" => Ok(Effect::AlphaInverse(AlphaInverseEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "alphaMod" => Ok(Effect::AlphaModulate(
+                AlphaModulateEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "alphaModFix" => Ok(Effect::AlphaModulateFixed(
+                AlphaModulateFixedEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "alphaOutset" => Ok(Effect::AlphaOutset(AlphaOutsetEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "alphaRepl" => Ok(Effect::AlphaReplace(AlphaReplaceEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "biLevel" => Ok(Effect::BiLevel(BiLevelEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "blend" => Ok(Effect::Blend(BlendEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "blur" => Ok(Effect::Blur(BlurEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "clrChange" => Ok(Effect::ColorChange(ColorChangeEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "clrRepl" => Ok(Effect::ColorReplace(ColorReplaceEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "duotone" => Ok(Effect::Duotone(DuotoneEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "fill" => Ok(Effect::Fill(FillEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "fillOverlay" => Ok(Effect::FillOverlay(FillOverlayEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "glow" => Ok(Effect::Glow(GlowEffect::from_xml_element_with_context(
+                xml_node, context,
             )?)),
-            "alphaOutset" => Ok(Effect::AlphaOutset(AlphaOutsetEffect::from_xml_element(xml_node)?)),
-            "alphaRepl" => Ok(Effect::AlphaReplace(AlphaReplaceEffect::from_xml_element(xml_node)?)),
-            "biLevel" => Ok(Effect::BiLevel(BiLevelEffect::from_xml_element(xml_node)?)),
-            "blend" => Ok(Effect::Blend(BlendEffect::from_xml_element(xml_node)?)),
-            "blur" => Ok(Effect::Blur(BlurEffect::from_xml_element(xml_node)?)),
-            "clrChange" => Ok(Effect::ColorChange(ColorChangeEffect::from_xml_element(xml_node)?)),
-            "clrRepl" => Ok(Effect::ColorReplace(ColorReplaceEffect::from_xml_element(xml_node)?)),
-            "duotone" => Ok(Effect::Duotone(DuotoneEffect::from_xml_element(xml_node)?)),
-            "fill" => Ok(Effect::Fill(FillEffect::from_xml_element(xml_node)?)),
-            "fillOverlay" => Ok(Effect::FillOverlay(FillOverlayEffect::from_xml_element(xml_node)?)),
-            "glow" => Ok(Effect::Glow(GlowEffect::from_xml_element(xml_node)?)),
             "grayscl" => Ok(Effect::Grayscale),
-            "hsl" => Ok(Effect::Hsl(HslEffect::from_xml_element(xml_node)?)),
-            "innerShdw" => Ok(Effect::InnerShadow(InnerShadowEffect::from_xml_element(xml_node)?)),
-            "lum" => Ok(Effect::Luminance(LuminanceEffect::from_xml_element(xml_node)?)),
-            "outerShdw" => Ok(Effect::OuterShadow(OuterShadowEffect::from_xml_element(xml_node)?)),
-            "prstShdw" => Ok(Effect::PresetShadow(PresetShadowEffect::from_xml_element(xml_node)?)),
-            "reflection" => Ok(Effect::Reflection(ReflectionEffect::from_xml_element(xml_node)?)),
-            "relOff" => Ok(Effect::RelativeOffset(RelativeOffsetEffect::from_xml_element(
-                xml_node,
+            "hsl" => Ok(Effect::Hsl(HslEffect::from_xml_element_with_context(
+                xml_node, context,
             )?)),
-            "softEdge" => Ok(Effect::SoftEdges(SoftEdgesEffect::from_xml_element(xml_node)?)),
-            "tint" => Ok(Effect::Tint(TintEffect::from_xml_element(xml_node)?)),
-            "xfrm" => Ok(Effect::Transform(TransformEffect::from_xml_element(xml_node)?)),
-            _ => Err(Box::new(NotGroupMemberError::new(xml_node.name.clone(), "EG_Effect"))),
+            "innerShdw" => Ok(Effect::InnerShadow(InnerShadowEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "lum" => Ok(Effect::Luminance(LuminanceEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "outerShdw" => Ok(Effect::OuterShadow(OuterShadowEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "prstShdw" => Ok(Effect::PresetShadow(PresetShadowEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "reflection" => Ok(Effect::Reflection(ReflectionEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "relOff" => Ok(Effect::RelativeOffset(
+                RelativeOffsetEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "softEdge" => Ok(Effect::SoftEdges(SoftEdgesEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "tint" => Ok(Effect::Tint(TintEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "xfrm" => Ok(Effect::Transform(TransformEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_Effect").into()),
         }
     }
 }
@@ -1072,12 +2042,79 @@ impl XsdChoice for Effect {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "cont" | "effect" | "alphaBiLevel" | "alphaCeiling" | "alphaFloor" | "alphaInv" | "alphaMod"
-            | "alphaModFix" | "alphaOutset" | "alphaRepl" | "biLevel" | "blend" | "blur" | "clrChange" | "clrRepl"
-            | "duotone" | "fill" | "fillOverlay" | "glow" | "grayscl" | "hsl" | "innerShdw" | "lum" | "outerShdw"
-            | "prstShdw" | "reflection" | "relOff" | "softEdge" | "tint" | "xfrm" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "cont"
+                | "effect"
+                | "alphaBiLevel"
+                | "alphaCeiling"
+                | "alphaFloor"
+                | "alphaInv"
+                | "alphaMod"
+                | "alphaModFix"
+                | "alphaOutset"
+                | "alphaRepl"
+                | "biLevel"
+                | "blend"
+                | "blur"
+                | "clrChange"
+                | "clrRepl"
+                | "duotone"
+                | "fill"
+                | "fillOverlay"
+                | "glow"
+                | "grayscl"
+                | "hsl"
+                | "innerShdw"
+                | "lum"
+                | "outerShdw"
+                | "prstShdw"
+                | "reflection"
+                | "relOff"
+                | "softEdge"
+                | "tint"
+                | "xfrm"
+        )
+    }
+}
+
+impl ToXmlChoice for Effect {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Effect::Container(effect) => effect.to_xml_element("a:cont"),
+            Effect::EffectReference(reference) => {
+                let mut node = dml_element("effect");
+                node.set_attribute("ref", reference);
+                node
+            }
+            Effect::AlphaBiLevel(effect) => effect.to_xml_element("a:alphaBiLevel"),
+            Effect::AlphaCeiling => dml_element("alphaCeiling"),
+            Effect::AlphaFloor => dml_element("alphaFloor"),
+            Effect::AlphaInverse(effect) => effect.to_xml_element("a:alphaInv"),
+            Effect::AlphaModulate(effect) => effect.to_xml_element("a:alphaMod"),
+            Effect::AlphaModulateFixed(effect) => effect.to_xml_element("a:alphaModFix"),
+            Effect::AlphaOutset(effect) => effect.to_xml_element("a:alphaOutset"),
+            Effect::AlphaReplace(effect) => effect.to_xml_element("a:alphaRepl"),
+            Effect::BiLevel(effect) => effect.to_xml_element("a:biLevel"),
+            Effect::Blend(effect) => effect.to_xml_element("a:blend"),
+            Effect::Blur(effect) => effect.to_xml_element("a:blur"),
+            Effect::ColorChange(effect) => effect.to_xml_element("a:clrChange"),
+            Effect::ColorReplace(effect) => effect.to_xml_element("a:clrRepl"),
+            Effect::Duotone(effect) => effect.to_xml_element("a:duotone"),
+            Effect::Fill(effect) => effect.to_xml_element("a:fill"),
+            Effect::FillOverlay(effect) => effect.to_xml_element("a:fillOverlay"),
+            Effect::Glow(effect) => effect.to_xml_element("a:glow"),
+            Effect::Grayscale => dml_element("grayscl"),
+            Effect::Hsl(effect) => effect.to_xml_element("a:hsl"),
+            Effect::InnerShadow(effect) => effect.to_xml_element("a:innerShdw"),
+            Effect::Luminance(effect) => effect.to_xml_element("a:lum"),
+            Effect::OuterShadow(effect) => effect.to_xml_element("a:outerShdw"),
+            Effect::PresetShadow(effect) => effect.to_xml_element("a:prstShdw"),
+            Effect::Reflection(effect) => effect.to_xml_element("a:reflection"),
+            Effect::RelativeOffset(effect) => effect.to_xml_element("a:relOff"),
+            Effect::SoftEdges(effect) => effect.to_xml_element("a:softEdge"),
+            Effect::Tint(effect) => effect.to_xml_element("a:tint"),
+            Effect::Transform(effect) => effect.to_xml_element("a:xfrm"),
         }
     }
 }
@@ -1117,49 +2154,103 @@ pub enum BlipEffect {
 }
 
 impl XsdType for BlipEffect {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<BlipEffect> {
-        match xml_node.local_name() {
-            "alphaBiLevel" => Ok(BlipEffect::AlphaBiLevel(AlphaBiLevelEffect::from_xml_element(
-                xml_node,
-            )?)),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<BlipEffect> {
+        match xml_node.dml_local_name() {
+            "alphaBiLevel" => Ok(BlipEffect::AlphaBiLevel(
+                AlphaBiLevelEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
             "alphaCeiling" => Ok(BlipEffect::AlphaCeiling),
             "alphaFloor" => Ok(BlipEffect::AlphaFloor),
-            "alphaInv" => Ok(BlipEffect::AlphaInverse(AlphaInverseEffect::from_xml_element(
-                xml_node,
+            "alphaInv" => Ok(BlipEffect::AlphaInverse(
+                AlphaInverseEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "alphaMod" => Ok(BlipEffect::AlphaModulate(
+                AlphaModulateEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "alphaModFix" => Ok(BlipEffect::AlphaModulateFixed(
+                AlphaModulateFixedEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "alphaRepl" => Ok(BlipEffect::AlphaReplace(
+                AlphaReplaceEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "biLevel" => Ok(BlipEffect::BiLevel(BiLevelEffect::from_xml_element_with_context(
+                xml_node, context,
             )?)),
-            "alphaMod" => Ok(BlipEffect::AlphaModulate(AlphaModulateEffect::from_xml_element(
-                xml_node,
+            "blur" => Ok(BlipEffect::Blur(BlurEffect::from_xml_element_with_context(
+                xml_node, context,
             )?)),
-            "alphaModFixed" => Ok(BlipEffect::AlphaModulateFixed(
-                AlphaModulateFixedEffect::from_xml_element(xml_node)?,
+            "clrChange" => Ok(BlipEffect::ColorChange(
+                ColorChangeEffect::from_xml_element_with_context(xml_node, context)?,
             )),
-            "alphaRepl" => Ok(BlipEffect::AlphaReplace(AlphaReplaceEffect::from_xml_element(
-                xml_node,
-            )?)),
-            "biLevel" => Ok(BlipEffect::BiLevel(BiLevelEffect::from_xml_element(xml_node)?)),
-            "blur" => Ok(BlipEffect::Blur(BlurEffect::from_xml_element(xml_node)?)),
-            "clrChange" => Ok(BlipEffect::ColorChange(ColorChangeEffect::from_xml_element(xml_node)?)),
-            "clrRepl" => Ok(BlipEffect::ColorReplace(ColorReplaceEffect::from_xml_element(
-                xml_node,
+            "clrRepl" => Ok(BlipEffect::ColorReplace(
+                ColorReplaceEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "duotone" => Ok(BlipEffect::Duotone(DuotoneEffect::from_xml_element_with_context(
+                xml_node, context,
             )?)),
-            "duotone" => Ok(BlipEffect::Duotone(DuotoneEffect::from_xml_element(xml_node)?)),
-            "fillOverlay" => Ok(BlipEffect::FillOverlay(FillOverlayEffect::from_xml_element(xml_node)?)),
+            "fillOverlay" => Ok(BlipEffect::FillOverlay(
+                FillOverlayEffect::from_xml_element_with_context(xml_node, context)?,
+            )),
             "grayscl" => Ok(BlipEffect::Grayscale),
-            "hsl" => Ok(BlipEffect::Hsl(HslEffect::from_xml_element(xml_node)?)),
-            "lum" => Ok(BlipEffect::Luminance(LuminanceEffect::from_xml_element(xml_node)?)),
-            "tint" => Ok(BlipEffect::Tint(TintEffect::from_xml_element(xml_node)?)),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_BlipEffect").into()),
+            "hsl" => Ok(BlipEffect::Hsl(HslEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "lum" => Ok(BlipEffect::Luminance(LuminanceEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            "tint" => Ok(BlipEffect::Tint(TintEffect::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_BlipEffect").into()),
         }
     }
 }
 
 impl XsdChoice for BlipEffect {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "alphaBiLevel" | "alphaCeiling" | "alphaFloor" | "alphaInv" | "alphaMod" | "alphaModFixed"
-            | "alphaRepl" | "biLevel" | "blur" | "clrChange" | "clrRepl" | "duotone" | "fillOverlay" | "grayscl"
-            | "hsl" | "lum" | "tint" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "alphaBiLevel"
+                | "alphaCeiling"
+                | "alphaFloor"
+                | "alphaInv"
+                | "alphaMod"
+                | "alphaModFix"
+                | "alphaRepl"
+                | "biLevel"
+                | "blur"
+                | "clrChange"
+                | "clrRepl"
+                | "duotone"
+                | "fillOverlay"
+                | "grayscl"
+                | "hsl"
+                | "lum"
+                | "tint"
+        )
+    }
+}
+
+impl ToXmlChoice for BlipEffect {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            BlipEffect::AlphaBiLevel(effect) => effect.to_xml_element("a:alphaBiLevel"),
+            BlipEffect::AlphaCeiling => dml_element("alphaCeiling"),
+            BlipEffect::AlphaFloor => dml_element("alphaFloor"),
+            BlipEffect::AlphaInverse(effect) => effect.to_xml_element("a:alphaInv"),
+            BlipEffect::AlphaModulate(effect) => effect.to_xml_element("a:alphaMod"),
+            BlipEffect::AlphaModulateFixed(effect) => effect.to_xml_element("a:alphaModFix"),
+            BlipEffect::AlphaReplace(effect) => effect.to_xml_element("a:alphaRepl"),
+            BlipEffect::BiLevel(effect) => effect.to_xml_element("a:biLevel"),
+            BlipEffect::Blur(effect) => effect.to_xml_element("a:blur"),
+            BlipEffect::ColorChange(effect) => effect.to_xml_element("a:clrChange"),
+            BlipEffect::ColorReplace(effect) => effect.to_xml_element("a:clrRepl"),
+            BlipEffect::Duotone(effect) => effect.to_xml_element("a:duotone"),
+            BlipEffect::FillOverlay(effect) => effect.to_xml_element("a:fillOverlay"),
+            BlipEffect::Grayscale => dml_element("grayscl"),
+            BlipEffect::Hsl(effect) => effect.to_xml_element("a:hsl"),
+            BlipEffect::Luminance(effect) => effect.to_xml_element("a:lum"),
+            BlipEffect::Tint(effect) => effect.to_xml_element("a:tint"),
         }
     }
 }
@@ -1194,18 +2285,15 @@ pub enum EffectProperties {
 }
 
 impl XsdType for EffectProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "effectLst" => Ok(EffectProperties::EffectList(Box::new(EffectList::from_xml_element(
-                xml_node,
-            )?))),
-            "effectDag" => Ok(EffectProperties::EffectContainer(Box::new(
-                EffectContainer::from_xml_element(xml_node)?,
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "effectLst" => Ok(EffectProperties::EffectList(Box::new(
+                EffectList::from_xml_element_with_context(xml_node, context)?,
             ))),
-            _ => Err(Box::new(NotGroupMemberError::new(
-                xml_node.name.clone(),
-                "EG_EffectProperties",
+            "effectDag" => Ok(EffectProperties::EffectContainer(Box::new(
+                EffectContainer::from_xml_element_with_context(xml_node, context)?,
             ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_EffectProperties").into()),
         }
     }
 }
@@ -1215,9 +2303,15 @@ impl XsdChoice for EffectProperties {
     where
         T: AsRef<str>,
     {
-        match name.as_ref() {
-            "effectLst" | "effectDag" => true,
-            _ => false,
+        matches!(name.as_ref(), "effectLst" | "effectDag")
+    }
+}
+
+impl ToXmlChoice for EffectProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            EffectProperties::EffectList(list) => list.to_xml_element("a:effectLst"),
+            EffectProperties::EffectContainer(container) => container.to_xml_element("a:effectDag"),
         }
     }
 }
@@ -1232,29 +2326,148 @@ pub struct EffectList {
     pub preset_shadow: Option<PresetShadowEffect>,
     pub reflection: Option<ReflectionEffect>,
     pub soft_edges: Option<SoftEdgesEffect>,
+
+    pub unknown: UnknownContent,
 }
 
-impl EffectList {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for EffectList {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         trace!("parsing EffectList '{}'", xml_node.name);
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "blur" => instance.blur = Some(BlurEffect::from_xml_element(child_node)?),
-                    "fillOverlay" => instance.fill_overlay = Some(FillOverlayEffect::from_xml_element(child_node)?),
-                    "glow" => instance.glow = Some(GlowEffect::from_xml_element(child_node)?),
-                    "innerShdw" => instance.inner_shadow = Some(InnerShadowEffect::from_xml_element(child_node)?),
-                    "outerShdw" => instance.outer_shadow = Some(OuterShadowEffect::from_xml_element(child_node)?),
-                    "prstShdw" => instance.preset_shadow = Some(PresetShadowEffect::from_xml_element(child_node)?),
-                    "reflection" => instance.reflection = Some(ReflectionEffect::from_xml_element(child_node)?),
-                    "softEdge" => instance.soft_edges = Some(SoftEdgesEffect::from_xml_element(child_node)?),
-                    _ => (),
+        let mut unknown = UnknownContent::default();
+        let mut blur = None;
+        let mut fill_overlay = None;
+        let mut glow = None;
+        let mut inner_shadow = None;
+        let mut outer_shadow = None;
+        let mut preset_shadow = None;
+        let mut reflection = None;
+        let mut soft_edges = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "blur" => {
+                    blur = unknown.recover_element(
+                        BlurEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "fillOverlay" => {
+                    fill_overlay = unknown.recover_element(
+                        FillOverlayEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "glow" => {
+                    glow = unknown.recover_element(
+                        GlowEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "innerShdw" => {
+                    inner_shadow = unknown.recover_element(
+                        InnerShadowEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
                 }
+                "outerShdw" => {
+                    outer_shadow = unknown.recover_element(
+                        OuterShadowEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "prstShdw" => {
+                    preset_shadow = unknown.recover_element(
+                        PresetShadowEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "reflection" => {
+                    reflection = unknown.recover_element(
+                        ReflectionEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                "softEdge" => {
+                    soft_edges = unknown.recover_element(
+                        SoftEdgesEffect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(Self {
+            blur,
+            fill_overlay,
+            glow,
+            inner_shadow,
+            outer_shadow,
+            preset_shadow,
+            reflection,
+            soft_edges,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for EffectList {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.blur.as_ref().map(|effect| effect.to_xml_element("a:blur")));
+        node.child_nodes.extend(
+            self.fill_overlay
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:fillOverlay")),
+        );
+        node.child_nodes
+            .extend(self.glow.as_ref().map(|effect| effect.to_xml_element("a:glow")));
+        node.child_nodes.extend(
+            self.inner_shadow
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:innerShdw")),
+        );
+        node.child_nodes.extend(
+            self.outer_shadow
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:outerShdw")),
+        );
+        node.child_nodes.extend(
+            self.preset_shadow
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:prstShdw")),
+        );
+        node.child_nodes.extend(
+            self.reflection
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:reflection")),
+        );
+        node.child_nodes.extend(
+            self.soft_edges
+                .as_ref()
+                .map(|effect| effect.to_xml_element("a:softEdge")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1270,34 +2483,54 @@ pub struct EffectContainer {
 
     /// Specifies the effects contained in this container.
     pub effects: Vec<Effect>,
+
+    pub unknown: UnknownContent,
 }
 
-impl EffectContainer {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<EffectContainer> {
+impl XsdType for EffectContainer {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "type" => instance.container_type = Some(value.parse::<EffectContainerType>()?),
+                    "type" => {
+                        instance.container_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<EffectContainerType>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
                     "name" => instance.name = Some(value.clone()),
-                    _ => (),
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
-            .and_then(|mut instance| {
-                instance.effects = xml_node
-                    .child_nodes
-                    .iter()
-                    .filter_map(Effect::try_from_xml_element)
-                    .collect::<Result<Vec<_>>>()?;
+            .and_then(|mut instance: Self| {
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    let effect = instance.unknown.parse_choice_element(position, child_node, context)?;
+                    instance.effects.extend(effect);
+                }
 
                 Ok(instance)
             })
     }
 }
 
+impl ToXmlElement for EffectContainer {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("type", self.container_type);
+        node.set_optional_attribute("name", self.name.as_ref());
+        node.child_nodes
+            .extend(self.effects.iter().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element defines a gradient fill.
 ///
 /// A gradient fill is a fill which is characterized by a smooth gradual transition from one color to the next. At its
@@ -1339,62 +2572,116 @@ pub struct GradientFillProperties {
     /// For example, a left offset of 25% specifies that the left edge of the tile rectangle is located to the right of the
     /// bounding box's left edge by an amount equal to 25% of the bounding box's width.
     pub tile_rect: Option<RelativeRect>,
+
+    pub unknown: UnknownContent,
 }
 
-impl GradientFillProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for GradientFillProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_str() {
-                    "flip" => instance.flip = Some(value.parse()?),
-                    "rotWithShape" => instance.rotate_with_shape = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "flip" => {
+                        instance.flip = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rotWithShape" => {
+                        instance.rotate_with_shape = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
-            .and_then(|instance| {
-                xml_node
-                    .child_nodes
-                    .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
-                            "gsLst" => {
-                                let gradient_stop_list = child_node
-                                    .child_nodes
-                                    .iter()
-                                    .filter(|gs_node| gs_node.local_name() == "gs")
-                                    .map(GradientStop::from_xml_element)
-                                    .collect::<Result<Vec<_>>>()?;
-
-                                match gradient_stop_list.len() {
-                                    len if len >= 2 => instance.gradient_stop_list = Some(gradient_stop_list),
-                                    len => {
-                                        return Err(Box::<dyn Error>::from(LimitViolationError::new(
-                                            xml_node.name.clone(),
-                                            "gsLst",
-                                            2,
-                                            MaxOccurs::Unbounded,
-                                            len as u32,
-                                        )))
-                                    }
+            .and_then(|mut instance: Self| {
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    match child_node.dml_local_name() {
+                        "gsLst" => {
+                            let gradient_stop_list = child_node
+                                .child_nodes
+                                .iter()
+                                .filter(|gs_node| gs_node.is(ns::DRAWINGML, "gs"))
+                                .filter_map(|child_node| {
+                                    recover_element(
+                                        GradientStop::from_xml_element_with_context(child_node, context),
+                                        context,
+                                    )
+                                    .transpose()
+                                })
+                                .collect::<Result<Vec<_>>>()?;
+
+                            match gradient_stop_list.len() {
+                                len if len >= 2 => instance.gradient_stop_list = Some(gradient_stop_list),
+                                len => {
+                                    return Err(Error::from(LimitViolationError::at(
+                                        xml_node,
+                                        "gsLst",
+                                        2,
+                                        MaxOccurs::Unbounded,
+                                        len as u32,
+                                    )))
                                 }
                             }
-                            "tileRect" => instance.tile_rect = Some(RelativeRect::from_xml_element(child_node)?),
-                            local_name if ShadeProperties::is_choice_member(local_name) => {
-                                instance.shade_properties = Some(ShadeProperties::from_xml_element(child_node)?)
-                            }
-                            _ => (),
                         }
+                        "tileRect" => {
+                            instance.tile_rect = instance.unknown.recover_element(
+                                RelativeRect::from_xml_element_with_context(child_node, context),
+                                position,
+                                child_node,
+                                context,
+                            )?
+                        }
+                        local_name if ShadeProperties::is_choice_member(local_name) => {
+                            instance.shade_properties = instance.unknown.recover_element(
+                                ShadeProperties::from_xml_element_with_context(child_node, context),
+                                position,
+                                child_node,
+                                context,
+                            )?
+                        }
+                        _ => instance.unknown.push_element(position, child_node),
+                    }
+                }
 
-                        Ok(instance)
-                    })
+                Ok(instance)
             })
     }
 }
 
+impl ToXmlElement for GradientFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("flip", self.flip);
+        node.set_optional_attribute("rotWithShape", self.rotate_with_shape.map(format_xml_bool));
+
+        if let Some(gradient_stop_list) = &self.gradient_stop_list {
+            let mut list_node = dml_element("gsLst");
+            list_node
+                .child_nodes
+                .extend(gradient_stop_list.iter().map(|stop| stop.to_xml_element("a:gs")));
+            node.child_nodes.push(list_node);
+        }
+
+        node.child_nodes
+            .extend(self.shade_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.tile_rect.as_ref().map(|rect| rect.to_xml_element("a:tileRect")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// Blip
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Blip {
@@ -1410,35 +2697,76 @@ pub struct Blip {
     /// application to specify the amount of compression that has been applied to a picture.
     pub compression: Option<BlipCompression>,
     pub effects: Vec<BlipEffect>,
+
+    /// Future extensions, e.g. whether the picture is stored in its original resolution.
+    pub extension_list: Option<OfficeArtExtensionList>,
+
+    pub unknown: UnknownContent,
 }
 
-impl Blip {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for Blip {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_ref() {
+                match xml_node.relationship_attr_name(attr).as_ref() {
                     "r:embed" => instance.embed_rel_id = Some(value.clone()),
                     "r:link" => instance.linked_rel_id = Some(value.clone()),
-                    "cstate" => instance.compression = Some(value.parse::<BlipCompression>()?),
-                    _ => (),
+                    "cstate" => {
+                        instance.compression = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<BlipCompression>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
-            .and_then(|instance| {
-                let effects = xml_node
-                    .child_nodes
-                    .iter()
-                    .filter_map(BlipEffect::try_from_xml_element)
-                    .collect::<Result<Vec<_>>>()?;
+            .and_then(|mut instance: Self| {
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    match child_node.dml_local_name() {
+                        "extLst" => {
+                            instance.extension_list = instance.unknown.recover_element(
+                                OfficeArtExtensionList::from_xml_element_with_context(child_node, context),
+                                position,
+                                child_node,
+                                context,
+                            )?
+                        }
+                        _ => {
+                            let effect = instance.unknown.parse_choice_element(position, child_node, context)?;
+                            instance.effects.extend(effect);
+                        }
+                    }
+                }
 
-                Ok(Self { effects, ..instance })
+                Ok(instance)
             })
     }
 }
 
+impl ToXmlElement for Blip {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("r:embed", self.embed_rel_id.as_ref());
+        node.set_optional_attribute("r:link", self.linked_rel_id.as_ref());
+        node.set_optional_attribute("cstate", self.compression);
+        node.child_nodes
+            .extend(self.effects.iter().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.extend(
+            self.extension_list
+                .as_ref()
+                .map(|extension_list| extension_list.to_xml_element("a:extLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct BlipFillProperties {
     /// Specifies the DPI (dots per inch) used to calculate the size of the blip. If not present or
@@ -1473,18 +2801,34 @@ pub struct BlipFillProperties {
 
     /// Specifies the fill mode of this blip.
     pub fill_mode_properties: Option<FillModeProperties>,
+
+    pub unknown: UnknownContent,
 }
 
-impl BlipFillProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for BlipFillProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "dpi" => instance.dpi = Some(value.parse()?),
-                    "rotWithShape" => instance.rotate_with_shape = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "dpi" => {
+                        instance.dpi = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rotWithShape" => {
+                        instance.rotate_with_shape = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -1493,14 +2837,37 @@ impl BlipFillProperties {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
-                            "blip" => instance.blip = Some(Box::new(Blip::from_xml_element(child_node)?)),
-                            "srcRect" => instance.source_rect = Some(RelativeRect::from_xml_element(child_node)?),
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
+                            "blip" => {
+                                instance.blip = instance
+                                    .unknown
+                                    .recover_element(
+                                        Blip::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
+                            }
+                            "srcRect" => {
+                                instance.source_rect = instance.unknown.recover_element(
+                                    RelativeRect::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
                             local_name if FillModeProperties::is_choice_member(local_name) => {
-                                instance.fill_mode_properties = Some(FillModeProperties::from_xml_element(child_node)?)
+                                instance.fill_mode_properties = instance.unknown.recover_element(
+                                    FillModeProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
                             }
-                            _ => (),
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -1509,6 +2876,22 @@ impl BlipFillProperties {
     }
 }
 
+impl ToXmlElement for BlipFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("dpi", self.dpi);
+        node.set_optional_attribute("rotWithShape", self.rotate_with_shape.map(format_xml_bool));
+        node.child_nodes
+            .extend(self.blip.as_ref().map(|blip| blip.to_xml_element("a:blip")));
+        node.child_nodes
+            .extend(self.source_rect.as_ref().map(|rect| rect.to_xml_element("a:srcRect")));
+        node.child_nodes
+            .extend(self.fill_mode_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 /// This element specifies a dash stop primitive. Dashing schemes are built by specifying an ordered list of dash stop
 /// primitive. A dash stop primitive consists of a dash and a space.
 #[derive(Debug, Clone, PartialEq)]
@@ -1518,31 +2901,61 @@ pub struct DashStop {
 
     /// Specifies the length of the space relative to the line width.
     pub space_length: PositivePercentage,
+
+    pub unknown: UnknownContent,
 }
 
-impl DashStop {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<DashStop> {
+impl XsdType for DashStop {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_dash_length = None;
         let mut opt_space_length = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_ref() {
-                "d" => opt_dash_length = Some(value.parse::<PositivePercentage>()?),
-                "sp" => opt_space_length = Some(value.parse::<PositivePercentage>()?),
-                _ => (),
+                "d" => {
+                    opt_dash_length = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<PositivePercentage>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "sp" => {
+                    opt_space_length = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<PositivePercentage>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let dash_length = opt_dash_length.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "d"))?;
-        let space_length = opt_space_length.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "sp"))?;
+        let dash_length = opt_dash_length.ok_or_else(|| MissingAttributeError::at(xml_node, "d"))?;
+        let space_length = opt_space_length.ok_or_else(|| MissingAttributeError::at(xml_node, "sp"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             dash_length,
             space_length,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for DashStop {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("d", self.dash_length);
+        node.set_attribute("sp", self.space_length);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GradientStop {
     /// The position of this gradient stop.
@@ -1550,24 +2963,50 @@ pub struct GradientStop {
 
     /// The color of this gradient stop.
     pub color: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl GradientStop {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let position = xml_node
-            .attributes
-            .get("pos")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "pos"))?
-            .parse()?;
+impl XsdType for GradientStop {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut opt_position = None;
+        let mut color = None;
 
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "color"))?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "pos" => opt_position = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if color.is_none() && Color::is_choice_member(local_name) => {
+                    color = Some(Color::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let position = opt_position.ok_or_else(|| MissingAttributeError::at(xml_node, "pos"))?;
+        let color = color.ok_or_else(|| MissingChildNodeError::at(xml_node, "color"))?;
+
+        Ok(Self {
+            position,
+            color,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { position, color })
+impl ToXmlElement for GradientStop {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("pos", self.position);
+        node.child_nodes.push(self.color.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1581,23 +3020,61 @@ pub struct LineEndProperties {
 
     /// Specifies the line end length in relation to the line width.
     pub length: Option<LineEndLength>,
+
+    pub unknown: UnknownContent,
 }
 
-impl LineEndProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<LineEndProperties> {
+impl XsdType for LineEndProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "type" => instance.end_type = Some(value.parse::<LineEndType>()?),
-                    "width" => instance.width = Some(value.parse::<LineEndWidth>()?),
-                    "length" => instance.length = Some(value.parse::<LineEndLength>()?),
-                    _ => (),
+                    "type" => {
+                        instance.end_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<LineEndType>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "width" => {
+                        instance.width = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<LineEndWidth>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "length" => {
+                        instance.length = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<LineEndLength>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for LineEndProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("type", self.end_type);
+        node.set_optional_attribute("w", self.width);
+        node.set_optional_attribute("len", self.length);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1617,22 +3094,52 @@ pub struct LinearShadeProperties {
     /// So a 45-degree gradient angle always give a gradient band whose line of constant color is
     /// parallel to the vector (1, -1).
     pub scaled: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl LinearShadeProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for LinearShadeProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "ang" => instance.angle = Some(value.parse::<PositiveFixedAngle>()?),
-                    "scaled" => instance.scaled = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "ang" => {
+                        instance.angle = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<PositiveFixedAngle>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "scaled" => {
+                        instance.scaled = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for LinearShadeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("ang", self.angle);
+        node.set_optional_attribute("scaled", self.scaled.map(format_xml_bool));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1663,20 +3170,63 @@ pub struct PathShadeProperties {
     /// In the above shape, the rectangle defined by fillToRect is a single point in the center of the shape. This creates
     /// the effect of the center shade focusing at a point in the center of the region.
     pub fill_to_rect: Option<RelativeRect>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PathShadeProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let path = xml_node.attributes.get("path").map(|value| value.parse()).transpose()?;
+impl XsdType for PathShadeProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut path = None;
+        let mut fill_to_rect = None;
 
-        let fill_to_rect = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "fillToRect")
-            .map(RelativeRect::from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "path" => {
+                    path = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "fillToRect" => {
+                    fill_to_rect = unknown.recover_element(
+                        RelativeRect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { path, fill_to_rect })
+        Ok(Self {
+            path,
+            fill_to_rect,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for PathShadeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("path", self.path);
+        node.child_nodes.extend(
+            self.fill_to_rect
+                .as_ref()
+                .map(|rect| rect.to_xml_element("a:fillToRect")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1690,22 +3240,30 @@ pub enum ShadeProperties {
 }
 
 impl XsdType for ShadeProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "lin" => Ok(ShadeProperties::Linear(LinearShadeProperties::from_xml_element(
-                xml_node,
-            )?)),
-            "path" => Ok(ShadeProperties::Path(PathShadeProperties::from_xml_element(xml_node)?)),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_ShadeProperties").into()),
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "lin" => Ok(ShadeProperties::Linear(
+                LinearShadeProperties::from_xml_element_with_context(xml_node, context)?,
+            )),
+            "path" => Ok(ShadeProperties::Path(
+                PathShadeProperties::from_xml_element_with_context(xml_node, context)?,
+            )),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_ShadeProperties").into()),
         }
     }
 }
 
 impl XsdChoice for ShadeProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "lin" | "path" => true,
-            _ => false,
+        matches!(name.as_ref(), "lin" | "path")
+    }
+}
+
+impl ToXmlChoice for ShadeProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            ShadeProperties::Linear(props) => props.to_xml_element("a:lin"),
+            ShadeProperties::Path(props) => props.to_xml_element("a:path"),
         }
     }
 }
@@ -1720,45 +3278,83 @@ pub struct PatternFillProperties {
 
     /// This element specifies the background color of a Pattern fill.
     pub bg_color: Option<Color>,
+
+    pub unknown: UnknownContent,
 }
 
-impl PatternFillProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let preset = xml_node.attributes.get("prst").map(|value| value.parse()).transpose()?;
-
-        xml_node.child_nodes.iter().try_fold(
-            Self {
-                preset,
-                ..Default::default()
-            },
-            |mut instance, child_node| {
-                match child_node.local_name() {
-                    "fgClr" => {
-                        let fg_color = child_node
-                            .child_nodes
-                            .iter()
-                            .find_map(Color::try_from_xml_element)
-                            .transpose()?
-                            .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "EG_Color"))?;
-
-                        instance.fg_color = Some(fg_color);
-                    }
-                    "bgClr" => {
-                        let bg_color = child_node
-                            .child_nodes
-                            .iter()
-                            .find_map(Color::try_from_xml_element)
-                            .transpose()?
-                            .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "EG_Color"))?;
-
-                        instance.bg_color = Some(bg_color);
-                    }
-                    _ => (),
+impl XsdType for PatternFillProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut preset = None;
+        let mut fg_color = None;
+        let mut bg_color = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "prst" => {
+                    preset = unknown.recover_attribute(
+                        xml_node.parse_extensible_attribute_value(attr, value, context),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
                 }
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-                Ok(instance)
-            },
-        )
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "fgClr" => {
+                    let color = child_node
+                        .child_nodes
+                        .iter()
+                        .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
+                        .transpose()?
+                        .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_Color"))?;
+                    fg_color = Some(color);
+                }
+                "bgClr" => {
+                    let color = child_node
+                        .child_nodes
+                        .iter()
+                        .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
+                        .transpose()?
+                        .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_Color"))?;
+                    bg_color = Some(color);
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self {
+            preset,
+            fg_color,
+            bg_color,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for PatternFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("prst", self.preset.as_ref());
+
+        if let Some(fg_color) = &self.fg_color {
+            let mut fg_color_node = dml_element("fgClr");
+            fg_color_node.child_nodes.push(fg_color.to_xml_element());
+            node.child_nodes.push(fg_color_node);
+        }
+
+        if let Some(bg_color) = &self.bg_color {
+            let mut bg_color_node = dml_element("bgClr");
+            bg_color_node.child_nodes.push(bg_color.to_xml_element());
+            node.child_nodes.push(bg_color_node);
+        }
+
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1802,43 +3398,62 @@ pub enum FillProperties {
 }
 
 impl XsdType for FillProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "noFill" => Ok(FillProperties::NoFill),
             "solidFill" => {
                 let color = xml_node
                     .child_nodes
                     .iter()
-                    .find_map(Color::try_from_xml_element)
+                    .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                     .transpose()?
-                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "color"))?;
+                    .ok_or_else(|| MissingChildNodeError::at(xml_node, "color"))?;
 
                 Ok(FillProperties::SolidFill(color))
             }
             "gradFill" => Ok(FillProperties::GradientFill(Box::new(
-                GradientFillProperties::from_xml_element(xml_node)?,
+                GradientFillProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
             "blipFill" => Ok(FillProperties::BlipFill(Box::new(
-                BlipFillProperties::from_xml_element(xml_node)?,
+                BlipFillProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
             "pattFill" => Ok(FillProperties::PatternFill(Box::new(
-                PatternFillProperties::from_xml_element(xml_node)?,
+                PatternFillProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
             "grpFill" => Ok(FillProperties::GroupFill),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_FillProperties").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_FillProperties").into()),
         }
     }
 }
 
 impl XsdChoice for FillProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "noFill" | "solidFill" | "gradFill" | "blipFill" | "pattFill" | "grpFill" => true,
-            _ => false,
+        matches!(
+            name.as_ref(),
+            "noFill" | "solidFill" | "gradFill" | "blipFill" | "pattFill" | "grpFill"
+        )
+    }
+}
+
+impl ToXmlChoice for FillProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            FillProperties::NoFill => dml_element("noFill"),
+            FillProperties::SolidFill(color) => solid_fill_to_xml_element(color),
+            FillProperties::GradientFill(props) => props.to_xml_element("a:gradFill"),
+            FillProperties::BlipFill(props) => props.to_xml_element("a:blipFill"),
+            FillProperties::PatternFill(props) => props.to_xml_element("a:pattFill"),
+            FillProperties::GroupFill => dml_element("grpFill"),
         }
     }
 }
 
+fn solid_fill_to_xml_element(color: &Color) -> XmlNode {
+    let mut node = dml_element("solidFill");
+    node.child_nodes.push(color.to_xml_element());
+    node
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum LineJoinProperties {
     /// This element specifies that lines joined together have a round join.
@@ -1857,25 +3472,36 @@ pub enum LineJoinProperties {
 }
 
 impl XsdType for LineJoinProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<LineJoinProperties> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<LineJoinProperties> {
+        match xml_node.dml_local_name() {
             "round" => Ok(LineJoinProperties::Round),
             "bevel" => Ok(LineJoinProperties::Bevel),
             "miter" => {
-                let lim = xml_node.attributes.get("lim").map(|value| value.parse()).transpose()?;
+                let lim = xml_node.parse_attribute("lim", context)?;
 
                 Ok(LineJoinProperties::Miter(lim))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_LineJoinProperties").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_LineJoinProperties").into()),
         }
     }
 }
 
 impl XsdChoice for LineJoinProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "round" | "bevel" | "miter" => true,
-            _ => false,
+        matches!(name.as_ref(), "round" | "bevel" | "miter")
+    }
+}
+
+impl ToXmlChoice for LineJoinProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match *self {
+            LineJoinProperties::Round => dml_element("round"),
+            LineJoinProperties::Bevel => dml_element("bevel"),
+            LineJoinProperties::Miter(limit) => {
+                let mut node = dml_element("miter");
+                node.set_optional_attribute("lim", limit);
+                node
+            }
         }
     }
 }
@@ -1904,18 +3530,42 @@ pub struct StretchInfoProperties {
     /// </a:blipFill>
     /// ```
     pub fill_rect: Option<RelativeRect>,
+
+    pub unknown: UnknownContent,
 }
 
-impl StretchInfoProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let fill_rect = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "fillRect")
-            .map(RelativeRect::from_xml_element)
-            .transpose()?;
+impl XsdType for StretchInfoProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut fill_rect = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "fillRect" => {
+                    fill_rect = unknown.recover_element(
+                        RelativeRect::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self { fill_rect, unknown })
+    }
+}
 
-        Ok(Self { fill_rect })
+impl ToXmlElement for StretchInfoProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.fill_rect.as_ref().map(|rect| rect.to_xml_element("a:fillRect")));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1940,26 +3590,88 @@ pub struct TileInfoProperties {
     /// Specifies where to align the first tile with respect to the shape. Alignment happens after
     /// the scaling, but before the additional offset.
     pub alignment: Option<RectAlignment>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TileInfoProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TileInfoProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "tx" => instance.translate_x = Some(value.parse()?),
-                    "ty" => instance.translate_y = Some(value.parse()?),
-                    "sx" => instance.scale_x = Some(value.parse()?),
-                    "sy" => instance.scale_y = Some(value.parse()?),
-                    "flip" => instance.flip_mode = Some(value.parse()?),
-                    "algn" => instance.alignment = Some(value.parse()?),
-                    _ => (),
+                    "tx" => {
+                        instance.translate_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "ty" => {
+                        instance.translate_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sx" => {
+                        instance.scale_x = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "sy" => {
+                        instance.scale_y = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "flip" => {
+                        instance.flip_mode = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "algn" => {
+                        instance.alignment = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for TileInfoProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("tx", self.translate_x);
+        node.set_optional_attribute("ty", self.translate_y);
+        node.set_optional_attribute("sx", self.scale_x);
+        node.set_optional_attribute("sy", self.scale_y);
+        node.set_optional_attribute("flip", self.flip_mode);
+        node.set_optional_attribute("algn", self.alignment);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1976,24 +3688,30 @@ pub enum FillModeProperties {
 }
 
 impl XsdType for FillModeProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "tile" => Ok(FillModeProperties::Tile(Box::new(
-                TileInfoProperties::from_xml_element(xml_node)?,
+                TileInfoProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
             "stretch" => Ok(FillModeProperties::Stretch(Box::new(
-                StretchInfoProperties::from_xml_element(xml_node)?,
+                StretchInfoProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_FillModeProperties").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_FillModeProperties").into()),
         }
     }
 }
 
 impl XsdChoice for FillModeProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "tile" | "stretch" => true,
-            _ => false,
+        matches!(name.as_ref(), "tile" | "stretch")
+    }
+}
+
+impl ToXmlChoice for FillModeProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            FillModeProperties::Tile(props) => props.to_xml_element("a:tile"),
+            FillModeProperties::Stretch(props) => props.to_xml_element("a:stretch"),
         }
     }
 }
@@ -2014,35 +3732,43 @@ pub enum LineFillProperties {
 }
 
 impl XsdType for LineFillProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<LineFillProperties> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<LineFillProperties> {
+        match xml_node.dml_local_name() {
             "noFill" => Ok(LineFillProperties::NoFill),
             "solidFill" => {
                 let color = xml_node
                     .child_nodes
                     .iter()
-                    .find_map(Color::try_from_xml_element)
+                    .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                     .transpose()?
-                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "color"))?;
+                    .ok_or_else(|| MissingChildNodeError::at(xml_node, "color"))?;
 
                 Ok(LineFillProperties::SolidFill(color))
             }
             "gradFill" => Ok(LineFillProperties::GradientFill(Box::new(
-                GradientFillProperties::from_xml_element(xml_node)?,
+                GradientFillProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
             "pattFill" => Ok(LineFillProperties::PatternFill(Box::new(
-                PatternFillProperties::from_xml_element(xml_node)?,
+                PatternFillProperties::from_xml_element_with_context(xml_node, context)?,
             ))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_LineFillProperties").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_LineFillProperties").into()),
         }
     }
 }
 
 impl XsdChoice for LineFillProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "noFill" | "solidFill" | "gradFill" | "pattFill" => true,
-            _ => false,
+        matches!(name.as_ref(), "noFill" | "solidFill" | "gradFill" | "pattFill")
+    }
+}
+
+impl ToXmlChoice for LineFillProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            LineFillProperties::NoFill => dml_element("noFill"),
+            LineFillProperties::SolidFill(color) => solid_fill_to_xml_element(color),
+            LineFillProperties::GradientFill(props) => props.to_xml_element("a:gradFill"),
+            LineFillProperties::PatternFill(props) => props.to_xml_element("a:pattFill"),
         }
     }
 }
@@ -2059,23 +3785,15 @@ pub enum LineDashProperties {
 
 impl XsdChoice for LineDashProperties {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "prstDash" | "custDash" => true,
-            _ => false,
-        }
+        matches!(name.as_ref(), "prstDash" | "custDash")
     }
 }
 
 impl XsdType for LineDashProperties {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<LineDashProperties> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<LineDashProperties> {
+        match xml_node.dml_local_name() {
             "prstDash" => {
-                let val = xml_node
-                    .attributes
-                    .get("val")
-                    .map(|value| value.parse())
-                    .transpose()?
-                    .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "val"))?;
+                let val = xml_node.parse_extensible_val_attribute(context)?;
 
                 Ok(LineDashProperties::PresetDash(val))
             }
@@ -2083,13 +3801,129 @@ impl XsdType for LineDashProperties {
                 let dash_vec = xml_node
                     .child_nodes
                     .iter()
-                    .filter(|child_node| child_node.local_name() == "ds")
-                    .map(DashStop::from_xml_element)
+                    .filter(|child_node| child_node.is(ns::DRAWINGML, "ds"))
+                    .filter_map(|child_node| {
+                        recover_element(DashStop::from_xml_element_with_context(child_node, context), context)
+                            .transpose()
+                    })
                     .collect::<Result<Vec<_>>>()?;
 
                 Ok(LineDashProperties::CustomDash(dash_vec))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_LineDashProperties").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_LineDashProperties").into()),
+        }
+    }
+}
+
+impl ToXmlChoice for LineDashProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            LineDashProperties::PresetDash(preset) => dml_val_element("prstDash", preset),
+            LineDashProperties::CustomDash(dash_stops) => {
+                let mut node = dml_element("custDash");
+                node.child_nodes
+                    .extend(dash_stops.iter().map(|stop| stop.to_xml_element("a:ds")));
+                node
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::writer::WriteOptions;
+    use std::str::FromStr;
+
+    const DRAWINGML_NS: &str = r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main""#;
+
+    #[test]
+    fn test_gradient_fill_round_trip() {
+        let xml = format!(
+            r#"<a:gradFill {} rotWithShape="1" flip="x">
+                <a:gsLst>
+                    <a:gs pos="0"><a:schemeClr val="phClr"><a:lumMod val="110000"/><a:satMod val="105000"/><a:tint val="67000"/></a:schemeClr></a:gs>
+                    <a:gs pos="50000"><a:srgbClr val="4472C4"><a:alpha val="50000"/></a:srgbClr></a:gs>
+                    <a:gs pos="100000"><a:prstClr val="black"/></a:gs>
+                </a:gsLst>
+                <a:lin ang="5400000" scaled="0"/>
+                <a:tileRect l="-10000" r="10000"/>
+            </a:gradFill>"#,
+            DRAWINGML_NS
+        );
+        let fill = FillProperties::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        match &fill {
+            FillProperties::GradientFill(gradient) => {
+                assert_eq!(gradient.gradient_stop_list.as_ref().map(Vec::len), Some(3));
+                assert_eq!(gradient.rotate_with_shape, Some(true));
+            }
+            _ => panic!("expected a gradient fill"),
         }
+
+        let written = fill.to_xml_element().to_xml_string(&WriteOptions::compact()).unwrap();
+        let reparsed = FillProperties::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap();
+        assert_eq!(reparsed, fill);
+    }
+
+    #[test]
+    fn test_effect_list_round_trip() {
+        let xml = format!(
+            r#"<a:effectLst {}>
+                <a:glow rad="63500"><a:schemeClr val="accent1"><a:alpha val="40000"/></a:schemeClr></a:glow>
+                <a:outerShdw blurRad="57150" dist="19050" dir="5400000" algn="ctr" rotWithShape="0"><a:srgbClr val="000000"><a:alpha val="63000"/></a:srgbClr></a:outerShdw>
+                <a:reflection blurRad="6350" stA="52000" endA="300" endPos="35000" dir="5400000" sy="-100000" algn="bl" rotWithShape="0"/>
+                <a:softEdge rad="31750"/>
+            </a:effectLst>"#,
+            DRAWINGML_NS
+        );
+        let effect_list = EffectList::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        assert!(effect_list.glow.is_some());
+        assert!(effect_list.outer_shadow.is_some());
+        assert!(effect_list.reflection.is_some());
+        assert!(effect_list.soft_edges.is_some());
+
+        let written = effect_list
+            .to_xml_element("a:effectLst")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        let names: Vec<String> = XmlNode::from_str(&written)
+            .unwrap()
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.name.clone())
+            .collect();
+        assert_eq!(names, vec!["a:glow", "a:outerShdw", "a:reflection", "a:softEdge"]);
+
+        let reparsed = EffectList::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap();
+        assert_eq!(reparsed, effect_list);
+    }
+
+    #[test]
+    fn test_blip_round_trip() {
+        let xml = format!(
+            r#"<a:blip {} xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+                xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main" r:embed="rId2" cstate="print">
+                <a:alphaModFix amt="60000"/>
+                <a:lum bright="20000" contrast="-10000"/>
+                <a:grayscl/>
+                <a:duotone><a:schemeClr val="bg2"/><a:srgbClr val="FFFFFF"/></a:duotone>
+                <a:extLst>
+                    <a:ext uri="{{28A0092B-C50C-407E-A947-70E740481C1C}}"><a14:useLocalDpi val="0"/></a:ext>
+                </a:extLst>
+            </a:blip>"#,
+            DRAWINGML_NS
+        );
+        let blip = Blip::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        assert_eq!(blip.embed_rel_id.as_deref(), Some("rId2"));
+        assert_eq!(blip.effects.len(), 4);
+        assert!(blip.unknown.elements.is_empty());
+        assert_eq!(blip.extension_list.as_ref().unwrap().extensions.len(), 1);
+
+        let written = blip
+            .to_xml_element("a:blip")
+            .to_xml_string(&WriteOptions::compact())
+            .unwrap();
+        let reparsed = Blip::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap();
+        assert_eq!(reparsed, blip);
     }
 }
@@ -1,18 +1,26 @@
 use crate::{
     drawingml::{
         colors::{Color, CustomColor},
+        extensions::OfficeArtExtensionList,
         simpletypes::ColorSchemeIndex,
         styles::{DefaultShapeDefinition, FontScheme, StyleMatrix},
+        util::{dml_element, XmlNodeExt},
     },
     error::{MissingAttributeError, MissingChildNodeError},
-    xml::XmlNode,
-    xsdtypes::XsdChoice,
+    xml::{
+        limits::ParseLimits,
+        ns,
+        reader::{StartElement, XmlReader},
+        unknown::UnknownContent,
+        zip_file_to_xsd_type_with_limits, XmlNode,
+    },
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 use log::trace;
-use std::{io::Read, str::FromStr};
+use std::io::BufRead;
 use zip::read::ZipFile;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColorMapping {
@@ -51,10 +59,13 @@ pub struct ColorMapping {
 
     /// Specifies a color defined which is associated as the color for a followed hyperlink.
     pub followed_hyperlink: ColorSchemeIndex,
+
+    pub unknown: UnknownContent,
 }
 
-impl ColorMapping {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for ColorMapping {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut background1 = None;
         let mut text1 = None;
         let mut background2 = None;
@@ -70,35 +81,36 @@ impl ColorMapping {
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "bg1" => background1 = Some(value.parse()?),
-                "tx1" => text1 = Some(value.parse()?),
-                "bg2" => background2 = Some(value.parse()?),
-                "tx2" => text2 = Some(value.parse()?),
-                "accent1" => accent1 = Some(value.parse()?),
-                "accent2" => accent2 = Some(value.parse()?),
-                "accent3" => accent3 = Some(value.parse()?),
-                "accent4" => accent4 = Some(value.parse()?),
-                "accent5" => accent5 = Some(value.parse()?),
-                "accent6" => accent6 = Some(value.parse()?),
-                "hlink" => hyperlink = Some(value.parse()?),
-                "folHlink" => followed_hyperlink = Some(value.parse()?),
-                _ => (),
+                "bg1" => background1 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "tx1" => text1 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "bg2" => background2 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "tx2" => text2 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent1" => accent1 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent2" => accent2 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent3" => accent3 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent4" => accent4 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent5" => accent5 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "accent6" => accent6 = Some(xml_node.parse_attribute_value(attr, value)?),
+                "hlink" => hyperlink = Some(xml_node.parse_attribute_value(attr, value)?),
+                "folHlink" => followed_hyperlink = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let background1 = background1.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "bg1"))?;
-        let text1 = text1.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "tx1"))?;
-        let background2 = background2.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "bg2"))?;
-        let text2 = text2.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "tx2"))?;
-        let accent1 = accent1.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent1"))?;
-        let accent2 = accent2.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent2"))?;
-        let accent3 = accent3.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent3"))?;
-        let accent4 = accent4.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent4"))?;
-        let accent5 = accent5.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent5"))?;
-        let accent6 = accent6.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "accent6"))?;
-        let hyperlink = hyperlink.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "hlink"))?;
-        let followed_hyperlink =
-            followed_hyperlink.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "folHlink"))?;
+        let background1 = background1.ok_or_else(|| MissingAttributeError::at(xml_node, "bg1"))?;
+        let text1 = text1.ok_or_else(|| MissingAttributeError::at(xml_node, "tx1"))?;
+        let background2 = background2.ok_or_else(|| MissingAttributeError::at(xml_node, "bg2"))?;
+        let text2 = text2.ok_or_else(|| MissingAttributeError::at(xml_node, "tx2"))?;
+        let accent1 = accent1.ok_or_else(|| MissingAttributeError::at(xml_node, "accent1"))?;
+        let accent2 = accent2.ok_or_else(|| MissingAttributeError::at(xml_node, "accent2"))?;
+        let accent3 = accent3.ok_or_else(|| MissingAttributeError::at(xml_node, "accent3"))?;
+        let accent4 = accent4.ok_or_else(|| MissingAttributeError::at(xml_node, "accent4"))?;
+        let accent5 = accent5.ok_or_else(|| MissingAttributeError::at(xml_node, "accent5"))?;
+        let accent6 = accent6.ok_or_else(|| MissingAttributeError::at(xml_node, "accent6"))?;
+        let hyperlink = hyperlink.ok_or_else(|| MissingAttributeError::at(xml_node, "hlink"))?;
+        let followed_hyperlink = followed_hyperlink.ok_or_else(|| MissingAttributeError::at(xml_node, "folHlink"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             background1,
@@ -113,10 +125,31 @@ impl ColorMapping {
             accent6,
             hyperlink,
             followed_hyperlink,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ColorMapping {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("bg1", self.background1);
+        node.set_attribute("tx1", self.text1);
+        node.set_attribute("bg2", self.background2);
+        node.set_attribute("tx2", self.text2);
+        node.set_attribute("accent1", self.accent1);
+        node.set_attribute("accent2", self.accent2);
+        node.set_attribute("accent3", self.accent3);
+        node.set_attribute("accent4", self.accent4);
+        node.set_attribute("accent5", self.accent5);
+        node.set_attribute("accent6", self.accent6);
+        node.set_attribute("hlink", self.hyperlink);
+        node.set_attribute("folHlink", self.followed_hyperlink);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColorScheme {
     /// The common name for this color scheme. This name can show up in the user interface in
@@ -169,15 +202,22 @@ pub struct ColorScheme {
     /// This element defines a color that happens to be the followed hyperlink color. The set of twelve colors come
     /// together to form the color scheme for a theme.
     pub followed_hyperlink: Color,
+
+    pub unknown: UnknownContent,
 }
 
-impl ColorScheme {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let name = xml_node
-            .attributes
-            .get("name")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?
-            .clone();
+impl XsdType for ColorScheme {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut name = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "name" => name = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        let name = name.ok_or_else(|| MissingAttributeError::at(xml_node, "name"))?;
 
         let mut dk1 = None;
         let mut lt1 = None;
@@ -192,44 +232,47 @@ impl ColorScheme {
         let mut hyperlink = None;
         let mut follow_hyperlink = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            let slot = match child_node.dml_local_name() {
+                "dk1" => &mut dk1,
+                "lt1" => &mut lt1,
+                "dk2" => &mut dk2,
+                "lt2" => &mut lt2,
+                "accent1" => &mut accent1,
+                "accent2" => &mut accent2,
+                "accent3" => &mut accent3,
+                "accent4" => &mut accent4,
+                "accent5" => &mut accent5,
+                "accent6" => &mut accent6,
+                "hlink" => &mut hyperlink,
+                "folHlink" => &mut follow_hyperlink,
+                _ => {
+                    unknown.push_element(position, child_node);
+                    continue;
+                }
+            };
+
             let color = child_node
                 .child_nodes
                 .iter()
-                .find_map(Color::try_from_xml_element)
+                .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                 .transpose()?
-                .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "EG_Color"))?;
-
-            match child_node.local_name() {
-                "dk1" => dk1 = Some(color),
-                "lt1" => lt1 = Some(color),
-                "dk2" => dk2 = Some(color),
-                "lt2" => lt2 = Some(color),
-                "accent1" => accent1 = Some(color),
-                "accent2" => accent2 = Some(color),
-                "accent3" => accent3 = Some(color),
-                "accent4" => accent4 = Some(color),
-                "accent5" => accent5 = Some(color),
-                "accent6" => accent6 = Some(color),
-                "hlink" => hyperlink = Some(color),
-                "folHlink" => follow_hyperlink = Some(color),
-                _ => (),
-            }
+                .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_Color"))?;
+            *slot = Some(color);
         }
 
-        let dark1 = dk1.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "dk1"))?;
-        let light1 = lt1.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "lt1"))?;
-        let dark2 = dk2.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "dk2"))?;
-        let light2 = lt2.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "lt2"))?;
-        let accent1 = accent1.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent1"))?;
-        let accent2 = accent2.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent2"))?;
-        let accent3 = accent3.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent3"))?;
-        let accent4 = accent4.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent4"))?;
-        let accent5 = accent5.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent5"))?;
-        let accent6 = accent6.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "accent6"))?;
-        let hyperlink = hyperlink.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "hlink"))?;
-        let followed_hyperlink =
-            follow_hyperlink.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "folHlink"))?;
+        let dark1 = dk1.ok_or_else(|| MissingChildNodeError::at(xml_node, "dk1"))?;
+        let light1 = lt1.ok_or_else(|| MissingChildNodeError::at(xml_node, "lt1"))?;
+        let dark2 = dk2.ok_or_else(|| MissingChildNodeError::at(xml_node, "dk2"))?;
+        let light2 = lt2.ok_or_else(|| MissingChildNodeError::at(xml_node, "lt2"))?;
+        let accent1 = accent1.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent1"))?;
+        let accent2 = accent2.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent2"))?;
+        let accent3 = accent3.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent3"))?;
+        let accent4 = accent4.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent4"))?;
+        let accent5 = accent5.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent5"))?;
+        let accent6 = accent6.ok_or_else(|| MissingChildNodeError::at(xml_node, "accent6"))?;
+        let hyperlink = hyperlink.ok_or_else(|| MissingChildNodeError::at(xml_node, "hlink"))?;
+        let followed_hyperlink = follow_hyperlink.ok_or_else(|| MissingChildNodeError::at(xml_node, "folHlink"))?;
 
         Ok(Self {
             name,
@@ -245,10 +288,42 @@ impl ColorScheme {
             accent6,
             hyperlink,
             followed_hyperlink,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ColorScheme {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("name", &self.name);
+
+        let colors = [
+            ("dk1", &self.dark1),
+            ("lt1", &self.light1),
+            ("dk2", &self.dark2),
+            ("lt2", &self.light2),
+            ("accent1", &self.accent1),
+            ("accent2", &self.accent2),
+            ("accent3", &self.accent3),
+            ("accent4", &self.accent4),
+            ("accent5", &self.accent5),
+            ("accent6", &self.accent6),
+            ("hlink", &self.hyperlink),
+            ("folHlink", &self.followed_hyperlink),
+        ];
+
+        node.child_nodes.extend(colors.iter().map(|(name, color)| {
+            let mut color_node = dml_element(name);
+            color_node.child_nodes.push(color.to_xml_element());
+            color_node
+        }));
+
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColorSchemeAndMapping {
     /// This element defines a set of colors which are referred to as a color scheme. The color scheme is responsible for
@@ -332,31 +407,63 @@ pub struct ColorSchemeAndMapping {
     ///
     /// In this example, we see that bg1 is mapped to lt1, tx1 is mapped to dk1, and so on.
     pub color_mapping: Option<Box<ColorMapping>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ColorSchemeAndMapping {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for ColorSchemeAndMapping {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut color_scheme = None;
         let mut color_mapping = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "clrScheme" => color_scheme = Some(Box::new(ColorScheme::from_xml_element(child_node)?)),
-                "clrMap" => color_mapping = Some(Box::new(ColorMapping::from_xml_element(child_node)?)),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "clrScheme" => {
+                    color_scheme = Some(Box::new(ColorScheme::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                "clrMap" => {
+                    color_mapping = unknown
+                        .recover_element(
+                            ColorMapping::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let color_scheme =
-            color_scheme.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrScheme"))?;
+        let color_scheme = color_scheme.ok_or_else(|| MissingChildNodeError::at(xml_node, "clrScheme"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             color_scheme,
             color_mapping,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for ColorSchemeAndMapping {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.color_scheme.to_xml_element("a:clrScheme"));
+        node.child_nodes.extend(
+            self.color_mapping
+                .as_ref()
+                .map(|mapping| mapping.to_xml_element("a:clrMap")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct ObjectStyleDefaults {
     /// This element defines the formatting that is associated with the default shape. The default formatting can be
@@ -465,30 +572,84 @@ pub struct ObjectStyleDefaults {
     /// </txDef>
     /// ```
     pub text_definition: Option<Box<DefaultShapeDefinition>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl ObjectStyleDefaults {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "spDef" => {
-                        instance.shape_definition =
-                            Some(Box::new(DefaultShapeDefinition::from_xml_element(child_node)?))
-                    }
-                    "lnDef" => {
-                        instance.line_definition = Some(Box::new(DefaultShapeDefinition::from_xml_element(child_node)?))
-                    }
-                    "txDef" => {
-                        instance.text_definition = Some(Box::new(DefaultShapeDefinition::from_xml_element(child_node)?))
-                    }
-                    _ => (),
+impl XsdType for ObjectStyleDefaults {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut shape_definition = None;
+        let mut line_definition = None;
+        let mut text_definition = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "spDef" => {
+                    shape_definition = unknown
+                        .recover_element(
+                            DefaultShapeDefinition::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lnDef" => {
+                    line_definition = unknown
+                        .recover_element(
+                            DefaultShapeDefinition::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
                 }
+                "txDef" => {
+                    text_definition = unknown
+                        .recover_element(
+                            DefaultShapeDefinition::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(Self {
+            shape_definition,
+            line_definition,
+            text_definition,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for ObjectStyleDefaults {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.extend(
+            self.shape_definition
+                .as_ref()
+                .map(|definition| definition.to_xml_element("a:spDef")),
+        );
+        node.child_nodes.extend(
+            self.line_definition
+                .as_ref()
+                .map(|definition| definition.to_xml_element("a:lnDef")),
+        );
+        node.child_nodes.extend(
+            self.text_definition
+                .as_ref()
+                .map(|definition| definition.to_xml_element("a:txDef")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -592,70 +753,216 @@ pub struct OfficeStyleSheet {
     /// ```
     pub extra_color_scheme_list: Option<Vec<ColorSchemeAndMapping>>,
 
+    /// Content of `a:extraClrSchemeLst` that isn't described by `extra_color_scheme_list`
+    pub extra_color_scheme_list_unknown: UnknownContent,
+
     /// This element allows for a custom color palette to be created and which shows up alongside other color schemes.
     /// This can be very useful, for example, when someone would like to maintain a corporate color palette.
     pub custom_color_list: Option<Vec<CustomColor>>,
+
+    /// Content of `a:custClrLst` that isn't described by `custom_color_list`
+    pub custom_color_list_unknown: UnknownContent,
+
+    /// Future extensions, e.g. theme family and variant information written by newer applications.
+    pub extension_list: Option<OfficeArtExtensionList>,
+
+    pub unknown: UnknownContent,
+}
+
+impl XsdType for OfficeStyleSheet {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        trace!("parsing OfficeStyleSheet '{}'", xml_node.name);
+        let mut parser = OfficeStyleSheetParser::new(xml_node);
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            parser.parse_child(position, child_node, context)?;
+        }
+
+        parser.finish(xml_node)
+    }
+
+    /// Reads the children of the theme one at a time and streams `a:themeElements`, which holds most of the content
+    /// of a theme, so the tree of the whole part is never built.
+    fn from_xml_reader_with_context<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        start: StartElement,
+        context: &mut ParseContext,
+    ) -> Result<Self> {
+        trace!("streaming OfficeStyleSheet '{}'", start.node.name);
+        let mut parser = OfficeStyleSheetParser::new(&start.node);
+        let mut position = 0;
+        while let Some(child) = reader.next_child(&start)? {
+            if child.node.dml_local_name() == "themeElements" {
+                let theme_elements = BaseStyles::from_xml_reader_with_context(reader, child, context)?;
+                parser.theme_elements = Some(Box::new(theme_elements));
+            } else {
+                let child_node = reader.read_node(child)?;
+                parser.parse_child(position, &child_node, context)?;
+            }
+
+            position += 1;
+        }
+
+        parser.finish(&start.node)
+    }
 }
 
 impl OfficeStyleSheet {
     pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
-        let mut xml_string = String::new();
-        zip_file.read_to_string(&mut xml_string)?;
-        let xml_node = XmlNode::from_str(xml_string.as_str())?;
+        Self::from_zip_file_with_limits(zip_file, &Default::default())
+    }
 
-        Self::from_xml_element(&xml_node)
+    /// Same as `from_zip_file`, but enforces the given limits.
+    pub fn from_zip_file_with_limits(zip_file: &mut ZipFile<'_>, limits: &ParseLimits) -> Result<Self> {
+        zip_file_to_xsd_type_with_limits(zip_file, limits, &mut ParseContext::new())
     }
+}
 
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        trace!("parsing OfficeStyleSheet '{}'", xml_node.name);
-        let name = xml_node.attributes.get("name").cloned();
-
-        let mut theme_elements = None;
-        let mut object_defaults = None;
-        let mut extra_color_scheme_list = None;
-        let mut custom_color_list = None;
-
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "themeElements" => theme_elements = Some(Box::new(BaseStyles::from_xml_element(child_node)?)),
-                "objectDefaults" => object_defaults = Some(ObjectStyleDefaults::from_xml_element(child_node)?),
-                "extraClrSchemeLst" => {
-                    extra_color_scheme_list = Some(
-                        child_node
-                            .child_nodes
-                            .iter()
-                            .filter(|child_node| child_node.local_name() == "extraClrScheme")
-                            .map(ColorSchemeAndMapping::from_xml_element)
-                            .collect::<Result<Vec<_>>>()?,
-                    );
-                }
-                "custClrLst" => {
-                    custom_color_list = Some(
-                        child_node
-                            .child_nodes
-                            .iter()
-                            .filter(|child_node| child_node.local_name() == "custClr")
-                            .map(CustomColor::from_xml_element)
-                            .collect::<Result<Vec<_>>>()?,
-                    )
-                }
-                _ => (),
+// Content of an `a:theme` element parsed so far. Shared by the parser of the tree and the streaming parser, which only
+// differ in how they read the children.
+struct OfficeStyleSheetParser {
+    name: Option<String>,
+    theme_elements: Option<Box<BaseStyles>>,
+    object_defaults: Option<ObjectStyleDefaults>,
+    extra_color_scheme_list: Option<Vec<ColorSchemeAndMapping>>,
+    extra_color_scheme_list_unknown: UnknownContent,
+    custom_color_list: Option<Vec<CustomColor>>,
+    custom_color_list_unknown: UnknownContent,
+    extension_list: Option<OfficeArtExtensionList>,
+    unknown: UnknownContent,
+}
+
+impl OfficeStyleSheetParser {
+    fn new(xml_node: &XmlNode) -> Self {
+        let mut unknown = UnknownContent::default();
+        let mut name = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "name" => name = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let theme_elements =
-            theme_elements.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "themeElements"))?;
-
-        Ok(Self {
+        Self {
             name,
+            theme_elements: None,
+            object_defaults: None,
+            extra_color_scheme_list: None,
+            extra_color_scheme_list_unknown: UnknownContent::default(),
+            custom_color_list: None,
+            custom_color_list_unknown: UnknownContent::default(),
+            extension_list: None,
+            unknown,
+        }
+    }
+
+    fn parse_child(&mut self, position: usize, child_node: &XmlNode, context: &mut ParseContext) -> Result<()> {
+        match child_node.dml_local_name() {
+            "themeElements" => {
+                self.theme_elements = Some(Box::new(BaseStyles::from_xml_element_with_context(
+                    child_node, context,
+                )?))
+            }
+            "objectDefaults" => {
+                self.object_defaults = self.unknown.recover_element(
+                    ObjectStyleDefaults::from_xml_element_with_context(child_node, context),
+                    position,
+                    child_node,
+                    context,
+                )?
+            }
+            "extraClrSchemeLst" => {
+                self.extra_color_scheme_list = Some(self.extra_color_scheme_list_unknown.parse_list_items(
+                    child_node,
+                    ns::DRAWINGML,
+                    "extraClrScheme",
+                    context,
+                    ColorSchemeAndMapping::from_xml_element_with_context,
+                )?)
+            }
+            "custClrLst" => {
+                self.custom_color_list = Some(self.custom_color_list_unknown.parse_list_items(
+                    child_node,
+                    ns::DRAWINGML,
+                    "custClr",
+                    context,
+                    CustomColor::from_xml_element_with_context,
+                )?)
+            }
+            "extLst" => {
+                self.extension_list = self.unknown.recover_element(
+                    OfficeArtExtensionList::from_xml_element_with_context(child_node, context),
+                    position,
+                    child_node,
+                    context,
+                )?
+            }
+            _ => self.unknown.push_element(position, child_node),
+        }
+
+        Ok(())
+    }
+
+    fn finish(self, xml_node: &XmlNode) -> Result<OfficeStyleSheet> {
+        let theme_elements = self
+            .theme_elements
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "themeElements"))?;
+
+        Ok(OfficeStyleSheet {
+            name: self.name,
             theme_elements,
-            object_defaults,
-            extra_color_scheme_list,
-            custom_color_list,
+            object_defaults: self.object_defaults,
+            extra_color_scheme_list: self.extra_color_scheme_list,
+            extra_color_scheme_list_unknown: self.extra_color_scheme_list_unknown,
+            custom_color_list: self.custom_color_list,
+            custom_color_list_unknown: self.custom_color_list_unknown,
+            extension_list: self.extension_list,
+            unknown: self.unknown,
         })
     }
 }
 
+impl ToXmlElement for OfficeStyleSheet {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("name", self.name.as_ref());
+        node.child_nodes
+            .push(self.theme_elements.to_xml_element("a:themeElements"));
+        node.child_nodes.extend(
+            self.object_defaults
+                .as_ref()
+                .map(|defaults| defaults.to_xml_element("a:objectDefaults")),
+        );
+
+        if let Some(extra_color_scheme_list) = &self.extra_color_scheme_list {
+            let mut list_node = dml_element("extraClrSchemeLst");
+            list_node.child_nodes.extend(
+                extra_color_scheme_list
+                    .iter()
+                    .map(|scheme| scheme.to_xml_element("a:extraClrScheme")),
+            );
+            self.extra_color_scheme_list_unknown.apply_to(&mut list_node);
+            node.child_nodes.push(list_node);
+        }
+
+        if let Some(custom_color_list) = &self.custom_color_list {
+            let mut list_node = dml_element("custClrLst");
+            list_node
+                .child_nodes
+                .extend(custom_color_list.iter().map(|color| color.to_xml_element("a:custClr")));
+            self.custom_color_list_unknown.apply_to(&mut list_node);
+            node.child_nodes.push(list_node);
+        }
+
+        node.child_nodes.extend(
+            self.extension_list
+                .as_ref()
+                .map(|extension_list| extension_list.to_xml_element("a:extLst")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BaseStyles {
     pub color_scheme: Box<ColorScheme>,
@@ -684,34 +991,348 @@ pub struct BaseStyles {
     /// style of the overall document. Usually, a given object chooses a single line style, a single fill style, and a single
     /// effect style in order to define the overall final look of the object.
     pub format_scheme: Box<StyleMatrix>,
+
+    pub unknown: UnknownContent,
 }
 
-impl BaseStyles {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for BaseStyles {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         trace!("parsing BaseStyles '{}'", xml_node.name);
-        let mut color_scheme = None;
-        let mut font_scheme = None;
-        let mut format_scheme = None;
-
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "clrScheme" => color_scheme = Some(Box::new(ColorScheme::from_xml_element(child_node)?)),
-                "fontScheme" => font_scheme = Some(FontScheme::from_xml_element(child_node)?),
-                "fmtScheme" => format_scheme = Some(Box::new(StyleMatrix::from_xml_element(child_node)?)),
-                _ => (),
+        let mut parser = BaseStylesParser::new(xml_node);
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            parser.parse_child(position, child_node, context)?;
+        }
+
+        parser.finish(xml_node)
+    }
+
+    /// Reads the color, font and format schemes one at a time, so only one of them is kept in memory as a tree.
+    fn from_xml_reader_with_context<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        start: StartElement,
+        context: &mut ParseContext,
+    ) -> Result<Self> {
+        trace!("streaming BaseStyles '{}'", start.node.name);
+        let mut parser = BaseStylesParser::new(&start.node);
+        let mut position = 0;
+        while let Some(child) = reader.next_child(&start)? {
+            let child_node = reader.read_node(child)?;
+            parser.parse_child(position, &child_node, context)?;
+            position += 1;
+        }
+
+        parser.finish(&start.node)
+    }
+}
+
+// Content of an `a:themeElements` element parsed so far, see `OfficeStyleSheetParser`
+struct BaseStylesParser {
+    color_scheme: Option<Box<ColorScheme>>,
+    font_scheme: Option<FontScheme>,
+    format_scheme: Option<Box<StyleMatrix>>,
+    unknown: UnknownContent,
+}
+
+impl BaseStylesParser {
+    fn new(xml_node: &XmlNode) -> Self {
+        let mut unknown = UnknownContent::default();
+        unknown.push_all_attributes(xml_node);
+
+        Self {
+            color_scheme: None,
+            font_scheme: None,
+            format_scheme: None,
+            unknown,
+        }
+    }
+
+    fn parse_child(&mut self, position: usize, child_node: &XmlNode, context: &mut ParseContext) -> Result<()> {
+        match child_node.dml_local_name() {
+            "clrScheme" => {
+                self.color_scheme = Some(Box::new(ColorScheme::from_xml_element_with_context(
+                    child_node, context,
+                )?))
             }
+            "fontScheme" => self.font_scheme = Some(FontScheme::from_xml_element_with_context(child_node, context)?),
+            "fmtScheme" => {
+                self.format_scheme = Some(Box::new(StyleMatrix::from_xml_element_with_context(
+                    child_node, context,
+                )?))
+            }
+            _ => self.unknown.push_element(position, child_node),
         }
 
-        let color_scheme =
-            color_scheme.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrScheme"))?;
-        let font_scheme = font_scheme.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "fontScheme"))?;
-        let format_scheme =
-            format_scheme.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "fmtScheme"))?;
+        Ok(())
+    }
 
-        Ok(Self {
+    fn finish(self, xml_node: &XmlNode) -> Result<BaseStyles> {
+        let color_scheme = self
+            .color_scheme
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "clrScheme"))?;
+        let font_scheme = self
+            .font_scheme
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "fontScheme"))?;
+        let format_scheme = self
+            .format_scheme
+            .ok_or_else(|| MissingChildNodeError::at(xml_node, "fmtScheme"))?;
+
+        Ok(BaseStyles {
             color_scheme,
             font_scheme,
             format_scheme,
+            unknown: self.unknown,
         })
     }
 }
+
+impl ToXmlElement for BaseStyles {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.color_scheme.to_xml_element("a:clrScheme"));
+        node.child_nodes.push(self.font_scheme.to_xml_element("a:fontScheme"));
+        node.child_nodes.push(self.format_scheme.to_xml_element("a:fmtScheme"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::diagnostics::Severity;
+    use crate::xml::writer::{WriteOptions, XML_DECLARATION};
+    use std::str::FromStr;
+
+    pub(crate) const THEME_XML: &str = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office Theme">
+        <a:themeElements>
+            <a:clrScheme name="Office">
+                <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+                <a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1>
+                <a:dk2><a:srgbClr val="44546A"/></a:dk2>
+                <a:lt2><a:srgbClr val="E7E6E6"/></a:lt2>
+                <a:accent1><a:srgbClr val="4472C4"/></a:accent1>
+                <a:accent2><a:srgbClr val="ED7D31"/></a:accent2>
+                <a:accent3><a:srgbClr val="A5A5A5"/></a:accent3>
+                <a:accent4><a:srgbClr val="FFC000"/></a:accent4>
+                <a:accent5><a:srgbClr val="5B9BD5"/></a:accent5>
+                <a:accent6><a:srgbClr val="70AD47"/></a:accent6>
+                <a:hlink><a:srgbClr val="0563C1"/></a:hlink>
+                <a:folHlink><a:srgbClr val="954F72"/></a:folHlink>
+            </a:clrScheme>
+            <a:fontScheme name="Office">
+                <a:majorFont><a:latin typeface="Calibri Light" panose="020F0302020204030204"/><a:ea typeface=""/><a:cs typeface=""/></a:majorFont>
+                <a:minorFont><a:latin typeface="Calibri" panose="020F0502020204030204"/><a:ea typeface=""/><a:cs typeface=""/></a:minorFont>
+            </a:fontScheme>
+            <a:fmtScheme name="Office">
+                <a:fillStyleLst>
+                    <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                    <a:gradFill rotWithShape="1">
+                        <a:gsLst>
+                            <a:gs pos="0"><a:schemeClr val="phClr"><a:lumMod val="110000"/><a:satMod val="105000"/></a:schemeClr></a:gs>
+                            <a:gs pos="100000"><a:schemeClr val="phClr"><a:tint val="63000"/></a:schemeClr></a:gs>
+                        </a:gsLst>
+                        <a:lin ang="5400000" scaled="0"/>
+                    </a:gradFill>
+                    <a:noFill/>
+                </a:fillStyleLst>
+                <a:lnStyleLst>
+                    <a:ln w="6350" cap="flat" cmpd="sng" algn="ctr"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:prstDash val="solid"/><a:miter lim="800000"/></a:ln>
+                    <a:ln w="12700"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:round/></a:ln>
+                    <a:ln w="19050"><a:noFill/></a:ln>
+                </a:lnStyleLst>
+                <a:effectStyleLst>
+                    <a:effectStyle><a:effectLst/></a:effectStyle>
+                    <a:effectStyle><a:effectLst/></a:effectStyle>
+                    <a:effectStyle>
+                        <a:effectLst>
+                            <a:outerShdw blurRad="57150" dist="19050" dir="5400000" algn="ctr" rotWithShape="0"><a:srgbClr val="000000"><a:alpha val="63000"/></a:srgbClr></a:outerShdw>
+                        </a:effectLst>
+                    </a:effectStyle>
+                </a:effectStyleLst>
+                <a:bgFillStyleLst>
+                    <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                    <a:solidFill><a:schemeClr val="phClr"><a:shade val="95000"/></a:schemeClr></a:solidFill>
+                    <a:grpFill/>
+                </a:bgFillStyleLst>
+            </a:fmtScheme>
+        </a:themeElements>
+        <a:objectDefaults/>
+        <a:extraClrSchemeLst/>
+    </a:theme>"#;
+
+    #[test]
+    fn test_office_style_sheet_round_trip() {
+        let style_sheet = OfficeStyleSheet::from_xml_element(&XmlNode::from_str(THEME_XML).unwrap()).unwrap();
+
+        let mut buffer = Vec::new();
+        style_sheet
+            .write_xml("a:theme", &mut buffer, &WriteOptions::compact())
+            .unwrap();
+        let written = String::from_utf8(buffer).unwrap();
+        let expected_start = format!(
+            "{}\r\n{}",
+            XML_DECLARATION,
+            r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office Theme">"#
+        );
+        assert!(written.starts_with(&expected_start));
+
+        let reparsed = OfficeStyleSheet::from_xml_element(&XmlNode::from_str(&written).unwrap()).unwrap();
+        assert_eq!(reparsed, style_sheet);
+    }
+
+    #[test]
+    fn test_unknown_content_round_trip() {
+        let xml = THEME_XML
+            .replace(
+                "<a:extraClrSchemeLst/>",
+                r#"<a:extraClrSchemeLst/><a:extLst><a:ext uri="{05A4C25C-085E-4340-85A3-A5531E510DB2}"><thm15:themeFamily xmlns:thm15="http://schemas.microsoft.com/office/thememl/2012/main" name="Office Theme" id="{62F939B6-93AF-4DB8-9C6B-D6C7DFDC589F}"/></a:ext></a:extLst>"#,
+            )
+            .replace(
+                r#"<a:ln w="12700"><a:solidFill>"#,
+                r#"<a:ln w="12700" xmlns:x="urn:x" x:width="1"><x:ext/><a:solidFill>"#,
+            );
+        let xml_node = XmlNode::from_str(&xml).unwrap();
+        let style_sheet = OfficeStyleSheet::from_xml_element(&xml_node).unwrap();
+        assert!(style_sheet.unknown.elements.is_empty());
+        let extension_list = style_sheet.extension_list.as_ref().unwrap();
+        assert_eq!(
+            extension_list.extensions[0].uri(),
+            Some("{05A4C25C-085E-4340-85A3-A5531E510DB2}")
+        );
+
+        let written = style_sheet.to_xml_element("a:theme");
+        let line = &written.child_nodes[0].child_nodes[2].child_nodes[1].child_nodes[1];
+        assert_eq!(line.attributes.get("x:width").map(String::as_str), Some("1"));
+        assert_eq!(line.child_nodes[0].name, "x:ext");
+        assert_eq!(written.child_nodes.last().unwrap().name, "a:extLst");
+
+        let reparsed = OfficeStyleSheet::from_xml_element(
+            &XmlNode::from_str(&written.to_xml_string(&WriteOptions::compact()).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(reparsed, style_sheet);
+    }
+
+    #[test]
+    fn test_theme_lists_round_trip() {
+        let xml = THEME_XML.replace(
+            "<a:extraClrSchemeLst/>",
+            r#"<a:extraClrSchemeLst xmlns:x="urn:x" x:y="1"><a:extraClrScheme/><x:scheme/></a:extraClrSchemeLst>
+            <a:custClrLst><a:custClr name="Red"><a:srgbClr val="FF0000"/></a:custClr><a:custClr name="Empty"/><x:color xmlns:x="urn:x"/></a:custClrLst>
+            <a:extLst><x:ext xmlns:x="urn:x"/><a:ext uri="{1}"/></a:extLst>"#,
+        );
+        let xml_node = XmlNode::from_str(&xml).unwrap();
+        assert!(OfficeStyleSheet::from_xml_element(&xml_node).is_err());
+
+        let parsed = OfficeStyleSheet::from_xml_element_lenient(&xml_node).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 2);
+        let style_sheet = parsed.value;
+        assert!(style_sheet.extra_color_scheme_list.as_ref().unwrap().is_empty());
+        assert_eq!(style_sheet.custom_color_list.as_ref().unwrap().len(), 1);
+        assert_eq!(style_sheet.extension_list.as_ref().unwrap().extensions.len(), 1);
+
+        let written = style_sheet.to_xml_element("a:theme");
+        assert_eq!(written.child_nodes[2..], xml_node.child_nodes[2..]);
+    }
+
+    #[test]
+    fn test_office_style_sheet_streaming() {
+        let xml = THEME_XML
+            .replace(r#"<a:lumMod val="110000"/>"#, r#"<a:lumMod val="lots"/>"#)
+            .replace("<a:objectDefaults/>", "<a:objectDefaults/><a:unknown/>");
+        let expected = OfficeStyleSheet::from_xml_element_lenient(&XmlNode::from_str(&xml).unwrap()).unwrap();
+
+        let mut reader = XmlReader::from_str(&xml);
+        let start = reader.read_root_start().unwrap();
+        let mut context = ParseContext::lenient();
+        let streamed = OfficeStyleSheet::from_xml_reader_with_context(&mut reader, start, &mut context).unwrap();
+        assert_eq!(streamed, expected.value);
+        assert_eq!(streamed.unknown.elements[0].node.name, "a:unknown");
+        assert_eq!(
+            context.diagnostics()[0].path().unwrap().to_string(),
+            "a:theme/a:themeElements/a:fmtScheme/a:fillStyleLst/a:gradFill/a:gsLst/a:gs/a:schemeClr/a:lumMod"
+        );
+
+        let mut reader = XmlReader::from_str("<a:theme><a:objectDefaults/></a:theme>");
+        let start = reader.read_root_start().unwrap();
+        assert!(OfficeStyleSheet::from_xml_reader_with_context(&mut reader, start, &mut ParseContext::new()).is_err());
+    }
+
+    #[test]
+    fn test_office_style_sheet_from_zip_file() {
+        let mut archive = zip::ZipArchive::new(crate::opc::tests::build_package(&[(
+            "theme1.xml",
+            THEME_XML.as_bytes(),
+        )]))
+        .unwrap();
+        let style_sheet = OfficeStyleSheet::from_zip_file(&mut archive.by_index(0).unwrap()).unwrap();
+        assert_eq!(
+            style_sheet,
+            OfficeStyleSheet::from_xml_element(&XmlNode::from_str(THEME_XML).unwrap()).unwrap()
+        );
+
+        let limits = ParseLimits {
+            max_depth: Some(4),
+            ..Default::default()
+        };
+        assert!(OfficeStyleSheet::from_zip_file_with_limits(&mut archive.by_index(0).unwrap(), &limits).is_err());
+    }
+
+    #[test]
+    fn test_office_style_sheet_lenient() {
+        let xml = THEME_XML
+            .replace(r#"lastClr="000000""#, r#"lastClr="00000""#)
+            .replace(r#"<a:lumMod val="110000"/>"#, r#"<a:lumMod val="lots"/>"#)
+            .replace(r#"<a:ln w="19050">"#, r#"<a:ln w="wide">"#)
+            .replace(
+                "<a:objectDefaults/>",
+                r#"<a:objectDefaults><a:spDef><a:spPr><a:prstGeom prst="bogus"/></a:spPr><a:bodyPr/><a:lstStyle/></a:spDef></a:objectDefaults>"#,
+            );
+        let xml_node = XmlNode::from_str(&xml).unwrap();
+        assert!(OfficeStyleSheet::from_xml_element(&xml_node).is_err());
+
+        let parsed = OfficeStyleSheet::from_xml_element_lenient(&xml_node).unwrap();
+        let diagnostics: Vec<(Severity, String)> = parsed
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.path().unwrap().to_string()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Severity::Warning,
+                    String::from("a:theme/a:themeElements/a:clrScheme/a:dk1/a:sysClr")
+                ),
+                (
+                    Severity::Error,
+                    String::from("a:theme/a:themeElements/a:fmtScheme/a:fillStyleLst/a:gradFill/a:gsLst/a:gs/a:schemeClr/a:lumMod")
+                ),
+                (
+                    Severity::Warning,
+                    String::from("a:theme/a:themeElements/a:fmtScheme/a:lnStyleLst/a:ln[3]")
+                ),
+                (
+                    Severity::Error,
+                    String::from("a:theme/a:objectDefaults/a:spDef/a:spPr/a:prstGeom")
+                ),
+            ]
+        );
+
+        let style_sheet = parsed.value;
+        let format_scheme = &style_sheet.theme_elements.format_scheme;
+        assert_eq!(format_scheme.line_style_list[2].width, None);
+        let shape_properties = &style_sheet
+            .object_defaults
+            .as_ref()
+            .and_then(|object_defaults| object_defaults.shape_definition.as_ref())
+            .unwrap()
+            .shape_properties;
+        assert!(shape_properties.geometry.is_none());
+
+        // Invalid content is written back unchanged
+        let written = style_sheet.to_xml_element("a:theme");
+        let reparsed = OfficeStyleSheet::from_xml_element_lenient(&written).unwrap();
+        assert_eq!(reparsed.diagnostics.len(), 4);
+    }
+}
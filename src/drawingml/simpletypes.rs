@@ -1,5 +1,55 @@
-use crate::error::{AdjustParseError, ParseHexColorRGBError, StringLengthMismatch};
-use std::str::FromStr;
+use super::validation::{Facet, SimpleType};
+use crate::{
+    error::{AdjustParseError, ParseHexColorRGBError, StringLengthMismatch},
+    xsdtypes::ExtensibleEnum,
+};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use strum::AsStaticRef;
+
+/// Implements `Display` and `ExtensibleEnum` for enums deriving `AsStaticStr` that have an `Unknown(String)` variant.
+macro_rules! impl_extensible_enum {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        $name::Unknown(value) => f.write_str(value),
+                        known => f.write_str(known.as_static()),
+                    }
+                }
+            }
+
+            impl ExtensibleEnum for $name {
+                fn unknown(value: String) -> Self {
+                    $name::Unknown(value)
+                }
+
+                fn unknown_value(&self) -> Option<&str> {
+                    match self {
+                        $name::Unknown(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_extensible_enum!(
+    ShapeType,
+    TextShapeType,
+    PresetColorVal,
+    SystemColorVal,
+    SchemeColorVal,
+    PresetShadowVal,
+    PresetPatternVal,
+    PresetLineDashVal,
+    TextAutonumberScheme,
+    BlendMode,
+);
 
 /// This simple type specifies that its values shall be a 128-bit globally unique identifier (GUID) value.
 ///
@@ -15,18 +65,33 @@ pub type Percentage = f32;
 /// types for details.
 pub type PositivePercentage = f32;
 
+pub(crate) const ST_POSITIVE_PERCENTAGE: SimpleType = SimpleType {
+    name: "ST_PositivePercentage",
+    facets: &[Facet::MinInclusive(0)],
+};
+
 /// This simple type specifies that its contents will contain a positive percentage value from zero through one
 /// hundred percent.
 ///
 /// Values represented by this type are restricted to: 0 <= n <= 100000
 pub type PositiveFixedPercentage = f32;
 
+pub(crate) const ST_POSITIVE_FIXED_PERCENTAGE: SimpleType = SimpleType {
+    name: "ST_PositiveFixedPercentage",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(100_000)],
+};
+
 /// This simple type represents a fixed percentage from negative one hundred to positive one hundred percent. See
 /// the union's member types for details.
 ///
 /// Values represented by this type are restricted to: -100000 <= n <= 100000
 pub type FixedPercentage = f32;
 
+pub(crate) const ST_FIXED_PERCENTAGE: SimpleType = SimpleType {
+    name: "ST_FixedPercentage",
+    facets: &[Facet::MinInclusive(-100_000), Facet::MaxInclusive(100_000)],
+};
+
 /// This simple type specifies that its contents shall contain a color value in RRGGBB hexadecimal format, specified
 /// using six hexadecimal digits. Each of the red, green, and blue color values, from 0-255, is encoded as two
 /// hexadecimal digits.
@@ -41,6 +106,11 @@ pub type FixedPercentage = f32;
 /// The resulting RRGGBB value would be 7A17D1, as each color is transformed into its hexadecimal equivalent.
 pub type HexColorRGB = [u8; 3];
 
+pub(crate) const ST_HEX_COLOR_RGB: SimpleType = SimpleType {
+    name: "ST_HexColorRGB",
+    facets: &[Facet::HexBinaryLength(3)],
+};
+
 pub fn parse_hex_color_rgb(s: &str) -> Result<HexColorRGB, ParseHexColorRGBError> {
     match s.len() {
         6 => Ok([
@@ -55,15 +125,32 @@ pub fn parse_hex_color_rgb(s: &str) -> Result<HexColorRGB, ParseHexColorRGBError
     }
 }
 
+pub fn format_hex_color_rgb(color: &HexColorRGB) -> String {
+    format!("{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
+
 /// This simple type represents a one dimensional position or length as either:
 ///
 /// * EMUs.
 /// * A number followed immediately by a unit identifier.
 pub type Coordinate = i64;
 
+pub(crate) const ST_COORDINATE: SimpleType = SimpleType {
+    name: "ST_Coordinate",
+    facets: &[
+        Facet::MinInclusive(-27_273_042_329_600),
+        Facet::MaxInclusive(27_273_042_316_900),
+    ],
+};
+
 /// This simple type represents a positive position or length in EMUs.
 pub type PositiveCoordinate = u64;
 
+pub(crate) const ST_POSITIVE_COORDINATE: SimpleType = SimpleType {
+    name: "ST_PositiveCoordinate",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(27_273_042_316_900)],
+};
+
 /// This simple type specifies a coordinate within the document. This can be used for measurements or spacing; its
 /// maximum size is 2147483647 EMUs.
 ///
@@ -83,6 +170,11 @@ pub type PositiveCoordinate32 = u32;
 /// Values represented by this type are restricted to: 0 <= n <= 20116800
 pub type LineWidth = Coordinate32;
 
+pub(crate) const ST_LINE_WIDTH: SimpleType = SimpleType {
+    name: "ST_LineWidth",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(20_116_800)],
+};
+
 /// This simple type specifies a unique integer identifier for each drawing element.
 pub type DrawingElementId = u32;
 
@@ -92,14 +184,24 @@ pub type Angle = i32;
 
 /// This simple type represents a fixed range angle in 60000ths of a degree. Range from (-90, 90 degrees).
 ///
-/// Values represented by this type are restricted to: -5400000 <= n <= 5400000
+/// Values represented by this type are restricted to: -5400000 < n < 5400000
 pub type FixedAngle = Angle;
 
+pub(crate) const ST_FIXED_ANGLE: SimpleType = SimpleType {
+    name: "ST_FixedAngle",
+    facets: &[Facet::MinExclusive(-5_400_000), Facet::MaxExclusive(5_400_000)],
+};
+
 /// This simple type represents a positive angle in 60000ths of a degree. Range from [0, 360 degrees).
 ///
-/// Values represented by this type are restricted to: 0 <= n <= 21600000
+/// Values represented by this type are restricted to: 0 <= n < 21600000
 pub type PositiveFixedAngle = Angle;
 
+pub(crate) const ST_POSITIVE_FIXED_ANGLE: SimpleType = SimpleType {
+    name: "ST_PositiveFixedAngle",
+    facets: &[Facet::MinInclusive(0), Facet::MaxExclusive(21_600_000)],
+};
+
 /// This simple type specifies a geometry guide name.
 pub type GeomGuideName = String;
 
@@ -116,44 +218,89 @@ pub type StyleMatrixColumnIndex = u32;
 /// Values represented by this type are restricted to: 1 <= n <= 16
 pub type TextColumnCount = i32;
 
+pub(crate) const ST_TEXT_COLUMN_COUNT: SimpleType = SimpleType {
+    name: "ST_TextColumnCount",
+    facets: &[Facet::MinInclusive(1), Facet::MaxInclusive(16)],
+};
+
 /// Values represented by this type are restricted to: 1000 <= n <= 100000
 pub type TextFontScalePercent = Percentage;
 
+pub(crate) const ST_TEXT_FONT_SCALE_PERCENT: SimpleType = SimpleType {
+    name: "ST_TextFontScalePercentOrPercentString",
+    facets: &[Facet::MinInclusive(1_000), Facet::MaxInclusive(100_000)],
+};
+
 /// Values represented by this type are restricted to: 0 <= n <= 13200000
 pub type TextSpacingPercent = Percentage;
 
+pub(crate) const ST_TEXT_SPACING_PERCENT: SimpleType = SimpleType {
+    name: "ST_TextSpacingPercentOrPercentString",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(13_200_000)],
+};
+
 /// This simple type specifies the Text Spacing that is used in terms of font point size.
 ///
 /// Values represented by this type are restricted to: 0 <= n <= 158400
 pub type TextSpacingPoint = i32;
 
+pub(crate) const ST_TEXT_SPACING_POINT: SimpleType = SimpleType {
+    name: "ST_TextSpacingPoint",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(158_400)],
+};
+
 /// This simple type specifies the margin that is used and its corresponding size.
 ///
 /// Values represented by this type are restricted to: 0 <= n <= 51206400
 pub type TextMargin = Coordinate32;
 
+pub(crate) const ST_TEXT_MARGIN: SimpleType = SimpleType {
+    name: "ST_TextMargin",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(51_206_400)],
+};
+
 /// This simple type specifies the text indentation amount to be used.
 ///
 /// Values represented by this type are restricted to: -51206400 <= n <= 51206400
 pub type TextIndent = Coordinate32;
 
+pub(crate) const ST_TEXT_INDENT: SimpleType = SimpleType {
+    name: "ST_TextIndent",
+    facets: &[Facet::MinInclusive(-51_206_400), Facet::MaxInclusive(51_206_400)],
+};
+
 /// This simple type specifies the indent level type. We support list level 0 to 8, and we use -1 and -2 for outline
 /// mode levels that should only exist in memory.
 ///
 /// Values represented by this type are restricted to: 0 <= n <= 8
 pub type TextIndentLevelType = i32;
 
+pub(crate) const ST_TEXT_INDENT_LEVEL_TYPE: SimpleType = SimpleType {
+    name: "ST_TextIndentLevelType",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(8)],
+};
+
 /// This simple type specifies the range that the bullet percent can be. A bullet percent is the size of the bullet with
 /// respect to the text that should follow it.
 ///
 /// Values represented by this type are restricted to: 25000 <= n <= 400000
 pub type TextBulletSizePercent = Percentage;
 
+pub(crate) const ST_TEXT_BULLET_SIZE_PERCENT: SimpleType = SimpleType {
+    name: "ST_TextBulletSizePercent",
+    facets: &[Facet::MinInclusive(25_000), Facet::MaxInclusive(400_000)],
+};
+
 /// This simple type specifies the size of any text in hundredths of a point. Shall be at least 1 point.
 ///
 /// Values represented by this type are restricted to: 100 <= n <= 400000
 pub type TextFontSize = i32;
 
+pub(crate) const ST_TEXT_FONT_SIZE: SimpleType = SimpleType {
+    name: "ST_TextFontSize",
+    facets: &[Facet::MinInclusive(100), Facet::MaxInclusive(400_000)],
+};
+
 /// This simple type specifies the way we represent a font typeface.
 pub type TextTypeFace = String;
 
@@ -173,7 +320,12 @@ pub type TextLanguageID = String;
 ///   …
 /// </w:font>
 /// ```
-pub type Panose = String; // TODO: hex, length=10
+pub type Panose = String;
+
+pub(crate) const ST_PANOSE: SimpleType = SimpleType {
+    name: "ST_Panose",
+    facets: &[Facet::HexBinaryLength(10)],
+};
 
 /// This simple type specifies the range that the start at number for a bullet's auto-numbering sequence can begin
 /// at. When the numbering is alphabetical, then the numbers map to the appropriate letter. 1->a, 2->b, etc. If the
@@ -182,6 +334,11 @@ pub type Panose = String; // TODO: hex, length=10
 /// Values represented by this type are restricted to: 1 <= n <= 32767
 pub type TextBulletStartAtNum = i32;
 
+pub(crate) const ST_TEXT_BULLET_START_AT_NUM: SimpleType = SimpleType {
+    name: "ST_TextBulletStartAtNum",
+    facets: &[Facet::MinInclusive(1), Facet::MaxInclusive(32_767)],
+};
+
 /// This simple type specifies that its contents contains a language identifier as defined by RFC 4646/BCP 47.
 ///
 /// The contents of this language are interpreted based on the context of the parent XML element.
@@ -201,11 +358,21 @@ pub type Lang = String;
 /// Values represented by this type are restricted to: 0 <= n <= 400000
 pub type TextNonNegativePoint = i32;
 
+pub(crate) const ST_TEXT_NON_NEGATIVE_POINT: SimpleType = SimpleType {
+    name: "ST_TextNonNegativePoint",
+    facets: &[Facet::MinInclusive(0), Facet::MaxInclusive(400_000)],
+};
+
 /// This simple type specifies a coordinate within the document. This can be used for measurements or spacing
 ///
 /// Values represented by this type are restricted to: -400000 <= n <= 400000
 pub type TextPoint = i32;
 
+pub(crate) const ST_TEXT_POINT: SimpleType = SimpleType {
+    name: "ST_TextPoint",
+    facets: &[Facet::MinInclusive(-400_000), Facet::MaxInclusive(400_000)],
+};
+
 /// Specifies the shape ID for legacy shape identification purposes.
 pub type ShapeId = String;
 
@@ -228,6 +395,15 @@ impl FromStr for AdjCoordinate {
     }
 }
 
+impl Display for AdjCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            AdjCoordinate::Coordinate(coord) => write!(f, "{}", coord),
+            AdjCoordinate::GeomGuideName(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 /// This simple type is an adjustable angle, either an absolute angle or a reference to a geometry guide. The units
 /// for an adjustable angle are 60,000ths of a degree.
 #[derive(Debug, Clone, PartialEq)]
@@ -247,9 +423,18 @@ impl FromStr for AdjAngle {
     }
 }
 
+impl Display for AdjAngle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            AdjAngle::Angle(angle) => write!(f, "{}", angle),
+            AdjAngle::GeomGuideName(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 /// This simple type indicates whether/how to flip the contents of a tile region when using it to fill a larger fill
 /// region.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TileFlipMode {
     #[strum(serialize = "none")]
     None,
@@ -262,7 +447,7 @@ pub enum TileFlipMode {
 }
 
 /// This simple type describes how to position two rectangles relative to each other.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum RectAlignment {
     #[strum(serialize = "l")]
     Left,
@@ -286,7 +471,7 @@ pub enum RectAlignment {
 
 /// This simple type specifies the manner in which a path should be filled. The lightening and darkening of a path
 /// allow for certain parts of the shape to be colored lighter of darker depending on user preference.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum PathFillMode {
     /// This specifies that the corresponding path should have no fill.
     #[strum(serialize = "none")]
@@ -334,7 +519,7 @@ pub enum PathFillMode {
 /// * **Shape Bottom Edge ('b') - Constant value of "h"**
 ///
 ///     This is the bottom edge of the shape and since the top edge of the shape is considered the 0 point, the
-///     bottom edge is thus the shape height.
+///   bottom edge is thus the shape height.
 ///
 /// * **1/2 of a Circle ('cd2') - Constant value of "10800000.0"**
 ///
@@ -351,7 +536,7 @@ pub enum PathFillMode {
 /// * **Shape Height ('h')**
 ///
 ///     This is the variable height of the shape defined in the shape properties. This value is received from the shape
-///     transform listed within the <spPr> element.
+///   transform listed within the <spPr> element.
 ///
 /// * **Horizontal Center ('hc') - Calculated value of "\*/ w 1.0 2.0"**
 ///
@@ -388,12 +573,12 @@ pub enum PathFillMode {
 /// * **Shape Right Edge ('r') - Constant value of "w"**
 ///
 ///     This is the right edge of the shape and since the left edge of the shape is considered the 0 point, the right edge
-///     is thus the shape width.
+///   is thus the shape width.
 ///
 /// * **Shortest Side of Shape ('ss') - Calculated value of "min w h"**
 ///
 ///     This is the shortest side of the shape. This value is either the width or the height depending on which is
-///     smaller.
+///   smaller.
 ///
 /// * **1/2 Shortest Side of Shape ('ssd2') - Calculated value of "\*/ ss 1.0 2.0"**
 ///
@@ -422,7 +607,7 @@ pub enum PathFillMode {
 /// * **Shape Width ('w')**
 ///
 ///     This is the variable width of the shape defined in the shape properties. This value is received from the shape
-///     transform listed within the <spPr> element.
+///   transform listed within the <spPr> element.
 ///
 /// * **1/2 of Shape Width ('wd2') - Calculated value of "\*/ w 1.0 2.0"**
 ///
@@ -447,7 +632,7 @@ pub enum PathFillMode {
 /// * **1/10 of Shape Width ('wd10') - Calculated value of "\*/ w 1.0 10.0"**
 ///
 ///     This is 1/10 the shape width.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum ShapeType {
     #[strum(serialize = "line")]
     Line,
@@ -823,11 +1008,15 @@ pub enum ShapeType {
     ChartStar,
     #[strum(serialize = "chartPlus")]
     ChartPlus,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type specifies how to cap the ends of lines. This also affects the ends of line segments for dashed
 /// lines.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum LineCap {
     /// Rounded ends. Semi-circle protrudes by half line width.
     #[strum(serialize = "rnd")]
@@ -841,7 +1030,7 @@ pub enum LineCap {
 }
 
 /// This simple type specifies the compound line type that is to be used for lines with text such as underlines.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum CompoundLine {
     /// Single line: one normal width
     #[strum(serialize = "sng")]
@@ -861,7 +1050,7 @@ pub enum CompoundLine {
 }
 
 /// This simple type specifies the Pen Alignment type for use within a text body.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum PenAlignment {
     /// Center pen (line drawn at center of path stroke).
     #[strum(serialize = "ctr")]
@@ -875,7 +1064,7 @@ pub enum PenAlignment {
 /// line style. Each style also contains a precise binary representation of the repeating dash style. Each 1
 /// corresponds to a line segment of the same length as the line width, and each 0 corresponds to a space of the
 /// same length as the line width.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum PresetLineDashVal {
     /// 1
     #[strum(serialize = "solid")]
@@ -910,11 +1099,15 @@ pub enum PresetLineDashVal {
     /// 11101010
     #[strum(serialize = "sysDashDotDot")]
     SystemDashDotDot,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type represents the shape decoration that appears at the ends of lines. For example, one choice is an
 /// arrow head.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum LineEndType {
     #[strum(serialize = "none")]
     None,
@@ -932,7 +1125,7 @@ pub enum LineEndType {
 
 /// This simple type represents the width of the line end decoration (e.g., arrowhead) relative to the width of the
 /// line itself.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum LineEndWidth {
     #[strum(serialize = "sm")]
     Small,
@@ -944,7 +1137,7 @@ pub enum LineEndWidth {
 
 /// This simple type represents the length of the line end decoration (e.g., arrowhead) relative to the width of the
 /// line itself.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum LineEndLength {
     #[strum(serialize = "sm")]
     Small,
@@ -957,7 +1150,7 @@ pub enum LineEndLength {
 /// This simple type indicates one of 20 preset shadow types. Each enumeration value description illustrates the
 /// type of shadow represented by the value. Each description contains the parameters to the outer shadow effect
 /// represented by the preset, in addition to those attributes common to all prstShdw effects.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum PresetShadowVal {
     /// No additional attributes specified.
     #[strum(serialize = "shdw1")]
@@ -1065,10 +1258,14 @@ pub enum PresetShadowVal {
     /// sy = -100°
     #[strum(serialize = "shdw20")]
     FrontBottomShadow,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type determines the relationship between effects in a container, either sibling or tree.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum EffectContainerType {
     /// Each effect is separately applied to the parent object.
     ///
@@ -1090,7 +1287,7 @@ pub enum EffectContainerType {
 }
 
 /// This simple type represents one of the fonts associated with the style.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum FontCollectionIndex {
     /// The major font of the style's font scheme.
     #[strum(serialize = "major")]
@@ -1104,7 +1301,7 @@ pub enum FontCollectionIndex {
 }
 
 /// This simple type specifies an animation build step within a diagram animation.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum DgmBuildStep {
     /// Animate a diagram shape for this animation build step
     #[strum(serialize = "sp")]
@@ -1115,7 +1312,7 @@ pub enum DgmBuildStep {
 }
 
 /// This simple type specifies an animation build step within a chart animation.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum ChartBuildStep {
     /// Animate a chart category for this animation build step
     #[strum(serialize = "category")]
@@ -1138,7 +1335,7 @@ pub enum ChartBuildStep {
 }
 
 /// This simple type represents whether a style property should be applied.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum OnOffStyleType {
     /// Property is on.
     #[strum(serialize = "on")]
@@ -1157,8 +1354,7 @@ pub enum OnOffStyleType {
 ///
 /// Applications shall use the lastClr attribute to determine the absolute value of the last color used if system colors
 /// are not supported.
-#[repr(C)]
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum SystemColorVal {
     /// Specifies the scroll bar gray area color.
     #[strum(serialize = "scrollBar")]
@@ -1254,11 +1450,14 @@ pub enum SystemColorVal {
     /// Specifies the background color for the menu bar when menus appear as flat menus.
     #[strum(serialize = "menubar")]
     MenuBar,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type represents a preset color value.
-#[repr(C)]
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum PresetColorVal {
     /// Specifies a color with RGB value (240,248,255)
     #[strum(serialize = "aliceBlue")]
@@ -1830,11 +2029,14 @@ pub enum PresetColorVal {
     /// Specifies a color with RGB value (154,205,50)
     #[strum(serialize = "yellowGreen")]
     YellowGreen,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type represents a scheme color value.
-#[repr(C)]
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum SchemeColorVal {
     #[strum(serialize = "bg1")]
     Background1,
@@ -1871,11 +2073,15 @@ pub enum SchemeColorVal {
     Dark2,
     #[strum(serialize = "lt2")]
     Light2,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// A reference to a color in the color scheme.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum ColorSchemeIndex {
     #[strum(serialize = "dk1")]
     Dark1,
@@ -1904,7 +2110,7 @@ pub enum ColorSchemeIndex {
 }
 
 /// This simple type specifies the text alignment types
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextAlignType {
     /// Align text to the left margin.
     #[strum(serialize = "l")]
@@ -1931,7 +2137,7 @@ pub enum TextAlignType {
 }
 
 /// This simple type specifies the different kinds of font alignment.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextFontAlignType {
     /// When the text flow is horizontal or simple vertical same as fontBaseline but for other vertical modes
     /// same as fontCenter.
@@ -1953,7 +2159,7 @@ pub enum TextFontAlignType {
 }
 
 /// This simple type specifies a list of automatic numbering schemes.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum TextAutonumberScheme {
     /// (a), (b), (c), …
     #[strum(serialize = "alphaLcParenBoth")]
@@ -2078,10 +2284,14 @@ pub enum TextAutonumberScheme {
     /// Hindi alphabet period - consonants
     #[strum(serialize = "hindiAlpha1Period")]
     HindiAlpha1Period,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type describes the shape of path to follow for a path gradient shade.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum PathShadeType {
     /// Gradient follows the shape
     #[strum(serialize = "shape")]
@@ -2101,7 +2311,7 @@ pub enum PathShadeType {
 ///
 /// These presets correspond to members of the HatchStyle enumeration in the Microsoft .NET Framework.
 /// A reference for this type can be found at http://msdn2.microsoft.com/enus/library/system.drawing.drawing2d.hatchstyle.aspx
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum PresetPatternVal {
     #[strum(serialize = "pct5")]
     Percent5,
@@ -2211,10 +2421,14 @@ pub enum PresetPatternVal {
     Trellis,
     #[strum(serialize = "zigzag")]
     ZigZag,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type describes how to render effects one on top of another.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum BlendMode {
     #[strum(serialize = "over")]
     Overlay,
@@ -2226,10 +2440,14 @@ pub enum BlendMode {
     Lighten,
     #[strum(serialize = "darken")]
     Darken,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type specifies the text tab alignment types.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextTabAlignType {
     /// The text at this tab stop is left aligned.
     #[strum(serialize = "l")]
@@ -2247,7 +2465,7 @@ pub enum TextTabAlignType {
 }
 
 /// This simple type specifies the text underline types that is used.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextUnderlineType {
     /// The reason we cannot implicitly have noUnderline be the scenario where underline is not specified is
     /// because not being specified implies deriving from a particular style and the user might want to override
@@ -2308,7 +2526,7 @@ pub enum TextUnderlineType {
 }
 
 /// This simple type specifies the strike type.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextStrikeType {
     #[strum(serialize = "noStrike")]
     NoStrike,
@@ -2319,7 +2537,7 @@ pub enum TextStrikeType {
 }
 
 /// This simple type specifies the cap types of the text.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextCapsType {
     /// The reason we cannot implicitly have noCaps be the scenario where capitalization is not specified is
     /// because not being specified implies deriving from a particular style and the user might want to override
@@ -2341,7 +2559,7 @@ pub enum TextCapsType {
 /// DrawingML code that would be used to construct this shape were it a custom geometry. Within the construction
 /// code for each of these preset text shapes there are predefined guides that the generating application shall
 /// maintain for calculation purposes at all times. See [ShapeType](enum.ShapeType.html) to see the necessary guide values.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, EnumString, AsStaticStr, PartialEq)]
 pub enum TextShapeType {
     #[strum(serialize = "textNoShape")]
     NoShape,
@@ -2425,10 +2643,14 @@ pub enum TextShapeType {
     CascadeUp,
     #[strum(serialize = "textCascadeDown")]
     CascadeDown,
+    /// A value unknown to this crate, e.g. one added by a newer version of Office. Only produced if unknown values are
+    /// accepted, see `ParseContext::with_unknown_values_accepted`.
+    #[strum(disabled = "true")]
+    Unknown(String),
 }
 
 /// This simple type specifies the text vertical overflow.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextVertOverflowType {
     /// Overflow the text and pay no attention to top and bottom barriers.
     #[strum(serialize = "overflow")]
@@ -2442,7 +2664,7 @@ pub enum TextVertOverflowType {
 }
 
 /// This simple type specifies the text horizontal overflow types
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextHorizontalOverflowType {
     /// When a big character does not fit into a line, allow a horizontal overflow.
     #[strum(serialize = "overflow")]
@@ -2453,7 +2675,7 @@ pub enum TextHorizontalOverflowType {
 }
 
 /// If there is vertical text, determines what kind of vertical text is going to be used.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextVerticalType {
     /// Horizontal text. This should be default.
     #[strum(serialize = "horz")]
@@ -2483,7 +2705,7 @@ pub enum TextVerticalType {
     WordArtVerticalRtl,
 }
 
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextWrappingType {
     /// No wrapping occurs on this text body. Words spill out without paying attention to the bounding rectangle
     /// boundaries.
@@ -2495,7 +2717,7 @@ pub enum TextWrappingType {
 }
 
 /// This simple type specifies a list of available anchoring types for text.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum TextAnchoringType {
     /// Anchor the text at the top of the bounding rectangle.
     #[strum(serialize = "t")]
@@ -2522,7 +2744,7 @@ pub enum TextAnchoringType {
 }
 
 /// This simple type specifies how an object should be rendered when specified to be in black and white mode.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum BlackWhiteMode {
     /// Object rendered with normal coloring
     #[strum(serialize = "clr")]
@@ -2560,7 +2782,7 @@ pub enum BlackWhiteMode {
 }
 
 /// This simple type specifies the ways that an animation can be built, or animated.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum AnimationBuildType {
     #[strum(serialize = "allAtOnce")]
     AllAtOnce,
@@ -2568,7 +2790,7 @@ pub enum AnimationBuildType {
 
 /// This simple type specifies the build options available only for animating a diagram. These options specify the
 /// manner in which the objects within the chart should be grouped and animated.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum AnimationDgmOnlyBuildType {
     /// Animate the diagram by elements. For a tree diagram the animation occurs by branch within the diagram tree.
     #[strum(serialize = "one")]
@@ -2583,7 +2805,7 @@ pub enum AnimationDgmOnlyBuildType {
 
 /// This simple type specifies the ways that a diagram animation can be built. That is, it specifies the way in which
 /// the objects within the diagram graphical object should be animated.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum AnimationDgmBuildType {
     #[strum(serialize = "allAtOnce")]
     AllAtOnce,
@@ -2597,7 +2819,7 @@ pub enum AnimationDgmBuildType {
 
 /// This simple type specifies the build options available only for animating a chart. These options specify the
 /// manner in which the objects within the chart should be grouped and animated.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum AnimationChartOnlyBuildType {
     /// Animate by each series
     #[strum(serialize = "series")]
@@ -2615,7 +2837,7 @@ pub enum AnimationChartOnlyBuildType {
 
 /// This simple type specifies the ways that a chart animation can be built. That is, it specifies the way in which the
 /// objects within the chart should be animated.
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum AnimationChartBuildType {
     #[strum(serialize = "allAtOnce")]
     AllAtOnce,
@@ -2631,7 +2853,7 @@ pub enum AnimationChartBuildType {
 
 /// This type specifies the amount of compression that has been used for a particular binary large image or picture
 /// (blip).
-#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumString, Display, PartialEq)]
 pub enum BlipCompression {
     /// Compression size suitable for inclusion with email
     #[strum(serialize = "email")]
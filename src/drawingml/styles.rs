@@ -1,19 +1,20 @@
 use crate::{
+    diagnostics::recover_element,
     drawingml::{
         colors::Color,
         core::{LineProperties, ShapeProperties, ShapeStyle},
         shapeprops::{EffectProperties, FillProperties},
         simpletypes::{FontCollectionIndex, StyleMatrixColumnIndex, TextTypeFace},
         text::{bodyformatting::TextBodyProperties, bullet::TextListStyle, runformatting::TextFont},
+        util::{dml_element, XmlNodeExt},
     },
     error::{LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError},
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 use log::trace;
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct EffectStyleItem {
@@ -21,23 +22,38 @@ pub struct EffectStyleItem {
     // TODO implement
     //pub scene_3d: Option<Scene3D>,
     //pub shape_3d: Option<Shape3D>,
+    pub unknown: UnknownContent,
 }
 
-impl EffectStyleItem {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for EffectStyleItem {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         trace!("parsing EffectStyleItem '{}'", xml_node.name);
         let mut effect_props = None;
 
-        for child_node in &xml_node.child_nodes {
-            if EffectProperties::is_choice_member(child_node.local_name()) {
-                effect_props = Some(EffectProperties::from_xml_element(child_node)?);
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if effect_props.is_none() && EffectProperties::is_choice_member(local_name) => {
+                    effect_props = Some(EffectProperties::from_xml_element_with_context(child_node, context)?);
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let effect_props =
-            effect_props.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_EffectProperties"))?;
+        let effect_props = effect_props.ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_EffectProperties"))?;
+
+        Ok(Self { effect_props, unknown })
+    }
+}
 
-        Ok(Self { effect_props })
+impl ToXmlElement for EffectStyleItem {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.effect_props.to_xml_element());
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -48,23 +64,51 @@ pub struct StyleMatrixReference {
 
     /// Specifies the color associated with this style matrix reference.
     pub color: Option<Color>,
+
+    pub unknown: UnknownContent,
 }
 
-impl StyleMatrixReference {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let index = xml_node
-            .attributes
-            .get("idx")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "idx"))?
-            .parse()?;
+impl XsdType for StyleMatrixReference {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut index = None;
+        let mut color = None;
 
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "idx" => index = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if Color::is_choice_member(local_name) => {
+                    color = unknown.recover_element(
+                        Color::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-        Ok(Self { index, color })
+        let index = index.ok_or_else(|| MissingAttributeError::at(xml_node, "idx"))?;
+
+        Ok(Self { index, color, unknown })
+    }
+}
+
+impl ToXmlElement for StyleMatrixReference {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("idx", self.index);
+        node.child_nodes
+            .extend(self.color.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -200,36 +244,47 @@ pub struct StyleMatrix {
     /// In this example, we see that the list contains a solid fill for the subtle fill, a gradient fill for the moderate fill and
     /// an image fill for the intense background fill.
     pub bg_fill_style_list: Vec<FillProperties>,
+
+    pub unknown: UnknownContent,
 }
 
-impl StyleMatrix {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for StyleMatrix {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         trace!("parsing StyleMatrix '{}'", xml_node.name);
-        let name = xml_node.attributes.get("name").cloned();
+        let mut name = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "name" => name = Some(value.clone()),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
+
         let mut fill_style_list = None;
         let mut line_style_list = None;
         let mut effect_style_list = None;
         let mut bg_fill_style_list = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
                 "fillStyleLst" => {
                     let vec = child_node
                         .child_nodes
                         .iter()
-                        .filter_map(FillProperties::try_from_xml_element)
+                        .filter_map(|child_node| FillProperties::try_from_xml_element_with_context(child_node, context))
                         .collect::<Result<Vec<_>>>()?;
 
                     fill_style_list = match vec.len() {
                         len if len >= 3 => Some(vec),
                         len => {
-                            return Err(Box::new(LimitViolationError::new(
-                                String::from("fillStyleLst"),
+                            return Err(LimitViolationError::at(
+                                child_node,
                                 "EG_FillProperties",
                                 3,
                                 MaxOccurs::Unbounded,
                                 len as u32,
-                            )))
+                            )
+                            .into())
                         }
                     };
                 }
@@ -237,20 +292,22 @@ impl StyleMatrix {
                     let vec = child_node
                         .child_nodes
                         .iter()
-                        .filter(|child_node| child_node.local_name() == "ln")
-                        .map(LineProperties::from_xml_element)
+                        .filter(|child_node| child_node.is(ns::DRAWINGML, "ln"))
+                        .filter_map(|child_node| {
+                            recover_element(
+                                LineProperties::from_xml_element_with_context(child_node, context),
+                                context,
+                            )
+                            .transpose()
+                        })
                         .collect::<Result<Vec<_>>>()?;
 
                     line_style_list = match vec.len() {
                         len if len >= 3 => Some(vec),
                         len => {
-                            return Err(Box::new(LimitViolationError::new(
-                                String::from("lnStyleLst"),
-                                "ln",
-                                3,
-                                MaxOccurs::Unbounded,
-                                len as u32,
-                            )))
+                            return Err(
+                                LimitViolationError::at(child_node, "ln", 3, MaxOccurs::Unbounded, len as u32).into(),
+                            )
                         }
                     };
                 }
@@ -258,20 +315,27 @@ impl StyleMatrix {
                     let vec = child_node
                         .child_nodes
                         .iter()
-                        .filter(|child_node| child_node.local_name() == "effectStyle")
-                        .map(EffectStyleItem::from_xml_element)
+                        .filter(|child_node| child_node.is(ns::DRAWINGML, "effectStyle"))
+                        .filter_map(|child_node| {
+                            recover_element(
+                                EffectStyleItem::from_xml_element_with_context(child_node, context),
+                                context,
+                            )
+                            .transpose()
+                        })
                         .collect::<Result<Vec<_>>>()?;
 
                     effect_style_list = match vec.len() {
                         len if len >= 3 => Some(vec),
                         len => {
-                            return Err(Box::new(LimitViolationError::new(
-                                String::from("effectStyleLst"),
+                            return Err(LimitViolationError::at(
+                                child_node,
                                 "effectStyle",
                                 3,
                                 MaxOccurs::Unbounded,
                                 len as u32,
-                            )))
+                            )
+                            .into())
                         }
                     };
                 }
@@ -279,37 +343,36 @@ impl StyleMatrix {
                     let vec = child_node
                         .child_nodes
                         .iter()
-                        .filter_map(FillProperties::try_from_xml_element)
+                        .filter_map(|child_node| FillProperties::try_from_xml_element_with_context(child_node, context))
                         .collect::<Result<Vec<_>>>()?;
 
                     bg_fill_style_list = match vec.len() {
                         len if len >= 3 => Some(vec),
                         len => {
-                            return Err(Box::new(LimitViolationError::new(
-                                String::from("bgFillStyleLst"),
+                            return Err(LimitViolationError::at(
+                                child_node,
                                 "EG_FillProperties",
                                 3,
                                 MaxOccurs::Unbounded,
                                 len as u32,
-                            )))
+                            )
+                            .into())
                         }
                     };
                 }
-                _ => (),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let fill_style_list =
-            fill_style_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "fillStyleLst"))?;
+        let fill_style_list = fill_style_list.ok_or_else(|| MissingChildNodeError::at(xml_node, "fillStyleLst"))?;
 
-        let line_style_list =
-            line_style_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "lnStyleLst"))?;
+        let line_style_list = line_style_list.ok_or_else(|| MissingChildNodeError::at(xml_node, "lnStyleLst"))?;
 
         let effect_style_list =
-            effect_style_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "effectStyleLst"))?;
+            effect_style_list.ok_or_else(|| MissingChildNodeError::at(xml_node, "effectStyleLst"))?;
 
         let bg_fill_style_list =
-            bg_fill_style_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "bgFillStyleLst"))?;
+            bg_fill_style_list.ok_or_else(|| MissingChildNodeError::at(xml_node, "bgFillStyleLst"))?;
 
         Ok(Self {
             name,
@@ -317,10 +380,47 @@ impl StyleMatrix {
             line_style_list,
             effect_style_list,
             bg_fill_style_list,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for StyleMatrix {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("name", self.name.as_ref());
+
+        let mut fill_style_list_node = dml_element("fillStyleLst");
+        fill_style_list_node
+            .child_nodes
+            .extend(self.fill_style_list.iter().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.push(fill_style_list_node);
+
+        let mut line_style_list_node = dml_element("lnStyleLst");
+        line_style_list_node
+            .child_nodes
+            .extend(self.line_style_list.iter().map(|line| line.to_xml_element("a:ln")));
+        node.child_nodes.push(line_style_list_node);
+
+        let mut effect_style_list_node = dml_element("effectStyleLst");
+        effect_style_list_node.child_nodes.extend(
+            self.effect_style_list
+                .iter()
+                .map(|effect_style| effect_style.to_xml_element("a:effectStyle")),
+        );
+        node.child_nodes.push(effect_style_list_node);
+
+        let mut bg_fill_style_list_node = dml_element("bgFillStyleLst");
+        bg_fill_style_list_node
+            .child_nodes
+            .extend(self.bg_fill_style_list.iter().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.push(bg_fill_style_list_node);
+
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SupplementalFont {
     /// Specifies the script, or language, in which the typeface is supposed to be used.
@@ -332,10 +432,13 @@ pub struct SupplementalFont {
 
     /// Specifies the font face to use.
     pub typeface: TextTypeFace,
+
+    pub unknown: UnknownContent,
 }
 
-impl SupplementalFont {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for SupplementalFont {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut script = None;
         let mut typeface = None;
 
@@ -343,14 +446,30 @@ impl SupplementalFont {
             match attr.as_str() {
                 "script" => script = Some(value.clone()),
                 "typeface" => typeface = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let script = script.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "script"))?;
-        let typeface = typeface.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "typeface"))?;
+        let script = script.ok_or_else(|| MissingAttributeError::at(xml_node, "script"))?;
+        let typeface = typeface.ok_or_else(|| MissingAttributeError::at(xml_node, "typeface"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self {
+            script,
+            typeface,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { script, typeface })
+impl ToXmlElement for SupplementalFont {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("script", &self.script);
+        node.set_attribute("typeface", &self.typeface);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -359,23 +478,51 @@ pub struct FontReference {
     /// Specifies the identifier of the font to reference.
     pub index: FontCollectionIndex,
     pub color: Option<Color>,
+
+    pub unknown: UnknownContent,
 }
 
-impl FontReference {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let index = xml_node
-            .attributes
-            .get("idx")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "idx"))?
-            .parse()?;
+impl XsdType for FontReference {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut index = None;
+        let mut color = None;
 
-        let color = xml_node
-            .child_nodes
-            .iter()
-            .find_map(Color::try_from_xml_element)
-            .transpose()?;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "idx" => index = Some(xml_node.parse_attribute_value(attr, value)?),
+                _ => unknown.push_attribute(xml_node, attr),
+            }
+        }
 
-        Ok(Self { index, color })
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                local_name if Color::is_choice_member(local_name) => {
+                    color = unknown.recover_element(
+                        Color::from_xml_element_with_context(child_node, context),
+                        position,
+                        child_node,
+                        context,
+                    )?
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        let index = index.ok_or_else(|| MissingAttributeError::at(xml_node, "idx"))?;
+
+        Ok(Self { index, color, unknown })
+    }
+}
+
+impl ToXmlElement for FontReference {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("idx", self.index);
+        node.child_nodes
+            .extend(self.color.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -437,38 +584,63 @@ pub struct FontScheme {
     /// In this example, we see the latin, east asian, and complex script fonts defined along with many fonts for
     /// different locals.
     pub minor_font: Box<FontCollection>,
+
+    pub unknown: UnknownContent,
 }
 
-impl FontScheme {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for FontScheme {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let name = xml_node
             .attributes
             .get("name")
-            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?
+            .ok_or_else(|| MissingAttributeError::at(xml_node, "name"))?
             .clone();
 
         let mut major_font = None;
         let mut minor_font = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "majorFont" => major_font = Some(Box::new(FontCollection::from_xml_element(child_node)?)),
-                "minorFont" => minor_font = Some(Box::new(FontCollection::from_xml_element(child_node)?)),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "majorFont" => {
+                    major_font = Some(Box::new(FontCollection::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                "minorFont" => {
+                    minor_font = Some(Box::new(FontCollection::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let major_font = major_font.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "majorFont"))?;
-        let minor_font = minor_font.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "minorFont"))?;
+        let major_font = major_font.ok_or_else(|| MissingChildNodeError::at(xml_node, "majorFont"))?;
+        let minor_font = minor_font.ok_or_else(|| MissingChildNodeError::at(xml_node, "minorFont"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             name,
             major_font,
             minor_font,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for FontScheme {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("name", &self.name);
+        node.child_nodes.push(self.major_font.to_xml_element("a:majorFont"));
+        node.child_nodes.push(self.minor_font.to_xml_element("a:minorFont"));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DefaultShapeDefinition {
     /// This element specifies the visual shape properties that can be applied to a shape.
@@ -478,41 +650,84 @@ pub struct DefaultShapeDefinition {
 
     /// This element specifies the style information for a shape.
     pub shape_style: Option<Box<ShapeStyle>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl DefaultShapeDefinition {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for DefaultShapeDefinition {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut shape_properties = None;
         let mut text_body_properties = None;
         let mut text_list_style = None;
         let mut shape_style = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "spPr" => shape_properties = Some(Box::new(ShapeProperties::from_xml_element(child_node)?)),
-                "bodyPr" => text_body_properties = Some(Box::new(TextBodyProperties::from_xml_element(child_node)?)),
-                "lstStyle" => text_list_style = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
-                "style" => shape_style = Some(Box::new(ShapeStyle::from_xml_element(child_node)?)),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "spPr" => {
+                    shape_properties = Some(Box::new(ShapeProperties::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                "bodyPr" => {
+                    text_body_properties = unknown
+                        .recover_element(
+                            TextBodyProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lstStyle" => {
+                    text_list_style = Some(Box::new(TextListStyle::from_xml_element_with_context(
+                        child_node, context,
+                    )?))
+                }
+                "style" => {
+                    shape_style = unknown
+                        .recover_element(
+                            ShapeStyle::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let shape_properties =
-            shape_properties.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "spPr"))?;
-        let text_body_properties =
-            text_body_properties.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "bodyPr"))?;
-        let text_list_style =
-            text_list_style.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "lstStyle"))?;
+        let shape_properties = shape_properties.ok_or_else(|| MissingChildNodeError::at(xml_node, "spPr"))?;
+        let text_body_properties = text_body_properties.ok_or_else(|| MissingChildNodeError::at(xml_node, "bodyPr"))?;
+        let text_list_style = text_list_style.ok_or_else(|| MissingChildNodeError::at(xml_node, "lstStyle"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             shape_properties,
             text_body_properties,
             text_list_style,
             shape_style,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for DefaultShapeDefinition {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.shape_properties.to_xml_element("a:spPr"));
+        node.child_nodes
+            .push(self.text_body_properties.to_xml_element("a:bodyPr"));
+        node.child_nodes.push(self.text_list_style.to_xml_element("a:lstStyle"));
+        node.child_nodes
+            .extend(self.shape_style.as_ref().map(|style| style.to_xml_element("a:style")));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FontCollection {
     /// Specifies the font used for latin characters.
@@ -527,34 +742,58 @@ pub struct FontCollection {
     /// This element defines a list of font within the styles area of DrawingML. A font is defined by a script along
     /// with a typeface.
     pub supplemental_font_list: Vec<SupplementalFont>,
+
+    pub unknown: UnknownContent,
 }
 
-impl FontCollection {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for FontCollection {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut opt_latin = None;
         let mut opt_ea = None;
         let mut opt_cs = None;
         let mut supplemental_font_list = Vec::new();
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "latin" => opt_latin = Some(TextFont::from_xml_element(child_node)?),
-                "ea" => opt_ea = Some(TextFont::from_xml_element(child_node)?),
-                "cs" => opt_cs = Some(TextFont::from_xml_element(child_node)?),
-                "font" => supplemental_font_list.push(SupplementalFont::from_xml_element(child_node)?),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "latin" => opt_latin = Some(TextFont::from_xml_element_with_context(child_node, context)?),
+                "ea" => opt_ea = Some(TextFont::from_xml_element_with_context(child_node, context)?),
+                "cs" => opt_cs = Some(TextFont::from_xml_element_with_context(child_node, context)?),
+                "font" => {
+                    supplemental_font_list.push(SupplementalFont::from_xml_element_with_context(child_node, context)?)
+                }
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let latin = opt_latin.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "latin"))?;
-        let east_asian = opt_ea.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "ea"))?;
-        let complex_script = opt_cs.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cs"))?;
+        let latin = opt_latin.ok_or_else(|| MissingChildNodeError::at(xml_node, "latin"))?;
+        let east_asian = opt_ea.ok_or_else(|| MissingChildNodeError::at(xml_node, "ea"))?;
+        let complex_script = opt_cs.ok_or_else(|| MissingChildNodeError::at(xml_node, "cs"))?;
+
+        unknown.push_all_attributes(xml_node);
 
         Ok(Self {
             latin,
             east_asian,
             complex_script,
             supplemental_font_list,
+            unknown,
         })
     }
 }
+
+impl ToXmlElement for FontCollection {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes.push(self.latin.to_xml_element("a:latin"));
+        node.child_nodes.push(self.east_asian.to_xml_element("a:ea"));
+        node.child_nodes.push(self.complex_script.to_xml_element("a:cs"));
+        node.child_nodes.extend(
+            self.supplemental_font_list
+                .iter()
+                .map(|font| font.to_xml_element("a:font")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
@@ -5,13 +5,14 @@ use crate::{
             Angle, Coordinate32, PositiveCoordinate32, TextAnchoringType, TextColumnCount, TextFontScalePercent,
             TextHorizontalOverflowType, TextSpacingPercent, TextVertOverflowType, TextVerticalType, TextWrappingType,
         },
+        util::{dml_element, XmlNodeExt},
     },
     error::NotGroupMemberError,
-    xml::{parse_xml_bool, XmlNode},
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{format_xml_bool, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TextBodyProperties {
@@ -369,35 +370,169 @@ pub struct TextBodyProperties {
     // TODO implement
     //pub scene_3d: Option<Scene3D>,
     //pub text_3d: Option<Text3D>,
+    pub unknown: UnknownContent,
 }
 
-impl TextBodyProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TextBodyProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "rot" => instance.rotate_angle = Some(value.parse()?),
-                    "spcFirstLastPara" => instance.paragraph_spacing = Some(parse_xml_bool(value)?),
-                    "vertOverflow" => instance.vertical_overflow = Some(value.parse()?),
-                    "horzOverflow" => instance.horizontal_overflow = Some(value.parse()?),
-                    "vert" => instance.vertical_type = Some(value.parse()?),
-                    "wrap" => instance.wrap_type = Some(value.parse()?),
-                    "lIns" => instance.left_inset = Some(value.parse()?),
-                    "tIns" => instance.top_inset = Some(value.parse()?),
-                    "rIns" => instance.right_inset = Some(value.parse()?),
-                    "bIns" => instance.bottom_inset = Some(value.parse()?),
-                    "numCol" => instance.column_count = Some(value.parse()?),
-                    "spcCol" => instance.space_between_columns = Some(value.parse()?),
-                    "rtlCol" => instance.rtl_columns = Some(parse_xml_bool(value)?),
-                    "fromWordArt" => instance.is_from_word_art = Some(parse_xml_bool(value)?),
-                    "anchor" => instance.anchor = Some(value.parse()?),
-                    "anchorCtr" => instance.anchor_center = Some(parse_xml_bool(value)?),
-                    "forceAA" => instance.force_antialias = Some(parse_xml_bool(value)?),
-                    "upright" => instance.upright = Some(parse_xml_bool(value)?),
-                    "compatLnSpc" => instance.compatible_line_spacing = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "rot" => {
+                        instance.rotate_angle = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "spcFirstLastPara" => {
+                        instance.paragraph_spacing = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "vertOverflow" => {
+                        instance.vertical_overflow = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "horzOverflow" => {
+                        instance.horizontal_overflow = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "vert" => {
+                        instance.vertical_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "wrap" => {
+                        instance.wrap_type = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "lIns" => {
+                        instance.left_inset = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "tIns" => {
+                        instance.top_inset = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rIns" => {
+                        instance.right_inset = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "bIns" => {
+                        instance.bottom_inset = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "numCol" => {
+                        instance.column_count = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "spcCol" => {
+                        instance.space_between_columns = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rtlCol" => {
+                        instance.rtl_columns = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "fromWordArt" => {
+                        instance.is_from_word_art = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "anchor" => {
+                        instance.anchor = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "anchorCtr" => {
+                        instance.anchor_center = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "forceAA" => {
+                        instance.force_antialias = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "upright" => {
+                        instance.upright = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "compatLnSpc" => {
+                        instance.compatible_line_spacing = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -406,16 +541,29 @@ impl TextBodyProperties {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
                             "prstTxWarp" => {
-                                instance.preset_text_warp =
-                                    Some(Box::new(PresetTextShape::from_xml_element(child_node)?))
+                                instance.preset_text_warp = instance
+                                    .unknown
+                                    .recover_element(
+                                        PresetTextShape::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
                             }
                             local_name if TextAutoFit::is_choice_member(local_name) => {
-                                instance.auto_fit_type = Some(TextAutoFit::from_xml_element(child_node)?);
+                                instance.auto_fit_type = instance.unknown.recover_element(
+                                    TextAutoFit::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
-                            _ => (),
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -424,6 +572,40 @@ impl TextBodyProperties {
     }
 }
 
+impl ToXmlElement for TextBodyProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("rot", self.rotate_angle);
+        node.set_optional_attribute("spcFirstLastPara", self.paragraph_spacing.map(format_xml_bool));
+        node.set_optional_attribute("vertOverflow", self.vertical_overflow);
+        node.set_optional_attribute("horzOverflow", self.horizontal_overflow);
+        node.set_optional_attribute("vert", self.vertical_type);
+        node.set_optional_attribute("wrap", self.wrap_type);
+        node.set_optional_attribute("lIns", self.left_inset);
+        node.set_optional_attribute("tIns", self.top_inset);
+        node.set_optional_attribute("rIns", self.right_inset);
+        node.set_optional_attribute("bIns", self.bottom_inset);
+        node.set_optional_attribute("numCol", self.column_count);
+        node.set_optional_attribute("spcCol", self.space_between_columns);
+        node.set_optional_attribute("rtlCol", self.rtl_columns.map(format_xml_bool));
+        node.set_optional_attribute("fromWordArt", self.is_from_word_art.map(format_xml_bool));
+        node.set_optional_attribute("anchor", self.anchor);
+        node.set_optional_attribute("anchorCtr", self.anchor_center.map(format_xml_bool));
+        node.set_optional_attribute("forceAA", self.force_antialias.map(format_xml_bool));
+        node.set_optional_attribute("upright", self.upright.map(format_xml_bool));
+        node.set_optional_attribute("compatLnSpc", self.compatible_line_spacing.map(format_xml_bool));
+        node.child_nodes.extend(
+            self.preset_text_warp
+                .as_ref()
+                .map(|shape| shape.to_xml_element("a:prstTxWarp")),
+        );
+        node.child_nodes
+            .extend(self.auto_fit_type.as_ref().map(ToXmlChoice::to_xml_element));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextAutoFit {
     /// This element specifies that text within the text body should not be auto-fit to the bounding box. Auto-fitting is
@@ -536,23 +718,30 @@ pub enum TextAutoFit {
 }
 
 impl XsdType for TextAutoFit {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "noAutofit" => Ok(TextAutoFit::NoAutoFit),
-            "normAutofit" => Ok(TextAutoFit::NormalAutoFit(TextNormalAutoFit::from_xml_element(
-                xml_node,
-            )?)),
+            "normAutofit" => Ok(TextAutoFit::NormalAutoFit(
+                TextNormalAutoFit::from_xml_element_with_context(xml_node, context)?,
+            )),
             "spAutoFit" => Ok(TextAutoFit::ShapeAutoFit),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextAutofit").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextAutofit").into()),
         }
     }
 }
 
 impl XsdChoice for TextAutoFit {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "noAutofit" | "normAutofit" | "spAutoFit" => true,
-            _ => false,
+        matches!(name.as_ref(), "noAutofit" | "normAutofit" | "spAutoFit")
+    }
+}
+
+impl ToXmlChoice for TextAutoFit {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextAutoFit::NoAutoFit => dml_element("noAutofit"),
+            TextAutoFit::NormalAutoFit(autofit) => autofit.to_xml_element("a:normAutofit"),
+            TextAutoFit::ShapeAutoFit => dml_element("spAutoFit"),
         }
     }
 }
@@ -582,24 +771,54 @@ pub struct TextNormalAutoFit {
     ///
     /// This attribute applies only to paragraphs with percentage line spacing.
     pub line_spacing_reduction: Option<TextSpacingPercent>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextNormalAutoFit {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TextNormalAutoFit {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut font_scale = None;
         let mut line_spacing_reduction = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "fontScale" => font_scale = Some(value.parse::<TextFontScalePercent>()?),
-                "lnSpcReduction" => line_spacing_reduction = Some(value.parse::<TextSpacingPercent>()?),
-                _ => (),
+                "fontScale" => {
+                    font_scale = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<TextFontScalePercent>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "lnSpcReduction" => {
+                    line_spacing_reduction = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<TextSpacingPercent>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
+        unknown.push_all_elements(xml_node);
+
         Ok(Self {
             font_scale,
             line_spacing_reduction,
+            unknown,
         })
     }
 }
+
+impl ToXmlElement for TextNormalAutoFit {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("fontScale", self.font_scale);
+        node.set_optional_attribute("lnSpcReduction", self.line_spacing_reduction);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
@@ -4,14 +4,14 @@ use crate::{
         colors::Color,
         shapeprops::Blip,
         simpletypes::{TextAutonumberScheme, TextBulletSizePercent, TextBulletStartAtNum, TextFontSize},
+        util::{dml_element, dml_val_element, XmlNodeExt},
     },
-    error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    error::{Error, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    xml::{ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextBulletColor {
@@ -67,29 +67,39 @@ pub enum TextBulletColor {
 }
 
 impl XsdType for TextBulletColor {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<TextBulletColor> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<TextBulletColor> {
+        match xml_node.dml_local_name() {
             "buClrTx" => Ok(TextBulletColor::FollowText),
             "buClr" => {
                 let color = xml_node
                     .child_nodes
                     .iter()
-                    .find_map(Color::try_from_xml_element)
+                    .find_map(|child_node| Color::try_from_xml_element_with_context(child_node, context))
                     .transpose()?
-                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "color"))?;
+                    .ok_or_else(|| MissingChildNodeError::at(xml_node, "color"))?;
 
                 Ok(TextBulletColor::Color(color))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextBulletColor").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextBulletColor").into()),
         }
     }
 }
 
 impl XsdChoice for TextBulletColor {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "buClrTx" | "buClr" => true,
-            _ => false,
+        matches!(name.as_ref(), "buClrTx" | "buClr")
+    }
+}
+
+impl ToXmlChoice for TextBulletColor {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextBulletColor::FollowText => dml_element("buClrTx"),
+            TextBulletColor::Color(color) => {
+                let mut node = dml_element("buClr");
+                node.child_nodes.push(color.to_xml_element());
+                node
+            }
         }
     }
 }
@@ -173,37 +183,36 @@ pub enum TextBulletSize {
 }
 
 impl XsdType for TextBulletSize {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<TextBulletSize> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<TextBulletSize> {
+        match xml_node.dml_local_name() {
             "buSzTx" => Ok(TextBulletSize::FollowText),
             "buSzPct" => {
-                let val = xml_node
-                    .attributes
-                    .get("val")
-                    .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "val"))?
-                    .parse()?;
+                let val = xml_node.parse_required_attribute("val")?;
 
                 Ok(TextBulletSize::Percent(val))
             }
             "buSzPts" => {
-                let val = xml_node
-                    .attributes
-                    .get("val")
-                    .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "val"))?
-                    .parse()?;
+                let val = xml_node.parse_required_attribute("val")?;
 
                 Ok(TextBulletSize::Point(val))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextBulletSize").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextBulletSize").into()),
         }
     }
 }
 
 impl XsdChoice for TextBulletSize {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "buSzTx" | "buSzPct" | "buSzPts" => true,
-            _ => false,
+        matches!(name.as_ref(), "buSzTx" | "buSzPct" | "buSzPts")
+    }
+}
+
+impl ToXmlChoice for TextBulletSize {
+    fn to_xml_element(&self) -> XmlNode {
+        match *self {
+            TextBulletSize::FollowText => dml_element("buSzTx"),
+            TextBulletSize::Percent(percent) => dml_val_element("buSzPct", percent),
+            TextBulletSize::Point(point) => dml_val_element("buSzPts", point),
         }
     }
 }
@@ -261,20 +270,28 @@ pub enum TextBulletTypeface {
 }
 
 impl XsdType for TextBulletTypeface {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<TextBulletTypeface> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<TextBulletTypeface> {
+        match xml_node.dml_local_name() {
             "buFontTx" => Ok(TextBulletTypeface::FollowText),
-            "buFont" => Ok(TextBulletTypeface::Font(TextFont::from_xml_element(xml_node)?)),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextBulletTypeface").into()),
+            "buFont" => Ok(TextBulletTypeface::Font(TextFont::from_xml_element_with_context(
+                xml_node, context,
+            )?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextBulletTypeface").into()),
         }
     }
 }
 
 impl XsdChoice for TextBulletTypeface {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "buFontTx" | "buFont" => true,
-            _ => false,
+        matches!(name.as_ref(), "buFontTx" | "buFont")
+    }
+}
+
+impl ToXmlChoice for TextBulletTypeface {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextBulletTypeface::FollowText => dml_element("buFontTx"),
+            TextBulletTypeface::Font(font) => font.to_xml_element("a:buFont"),
         }
     }
 }
@@ -441,17 +458,17 @@ pub enum TextBullet {
 }
 
 impl XsdType for TextBullet {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<TextBullet> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<TextBullet> {
+        match xml_node.dml_local_name() {
             "buNone" => Ok(TextBullet::None),
-            "buAutoNum" => Ok(TextBullet::AutoNumbered(TextAutonumberedBullet::from_xml_element(
-                xml_node,
-            )?)),
+            "buAutoNum" => Ok(TextBullet::AutoNumbered(
+                TextAutonumberedBullet::from_xml_element_with_context(xml_node, context)?,
+            )),
             "buChar" => {
                 let character = xml_node
                     .attributes
                     .get("char")
-                    .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "char"))?
+                    .ok_or_else(|| MissingAttributeError::at(xml_node, "char"))?
                     .clone();
 
                 Ok(TextBullet::Character(character))
@@ -460,24 +477,38 @@ impl XsdType for TextBullet {
                 let blip = xml_node
                     .child_nodes
                     .iter()
-                    .find(|child_node| child_node.local_name() == "blip")
-                    .ok_or_else(|| {
-                        Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "EG_TextBullet"))
-                    })
-                    .and_then(Blip::from_xml_element)?;
+                    .find(|child_node| child_node.is(ns::DRAWINGML, "blip"))
+                    .ok_or_else(|| Error::from(MissingChildNodeError::at(xml_node, "EG_TextBullet")))
+                    .and_then(|child_node| Blip::from_xml_element_with_context(child_node, context))?;
 
                 Ok(TextBullet::Picture(Box::new(blip)))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextBullet").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextBullet").into()),
         }
     }
 }
 
 impl XsdChoice for TextBullet {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "buNone" | "buAutoNum" | "buChar" | "buBlip" => true,
-            _ => false,
+        matches!(name.as_ref(), "buNone" | "buAutoNum" | "buChar" | "buBlip")
+    }
+}
+
+impl ToXmlChoice for TextBullet {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextBullet::None => dml_element("buNone"),
+            TextBullet::AutoNumbered(bullet) => bullet.to_xml_element("a:buAutoNum"),
+            TextBullet::Character(character) => {
+                let mut node = dml_element("buChar");
+                node.set_attribute("char", character);
+                node
+            }
+            TextBullet::Picture(blip) => {
+                let mut node = dml_element("buBlip");
+                node.child_nodes.push(blip.to_xml_element("a:blip"));
+                node
+            }
         }
     }
 }
@@ -495,24 +526,50 @@ pub struct TextAutonumberedBullet {
     /// multiple letters should be used. For instance 27 should be represented as 'aa' and
     /// similarly 53 should be 'aaa'.
     pub start_at: Option<TextBulletStartAtNum>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextAutonumberedBullet {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextAutonumberedBullet> {
+impl XsdType for TextAutonumberedBullet {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut scheme = None;
         let mut start_at = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
-                "type" => scheme = Some(value.parse()?),
-                "startAt" => start_at = Some(value.parse()?),
-                _ => (),
+                "type" => scheme = Some(xml_node.parse_extensible_attribute_value(attr, value, context)?),
+                "startAt" => {
+                    start_at = unknown.recover_attribute(
+                        xml_node.parse_attribute_value(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let scheme = scheme.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "type"))?;
+        let scheme = scheme.ok_or_else(|| MissingAttributeError::at(xml_node, "type"))?;
+
+        unknown.push_all_elements(xml_node);
+
+        Ok(Self {
+            scheme,
+            start_at,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { scheme, start_at })
+impl ToXmlElement for TextAutonumberedBullet {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("type", &self.scheme);
+        node.set_optional_attribute("startAt", self.start_at);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -606,59 +663,170 @@ pub struct TextListStyle {
 
     /// This element specifies all paragraph level text properties for all elements that have the attribute lvl="8".
     pub lvl9_paragraph_props: Option<Box<TextParagraphProperties>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextListStyle {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "defPPr" => {
-                        instance.def_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl1pPr" => {
-                        instance.lvl1_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl2pPr" => {
-                        instance.lvl2_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl3pPr" => {
-                        instance.lvl3_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl4pPr" => {
-                        instance.lvl4_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl5pPr" => {
-                        instance.lvl5_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl6pPr" => {
-                        instance.lvl6_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl7pPr" => {
-                        instance.lvl7_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl8pPr" => {
-                        instance.lvl8_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "lvl9pPr" => {
-                        instance.lvl9_paragraph_props =
-                            Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    _ => (),
+impl XsdType for TextListStyle {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut def_paragraph_props = None;
+        let mut lvl1_paragraph_props = None;
+        let mut lvl2_paragraph_props = None;
+        let mut lvl3_paragraph_props = None;
+        let mut lvl4_paragraph_props = None;
+        let mut lvl5_paragraph_props = None;
+        let mut lvl6_paragraph_props = None;
+        let mut lvl7_paragraph_props = None;
+        let mut lvl8_paragraph_props = None;
+        let mut lvl9_paragraph_props = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "defPPr" => {
+                    def_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl1pPr" => {
+                    lvl1_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl2pPr" => {
+                    lvl2_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl3pPr" => {
+                    lvl3_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl4pPr" => {
+                    lvl4_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
                 }
+                "lvl5pPr" => {
+                    lvl5_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl6pPr" => {
+                    lvl6_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl7pPr" => {
+                    lvl7_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl8pPr" => {
+                    lvl8_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "lvl9pPr" => {
+                    lvl9_paragraph_props = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self {
+            def_paragraph_props,
+            lvl1_paragraph_props,
+            lvl2_paragraph_props,
+            lvl3_paragraph_props,
+            lvl4_paragraph_props,
+            lvl5_paragraph_props,
+            lvl6_paragraph_props,
+            lvl7_paragraph_props,
+            lvl8_paragraph_props,
+            lvl9_paragraph_props,
+            unknown,
+        })
+    }
+}
 
-                Ok(instance)
-            })
+impl ToXmlElement for TextListStyle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        let levels = [
+            ("a:defPPr", &self.def_paragraph_props),
+            ("a:lvl1pPr", &self.lvl1_paragraph_props),
+            ("a:lvl2pPr", &self.lvl2_paragraph_props),
+            ("a:lvl3pPr", &self.lvl3_paragraph_props),
+            ("a:lvl4pPr", &self.lvl4_paragraph_props),
+            ("a:lvl5pPr", &self.lvl5_paragraph_props),
+            ("a:lvl6pPr", &self.lvl6_paragraph_props),
+            ("a:lvl7pPr", &self.lvl7_paragraph_props),
+            ("a:lvl8pPr", &self.lvl8_paragraph_props),
+            ("a:lvl9pPr", &self.lvl9_paragraph_props),
+        ];
+
+        node.child_nodes.extend(
+            levels
+                .iter()
+                .filter_map(|(name, props)| props.as_ref().map(|props| props.to_xml_element(name))),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
@@ -3,6 +3,7 @@ use super::{
     runformatting::{TextFont, TextRun, TextUnderlineFill, TextUnderlineLine},
 };
 use crate::{
+    diagnostics::recover_element,
     drawingml::{
         colors::Color,
         core::{Hyperlink, LineProperties},
@@ -12,32 +13,59 @@ use crate::{
             TextIndentLevelType, TextLanguageID, TextMargin, TextNonNegativePoint, TextPoint, TextSpacingPercent,
             TextSpacingPoint, TextStrikeType, TextTabAlignType, TextUnderlineType,
         },
-        util::XmlNodeExt,
+        util::{dml_element, dml_val_element, XmlNodeExt},
     },
-    error::{LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
-    xml::{parse_xml_bool, XmlNode},
-    xsdtypes::{XsdChoice, XsdType},
+    error::{Error, LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    xml::{format_xml_bool, ns, unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
-use std::error::Error;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+pub use crate::error::Result;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TextLineBreak {
     pub char_properties: Option<Box<TextCharacterProperties>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextLineBreak {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let char_properties = xml_node
-            .child_nodes
-            .iter()
-            .find(|child_node| child_node.local_name() == "rPr")
-            .map(TextCharacterProperties::from_xml_element)
-            .transpose()?
-            .map(Box::new);
+impl XsdType for TextLineBreak {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut char_properties = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "rPr" => {
+                    char_properties = unknown
+                        .recover_element(
+                            TextCharacterProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
+
+        Ok(Self {
+            char_properties,
+            unknown,
+        })
+    }
+}
 
-        Ok(Self { char_properties })
+impl ToXmlElement for TextLineBreak {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.char_properties.as_ref().map(|props| props.to_xml_element("a:rPr")));
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -97,10 +125,13 @@ pub struct TextField {
 
     /// The text of this text field.
     pub text: Option<String>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextField {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for TextField {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut id = None;
         let mut field_type = None;
 
@@ -108,22 +139,40 @@ impl TextField {
             match attr.as_str() {
                 "id" => id = Some(value.clone()),
                 "type" => field_type = Some(value.clone()),
-                _ => (),
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let id = id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?;
+        let id = id.ok_or_else(|| MissingAttributeError::at(xml_node, "id"))?;
 
         let mut char_properties = None;
         let mut paragraph_properties = None;
         let mut text = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "rPr" => char_properties = Some(Box::new(TextCharacterProperties::from_xml_element(child_node)?)),
-                "pPr" => paragraph_properties = Some(Box::new(TextParagraph::from_xml_element(child_node)?)),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "rPr" => {
+                    char_properties = unknown
+                        .recover_element(
+                            TextCharacterProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "pPr" => {
+                    paragraph_properties = unknown
+                        .recover_element(
+                            TextParagraph::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
                 "t" => text = child_node.text.clone(),
-                _ => (),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
@@ -133,10 +182,35 @@ impl TextField {
             char_properties,
             paragraph_properties,
             text,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for TextField {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("id", &self.id);
+        node.set_optional_attribute("type", self.field_type.as_ref());
+        node.child_nodes
+            .extend(self.char_properties.as_ref().map(|props| props.to_xml_element("a:rPr")));
+        node.child_nodes.extend(
+            self.paragraph_properties
+                .as_ref()
+                .map(|props| props.to_xml_element("a:pPr")),
+        );
+
+        if let Some(text) = &self.text {
+            let mut text_node = dml_element("t");
+            text_node.text = Some(text.clone());
+            node.child_nodes.push(text_node);
+        }
+
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TextParagraphProperties {
     /// Specifies the left margin of the paragraph. This is specified in addition to the text body
@@ -526,27 +600,106 @@ pub struct TextParagraphProperties {
     ///
     /// The run of text described above is formatting with a single underline of text matching color.
     pub default_run_properties: Option<Box<TextCharacterProperties>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextParagraphProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextParagraphProperties> {
+impl XsdType for TextParagraphProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "marL" => instance.margin_left = Some(value.parse()?),
-                    "marR" => instance.margin_right = Some(value.parse()?),
-                    "lvl" => instance.level = Some(value.parse()?),
-                    "indent" => instance.indent = Some(value.parse()?),
-                    "algn" => instance.align = Some(value.parse()?),
-                    "defTabSz" => instance.default_tab_size = Some(value.parse()?),
-                    "rtl" => instance.rtl = Some(parse_xml_bool(value)?),
-                    "eaLnBrk" => instance.east_asian_line_break = Some(parse_xml_bool(value)?),
-                    "fontAlgn" => instance.font_align = Some(value.parse()?),
-                    "latinLnBrk" => instance.latin_line_break = Some(parse_xml_bool(value)?),
-                    "hangingPunct" => instance.hanging_punctuations = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    "marL" => {
+                        instance.margin_left = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "marR" => {
+                        instance.margin_right = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "lvl" => {
+                        instance.level = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "indent" => {
+                        instance.indent = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "algn" => {
+                        instance.align = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "defTabSz" => {
+                        instance.default_tab_size = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "rtl" => {
+                        instance.rtl = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "eaLnBrk" => {
+                        instance.east_asian_line_break = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "fontAlgn" => {
+                        instance.font_align = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "latinLnBrk" => {
+                        instance.latin_line_break = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "hangingPunct" => {
+                        instance.hanging_punctuations = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -555,18 +708,19 @@ impl TextParagraphProperties {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
                             "lnSpc" => {
                                 instance.line_spacing = Some(
                                     child_node
                                         .child_nodes
                                         .iter()
-                                        .find_map(TextSpacing::try_from_xml_element)
+                                        .find_map(|child_node| {
+                                            TextSpacing::try_from_xml_element_with_context(child_node, context)
+                                        })
                                         .transpose()?
-                                        .ok_or_else(|| {
-                                            MissingChildNodeError::new(child_node.name.clone(), "EG_TextSpacing")
-                                        })?,
+                                        .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_TextSpacing"))?,
                                 );
                             }
                             "spcBef" => {
@@ -574,11 +728,11 @@ impl TextParagraphProperties {
                                     child_node
                                         .child_nodes
                                         .iter()
-                                        .find_map(TextSpacing::try_from_xml_element)
+                                        .find_map(|child_node| {
+                                            TextSpacing::try_from_xml_element_with_context(child_node, context)
+                                        })
                                         .transpose()?
-                                        .ok_or_else(|| {
-                                            MissingChildNodeError::new(child_node.name.clone(), "EG_TextSpacing")
-                                        })?,
+                                        .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_TextSpacing"))?,
                                 );
                             }
                             "spcAft" => {
@@ -586,26 +740,32 @@ impl TextParagraphProperties {
                                     child_node
                                         .child_nodes
                                         .iter()
-                                        .find_map(TextSpacing::try_from_xml_element)
+                                        .find_map(|child_node| {
+                                            TextSpacing::try_from_xml_element_with_context(child_node, context)
+                                        })
                                         .transpose()?
-                                        .ok_or_else(|| {
-                                            MissingChildNodeError::new(child_node.name.clone(), "EG_TextSpacing")
-                                        })?,
+                                        .ok_or_else(|| MissingChildNodeError::at(child_node, "EG_TextSpacing"))?,
                                 );
                             }
                             "tabLst" => {
                                 let vec = child_node
                                     .child_nodes
                                     .iter()
-                                    .filter(|tab_stop_node| tab_stop_node.local_name() == "tab")
-                                    .map(TextTabStop::from_xml_element)
+                                    .filter(|tab_stop_node| tab_stop_node.is(ns::DRAWINGML, "tab"))
+                                    .filter_map(|child_node| {
+                                        recover_element(
+                                            TextTabStop::from_xml_element_with_context(child_node, context),
+                                            context,
+                                        )
+                                        .transpose()
+                                    })
                                     .collect::<Result<Vec<_>>>()?;
 
                                 instance.tab_stop_list = match vec.len() {
                                     len if len <= 32 => Some(vec),
                                     len => {
-                                        return Err(Box::<dyn Error>::from(LimitViolationError::new(
-                                            xml_node.name.clone(),
+                                        return Err(Error::from(LimitViolationError::at(
+                                            xml_node,
                                             "tabLst",
                                             0,
                                             MaxOccurs::Value(32),
@@ -615,22 +775,49 @@ impl TextParagraphProperties {
                                 };
                             }
                             "defRPr" => {
-                                instance.default_run_properties =
-                                    Some(Box::new(TextCharacterProperties::from_xml_element(child_node)?))
+                                instance.default_run_properties = instance
+                                    .unknown
+                                    .recover_element(
+                                        TextCharacterProperties::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
                             }
                             local_name if TextBulletColor::is_choice_member(local_name) => {
-                                instance.bullet_color = Some(TextBulletColor::from_xml_element(child_node)?);
+                                instance.bullet_color = instance.unknown.recover_element(
+                                    TextBulletColor::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
                             local_name if TextBulletSize::is_choice_member(local_name) => {
-                                instance.bullet_size = Some(TextBulletSize::from_xml_element(child_node)?);
+                                instance.bullet_size = instance.unknown.recover_element(
+                                    TextBulletSize::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
                             local_name if TextBulletTypeface::is_choice_member(local_name) => {
-                                instance.bullet_typeface = Some(TextBulletTypeface::from_xml_element(child_node)?);
+                                instance.bullet_typeface = instance.unknown.recover_element(
+                                    TextBulletTypeface::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
-                            local_name if TextBulletTypeface::is_choice_member(local_name) => {
-                                instance.bullet = Some(TextBullet::from_xml_element(child_node)?);
+                            local_name if TextBullet::is_choice_member(local_name) => {
+                                instance.bullet = instance.unknown.recover_element(
+                                    TextBullet::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
-                            _ => (),
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -639,6 +826,62 @@ impl TextParagraphProperties {
     }
 }
 
+impl ToXmlElement for TextParagraphProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("marL", self.margin_left);
+        node.set_optional_attribute("marR", self.margin_right);
+        node.set_optional_attribute("lvl", self.level);
+        node.set_optional_attribute("indent", self.indent);
+        node.set_optional_attribute("algn", self.align);
+        node.set_optional_attribute("defTabSz", self.default_tab_size);
+        node.set_optional_attribute("rtl", self.rtl.map(format_xml_bool));
+        node.set_optional_attribute("eaLnBrk", self.east_asian_line_break.map(format_xml_bool));
+        node.set_optional_attribute("fontAlgn", self.font_align);
+        node.set_optional_attribute("latinLnBrk", self.latin_line_break.map(format_xml_bool));
+        node.set_optional_attribute("hangingPunct", self.hanging_punctuations.map(format_xml_bool));
+
+        let spacings = [
+            ("lnSpc", &self.line_spacing),
+            ("spcBef", &self.space_before),
+            ("spcAft", &self.space_after),
+        ];
+
+        for (name, spacing) in spacings.iter() {
+            if let Some(spacing) = spacing {
+                let mut spacing_node = dml_element(name);
+                spacing_node.child_nodes.push(spacing.to_xml_element());
+                node.child_nodes.push(spacing_node);
+            }
+        }
+
+        node.child_nodes
+            .extend(self.bullet_color.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.bullet_size.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.bullet_typeface.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.bullet.as_ref().map(ToXmlChoice::to_xml_element));
+
+        if let Some(tab_stop_list) = &self.tab_stop_list {
+            let mut list_node = dml_element("tabLst");
+            list_node
+                .child_nodes
+                .extend(tab_stop_list.iter().map(|tab_stop| tab_stop.to_xml_element("a:tab")));
+            node.child_nodes.push(list_node);
+        }
+
+        node.child_nodes.extend(
+            self.default_run_properties
+                .as_ref()
+                .map(|props| props.to_xml_element("a:defRPr")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TextParagraph {
     /// This element contains all paragraph level text properties for the containing paragraph. These paragraph
@@ -678,30 +921,74 @@ pub struct TextParagraph {
     /// recommended that this element be specified at the end of the list of text runs within the paragraph so that an
     /// orderly list is maintained.
     pub end_paragraph_char_properties: Option<Box<TextCharacterProperties>>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextParagraph {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "pPr" => {
-                        instance.properties = Some(Box::new(TextParagraphProperties::from_xml_element(child_node)?))
-                    }
-                    "endParaRPr" => {
-                        instance.end_paragraph_char_properties =
-                            Some(Box::new(TextCharacterProperties::from_xml_element(child_node)?))
-                    }
-                    local_name if TextRun::is_choice_member(local_name) => {
-                        instance.text_run_list.push(TextRun::from_xml_element(child_node)?);
-                    }
-                    _ => (),
+impl XsdType for TextParagraph {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
+        let mut properties = None;
+        let mut text_run_list = Vec::new();
+        let mut end_paragraph_char_properties = None;
+
+        unknown.push_all_attributes(xml_node);
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "pPr" => {
+                    properties = unknown
+                        .recover_element(
+                            TextParagraphProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                local_name if TextRun::is_choice_member(local_name) => text_run_list.extend(unknown.recover_element(
+                    TextRun::from_xml_element_with_context(child_node, context),
+                    position,
+                    child_node,
+                    context,
+                )?),
+                "endParaRPr" => {
+                    end_paragraph_char_properties = unknown
+                        .recover_element(
+                            TextCharacterProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
                 }
+                _ => unknown.push_element(position, child_node),
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(Self {
+            properties,
+            text_run_list,
+            end_paragraph_char_properties,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for TextParagraph {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.properties.as_ref().map(|props| props.to_xml_element("a:pPr")));
+        node.child_nodes
+            .extend(self.text_run_list.iter().map(ToXmlChoice::to_xml_element));
+        node.child_nodes.extend(
+            self.end_paragraph_char_properties
+                .as_ref()
+                .map(|props| props.to_xml_element("a:endParaRPr")),
+        );
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -1058,35 +1345,149 @@ pub struct TextCharacterProperties {
     ///
     /// Note that the second, third and fourth runs could be joined as one run with the rtl element specified.
     pub rtl: Option<bool>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextCharacterProperties {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextCharacterProperties> {
+impl XsdType for TextCharacterProperties {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "kumimoji" => instance.kumimoji = Some(parse_xml_bool(value)?),
+                    "kumimoji" => {
+                        instance.kumimoji = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
                     "lang" => instance.language = Some(value.clone()),
                     "altLang" => instance.alternative_language = Some(value.clone()),
-                    "sz" => instance.font_size = Some(value.parse()?),
-                    "b" => instance.bold = Some(parse_xml_bool(value)?),
-                    "i" => instance.italic = Some(parse_xml_bool(value)?),
-                    "u" => instance.underline = Some(value.parse()?),
-                    "strike" => instance.strikethrough = Some(value.parse()?),
-                    "kern" => instance.kerning = Some(value.parse()?),
-                    "cap" => instance.capitalization = Some(value.parse()?),
-                    "spc" => instance.spacing = Some(value.parse()?),
-                    "normalizeH" => instance.normalize_heights = Some(parse_xml_bool(value)?),
-                    "baseline" => instance.baseline = Some(value.parse()?),
-                    "noProof" => instance.no_proofing = Some(parse_xml_bool(value)?),
-                    "dirty" => instance.dirty = Some(parse_xml_bool(value)?),
-                    "err" => instance.spelling_error = Some(parse_xml_bool(value)?),
-                    "smtClean" => instance.smarttag_clean = Some(parse_xml_bool(value)?),
-                    "smtId" => instance.smarttag_id = Some(value.parse()?),
+                    "sz" => {
+                        instance.font_size = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "b" => {
+                        instance.bold = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "i" => {
+                        instance.italic = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "u" => {
+                        instance.underline = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "strike" => {
+                        instance.strikethrough = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "kern" => {
+                        instance.kerning = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "cap" => {
+                        instance.capitalization = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "spc" => {
+                        instance.spacing = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "normalizeH" => {
+                        instance.normalize_heights = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "baseline" => {
+                        instance.baseline = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "noProof" => {
+                        instance.no_proofing = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "dirty" => {
+                        instance.dirty = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "err" => {
+                        instance.spelling_error = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "smtClean" => {
+                        instance.smarttag_clean = instance.unknown.recover_attribute(
+                            xml_node.parse_bool_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "smtId" => {
+                        instance.smarttag_id = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
                     "bmk" => instance.bookmark_link_target = Some(value.clone()),
-                    _ => (),
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
@@ -1095,47 +1496,122 @@ impl TextCharacterProperties {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
-                        match child_node.local_name() {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
+                        match child_node.dml_local_name() {
                             "ln" => {
-                                instance.line_properties = Some(Box::new(LineProperties::from_xml_element(child_node)?))
+                                instance.line_properties = instance
+                                    .unknown
+                                    .recover_element(
+                                        LineProperties::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
                             }
                             "highlight" => {
                                 let color = child_node
                                     .child_nodes
                                     .iter()
-                                    .find_map(Color::try_from_xml_element)
+                                    .find_map(|child_node| {
+                                        Color::try_from_xml_element_with_context(child_node, context)
+                                    })
                                     .transpose()?
-                                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "CT_Color"))?;
+                                    .ok_or_else(|| MissingChildNodeError::at(xml_node, "CT_Color"))?;
 
                                 instance.highlight_color = Some(color);
                             }
-                            "latin" => instance.latin_font = Some(TextFont::from_xml_element(child_node)?),
-                            "ea" => instance.east_asian_font = Some(TextFont::from_xml_element(child_node)?),
-                            "cs" => instance.complex_script_font = Some(TextFont::from_xml_element(child_node)?),
-                            "sym" => instance.symbol_font = Some(TextFont::from_xml_element(child_node)?),
+                            "latin" => {
+                                instance.latin_font = instance.unknown.recover_element(
+                                    TextFont::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "ea" => {
+                                instance.east_asian_font = instance.unknown.recover_element(
+                                    TextFont::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "cs" => {
+                                instance.complex_script_font = instance.unknown.recover_element(
+                                    TextFont::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
+                            "sym" => {
+                                instance.symbol_font = instance.unknown.recover_element(
+                                    TextFont::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?
+                            }
                             "hlinkClick" => {
-                                instance.hyperlink_click = Some(Box::new(Hyperlink::from_xml_element(child_node)?))
+                                instance.hyperlink_click = instance
+                                    .unknown
+                                    .recover_element(
+                                        Hyperlink::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
                             }
                             "hlinkMouseOver" => {
-                                instance.hyperlink_mouse_over = Some(Box::new(Hyperlink::from_xml_element(child_node)?))
+                                instance.hyperlink_mouse_over = instance
+                                    .unknown
+                                    .recover_element(
+                                        Hyperlink::from_xml_element_with_context(child_node, context),
+                                        position,
+                                        child_node,
+                                        context,
+                                    )?
+                                    .map(Box::new)
                             }
                             "rtl" => {
-                                instance.rtl = child_node.text.as_ref().map(parse_xml_bool).transpose()?;
+                                instance.rtl = Some(child_node.parse_bool_attribute("val", context)?.unwrap_or(true));
                             }
                             local_name if FillProperties::is_choice_member(local_name) => {
-                                instance.fill_properties = Some(FillProperties::from_xml_element(child_node)?);
+                                instance.fill_properties = instance.unknown.recover_element(
+                                    FillProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
                             local_name if EffectProperties::is_choice_member(local_name) => {
-                                instance.effect_properties = Some(EffectProperties::from_xml_element(child_node)?);
+                                instance.effect_properties = instance.unknown.recover_element(
+                                    EffectProperties::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
                             local_name if TextUnderlineLine::is_choice_member(local_name) => {
-                                instance.text_underline_line = Some(TextUnderlineLine::from_xml_element(child_node)?);
+                                instance.text_underline_line = instance.unknown.recover_element(
+                                    TextUnderlineLine::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
                             local_name if TextUnderlineFill::is_choice_member(local_name) => {
-                                instance.text_underline_fill = Some(TextUnderlineFill::from_xml_element(child_node)?);
+                                instance.text_underline_fill = instance.unknown.recover_element(
+                                    TextUnderlineFill::from_xml_element_with_context(child_node, context),
+                                    position,
+                                    child_node,
+                                    context,
+                                )?;
                             }
-                            _ => (),
+                            _ => instance.unknown.push_element(position, child_node),
                         }
 
                         Ok(instance)
@@ -1144,6 +1620,77 @@ impl TextCharacterProperties {
     }
 }
 
+impl ToXmlElement for TextCharacterProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("kumimoji", self.kumimoji.map(format_xml_bool));
+        node.set_optional_attribute("lang", self.language.as_ref());
+        node.set_optional_attribute("altLang", self.alternative_language.as_ref());
+        node.set_optional_attribute("sz", self.font_size);
+        node.set_optional_attribute("b", self.bold.map(format_xml_bool));
+        node.set_optional_attribute("i", self.italic.map(format_xml_bool));
+        node.set_optional_attribute("u", self.underline);
+        node.set_optional_attribute("strike", self.strikethrough);
+        node.set_optional_attribute("kern", self.kerning);
+        node.set_optional_attribute("cap", self.capitalization);
+        node.set_optional_attribute("spc", self.spacing);
+        node.set_optional_attribute("normalizeH", self.normalize_heights.map(format_xml_bool));
+        node.set_optional_attribute("baseline", self.baseline);
+        node.set_optional_attribute("noProof", self.no_proofing.map(format_xml_bool));
+        node.set_optional_attribute("dirty", self.dirty.map(format_xml_bool));
+        node.set_optional_attribute("err", self.spelling_error.map(format_xml_bool));
+        node.set_optional_attribute("smtClean", self.smarttag_clean.map(format_xml_bool));
+        node.set_optional_attribute("smtId", self.smarttag_id);
+        node.set_optional_attribute("bmk", self.bookmark_link_target.as_ref());
+
+        node.child_nodes
+            .extend(self.line_properties.as_ref().map(|line| line.to_xml_element("a:ln")));
+        node.child_nodes
+            .extend(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.effect_properties.as_ref().map(ToXmlChoice::to_xml_element));
+
+        if let Some(highlight_color) = &self.highlight_color {
+            let mut highlight_node = dml_element("highlight");
+            highlight_node.child_nodes.push(highlight_color.to_xml_element());
+            node.child_nodes.push(highlight_node);
+        }
+
+        node.child_nodes
+            .extend(self.text_underline_line.as_ref().map(ToXmlChoice::to_xml_element));
+        node.child_nodes
+            .extend(self.text_underline_fill.as_ref().map(ToXmlChoice::to_xml_element));
+
+        let fonts = [
+            ("a:latin", &self.latin_font),
+            ("a:ea", &self.east_asian_font),
+            ("a:cs", &self.complex_script_font),
+            ("a:sym", &self.symbol_font),
+        ];
+
+        node.child_nodes.extend(
+            fonts
+                .iter()
+                .filter_map(|(name, font)| font.as_ref().map(|font| font.to_xml_element(name))),
+        );
+
+        node.child_nodes.extend(
+            self.hyperlink_click
+                .as_ref()
+                .map(|hyperlink| hyperlink.to_xml_element("a:hlinkClick")),
+        );
+        node.child_nodes.extend(
+            self.hyperlink_mouse_over
+                .as_ref()
+                .map(|hyperlink| hyperlink.to_xml_element("a:hlinkMouseOver")),
+        );
+        node.child_nodes
+            .extend(self.rtl.map(|rtl| dml_val_element("rtl", format_xml_bool(rtl))));
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextSpacing {
     /// This element specifies the amount of white space that is to be used between lines and paragraphs in the form of
@@ -1204,20 +1751,26 @@ pub enum TextSpacing {
 }
 
 impl XsdType for TextSpacing {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<TextSpacing> {
-        match xml_node.local_name() {
-            "spcPct" => Ok(TextSpacing::Percent(xml_node.get_val_attribute()?.parse()?)),
-            "spcPts" => Ok(TextSpacing::Point(xml_node.get_val_attribute()?.parse()?)),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextSpacing").into()),
+    fn from_xml_element_with_context(xml_node: &XmlNode, _context: &mut ParseContext) -> Result<TextSpacing> {
+        match xml_node.dml_local_name() {
+            "spcPct" => Ok(TextSpacing::Percent(xml_node.parse_val_attribute()?)),
+            "spcPts" => Ok(TextSpacing::Point(xml_node.parse_val_attribute()?)),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextSpacing").into()),
         }
     }
 }
 
 impl XsdChoice for TextSpacing {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "spcPct" | "spcPts" => true,
-            _ => false,
+        matches!(name.as_ref(), "spcPct" | "spcPts")
+    }
+}
+
+impl ToXmlChoice for TextSpacing {
+    fn to_xml_element(&self) -> XmlNode {
+        match *self {
+            TextSpacing::Percent(percent) => dml_val_element("spcPct", percent),
+            TextSpacing::Point(point) => dml_val_element("spcPts", point),
         }
     }
 }
@@ -1260,21 +1813,51 @@ pub struct TextTabStop {
     /// Specifies the alignment that is to be applied to text using this tab stop. If this attribute is
     /// omitted then the application default for the generating application.
     pub alignment: Option<TextTabAlignType>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextTabStop {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextTabStop> {
+impl XsdType for TextTabStop {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
         xml_node
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
-                    "pos" => instance.position = Some(value.parse::<Coordinate32>()?),
-                    "algn" => instance.alignment = Some(value.parse::<TextTabAlignType>()?),
-                    _ => (),
+                    "pos" => {
+                        instance.position = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<Coordinate32>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    "algn" => {
+                        instance.alignment = instance.unknown.recover_attribute(
+                            xml_node.parse_attribute_value::<TextTabAlignType>(attr, value),
+                            xml_node,
+                            attr,
+                            context,
+                        )?
+                    }
+                    _ => instance.unknown.push_attribute(xml_node, attr),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance: Self| {
+                instance.unknown.push_all_elements(xml_node);
+                instance
+            })
+    }
+}
+
+impl ToXmlElement for TextTabStop {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_optional_attribute("pos", self.position);
+        node.set_optional_attribute("algn", self.alignment);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
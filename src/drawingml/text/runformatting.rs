@@ -4,13 +4,14 @@ use crate::{
         core::LineProperties,
         shapeprops::FillProperties,
         simpletypes::{Panose, TextTypeFace},
+        util::{dml_element, XmlNodeExt},
     },
     error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
-    xml::XmlNode,
-    xsdtypes::{XsdChoice, XsdType},
+    xml::{unknown::UnknownContent, XmlNode},
+    xsdtypes::{ParseContext, ToXmlChoice, ToXmlElement, XsdChoice, XsdType},
 };
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextFont {
@@ -97,10 +98,13 @@ pub struct TextFont {
     ///
     /// Defaults to 0x01
     pub charset: Option<i32>,
+
+    pub unknown: UnknownContent,
 }
 
-impl TextFont {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextFont> {
+impl XsdType for TextFont {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut typeface = None;
         let mut panose = None;
         let mut pitch_family = None;
@@ -110,23 +114,52 @@ impl TextFont {
             match attr.as_str() {
                 "typeface" => typeface = Some(value.clone()),
                 "panose" => panose = Some(value.clone()),
-                "pitchFamily" => pitch_family = Some(value.parse::<i32>()?),
-                "charset" => charset = Some(value.parse::<i32>()?),
-                _ => (),
+                "pitchFamily" => {
+                    pitch_family = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<i32>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                "charset" => {
+                    charset = unknown.recover_attribute(
+                        xml_node.parse_attribute_value::<i32>(attr, value),
+                        xml_node,
+                        attr,
+                        context,
+                    )?
+                }
+                _ => unknown.push_attribute(xml_node, attr),
             }
         }
 
-        let typeface = typeface.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "typeface"))?;
+        let typeface = typeface.ok_or_else(|| MissingAttributeError::at(xml_node, "typeface"))?;
+
+        unknown.push_all_elements(xml_node);
 
         Ok(Self {
             typeface,
             panose,
             pitch_family,
             charset,
+            unknown,
         })
     }
 }
 
+impl ToXmlElement for TextFont {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.set_attribute("typeface", &self.typeface);
+        node.set_optional_attribute("panose", self.panose.as_ref());
+        node.set_optional_attribute("pitchFamily", self.pitch_family);
+        node.set_optional_attribute("charset", self.charset);
+        self.unknown.apply_to(&mut node);
+        node
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextRun {
     /// This element specifies the presence of a run of text within the containing text body. The run element is the
@@ -216,23 +249,34 @@ pub enum TextRun {
 }
 
 impl XsdType for TextRun {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
-            "r" => Ok(TextRun::RegularTextRun(Box::new(RegularTextRun::from_xml_element(
-                xml_node,
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
+            "r" => Ok(TextRun::RegularTextRun(Box::new(
+                RegularTextRun::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            "br" => Ok(TextRun::LineBreak(Box::new(
+                TextLineBreak::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            "fld" => Ok(TextRun::TextField(Box::new(TextField::from_xml_element_with_context(
+                xml_node, context,
             )?))),
-            "br" => Ok(TextRun::LineBreak(Box::new(TextLineBreak::from_xml_element(xml_node)?))),
-            "fld" => Ok(TextRun::TextField(Box::new(TextField::from_xml_element(xml_node)?))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextRun").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextRun").into()),
         }
     }
 }
 
 impl XsdChoice for TextRun {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "r" | "br" | "fld" => true,
-            _ => false,
+        matches!(name.as_ref(), "r" | "br" | "fld")
+    }
+}
+
+impl ToXmlChoice for TextRun {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextRun::RegularTextRun(run) => run.to_xml_element("a:r"),
+            TextRun::LineBreak(line_break) => line_break.to_xml_element("a:br"),
+            TextRun::TextField(field) => field.to_xml_element("a:fld"),
         }
     }
 }
@@ -280,23 +324,55 @@ pub struct RegularTextRun {
     /// The above DrawingML specifies a text body containing a single paragraph, containing a single run which contains
     /// the actual text specified with the <a:t> element.
     pub text: String,
+
+    pub unknown: UnknownContent,
 }
 
-impl RegularTextRun {
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+impl XsdType for RegularTextRun {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        let mut unknown = UnknownContent::default();
         let mut char_properties = None;
         let mut text = None;
 
-        for child_node in &xml_node.child_nodes {
-            match child_node.local_name() {
-                "rPr" => char_properties = Some(Box::new(TextCharacterProperties::from_xml_element(child_node)?)),
-                "t" => text = child_node.text.clone(),
-                _ => (),
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.dml_local_name() {
+                "rPr" => {
+                    char_properties = unknown
+                        .recover_element(
+                            TextCharacterProperties::from_xml_element_with_context(child_node, context),
+                            position,
+                            child_node,
+                            context,
+                        )?
+                        .map(Box::new)
+                }
+                "t" => text = Some(child_node.text.clone().unwrap_or_default()),
+                _ => unknown.push_element(position, child_node),
             }
         }
 
-        let text = text.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "t"))?;
-        Ok(Self { char_properties, text })
+        let text = text.ok_or_else(|| MissingChildNodeError::at(xml_node, "t"))?;
+        unknown.push_all_attributes(xml_node);
+
+        Ok(Self {
+            char_properties,
+            text,
+            unknown,
+        })
+    }
+}
+
+impl ToXmlElement for RegularTextRun {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let mut node = XmlNode::new_with_conventional_namespace(node_name);
+        node.child_nodes
+            .extend(self.char_properties.as_ref().map(|props| props.to_xml_element("a:rPr")));
+
+        let mut text_node = dml_element("t");
+        text_node.text = Some(self.text.clone());
+        node.child_nodes.push(text_node);
+        self.unknown.apply_to(&mut node);
+        node
     }
 }
 
@@ -351,22 +427,28 @@ pub enum TextUnderlineLine {
 }
 
 impl XsdType for TextUnderlineLine {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "uLnTx" => Ok(TextUnderlineLine::FollowText),
-            "uLn" => Ok(TextUnderlineLine::Line(Box::new(LineProperties::from_xml_element(
-                xml_node,
-            )?))),
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextUnderlineLine").into()),
+            "uLn" => Ok(TextUnderlineLine::Line(Box::new(
+                LineProperties::from_xml_element_with_context(xml_node, context)?,
+            ))),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextUnderlineLine").into()),
         }
     }
 }
 
 impl XsdChoice for TextUnderlineLine {
     fn is_choice_member<T: AsRef<str>>(name: T) -> bool {
-        match name.as_ref() {
-            "uLnTx" | "uLn" => true,
-            _ => false,
+        matches!(name.as_ref(), "uLnTx" | "uLn")
+    }
+}
+
+impl ToXmlChoice for TextUnderlineLine {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextUnderlineLine::FollowText => dml_element("uLnTx"),
+            TextUnderlineLine::Line(line) => line.to_xml_element("a:uLn"),
         }
     }
 }
@@ -425,28 +507,40 @@ pub enum TextUnderlineFill {
     Fill(FillProperties),
 }
 
-impl TextUnderlineFill {
-    pub fn is_choice_member(name: &str) -> bool {
-        match name {
-            "uFillTx" | "uFill" => true,
-            _ => false,
-        }
-    }
-
-    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        match xml_node.local_name() {
+impl XsdType for TextUnderlineFill {
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self> {
+        match xml_node.dml_local_name() {
             "uFillTx" => Ok(TextUnderlineFill::FollowText),
             "uFill" => {
                 let fill_properties = xml_node
                     .child_nodes
                     .iter()
-                    .find_map(FillProperties::try_from_xml_element)
+                    .find_map(|child_node| FillProperties::try_from_xml_element_with_context(child_node, context))
                     .transpose()?
-                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_FillProperties"))?;
+                    .ok_or_else(|| MissingChildNodeError::at(xml_node, "EG_FillProperties"))?;
 
                 Ok(TextUnderlineFill::Fill(fill_properties))
             }
-            _ => Err(NotGroupMemberError::new(xml_node.name.clone(), "EG_TextUnderlineFill").into()),
+            _ => Err(NotGroupMemberError::at(xml_node, "EG_TextUnderlineFill").into()),
+        }
+    }
+}
+
+impl TextUnderlineFill {
+    pub fn is_choice_member(name: &str) -> bool {
+        matches!(name, "uFillTx" | "uFill")
+    }
+}
+
+impl ToXmlChoice for TextUnderlineFill {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TextUnderlineFill::FollowText => dml_element("uFillTx"),
+            TextUnderlineFill::Fill(fill) => {
+                let mut node = dml_element("uFill");
+                node.child_nodes.push(fill.to_xml_element());
+                node
+            }
         }
     }
 }
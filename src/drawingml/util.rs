@@ -1,5 +1,9 @@
-use crate::{error::MissingAttributeError, xml::XmlNode};
-use std::{error::Error, str::FromStr};
+use crate::{
+    error::{self, InvalidAttributeValueError, MissingAttributeError},
+    xml::{ns, XmlNode},
+    xsdtypes::{ExtensibleEnum, ParseContext},
+};
+use std::{borrow::Cow, error::Error, fmt::Display, str::FromStr};
 
 pub(crate) trait XmlNodeExt {
     // It's a common pattern throughout the OpenOffice XML file format that a simple type is wrapped in a complex type
@@ -7,14 +11,46 @@ pub(crate) trait XmlNodeExt {
     // complex types
     fn get_val_attribute(&self) -> Result<&String, MissingAttributeError>;
 
-    fn parse_val_attribute<T>(&self) -> Result<T, Box<dyn Error>>
+    fn parse_val_attribute<T>(&self) -> error::Result<T>
     where
         T: FromStr,
-        T::Err: Error + 'static,
+        T::Err: Into<Box<dyn Error + Send + Sync>>;
+
+    // Same as `parse_attribute_value`, except that values unknown to the crate are parsed as the `Unknown` variant if
+    // `context` accepts unknown values
+    fn parse_extensible_attribute_value<T>(&self, attr: &str, value: &str, context: &ParseContext) -> error::Result<T>
+    where
+        T: ExtensibleEnum,
+        T::Err: Into<Box<dyn Error + Send + Sync>>;
+
+    fn parse_extensible_val_attribute<T>(&self, context: &ParseContext) -> error::Result<T>
+    where
+        T: ExtensibleEnum,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
     {
-        self.get_val_attribute()
-            .map_err(Box::<dyn Error>::from)
-            .and_then(|value| value.parse().map_err(Box::from))
+        self.parse_extensible_attribute_value("val", self.get_val_attribute()?, context)
+    }
+
+    // Returns the local name of the node if it's in the DrawingML main namespace or an empty string otherwise, so
+    // that foreign elements fall through to the default arm when matched
+    fn dml_local_name(&self) -> &str;
+
+    // Returns the attribute name as `r:<local name>` if it's in the relationships namespace, regardless of the prefix
+    // used in the document
+    fn relationship_attr_name<'a>(&self, attr: &'a str) -> Cow<'a, str>;
+
+    // Sets an attribute. If the attribute is prefixed, it's bound to the namespace conventionally used with the
+    // prefix, so the namespace gets declared when the node is written
+    fn set_attribute<T: Display>(&mut self, attr: &str, value: T);
+
+    fn set_optional_attribute<T: Display>(&mut self, attr: &str, value: Option<T>) {
+        if let Some(value) = value {
+            self.set_attribute(attr, value);
+        }
+    }
+
+    fn set_val_attribute<T: Display>(&mut self, value: T) {
+        self.set_attribute("val", value);
     }
 }
 
@@ -22,6 +58,56 @@ impl XmlNodeExt for XmlNode {
     fn get_val_attribute(&self) -> Result<&String, MissingAttributeError> {
         self.attributes
             .get("val")
-            .ok_or_else(|| MissingAttributeError::new(self.name.clone(), "val"))
+            .ok_or_else(|| MissingAttributeError::at(self, "val"))
+    }
+
+    fn parse_val_attribute<T>(&self) -> error::Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.parse_attribute_value("val", self.get_val_attribute()?)
     }
+
+    fn parse_extensible_attribute_value<T>(&self, attr: &str, value: &str, context: &ParseContext) -> error::Result<T>
+    where
+        T: ExtensibleEnum,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        T::parse_extensible(value, context)
+            .map_err(|err| InvalidAttributeValueError::new(self, attr, value, err).into())
+    }
+
+    fn dml_local_name(&self) -> &str {
+        self.local_name_in(ns::DRAWINGML).unwrap_or_default()
+    }
+
+    fn relationship_attr_name<'a>(&self, attr: &'a str) -> Cow<'a, str> {
+        match (attr.find(':'), self.attribute_namespace(attr)) {
+            (Some(idx), Some(uri)) if ns::is_same_namespace(uri, ns::RELATIONSHIPS) => {
+                Cow::Owned(format!("r:{}", &attr[idx + 1..]))
+            }
+            _ => Cow::Borrowed(attr),
+        }
+    }
+
+    fn set_attribute<T: Display>(&mut self, attr: &str, value: T) {
+        if let Some(uri) = attr.find(':').and_then(|idx| ns::conventional_namespace(&attr[..idx])) {
+            self.attribute_namespaces.insert(String::from(attr), String::from(uri));
+        }
+
+        self.attributes.insert(String::from(attr), value.to_string());
+    }
+}
+
+// Creates a DrawingML element, e.g. `dml_element("solidFill")` creates an `a:solidFill` node
+pub(crate) fn dml_element(local_name: &str) -> XmlNode {
+    XmlNode::new_with_namespace(format!("a:{}", local_name), ns::DRAWINGML)
+}
+
+// Creates a DrawingML complex type element holding a single `val` attribute
+pub(crate) fn dml_val_element<T: Display>(local_name: &str, value: T) -> XmlNode {
+    let mut node = dml_element(local_name);
+    node.set_val_attribute(value);
+    node
 }
@@ -0,0 +1,511 @@
+//! Validation of the schema facets of DrawingML simple types.
+//!
+//! Most simple types of `simpletypes` are aliases of primitive types, so their facets (e.g. the range of a
+//! `FixedPercentage`) can't be enforced by the type system and aren't checked by the parsers either. The facets are
+//! defined along with the simple types as `SimpleType`s instead. `Validate` checks them on any model, whether it was
+//! parsed or built in code, by validating the elements the model is written to. So every violation is reported with
+//! the path of the element it would be written to.
+//!
+//! # Example
+//!
+//! ```
+//! use msoffice_shared::drawingml::{text::paragraphs::TextCharacterProperties, validation::Validate};
+//!
+//! let properties = TextCharacterProperties {
+//!     font_size: Some(50),
+//!     ..Default::default()
+//! };
+//! let violations = properties.validate("a:rPr");
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].attr, "sz");
+//! assert_eq!(violations[0].simple_type, "ST_TextFontSize");
+//! ```
+
+use super::simpletypes::{
+    ST_COORDINATE, ST_FIXED_ANGLE, ST_FIXED_PERCENTAGE, ST_HEX_COLOR_RGB, ST_LINE_WIDTH, ST_PANOSE,
+    ST_POSITIVE_COORDINATE, ST_POSITIVE_FIXED_ANGLE, ST_POSITIVE_FIXED_PERCENTAGE, ST_POSITIVE_PERCENTAGE,
+    ST_TEXT_BULLET_SIZE_PERCENT, ST_TEXT_BULLET_START_AT_NUM, ST_TEXT_COLUMN_COUNT, ST_TEXT_FONT_SCALE_PERCENT,
+    ST_TEXT_FONT_SIZE, ST_TEXT_INDENT, ST_TEXT_INDENT_LEVEL_TYPE, ST_TEXT_MARGIN, ST_TEXT_NON_NEGATIVE_POINT,
+    ST_TEXT_POINT, ST_TEXT_SPACING_PERCENT, ST_TEXT_SPACING_POINT,
+};
+use crate::{
+    xml::{location::ElementPath, ns, XmlNode},
+    xsdtypes::{ToXmlChoice, ToXmlElement},
+};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+/// A restriction of a simple type of the schema
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Facet {
+    /// The value shall be a number greater than or equal to the limit
+    MinInclusive(i64),
+    /// The value shall be a number greater than the limit
+    MinExclusive(i64),
+    /// The value shall be a number less than or equal to the limit
+    MaxInclusive(i64),
+    /// The value shall be a number less than the limit
+    MaxExclusive(i64),
+    /// The value shall be hex encoded binary data of the specified number of bytes
+    HexBinaryLength(usize),
+}
+
+impl Facet {
+    fn is_satisfied_by(self, value: &str) -> bool {
+        let is_number_satisfying = |predicate: &dyn Fn(f64) -> bool| match value.parse::<f64>() {
+            Ok(value) => predicate(value),
+            Err(_) => false,
+        };
+
+        match self {
+            Facet::MinInclusive(limit) => is_number_satisfying(&|value| value >= limit as f64),
+            Facet::MinExclusive(limit) => is_number_satisfying(&|value| value > limit as f64),
+            Facet::MaxInclusive(limit) => is_number_satisfying(&|value| value <= limit as f64),
+            Facet::MaxExclusive(limit) => is_number_satisfying(&|value| value < limit as f64),
+            Facet::HexBinaryLength(length) => value.len() == length * 2 && value.chars().all(|c| c.is_ascii_hexdigit()),
+        }
+    }
+}
+
+impl Display for Facet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            Facet::MinInclusive(limit) => write!(f, "n >= {}", limit),
+            Facet::MinExclusive(limit) => write!(f, "n > {}", limit),
+            Facet::MaxInclusive(limit) => write!(f, "n <= {}", limit),
+            Facet::MaxExclusive(limit) => write!(f, "n < {}", limit),
+            Facet::HexBinaryLength(length) => write!(f, "hex binary of {} bytes", length),
+        }
+    }
+}
+
+/// A simple type of the schema along with its facets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimpleType {
+    /// Name of the simple type in the schema, e.g. `ST_FixedPercentage`
+    pub name: &'static str,
+    pub facets: &'static [Facet],
+}
+
+impl SimpleType {
+    /// Returns the first facet `value` violates, if there's any
+    pub fn violated_facet(&self, value: &str) -> Option<Facet> {
+        self.facets.iter().copied().find(|facet| !facet.is_satisfied_by(value))
+    }
+}
+
+/// An attribute value that violates a facet of its simple type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetViolation {
+    /// Path of the element the attribute belongs to
+    pub path: ElementPath,
+    pub attr: String,
+    pub value: String,
+    /// Name of the simple type in the schema, e.g. `ST_FixedPercentage`
+    pub simple_type: &'static str,
+    pub facet: Facet,
+}
+
+impl Display for FacetViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        write!(
+            f,
+            "Value '{}' of attribute {} at {} violates the facet of {}: {}",
+            self.value, self.attr, self.path, self.simple_type, self.facet
+        )
+    }
+}
+
+/// Validates the schema facets of a model written to an element with a name chosen by the caller. Implemented for
+/// every type implementing `ToXmlElement`.
+pub trait Validate {
+    /// Returns every facet violation of the model written as the element `node_name`, in document order.
+    fn validate(&self, node_name: &str) -> Vec<FacetViolation>;
+
+    fn is_valid(&self, node_name: &str) -> bool {
+        self.validate(node_name).is_empty()
+    }
+}
+
+impl<T: ToXmlElement> Validate for T {
+    fn validate(&self, node_name: &str) -> Vec<FacetViolation> {
+        validate_xml_node(&self.to_xml_element(node_name))
+    }
+}
+
+/// Counterpart of `Validate` for choices, whose element name is determined by the choice member the value holds.
+/// Implemented for every type implementing `ToXmlChoice`.
+pub trait ValidateChoice {
+    /// Returns every facet violation of the model, in document order.
+    fn validate(&self) -> Vec<FacetViolation>;
+
+    fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+}
+
+impl<T: ToXmlChoice> ValidateChoice for T {
+    fn validate(&self) -> Vec<FacetViolation> {
+        validate_xml_node(&self.to_xml_element())
+    }
+}
+
+/// A simple type along with the attributes of DrawingML elements declared with the type
+struct AttributeRule {
+    simple_type: SimpleType,
+    /// Local names of the elements and their attributes
+    attributes: &'static [(&'static [&'static str], &'static str)],
+}
+
+const PARAGRAPH_PROPERTIES: &[&str] = &[
+    "pPr", "defPPr", "lvl1pPr", "lvl2pPr", "lvl3pPr", "lvl4pPr", "lvl5pPr", "lvl6pPr", "lvl7pPr", "lvl8pPr", "lvl9pPr",
+];
+const CHARACTER_PROPERTIES: &[&str] = &["rPr", "defRPr", "endParaRPr"];
+
+const RULES: &[AttributeRule] = &[
+    AttributeRule {
+        simple_type: ST_POSITIVE_PERCENTAGE,
+        attributes: &[
+            (&["alphaMod", "hueMod", "satMod", "lumMod"], "val"),
+            (&["alphaModFix"], "amt"),
+            (&["miter"], "lim"),
+            (&["ds"], "d"),
+            (&["ds"], "sp"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_POSITIVE_FIXED_PERCENTAGE,
+        attributes: &[
+            (&["tint", "shade", "alpha"], "val"),
+            (&["alphaBiLevel", "biLevel"], "thresh"),
+            (&["alphaRepl"], "a"),
+            (&["reflection"], "stA"),
+            (&["reflection"], "stPos"),
+            (&["reflection"], "endA"),
+            (&["reflection"], "endPos"),
+            (&["gs"], "pos"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_FIXED_PERCENTAGE,
+        attributes: &[
+            (&["alphaOff"], "val"),
+            (&["lum"], "bright"),
+            (&["lum"], "contrast"),
+            (&["hsl"], "sat"),
+            (&["hsl"], "lum"),
+            (&["tint"], "amt"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_HEX_COLOR_RGB,
+        attributes: &[(&["srgbClr"], "val"), (&["sysClr"], "lastClr")],
+    },
+    AttributeRule {
+        simple_type: ST_COORDINATE,
+        attributes: &[(&["off", "chOff"], "x"), (&["off", "chOff"], "y")],
+    },
+    AttributeRule {
+        simple_type: ST_POSITIVE_COORDINATE,
+        attributes: &[
+            (&["ext", "chExt"], "cx"),
+            (&["ext", "chExt"], "cy"),
+            (&["blur", "glow", "softEdge"], "rad"),
+            (&["innerShdw", "outerShdw", "reflection"], "blurRad"),
+            (&["innerShdw", "outerShdw", "prstShdw", "reflection"], "dist"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_POSITIVE_FIXED_ANGLE,
+        attributes: &[
+            (&["hue"], "val"),
+            (&["hslClr", "hsl", "tint"], "hue"),
+            (&["innerShdw", "outerShdw", "prstShdw", "reflection"], "dir"),
+            (&["reflection"], "fadeDir"),
+            (&["lin"], "ang"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_FIXED_ANGLE,
+        attributes: &[
+            (&["outerShdw", "reflection"], "kx"),
+            (&["outerShdw", "reflection"], "ky"),
+        ],
+    },
+    AttributeRule {
+        simple_type: ST_LINE_WIDTH,
+        attributes: &[(&["ln", "uLn"], "w")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_COLUMN_COUNT,
+        attributes: &[(&["bodyPr"], "numCol")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_FONT_SCALE_PERCENT,
+        attributes: &[(&["normAutofit"], "fontScale")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_SPACING_PERCENT,
+        attributes: &[(&["normAutofit"], "lnSpcReduction"), (&["spcPct"], "val")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_SPACING_POINT,
+        attributes: &[(&["spcPts"], "val")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_MARGIN,
+        attributes: &[(PARAGRAPH_PROPERTIES, "marL"), (PARAGRAPH_PROPERTIES, "marR")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_INDENT,
+        attributes: &[(PARAGRAPH_PROPERTIES, "indent")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_INDENT_LEVEL_TYPE,
+        attributes: &[(PARAGRAPH_PROPERTIES, "lvl")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_BULLET_SIZE_PERCENT,
+        attributes: &[(&["buSzPct"], "val")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_FONT_SIZE,
+        attributes: &[(CHARACTER_PROPERTIES, "sz"), (&["buSzPts"], "val")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_NON_NEGATIVE_POINT,
+        attributes: &[(CHARACTER_PROPERTIES, "kern")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_POINT,
+        attributes: &[(CHARACTER_PROPERTIES, "spc")],
+    },
+    AttributeRule {
+        simple_type: ST_TEXT_BULLET_START_AT_NUM,
+        attributes: &[(&["buAutoNum"], "startAt")],
+    },
+    AttributeRule {
+        simple_type: ST_PANOSE,
+        attributes: &[(&["latin", "ea", "cs", "sym", "buFont", "font"], "panose")],
+    },
+];
+
+/// Returns every facet violation in the DrawingML elements of `xml_node` and its descendants, in document order.
+pub fn validate_xml_node(xml_node: &XmlNode) -> Vec<FacetViolation> {
+    let mut violations = Vec::new();
+    validate_node(xml_node, ElementPath::root(xml_node.name.clone()), &mut violations);
+    violations
+}
+
+fn validate_node(xml_node: &XmlNode, path: ElementPath, violations: &mut Vec<FacetViolation>) {
+    if xml_node.is_in_namespace(ns::DRAWINGML) {
+        for rule in RULES {
+            for (element_names, attr) in rule.attributes {
+                if !element_names.contains(&xml_node.local_name()) {
+                    continue;
+                }
+
+                let value = match xml_node.attributes.get(*attr) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                if let Some(facet) = rule.simple_type.violated_facet(value) {
+                    violations.push(FacetViolation {
+                        path: path.clone(),
+                        attr: String::from(*attr),
+                        value: value.clone(),
+                        simple_type: rule.simple_type.name,
+                        facet,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for child_node in &xml_node.child_nodes {
+        let position = positions.entry(child_node.name.as_str()).or_insert(0);
+        *position += 1;
+        validate_node(child_node, path.child(child_node.name.clone(), *position), violations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawingml::{
+        colors::{Color, SRgbColor},
+        coordsys::{Point2D, PositiveSize2D, Transform2D},
+        text::{bodyformatting::TextBodyProperties, paragraphs::TextParagraphProperties},
+    };
+    use crate::xsdtypes::XsdType;
+    use std::str::FromStr;
+
+    fn summarize(violations: &[FacetViolation]) -> Vec<(String, &str, &str, Facet)> {
+        violations
+            .iter()
+            .map(|violation| {
+                (
+                    violation.path.to_string(),
+                    violation.value.as_str(),
+                    violation.simple_type,
+                    violation.facet,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_parsed_model() {
+        let xml_node = XmlNode::from_str(
+            r#"<a:srgbClr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" val="FF0000">
+                <a:alpha val="150000"/><a:hue val="21600000"/><a:hue val="21599999"/><a:alphaOff val="-100001"/>
+                <a:lumMod val="-1"/>
+            </a:srgbClr>"#,
+        )
+        .unwrap();
+        let color = Color::from_xml_element(&xml_node).unwrap();
+
+        assert_eq!(
+            summarize(&ValidateChoice::validate(&color)),
+            vec![
+                (
+                    String::from("a:srgbClr/a:alpha"),
+                    "150000",
+                    "ST_PositiveFixedPercentage",
+                    Facet::MaxInclusive(100_000)
+                ),
+                (
+                    String::from("a:srgbClr/a:hue"),
+                    "21600000",
+                    "ST_PositiveFixedAngle",
+                    Facet::MaxExclusive(21_600_000)
+                ),
+                (
+                    String::from("a:srgbClr/a:alphaOff"),
+                    "-100001",
+                    "ST_FixedPercentage",
+                    Facet::MinInclusive(-100_000)
+                ),
+                (
+                    String::from("a:srgbClr/a:lumMod"),
+                    "-1",
+                    "ST_PositivePercentage",
+                    Facet::MinInclusive(0)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_rgb_color() {
+        let color = SRgbColor {
+            value: 0x0100_0000,
+            color_transforms: Vec::new(),
+            unknown: Default::default(),
+        };
+        assert_eq!(
+            summarize(&color.validate("a:srgbClr")),
+            vec![(
+                String::from("a:srgbClr"),
+                "1000000",
+                "ST_HexColorRGB",
+                Facet::HexBinaryLength(3)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_validate_transform() {
+        let transform = Transform2D {
+            offset: Some(Point2D::new(-27_273_042_329_601, 27_273_042_316_900)),
+            extents: Some(PositiveSize2D::new(27_273_042_316_900, 27_273_042_316_901)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            summarize(&transform.validate("a:xfrm")),
+            vec![
+                (
+                    String::from("a:xfrm/a:off"),
+                    "-27273042329601",
+                    "ST_Coordinate",
+                    Facet::MinInclusive(-27_273_042_329_600)
+                ),
+                (
+                    String::from("a:xfrm/a:ext"),
+                    "27273042316901",
+                    "ST_PositiveCoordinate",
+                    Facet::MaxInclusive(27_273_042_316_900)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_text_properties() {
+        let paragraph_properties = TextParagraphProperties {
+            margin_left: Some(51_206_401),
+            indent: Some(-51_206_400),
+            level: Some(9),
+            ..Default::default()
+        };
+        assert_eq!(
+            summarize(&paragraph_properties.validate("a:pPr")),
+            vec![
+                (
+                    String::from("a:pPr"),
+                    "51206401",
+                    "ST_TextMargin",
+                    Facet::MaxInclusive(51_206_400)
+                ),
+                (
+                    String::from("a:pPr"),
+                    "9",
+                    "ST_TextIndentLevelType",
+                    Facet::MaxInclusive(8)
+                ),
+            ]
+        );
+
+        let body_properties = TextBodyProperties {
+            column_count: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            summarize(&body_properties.validate("a:bodyPr")),
+            vec![(
+                String::from("a:bodyPr"),
+                "0",
+                "ST_TextColumnCount",
+                Facet::MinInclusive(1)
+            )]
+        );
+        assert!(TextBodyProperties::default().is_valid("a:bodyPr"));
+    }
+
+    #[test]
+    fn test_facets() {
+        assert_eq!(ST_FIXED_ANGLE.violated_facet("-5399999"), None);
+        assert_eq!(
+            ST_FIXED_ANGLE.violated_facet("-5400000"),
+            Some(Facet::MinExclusive(-5_400_000))
+        );
+        assert_eq!(
+            ST_FIXED_ANGLE.violated_facet("5400000"),
+            Some(Facet::MaxExclusive(5_400_000))
+        );
+        assert_eq!(ST_HEX_COLOR_RGB.violated_facet("0a0B0c"), None);
+        assert_eq!(
+            ST_HEX_COLOR_RGB.violated_facet("0A0B0"),
+            Some(Facet::HexBinaryLength(3))
+        );
+        assert_eq!(
+            ST_TEXT_FONT_SIZE.violated_facet("large"),
+            Some(Facet::MinInclusive(100))
+        );
+    }
+}
@@ -1,14 +1,31 @@
+use crate::xml::{
+    limits::ParseLimit,
+    location::{ElementPath, SourceLocation},
+    XmlNode,
+};
 use std::{
-    error::Error,
-    fmt::{Display, Formatter, Result},
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    io,
     num::ParseIntError,
 };
+use zip::result::ZipError;
+
+// Appends the location of the element the error was reported for, if it's known
+fn write_location(f: &mut Formatter<'_>, location: &Option<SourceLocation>) -> fmt::Result {
+    match location {
+        Some(location) => write!(f, " at {}", location),
+        None => Ok(()),
+    }
+}
 
 /// An error indicating that an xml element doesn't have an attribute that's marked as required in the schema
 #[derive(Debug, Clone, PartialEq)]
 pub struct MissingAttributeError {
     pub node_name: String,
     pub attr: &'static str,
+    /// Location of the element, if it was parsed by this crate
+    pub location: Option<SourceLocation>,
 }
 
 impl MissingAttributeError {
@@ -19,21 +36,31 @@ impl MissingAttributeError {
         Self {
             node_name: node_name.into(),
             attr,
+            location: None,
+        }
+    }
+
+    /// Creates the error for `node`, including its location
+    pub fn at(node: &XmlNode, attr: &'static str) -> Self {
+        Self {
+            location: node.location.clone(),
+            ..Self::new(node.name.as_str(), attr)
         }
     }
 }
 
 impl Display for MissingAttributeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "Xml element '{}' is missing a required attribute: {}",
             self.node_name, self.attr
-        )
+        )?;
+        write_location(f, &self.location)
     }
 }
 
-impl Error for MissingAttributeError {
+impl StdError for MissingAttributeError {
     fn description(&self) -> &str {
         "Missing required attribute"
     }
@@ -44,6 +71,8 @@ impl Error for MissingAttributeError {
 pub struct MissingChildNodeError {
     pub node_name: String,
     pub child_node: &'static str,
+    /// Location of the element, if it was parsed by this crate
+    pub location: Option<SourceLocation>,
 }
 
 impl MissingChildNodeError {
@@ -54,21 +83,31 @@ impl MissingChildNodeError {
         Self {
             node_name: node_name.into(),
             child_node,
+            location: None,
+        }
+    }
+
+    /// Creates the error for `node`, including its location
+    pub fn at(node: &XmlNode, child_node: &'static str) -> Self {
+        Self {
+            location: node.location.clone(),
+            ..Self::new(node.name.as_str(), child_node)
         }
     }
 }
 
 impl Display for MissingChildNodeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "Xml element '{}' is missing a required child element: {}",
             self.node_name, self.child_node
-        )
+        )?;
+        write_location(f, &self.location)
     }
 }
 
-impl Error for MissingChildNodeError {
+impl StdError for MissingChildNodeError {
     fn description(&self) -> &str {
         "Xml element missing required child element"
     }
@@ -79,6 +118,7 @@ impl Error for MissingChildNodeError {
 pub struct NotGroupMemberError {
     node_name: String,
     group: &'static str,
+    location: Option<SourceLocation>,
 }
 
 impl NotGroupMemberError {
@@ -89,21 +129,36 @@ impl NotGroupMemberError {
         Self {
             node_name: node_name.into(),
             group,
+            location: None,
+        }
+    }
+
+    /// Creates the error for `node`, including its location
+    pub fn at(node: &XmlNode, group: &'static str) -> Self {
+        Self {
+            location: node.location.clone(),
+            ..Self::new(node.name.as_str(), group)
         }
     }
+
+    /// Location of the element, if it was parsed by this crate
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
 }
 
 impl Display for NotGroupMemberError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "XmlNode '{}' is not a member of {} group",
             self.node_name, self.group
-        )
+        )?;
+        write_location(f, &self.location)
     }
 }
 
-impl Error for NotGroupMemberError {
+impl StdError for NotGroupMemberError {
     fn description(&self) -> &str {
         "Xml element is not a group member error"
     }
@@ -116,7 +171,7 @@ pub enum MaxOccurs {
 }
 
 impl Display for MaxOccurs {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             MaxOccurs::Value(val) => write!(f, "{}", val),
             MaxOccurs::Unbounded => write!(f, "unbounded"),
@@ -132,6 +187,7 @@ pub struct LimitViolationError {
     min_occurs: u32,
     max_occurs: MaxOccurs,
     occurs: u32,
+    location: Option<SourceLocation>,
 }
 
 impl LimitViolationError {
@@ -151,42 +207,290 @@ impl LimitViolationError {
             min_occurs,
             max_occurs,
             occurs,
+            location: None,
+        }
+    }
+
+    /// Creates the error for `node`, including its location
+    pub fn at(
+        node: &XmlNode,
+        violating_node_name: &'static str,
+        min_occurs: u32,
+        max_occurs: MaxOccurs,
+        occurs: u32,
+    ) -> Self {
+        LimitViolationError {
+            location: node.location.clone(),
+            ..Self::new(node.name.as_str(), violating_node_name, min_occurs, max_occurs, occurs)
         }
     }
+
+    /// Location of the element, if it was parsed by this crate
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
 }
 
 impl Display for LimitViolationError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "Element {} violates the limits of occurance in element: {}. minOccurs: {}, maxOccurs: {}, occurance: {}",
             self.node_name, self.violating_node_name, self.min_occurs, self.max_occurs, self.occurs,
-        )
+        )?;
+        write_location(f, &self.location)
     }
 }
 
-impl Error for LimitViolationError {
+impl StdError for LimitViolationError {
     fn description(&self) -> &str {
         "Occurance limit violation"
     }
 }
 
+/// The reason an xml document couldn't be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidXmlErrorKind {
+    /// The document is not well-formed. Contains the description of the problem
+    Syntax(String),
+    /// A name, attribute value or text is not valid UTF-8
+    InvalidUtf8,
+    /// An attribute value or text contains an invalid character or entity reference. Contains the description of the
+    /// problem
+    InvalidEscape(String),
+    /// The document ends before its root element is closed
+    UnexpectedEof,
+    /// An end tag is found without a matching start tag
+    UnexpectedEndTag,
+    /// Something other than whitespace, comments and processing instructions follows the root element
+    ContentAfterRoot,
+    /// Reading the document from the underlying reader failed. Contains the description of the io error
+    Io(String),
+    /// One of the configured `ParseLimits` is exceeded
+    LimitExceeded(ParseLimit),
+}
+
+impl Display for InvalidXmlErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidXmlErrorKind::Syntax(description) => write!(f, "{}", description),
+            InvalidXmlErrorKind::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            InvalidXmlErrorKind::InvalidEscape(description) => write!(f, "{}", description),
+            InvalidXmlErrorKind::UnexpectedEof => write!(f, "unexpected end of document"),
+            InvalidXmlErrorKind::UnexpectedEndTag => write!(f, "end tag without a matching start tag"),
+            InvalidXmlErrorKind::ContentAfterRoot => write!(f, "content after the root element"),
+            InvalidXmlErrorKind::Io(description) => write!(f, "I/O error: {}", description),
+            InvalidXmlErrorKind::LimitExceeded(limit) => write!(f, "{}", limit),
+        }
+    }
+}
+
+impl From<quick_xml::Error> for InvalidXmlErrorKind {
+    fn from(err: quick_xml::Error) -> Self {
+        match err {
+            quick_xml::Error::Io(err) => InvalidXmlErrorKind::Io(err.to_string()),
+            quick_xml::Error::Utf8(_) => InvalidXmlErrorKind::InvalidUtf8,
+            quick_xml::Error::UnexpectedEof(_) => InvalidXmlErrorKind::UnexpectedEof,
+            quick_xml::Error::EscapeError(err) => InvalidXmlErrorKind::InvalidEscape(err.to_string()),
+            err => InvalidXmlErrorKind::Syntax(err.to_string()),
+        }
+    }
+}
+
 /// An error indicating that the parsed xml document is invalid
-#[derive(Debug, Clone, Copy, Default)]
-pub struct InvalidXmlError {}
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidXmlError {
+    pub kind: InvalidXmlErrorKind,
+    /// Position of the reader when the error occured
+    pub location: Option<SourceLocation>,
+}
+
+impl InvalidXmlError {
+    pub fn new(kind: InvalidXmlErrorKind, location: Option<SourceLocation>) -> Self {
+        Self { kind, location }
+    }
+}
 
 impl Display for InvalidXmlError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "Invalid xml document")
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid xml document: {}", self.kind)?;
+        write_location(f, &self.location)
     }
 }
 
-impl Error for InvalidXmlError {
+impl StdError for InvalidXmlError {
     fn description(&self) -> &str {
         "Invalid xml error"
     }
 }
 
+/// An error indicating that the value of an xml element's attribute couldn't be parsed as the type required by the
+/// schema. The error returned by the parser is available as the `source` of this error.
+#[derive(Debug)]
+pub struct InvalidAttributeValueError {
+    pub node_name: String,
+    pub attr: String,
+    pub value: String,
+    /// Location of the element, if it was parsed by this crate
+    pub location: Option<SourceLocation>,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl InvalidAttributeValueError {
+    pub fn new<T, U, E>(node: &XmlNode, attr: T, value: U, source: E) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        Self {
+            node_name: node.name.clone(),
+            attr: attr.into(),
+            value: value.into(),
+            location: node.location.clone(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Display for InvalidAttributeValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Xml element '{}' has an invalid value for attribute {}: '{}' ({})",
+            self.node_name, self.attr, self.value, self.source
+        )?;
+        write_location(f, &self.location)
+    }
+}
+
+impl StdError for InvalidAttributeValueError {
+    fn description(&self) -> &str {
+        "Invalid attribute value"
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// An error indicating that the text content of an xml element couldn't be parsed as the type required by the schema.
+/// The error returned by the parser is available as the `source` of this error.
+#[derive(Debug)]
+pub struct InvalidTextValueError {
+    pub node_name: String,
+    pub value: String,
+    /// Location of the element, if it was parsed by this crate
+    pub location: Option<SourceLocation>,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl InvalidTextValueError {
+    pub fn new<T, E>(node: &XmlNode, value: T, source: E) -> Self
+    where
+        T: Into<String>,
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        Self {
+            node_name: node.name.clone(),
+            value: value.into(),
+            location: node.location.clone(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Display for InvalidTextValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Xml element '{}' has an invalid text value: '{}' ({})",
+            self.node_name, self.value, self.source
+        )?;
+        write_location(f, &self.location)
+    }
+}
+
+impl StdError for InvalidTextValueError {
+    fn description(&self) -> &str {
+        "Invalid text value"
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// The reason markup compatibility processing has failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupCompatibilityErrorKind {
+    /// A namespace listed in `mc:MustUnderstand` is not understood. Contains the namespace URI
+    NotUnderstood(String),
+    /// A prefix listed in one of the markup compatibility attributes is not declared
+    UndeclaredPrefix(String),
+    /// `mc:AlternateContent` contains something other than `mc:Choice` elements followed by an optional
+    /// `mc:Fallback`, or a `mc:Choice` is missing its `Requires` attribute
+    InvalidAlternateContent,
+    /// `mc:Choice` or `mc:Fallback` appears outside of `mc:AlternateContent`
+    UnexpectedElement,
+    /// The root element was removed or replaced by more than one element
+    InvalidRootElement,
+}
+
+/// An error indicating that a document violates the rules of markup compatibility (ECMA-376 Part 3), or requires a
+/// namespace the consumer doesn't understand
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupCompatibilityError {
+    pub kind: MarkupCompatibilityErrorKind,
+    /// Name of the element the error was found in, if it's related to an element
+    pub node_name: Option<String>,
+    /// Location of the element, if it was parsed by this crate
+    pub location: Option<SourceLocation>,
+}
+
+impl MarkupCompatibilityError {
+    pub fn new(kind: MarkupCompatibilityErrorKind, node: Option<&XmlNode>) -> Self {
+        Self {
+            kind,
+            node_name: node.map(|node| node.name.clone()),
+            location: node.and_then(|node| node.location.clone()),
+        }
+    }
+}
+
+impl Display for MarkupCompatibilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MarkupCompatibilityErrorKind::NotUnderstood(namespace) => {
+                write!(f, "Namespace must be understood: {}", namespace)?
+            }
+            MarkupCompatibilityErrorKind::UndeclaredPrefix(prefix) => {
+                write!(f, "Undeclared prefix in markup compatibility attribute: {}", prefix)?
+            }
+            MarkupCompatibilityErrorKind::InvalidAlternateContent => write!(f, "Invalid mc:AlternateContent")?,
+            MarkupCompatibilityErrorKind::UnexpectedElement => {
+                write!(f, "Markup compatibility element outside of mc:AlternateContent")?
+            }
+            MarkupCompatibilityErrorKind::InvalidRootElement => write!(
+                f,
+                "Markup compatibility processing doesn't result in a single root element"
+            )?,
+        }
+
+        if let Some(node_name) = &self.node_name {
+            write!(f, " in element '{}'", node_name)?;
+        }
+
+        write_location(f, &self.location)
+    }
+}
+
+impl StdError for MarkupCompatibilityError {
+    fn description(&self) -> &str {
+        "Markup compatibility error"
+    }
+}
+
 /// Error indicating that an xml element's attribute is not a valid bool value
 /// Valid bool values are: true, false, 0, 1
 #[derive(Debug, Clone, PartialEq)]
@@ -206,17 +510,44 @@ impl ParseBoolError {
 }
 
 impl Display for ParseBoolError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "Xml attribute is not a valid bool value: {}", self.attr_value)
     }
 }
 
-impl Error for ParseBoolError {
+impl StdError for ParseBoolError {
     fn description(&self) -> &str {
         "Xml attribute is not a valid bool value"
     }
 }
 
+/// Error indicating that a string is not a valid W3CDTF date-time, the format of the dates of the core properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDateTimeError {
+    pub value: String,
+}
+
+impl ParseDateTimeError {
+    pub fn new<T>(value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self { value: value.into() }
+    }
+}
+
+impl Display for ParseDateTimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "String is not a valid W3CDTF date-time: {}", self.value)
+    }
+}
+
+impl StdError for ParseDateTimeError {
+    fn description(&self) -> &str {
+        "String is not a valid W3CDTF date-time"
+    }
+}
+
 /// Error indicating that a string cannot be converted to an enum type
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ParseEnumError {
@@ -230,12 +561,12 @@ impl ParseEnumError {
 }
 
 impl Display for ParseEnumError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "Cannot convert string to {}", self.enum_name)
     }
 }
 
-impl Error for ParseEnumError {
+impl StdError for ParseEnumError {
     fn description(&self) -> &str {
         "Cannot convert string to enum"
     }
@@ -246,12 +577,12 @@ impl Error for ParseEnumError {
 pub struct AdjustParseError {}
 
 impl Display for AdjustParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "AdjCoordinate or AdjAngle parse error")
     }
 }
 
-impl Error for AdjustParseError {
+impl StdError for AdjustParseError {
     fn description(&self) -> &str {
         "Adjust parse error"
     }
@@ -265,7 +596,7 @@ pub enum ParseHexColorRGBError {
 }
 
 impl Display for ParseHexColorRGBError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match *self {
             ParseHexColorRGBError::Parse(ref err) => err.fmt(f),
             ParseHexColorRGBError::InvalidLength(ref mismatch) => write!(
@@ -283,7 +614,7 @@ impl From<ParseIntError> for ParseHexColorRGBError {
     }
 }
 
-impl Error for ParseHexColorRGBError {}
+impl StdError for ParseHexColorRGBError {}
 
 /// Struct used to describe invalid length errors
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -298,9 +629,220 @@ pub enum PatternRestrictionError {
 }
 
 impl Display for PatternRestrictionError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "string doesn't match pattern")
     }
 }
 
-impl Error for PatternRestrictionError {}
+impl StdError for PatternRestrictionError {}
+
+/// The rule of the Open Packaging Conventions a part name breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPartNameReason {
+    Empty,
+    /// The part name contains an empty segment, e.g. `/ppt//slide1.xml`, or ends with a `/`
+    EmptySegment,
+    /// A segment of the part name ends with a dot, which includes the `.` and `..` segments
+    SegmentEndsWithDot,
+    /// The part name contains a character that's not allowed in a URI path segment
+    InvalidCharacter(char),
+    /// The part name contains a percent-encoded `/` or `\`, or an invalid percent-encoding
+    InvalidPercentEncoding,
+    /// The part name is reserved for a part the package manages itself, e.g. a relationship part
+    Reserved,
+    /// Part names are compared case-insensitively, and the package already has a part with this name
+    Duplicate,
+    /// The part name is the prefix of another part name, e.g. `/ppt` and `/ppt/presentation.xml`
+    Prefix,
+    /// The part name is the name of a relationship part, but the package doesn't have its source part
+    MissingSourcePart,
+    /// The target of a relationship that must point to a part is external, or doesn't resolve to a valid part name
+    InvalidRelationshipTarget,
+}
+
+/// Error indicating that a part can't be added to a package because of its name
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidPartNameError {
+    pub part_name: String,
+    pub reason: InvalidPartNameReason,
+}
+
+impl InvalidPartNameError {
+    pub fn new<T: Into<String>>(part_name: T, reason: InvalidPartNameReason) -> Self {
+        Self {
+            part_name: part_name.into(),
+            reason,
+        }
+    }
+}
+
+impl Display for InvalidPartNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid part name '{}': ", self.part_name)?;
+        match self.reason {
+            InvalidPartNameReason::Empty => write!(f, "part name is empty"),
+            InvalidPartNameReason::EmptySegment => write!(f, "part name contains an empty segment"),
+            InvalidPartNameReason::SegmentEndsWithDot => write!(f, "segment ends with a dot"),
+            InvalidPartNameReason::InvalidCharacter(c) => write!(f, "character '{}' is not allowed", c),
+            InvalidPartNameReason::InvalidPercentEncoding => write!(f, "invalid percent-encoding"),
+            InvalidPartNameReason::Reserved => write!(f, "part name is reserved"),
+            InvalidPartNameReason::Duplicate => write!(f, "package already has a part with the same name"),
+            InvalidPartNameReason::Prefix => write!(f, "part name is the prefix of another part name"),
+            InvalidPartNameReason::MissingSourcePart => write!(f, "source part of the relationship part is missing"),
+            InvalidPartNameReason::InvalidRelationshipTarget => write!(f, "relationship doesn't target a part"),
+        }
+    }
+}
+
+impl StdError for InvalidPartNameError {
+    fn description(&self) -> &str {
+        "Invalid part name"
+    }
+}
+
+/// The error type of every parser in the crate. It is `Send` and `Sync`, so it can be sent across threads and
+/// converted to `Box<dyn std::error::Error + Send + Sync>`.
+///
+/// Each variant wraps one of the error types above, so callers can `match` on the kind of the error instead of
+/// downcasting. Errors reported for an element carry its location, including its path, which is available through
+/// `location` and `path` regardless of the variant. Value errors (e.g. `ParseBool`) are reported as
+/// `InvalidAttributeValue` when they're found while parsing an element, so they also carry the element's location.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    MissingAttribute(Box<MissingAttributeError>),
+    MissingChildNode(Box<MissingChildNodeError>),
+    NotGroupMember(Box<NotGroupMemberError>),
+    LimitViolation(Box<LimitViolationError>),
+    InvalidAttributeValue(Box<InvalidAttributeValueError>),
+    InvalidTextValue(Box<InvalidTextValueError>),
+    MarkupCompatibility(Box<MarkupCompatibilityError>),
+    ParseBool(Box<ParseBoolError>),
+    ParseEnum(ParseEnumError),
+    AdjustParse(AdjustParseError),
+    ParseHexColorRGB(Box<ParseHexColorRGBError>),
+    PatternRestriction(PatternRestrictionError),
+    InvalidPartName(Box<InvalidPartNameError>),
+    /// The document is not well-formed or exceeds one of the configured `ParseLimits`
+    Xml(Box<InvalidXmlError>),
+    Io(io::Error),
+    Zip(ZipError),
+}
+
+/// Result type of every parser in the crate.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl Error {
+    /// Returns the location of the element the error was reported for, or the position of the reader for xml errors.
+    /// None if the error isn't related to an element, or the element wasn't parsed by this crate.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            Error::MissingAttribute(err) => err.location.as_ref(),
+            Error::MissingChildNode(err) => err.location.as_ref(),
+            Error::NotGroupMember(err) => err.location(),
+            Error::LimitViolation(err) => err.location(),
+            Error::InvalidAttributeValue(err) => err.location.as_ref(),
+            Error::InvalidTextValue(err) => err.location.as_ref(),
+            Error::MarkupCompatibility(err) => err.location.as_ref(),
+            Error::Xml(err) => err.location.as_ref(),
+            Error::ParseBool(_)
+            | Error::ParseEnum(_)
+            | Error::AdjustParse(_)
+            | Error::ParseHexColorRGB(_)
+            | Error::PatternRestriction(_)
+            | Error::InvalidPartName(_)
+            | Error::Io(_)
+            | Error::Zip(_) => None,
+        }
+    }
+
+    /// Returns the path of the element the error was reported for
+    pub fn path(&self) -> Option<&ElementPath> {
+        self.location().and_then(|location| location.path.as_ref())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingAttribute(err) => err.fmt(f),
+            Error::MissingChildNode(err) => err.fmt(f),
+            Error::NotGroupMember(err) => err.fmt(f),
+            Error::LimitViolation(err) => err.fmt(f),
+            Error::InvalidAttributeValue(err) => err.fmt(f),
+            Error::InvalidTextValue(err) => err.fmt(f),
+            Error::MarkupCompatibility(err) => err.fmt(f),
+            Error::ParseBool(err) => err.fmt(f),
+            Error::ParseEnum(err) => err.fmt(f),
+            Error::AdjustParse(err) => err.fmt(f),
+            Error::ParseHexColorRGB(err) => err.fmt(f),
+            Error::PatternRestriction(err) => err.fmt(f),
+            Error::InvalidPartName(err) => err.fmt(f),
+            Error::Xml(err) => err.fmt(f),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Zip(err) => write!(f, "Zip error: {}", err),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::InvalidAttributeValue(err) => err.source(),
+            Error::InvalidTextValue(err) => err.source(),
+            Error::Io(err) => Some(err),
+            Error::Zip(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_error {
+    ($($variant:ident($error:ty)),* $(,)?) => {
+        $(
+            impl From<$error> for Error {
+                fn from(err: $error) -> Self {
+                    Error::$variant(err.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_error!(
+    MissingAttribute(MissingAttributeError),
+    MissingChildNode(MissingChildNodeError),
+    NotGroupMember(NotGroupMemberError),
+    LimitViolation(LimitViolationError),
+    InvalidAttributeValue(InvalidAttributeValueError),
+    InvalidTextValue(InvalidTextValueError),
+    MarkupCompatibility(MarkupCompatibilityError),
+    ParseBool(ParseBoolError),
+    ParseEnum(ParseEnumError),
+    AdjustParse(AdjustParseError),
+    ParseHexColorRGB(ParseHexColorRGBError),
+    PatternRestriction(PatternRestrictionError),
+    InvalidPartName(InvalidPartNameError),
+    Xml(InvalidXmlError),
+    Io(io::Error),
+    Zip(ZipError),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_error_is_send_and_sync() {
+        let node = XmlNode::new("a:alpha");
+        let err = thread::spawn(move || -> Error {
+            InvalidAttributeValueError::new(&node, "val", "half", "half".parse::<i32>().unwrap_err()).into()
+        })
+        .join()
+        .unwrap();
+
+        let boxed: Box<dyn StdError + Send + Sync> = Box::new(err);
+        assert!(boxed.source().is_some());
+    }
+}
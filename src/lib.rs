@@ -1,8 +1,10 @@
 #![forbid(unsafe_code)]
 
+pub mod diagnostics;
 pub mod docprops;
 pub mod drawingml;
 pub mod error;
+pub mod opc;
 pub mod relationship;
 pub mod sharedtypes;
 pub mod update;
@@ -0,0 +1,302 @@
+//! The content types stream of a package, stored as `[Content_Types].xml`.
+
+use crate::{
+    error::{MissingAttributeError, Result},
+    xml::{ns, writer::WriteOptions, XmlNode},
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+/// Name of the content types stream in the archive
+pub const CONTENT_TYPES_PART_NAME: &str = "[Content_Types].xml";
+
+pub const RELATIONSHIPS_CONTENT_TYPE: &str = "application/vnd.openxmlformats-package.relationships+xml";
+pub const CORE_PROPERTIES_CONTENT_TYPE: &str = "application/vnd.openxmlformats-package.core-properties+xml";
+pub const EXTENDED_PROPERTIES_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.extended-properties+xml";
+pub const CUSTOM_PROPERTIES_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.custom-properties+xml";
+pub const THEME_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.theme+xml";
+pub const XML_CONTENT_TYPE: &str = "application/xml";
+
+/// Content types that `ContentTypes::from_parts` specifies with a default for their extension, the way Office does.
+/// Every other part gets an override.
+const WELL_KNOWN_DEFAULTS: &[(&str, &str)] = &[
+    ("bin", "application/vnd.openxmlformats-officedocument.oleObject"),
+    ("bmp", "image/bmp"),
+    ("emf", "image/x-emf"),
+    ("gif", "image/gif"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("png", "image/png"),
+    ("rels", RELATIONSHIPS_CONTENT_TYPE),
+    ("svg", "image/svg+xml"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("wdp", "image/vnd.ms-photo"),
+    ("wmf", "image/x-wmf"),
+    ("xml", XML_CONTENT_TYPE),
+];
+
+/// The content types of the parts of a package. The content type of a part is specified either by an override for its
+/// part name, or by a default for its extension.
+///
+/// Extensions and part names are compared case-insensitively (for ASCII characters). Defaults and overrides are kept
+/// sorted, so the same content types are always written the same way.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentTypes {
+    // Keyed by the ASCII lowercase extension
+    defaults: BTreeMap<String, String>,
+    // Keyed by the ASCII lowercase part name, which starts with a '/'. The original part name is kept for writing.
+    overrides: BTreeMap<String, (String, String)>,
+}
+
+impl ContentTypes {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "Default" => {
+                    let extension = child_node
+                        .attributes
+                        .get("Extension")
+                        .ok_or_else(|| MissingAttributeError::at(child_node, "Extension"))?;
+                    let content_type = child_node
+                        .attributes
+                        .get("ContentType")
+                        .ok_or_else(|| MissingAttributeError::at(child_node, "ContentType"))?;
+                    instance.add_default(extension.as_str(), content_type.as_str());
+                }
+                "Override" => {
+                    let part_name = child_node
+                        .attributes
+                        .get("PartName")
+                        .ok_or_else(|| MissingAttributeError::at(child_node, "PartName"))?;
+                    let content_type = child_node
+                        .attributes
+                        .get("ContentType")
+                        .ok_or_else(|| MissingAttributeError::at(child_node, "ContentType"))?;
+                    instance.add_override(part_name.as_str(), content_type.as_str());
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Creates the content types of a package consisting of `parts`, given as pairs of part name and content type.
+    ///
+    /// Parts with a well-known content type for their extension (e.g. relationship parts and images) are covered by a
+    /// default, every other part gets an override.
+    pub fn from_parts<I, T, U>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = (T, U)>,
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        let mut instance: Self = Default::default();
+
+        for (part_name, content_type) in parts {
+            let (part_name, content_type) = (part_name.as_ref(), content_type.as_ref());
+            if instance.content_type(part_name) == Some(content_type) {
+                continue;
+            }
+
+            let well_known_default = extension(part_name).filter(|extension| {
+                WELL_KNOWN_DEFAULTS.iter().any(|(known_extension, known_content_type)| {
+                    extension.eq_ignore_ascii_case(known_extension) && *known_content_type == content_type
+                })
+            });
+
+            match well_known_default {
+                Some(extension) if instance.default_content_type(extension).is_none() => {
+                    instance.add_default(extension, content_type)
+                }
+                _ => instance.add_override(part_name, content_type),
+            }
+        }
+
+        instance
+    }
+
+    /// Maps the extension `extension` to `content_type`, replacing the previous mapping
+    pub fn add_default<T, U>(&mut self, extension: T, content_type: U)
+    where
+        T: AsRef<str>,
+        U: Into<String>,
+    {
+        self.defaults
+            .insert(extension.as_ref().to_ascii_lowercase(), content_type.into());
+    }
+
+    /// Maps the part `part_name` to `content_type`, replacing the previous mapping
+    pub fn add_override<T, U>(&mut self, part_name: T, content_type: U)
+    where
+        T: AsRef<str>,
+        U: Into<String>,
+    {
+        let part_name = normalize_part_name(part_name.as_ref());
+        self.overrides
+            .insert(part_name.to_ascii_lowercase(), (part_name, content_type.into()));
+    }
+
+    /// Removes the default of `extension`, returning its content type
+    pub fn remove_default(&mut self, extension: &str) -> Option<String> {
+        self.defaults.remove(&extension.to_ascii_lowercase())
+    }
+
+    /// Removes the override of `part_name`, returning its content type
+    pub fn remove_override(&mut self, part_name: &str) -> Option<String> {
+        self.overrides
+            .remove(&normalize_part_name(part_name).to_ascii_lowercase())
+            .map(|(_, content_type)| content_type)
+    }
+
+    /// Defaults as pairs of ASCII lowercase extension and content type, sorted by extension
+    pub fn defaults(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.defaults
+            .iter()
+            .map(|(extension, content_type)| (extension.as_str(), content_type.as_str()))
+    }
+
+    /// Overrides as pairs of part name and content type, sorted by part name
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.overrides
+            .values()
+            .map(|(part_name, content_type)| (part_name.as_str(), content_type.as_str()))
+    }
+
+    pub fn default_content_type(&self, extension: &str) -> Option<&str> {
+        self.defaults.get(&extension.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn override_content_type(&self, part_name: &str) -> Option<&str> {
+        self.overrides
+            .get(&normalize_part_name(part_name).to_ascii_lowercase())
+            .map(|(_, content_type)| content_type.as_str())
+    }
+
+    /// Returns the content type of the part `part_name`. The override of the part takes precedence over the default of
+    /// its extension.
+    pub fn content_type(&self, part_name: &str) -> Option<&str> {
+        self.override_content_type(part_name)
+            .or_else(|| extension(part_name).and_then(|extension| self.default_content_type(extension)))
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace("Types", ns::CONTENT_TYPES);
+
+        for (extension, content_type) in self.defaults() {
+            let mut default_node = XmlNode::new_with_namespace("Default", ns::CONTENT_TYPES);
+            default_node
+                .attributes
+                .insert(String::from("Extension"), String::from(extension));
+            default_node
+                .attributes
+                .insert(String::from("ContentType"), String::from(content_type));
+            node.child_nodes.push(default_node);
+        }
+
+        for (part_name, content_type) in self.overrides() {
+            let mut override_node = XmlNode::new_with_namespace("Override", ns::CONTENT_TYPES);
+            override_node
+                .attributes
+                .insert(String::from("PartName"), String::from(part_name));
+            override_node
+                .attributes
+                .insert(String::from("ContentType"), String::from(content_type));
+            node.child_nodes.push(override_node);
+        }
+
+        node
+    }
+
+    pub fn write_xml<W: Write>(&self, writer: W, options: &WriteOptions) -> io::Result<()> {
+        self.to_xml_element().write_to(writer, options)
+    }
+}
+
+/// Part names in the content types stream start with a '/', unlike zip entry names
+fn normalize_part_name(part_name: &str) -> String {
+    format!("/{}", part_name.trim_start_matches('/'))
+}
+
+fn extension(part_name: &str) -> Option<&str> {
+    let file_name = &part_name[part_name.rfind('/').map_or(0, |idx| idx + 1)..];
+    file_name.rfind('.').map(|idx| &file_name[idx + 1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_content_types_round_trip() {
+        let xml = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+            <Default Extension="PNG" ContentType="image/png"/>
+            <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+            <Override PartName="/ppt/Presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/>
+        </Types>"#;
+        let content_types = ContentTypes::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(content_types.content_type("ppt/media/image1.png"), Some("image/png"));
+        assert_eq!(
+            content_types.content_type("/PPT/presentation.xml"),
+            Some("application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml")
+        );
+        assert_eq!(content_types.content_type("ppt/slides/slide1.xml"), None);
+
+        let written = content_types.to_xml_element();
+        assert_eq!(written.child_nodes[0].attributes["Extension"], "png");
+        assert_eq!(written.child_nodes[2].attributes["PartName"], "/ppt/Presentation.xml");
+        let reparsed = XmlNode::from_str(&written.to_xml_string(&WriteOptions::compact()).unwrap()).unwrap();
+        assert_eq!(ContentTypes::from_xml_element(&reparsed).unwrap(), content_types);
+    }
+
+    #[test]
+    fn test_content_types_from_parts() {
+        let content_types = ContentTypes::from_parts(vec![
+            ("_rels/.rels", RELATIONSHIPS_CONTENT_TYPE),
+            ("docProps/core.xml", CORE_PROPERTIES_CONTENT_TYPE),
+            ("ppt/media/image1.png", "image/png"),
+            ("ppt/media/image2.PNG", "image/png"),
+            ("ppt/theme/theme1.xml", THEME_CONTENT_TYPE),
+            ("ppt/_rels/presentation.xml.rels", RELATIONSHIPS_CONTENT_TYPE),
+        ]);
+
+        assert_eq!(
+            content_types.defaults().collect::<Vec<_>>(),
+            vec![("png", "image/png"), ("rels", RELATIONSHIPS_CONTENT_TYPE)]
+        );
+        assert_eq!(
+            content_types.overrides().collect::<Vec<_>>(),
+            vec![
+                ("/docProps/core.xml", CORE_PROPERTIES_CONTENT_TYPE),
+                ("/ppt/theme/theme1.xml", THEME_CONTENT_TYPE),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_content_types_non_ascii_case() {
+        let content_types = ContentTypes::from_parts(vec![
+            ("ppt/Ä.xml", THEME_CONTENT_TYPE),
+            ("ppt/ä.xml", CORE_PROPERTIES_CONTENT_TYPE),
+        ]);
+
+        assert_eq!(content_types.overrides().count(), 2);
+        assert_eq!(content_types.content_type("/PPT/Ä.xml"), Some(THEME_CONTENT_TYPE));
+        assert_eq!(
+            content_types.content_type("/ppt/ä.XML"),
+            Some(CORE_PROPERTIES_CONTENT_TYPE)
+        );
+    }
+}
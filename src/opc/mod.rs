@@ -0,0 +1,667 @@
+//! Open Packaging Conventions, the container format of every Office Open XML document.
+//!
+//! A package is a zip archive of parts. The parts are linked together by relationships, stored in relationship parts
+//! next to their source part (e.g. `ppt/_rels/presentation.xml.rels` holds the relationships of
+//! `ppt/presentation.xml`), while the relationships of the package itself are stored in `_rels/.rels`. The content
+//! type of every part is listed in `[Content_Types].xml`.
+//!
+//! Part names are accepted both in the form used by the OPC specification (e.g. `/ppt/presentation.xml`) and in the
+//! form used by the archive (e.g. `ppt/presentation.xml`). Like in the specification, part names are compared case
+//! insensitively (for ASCII characters), so `/PPT/Presentation.xml` names the same part.
+
+pub mod content_types;
+pub mod writer;
+
+use self::content_types::{ContentTypes, CONTENT_TYPES_PART_NAME};
+use crate::{
+    docprops::{AppInfo, Core, CustomProperties},
+    drawingml::sharedstylesheet::OfficeStyleSheet,
+    error::{Error, InvalidPartNameError, InvalidPartNameReason, InvalidXmlError, InvalidXmlErrorKind, Result},
+    relationship::{
+        relationships_from_xml_reader, relationships_from_zip_file_with_limits, relationships_part_name,
+        RelationshipType, Relationships,
+    },
+    xml::{
+        limits::{ParseLimit, ParseLimits},
+        mce::MarkupCompatibility,
+        reader::XmlReader,
+        sized_xml_reader, xml_reader_to_xsd_type, zip_file_to_xml_node_with_limits, XmlNode,
+    },
+    xsdtypes::{ParseContext, XsdType},
+};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    io::{self, BufReader, Read, Seek, Take},
+    str,
+    sync::Arc,
+};
+use zip::{read::ZipFile, result::ZipError, ZipArchive};
+
+/// Name of the part holding the relationships of the package
+pub const PACKAGE_RELATIONSHIPS_PART_NAME: &str = "_rels/.rels";
+
+/// The typed model of a part, parsed from the root element of the part. Models can be read from a package with
+/// `Package::part`.
+pub trait PartModel: XsdType + Any + Send + Sync {}
+
+impl PartModel for OfficeStyleSheet {}
+
+impl PartModel for Core {}
+
+impl PartModel for CustomProperties {}
+
+impl PartModel for AppInfo {}
+
+/// An Office Open XML package opened for reading.
+///
+/// The content types and the package relationships are read when the package is opened, everything else is read on
+/// demand. The `ParseLimits` of the package are enforced for every part read from it.
+///
+/// Markup compatibility constructs are kept in the parts by default, so they survive a round trip. Set a
+/// `MarkupCompatibility` with `set_markup_compatibility` to process them instead.
+///
+/// Typed models of parts are parsed with the `ParseContext` of the package, which is strict by default. Set a lenient
+/// context or one with another `ExtensionRegistry` with `set_parse_context`. The models are parsed on first access and
+/// cached, so later requests for the same model of the same part return the same instance. Replacing the content of a
+/// part with `replace_part` invalidates its cached models. `invalidate` drops them without replacing the part, e.g. to
+/// release the memory held by models that are no longer needed.
+pub struct Package<R: Read + Seek> {
+    archive: ZipArchive<R>,
+    // The names of the zip entries, keyed by their ASCII lowercase form
+    entry_names: HashMap<String, String>,
+    // Content of the parts replaced with `replace_part`, keyed by the name of their zip entry
+    replaced_parts: HashMap<String, Vec<u8>>,
+    content_types: ContentTypes,
+    relationships: Relationships,
+    limits: ParseLimits,
+    markup_compatibility: Option<MarkupCompatibility>,
+    context: ParseContext,
+    // Keyed by the name of the zip entry of the part and the type of the model
+    models: HashMap<(String, TypeId), Arc<dyn Any + Send + Sync>>,
+}
+
+impl<R: Read + Seek> Package<R> {
+    /// Opens the package stored in `reader` with the default `ParseLimits`
+    pub fn open(reader: R) -> Result<Self> {
+        Self::open_with_limits(reader, ParseLimits::default())
+    }
+
+    pub fn open_with_limits(reader: R, limits: ParseLimits) -> Result<Self> {
+        let mut archive = ZipArchive::new(reader)?;
+        let entry_names = archive
+            .file_names()
+            .map(|name| (name.to_ascii_lowercase(), String::from(name)))
+            .collect();
+
+        let content_types = {
+            let mut zip_file = archive.by_name(CONTENT_TYPES_PART_NAME)?;
+            ContentTypes::from_xml_element(&zip_file_to_xml_node_with_limits(&mut zip_file, &limits)?)?
+        };
+
+        let relationships = match archive.by_name(PACKAGE_RELATIONSHIPS_PART_NAME) {
+            Ok(mut zip_file) => relationships_from_zip_file_with_limits(&mut zip_file, &limits)?.into(),
+            Err(ZipError::FileNotFound) => Relationships::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            archive,
+            entry_names,
+            replaced_parts: HashMap::new(),
+            content_types,
+            relationships,
+            limits,
+            markup_compatibility: None,
+            context: ParseContext::new(),
+            models: HashMap::new(),
+        })
+    }
+
+    pub fn limits(&self) -> &ParseLimits {
+        &self.limits
+    }
+
+    pub fn markup_compatibility(&self) -> Option<&MarkupCompatibility> {
+        self.markup_compatibility.as_ref()
+    }
+
+    /// Sets the processor of the markup compatibility constructs of the xml parts read from now on, or None to keep
+    /// the constructs. The cached models are dropped, as they may have been parsed from differently processed parts.
+    pub fn set_markup_compatibility(&mut self, markup_compatibility: Option<MarkupCompatibility>) {
+        self.markup_compatibility = markup_compatibility;
+        self.invalidate_all();
+    }
+
+    pub fn parse_context(&self) -> &ParseContext {
+        &self.context
+    }
+
+    /// Gives access to the context the typed models are parsed with, e.g. to take the diagnostics collected in
+    /// lenient mode.
+    pub fn parse_context_mut(&mut self) -> &mut ParseContext {
+        &mut self.context
+    }
+
+    /// Sets the context the typed models of the parts are parsed with from now on. The cached models are dropped, as
+    /// they may have been parsed with different settings.
+    pub fn set_parse_context(&mut self, context: ParseContext) {
+        self.context = context;
+        self.invalidate_all();
+    }
+
+    pub fn content_types(&self) -> &ContentTypes {
+        &self.content_types
+    }
+
+    /// Returns the content type of the part `part_name`
+    pub fn content_type(&self, part_name: &str) -> Option<&str> {
+        self.content_types.content_type(part_name)
+    }
+
+    /// Creates the content types of the parts in the package from scratch, dropping the defaults and overrides that
+    /// don't apply to any part. Parts without a content type are left out.
+    pub fn regenerate_content_types(&self) -> ContentTypes {
+        let parts = self
+            .archive
+            .file_names()
+            .filter(|name| *name != CONTENT_TYPES_PART_NAME && !name.ends_with('/'))
+            .filter_map(|name| self.content_type(name).map(|content_type| (name, content_type)));
+
+        ContentTypes::from_parts(parts)
+    }
+
+    /// Relationships of the package, stored in `_rels/.rels`
+    pub fn relationships(&self) -> &Relationships {
+        &self.relationships
+    }
+
+    /// Names of every part in the archive, including the content types stream and the relationship parts, in the form
+    /// used by the archive.
+    pub fn part_names(&self) -> impl Iterator<Item = &str> {
+        self.archive.file_names()
+    }
+
+    pub fn has_part(&self, part_name: &str) -> bool {
+        resolve_entry_name(&self.entry_names, part_name).is_some()
+    }
+
+    /// Reads the content of the part `part_name`. Fails with `ZipError::FileNotFound` if there's no such part.
+    pub fn part_bytes(&mut self, part_name: &str) -> Result<Vec<u8>> {
+        let entry_name = resolve_entry_name(&self.entry_names, part_name).ok_or(ZipError::FileNotFound)?;
+        let part_content = open_part(&mut self.archive, &self.replaced_parts, entry_name)?;
+        let size = part_content.size();
+        self.limits.check_part_size(size).map_err(limit_exceeded)?;
+
+        let max_part_size = self.limits.max_part_size.unwrap_or(u64::MAX);
+        let mut bytes = Vec::with_capacity(size.min(max_part_size) as usize);
+        part_content
+            .take(max_part_size.saturating_add(1))
+            .read_to_end(&mut bytes)?;
+        self.limits
+            .check_part_size(bytes.len() as u64)
+            .map_err(limit_exceeded)?;
+
+        Ok(bytes)
+    }
+
+    /// Reads and parses the xml part `part_name`
+    pub fn part_xml(&mut self, part_name: &str) -> Result<XmlNode> {
+        let xml_node = self.part_xml_reader(part_name)?.read_root_node()?;
+        match &self.markup_compatibility {
+            Some(markup_compatibility) => Ok(markup_compatibility.process(xml_node)?),
+            None => Ok(xml_node),
+        }
+    }
+
+    /// Reads the relationships of the part `part_name`. A part without a relationship part has no relationships.
+    pub fn part_relationships(&mut self, part_name: &str) -> Result<Relationships> {
+        let relationships_part_name = relationships_part_name(part_name);
+        if !self.has_part(&relationships_part_name) {
+            return Ok(Relationships::new());
+        }
+
+        let mut reader = self.part_xml_reader(&relationships_part_name)?;
+        Ok(relationships_from_xml_reader(&mut reader)?.into())
+    }
+
+    /// Replaces the content of the part `part_name` with `bytes`, which is read instead of the content stored in the
+    /// archive from now on. The cached models of the part are invalidated. Replacing the content types stream or the
+    /// package relationships updates the ones returned by `content_types` and `relationships` as well.
+    ///
+    /// Fails with `ZipError::FileNotFound` if there's no such part, and with the parse error if the new content types
+    /// or package relationships are invalid.
+    pub fn replace_part(&mut self, part_name: &str, bytes: Vec<u8>) -> Result<()> {
+        let entry_name = String::from(resolve_entry_name(&self.entry_names, part_name).ok_or(ZipError::FileNotFound)?);
+        if entry_name == CONTENT_TYPES_PART_NAME {
+            let xml_node = sized_xml_reader(bytes.as_slice(), bytes.len() as u64, &self.limits)?.read_root_node()?;
+            self.content_types = ContentTypes::from_xml_element(&xml_node)?;
+        } else if entry_name == PACKAGE_RELATIONSHIPS_PART_NAME {
+            let mut reader = sized_xml_reader(bytes.as_slice(), bytes.len() as u64, &self.limits)?;
+            self.relationships = relationships_from_xml_reader(&mut reader)?.into();
+        }
+
+        self.invalidate(&entry_name);
+        self.replaced_parts.insert(entry_name, bytes);
+        Ok(())
+    }
+
+    /// Name of the main part of the document, e.g. `/ppt/presentation.xml` for a presentation, the target of the
+    /// office document relationship of the package.
+    pub fn main_part_name(&self) -> Option<String> {
+        self.package_relationship_target(&RelationshipType::OfficeDocument)
+    }
+
+    /// Returns the model of type `T` of the part `part_name`. The part is parsed on the first request with the
+    /// context of the package, later requests return the cached model until the part is replaced or invalidated.
+    ///
+    /// The part is streamed to `XsdType::from_xml_reader_with_context`, unless markup compatibility constructs are
+    /// processed, which needs the tree of the whole part. A failed parse is not cached.
+    pub fn part<T: PartModel>(&mut self, part_name: &str) -> Result<Arc<T>> {
+        let entry_name = resolve_entry_name(&self.entry_names, part_name).ok_or(ZipError::FileNotFound)?;
+        let key = (String::from(entry_name), TypeId::of::<T>());
+        if let Some(model) = self.models.get(&key) {
+            if let Ok(model) = Arc::clone(model).downcast::<T>() {
+                return Ok(model);
+            }
+        }
+
+        let model = if self.markup_compatibility.is_some() {
+            let xml_node = self.part_xml(&key.0)?;
+            T::from_xml_element_with_context(&xml_node, &mut self.context)?
+        } else {
+            let part_content = open_part(&mut self.archive, &self.replaced_parts, &key.0)?;
+            let size = part_content.size();
+            xml_reader_to_xsd_type(
+                &mut sized_xml_reader(part_content, size, &self.limits)?,
+                &mut self.context,
+            )?
+        };
+
+        let model = Arc::new(model);
+        self.models
+            .insert(key, Arc::clone(&model) as Arc<dyn Any + Send + Sync>);
+        Ok(model)
+    }
+
+    /// Returns true if a model of the part `part_name` is cached
+    pub fn is_cached(&self, part_name: &str) -> bool {
+        match resolve_entry_name(&self.entry_names, part_name) {
+            Some(entry_name) => self.models.keys().any(|(name, _)| name == entry_name),
+            None => false,
+        }
+    }
+
+    /// Drops every cached model of the part `part_name`, so it's parsed again on the next request. Models that were
+    /// already returned are not affected.
+    pub fn invalidate(&mut self, part_name: &str) {
+        if let Some(entry_name) = resolve_entry_name(&self.entry_names, part_name) {
+            self.models.retain(|(name, _), _| name != entry_name);
+        }
+    }
+
+    /// Drops every cached model
+    pub fn invalidate_all(&mut self) {
+        self.models.clear();
+    }
+
+    /// Returns the core properties of the package, or None if the package doesn't have any.
+    pub fn core_properties(&mut self) -> Result<Option<Arc<Core>>> {
+        match self.package_relationship_target(&RelationshipType::CoreProperties) {
+            Some(part_name) => self.part(&part_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the extended properties of the package, or None if the package doesn't have any.
+    pub fn app_properties(&mut self) -> Result<Option<Arc<AppInfo>>> {
+        match self.package_relationship_target(&RelationshipType::ExtendedProperties) {
+            Some(part_name) => self.part(&part_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the custom properties of the package, or None if the package doesn't have any.
+    pub fn custom_properties(&mut self) -> Result<Option<Arc<CustomProperties>>> {
+        match self.package_relationship_target(&RelationshipType::CustomProperties) {
+            Some(part_name) => self.part(&part_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the theme related to the main part of the document, or None if there's no such theme.
+    pub fn theme(&mut self) -> Result<Option<Arc<OfficeStyleSheet>>> {
+        let main_part_name = match self.main_part_name() {
+            Some(main_part_name) => main_part_name,
+            None => return Ok(None),
+        };
+
+        let theme_part_name = self
+            .part_relationships(&main_part_name)?
+            .iter()
+            .find(|relationship| relationship.has_type(&RelationshipType::Theme) && !relationship.is_external())
+            .and_then(|relationship| relationship.target_part_name(&main_part_name));
+
+        match theme_part_name {
+            Some(part_name) => self.part(&part_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // Creates a reader streaming the xml part `part_name`, enforcing the limits of the package
+    fn part_xml_reader(&mut self, part_name: &str) -> Result<XmlReader<Take<BufReader<PartContent<'_>>>>> {
+        let entry_name = resolve_entry_name(&self.entry_names, part_name).ok_or(ZipError::FileNotFound)?;
+        let part_content = open_part(&mut self.archive, &self.replaced_parts, entry_name)?;
+        let size = part_content.size();
+        sized_xml_reader(part_content, size, &self.limits)
+    }
+
+    fn package_relationship_target(&self, rel_type: &RelationshipType) -> Option<String> {
+        self.relationships
+            .iter()
+            .find(|relationship| relationship.has_type(rel_type) && !relationship.is_external())
+            .and_then(|relationship| relationship.target_part_name("/"))
+    }
+}
+
+// The content of a part, either stored in the archive or replaced with `Package::replace_part`
+enum PartContent<'a> {
+    Stored(Box<ZipFile<'a>>),
+    Replaced(&'a [u8]),
+}
+
+impl PartContent<'_> {
+    // Uncompressed size of the content
+    fn size(&self) -> u64 {
+        match self {
+            PartContent::Stored(zip_file) => zip_file.size(),
+            PartContent::Replaced(bytes) => bytes.len() as u64,
+        }
+    }
+}
+
+impl Read for PartContent<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            PartContent::Stored(zip_file) => zip_file.read(buf),
+            PartContent::Replaced(bytes) => bytes.read(buf),
+        }
+    }
+}
+
+// Opens the content of the zip entry `entry_name`, preferring the replaced content if there's any
+fn open_part<'a, R: Read + Seek>(
+    archive: &'a mut ZipArchive<R>,
+    replaced_parts: &'a HashMap<String, Vec<u8>>,
+    entry_name: &str,
+) -> Result<PartContent<'a>> {
+    match replaced_parts.get(entry_name) {
+        Some(bytes) => Ok(PartContent::Replaced(bytes)),
+        None => Ok(PartContent::Stored(Box::new(archive.by_name(entry_name)?))),
+    }
+}
+
+fn limit_exceeded(limit: ParseLimit) -> Error {
+    InvalidXmlError::new(InvalidXmlErrorKind::LimitExceeded(limit), None).into()
+}
+
+// Name of the zip entry of the part `part_name` in `entry_names`, matched case insensitively
+fn resolve_entry_name<'a>(entry_names: &'a HashMap<String, String>, part_name: &str) -> Option<&'a str> {
+    entry_names
+        .get(&entry_name(part_name).to_ascii_lowercase())
+        .map(String::as_str)
+}
+
+/// Name of the zip entry of a part
+pub(crate) fn entry_name(part_name: &str) -> &str {
+    part_name.trim_start_matches('/')
+}
+
+/// Checks that `part_name` is a valid part name, as specified by the Open Packaging Conventions, and returns it with a
+/// leading `/`. The leading `/` is optional in `part_name`.
+///
+/// Every segment of a part name must be non-empty, mustn't end with a dot and may only contain the characters allowed
+/// in a URI path segment (or non-ASCII characters). Percent-encoded `/` and `\` characters aren't allowed.
+pub fn validate_part_name(part_name: &str) -> ::std::result::Result<String, InvalidPartNameError> {
+    let invalid = |reason| Err(InvalidPartNameError::new(part_name, reason));
+    let name = entry_name(part_name);
+    if name.is_empty() {
+        return invalid(InvalidPartNameReason::Empty);
+    }
+
+    for segment in name.split('/') {
+        if segment.is_empty() {
+            return invalid(InvalidPartNameReason::EmptySegment);
+        }
+
+        if segment.ends_with('.') {
+            return invalid(InvalidPartNameReason::SegmentEndsWithDot);
+        }
+
+        let mut chars = segment.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '%' => {
+                    let encoded: String = chars.by_ref().take(2).collect();
+                    let decoded = u8::from_str_radix(&encoded, 16).ok().filter(|_| encoded.len() == 2);
+                    match decoded {
+                        Some(b'/') | Some(b'\\') | None => {
+                            return invalid(InvalidPartNameReason::InvalidPercentEncoding)
+                        }
+                        Some(_) => (),
+                    }
+                }
+                c if c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=:@".contains(c) => (),
+                c if !c.is_ascii() && !c.is_control() && !c.is_whitespace() => (),
+                c => return invalid(InvalidPartNameReason::InvalidCharacter(c)),
+            }
+        }
+    }
+
+    Ok(format!("/{}", name))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::drawingml::sharedstylesheet::tests::THEME_XML;
+    use crate::xml::ns;
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, ZipWriter};
+
+    const CONTENT_TYPES_XML: &str = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+        <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+        <Default Extension="xml" ContentType="application/xml"/>
+        <Default Extension="PNG" ContentType="image/png"/>
+        <Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/>
+        <Override PartName="/ppt/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>
+    </Types>"#;
+
+    const PACKAGE_RELS_XML: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+        <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+        <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+        <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="ppt/presentation.xml"/>
+    </Relationships>"#;
+
+    const PRESENTATION_RELS_XML: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+        <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="theme/../theme/theme1.xml"/>
+        <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/>
+    </Relationships>"#;
+
+    const CORE_XML: &str = r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>Title</dc:title><cp:revision>3</cp:revision>
+    </cp:coreProperties>"#;
+
+    const APP_XML: &str = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
+        <Application>Microsoft Office PowerPoint</Application><AppVersion>16.0000</AppVersion>
+    </Properties>"#;
+
+    pub(crate) fn build_package(parts: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn test_package() {
+        let reader = build_package(&[
+            ("[Content_Types].xml", CONTENT_TYPES_XML.as_bytes()),
+            ("_rels/.rels", PACKAGE_RELS_XML.as_bytes()),
+            ("docProps/core.xml", CORE_XML.as_bytes()),
+            ("docProps/app.xml", APP_XML.as_bytes()),
+            ("ppt/presentation.xml", b"<p:presentation/>"),
+            ("ppt/_rels/presentation.xml.rels", PRESENTATION_RELS_XML.as_bytes()),
+            ("ppt/theme/theme1.xml", THEME_XML.as_bytes()),
+            ("ppt/media/image1.png", &[0x89, 0x50, 0x4e, 0x47]),
+        ]);
+        let mut package = Package::open(reader).unwrap();
+
+        assert_eq!(package.relationships().len(), 3);
+        assert_eq!(package.main_part_name().as_deref(), Some("/ppt/presentation.xml"));
+        assert_eq!(
+            package.content_type("/ppt/presentation.xml"),
+            Some("application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml")
+        );
+        assert_eq!(package.content_type("ppt/media/image1.png"), Some("image/png"));
+        assert_eq!(package.content_type("docProps/core.xml"), Some("application/xml"));
+        assert_eq!(package.content_type("ppt/media/movie.mp4"), None);
+        let regenerated = package.regenerate_content_types();
+        assert_eq!(regenerated.content_type("ppt/media/image1.png"), Some("image/png"));
+        assert_eq!(
+            regenerated.default_content_type("rels"),
+            package.content_types().default_content_type("rels")
+        );
+        assert_eq!(regenerated.overrides().count(), 2);
+
+        assert!(package.has_part("/ppt/media/image1.png"));
+        assert!(package.has_part("/PPT/Media/Image1.PNG"));
+        assert!(!package.has_part("/ppt/media/image2.png"));
+        assert_eq!(
+            package.part_bytes("/ppt/media/image1.png").unwrap(),
+            vec![0x89, 0x50, 0x4e, 0x47]
+        );
+        assert_eq!(package.part_bytes("ppt/MEDIA/image1.png").unwrap().len(), 4);
+        assert_eq!(
+            package.part_xml("/PPT/presentation.xml").unwrap().name,
+            "p:presentation"
+        );
+        assert!(matches!(
+            package.part_bytes("ppt/missing.xml"),
+            Err(Error::Zip(ZipError::FileNotFound))
+        ));
+
+        let relationships = package.part_relationships("/ppt/presentation.xml").unwrap();
+        assert_eq!(relationships.len(), 2);
+        assert!(package.part_relationships("ppt/theme/theme1.xml").unwrap().is_empty());
+
+        let core = package.core_properties().unwrap().unwrap();
+        assert_eq!(core.title.as_deref(), Some("Title"));
+        assert_eq!(core.revision, Some(3));
+        let app = package.app_properties().unwrap().unwrap();
+        assert_eq!(app.app_name.as_deref(), Some("Microsoft Office PowerPoint"));
+        let theme = package.theme().unwrap().unwrap();
+        assert_eq!(theme.name.as_deref(), Some("Office Theme"));
+    }
+
+    #[test]
+    fn test_cached_part_models() {
+        let reader = build_package(&[
+            ("[Content_Types].xml", CONTENT_TYPES_XML.as_bytes()),
+            ("_rels/.rels", PACKAGE_RELS_XML.as_bytes()),
+            ("ppt/presentation.xml", b"<p:presentation/>"),
+            ("ppt/_rels/presentation.xml.rels", PRESENTATION_RELS_XML.as_bytes()),
+            ("ppt/theme/theme1.xml", THEME_XML.as_bytes()),
+        ]);
+        let mut package = Package::open(reader).unwrap();
+        assert!(!package.is_cached("/ppt/theme/theme1.xml"));
+
+        let theme = package.part::<OfficeStyleSheet>("/ppt/theme/theme1.xml").unwrap();
+        assert!(package.is_cached("ppt/theme/theme1.xml"));
+        assert!(package.is_cached("/PPT/THEME/theme1.xml"));
+        assert!(Arc::ptr_eq(&theme, &package.part("ppt/Theme/theme1.xml").unwrap()));
+        assert!(Arc::ptr_eq(&theme, &package.theme().unwrap().unwrap()));
+
+        package.invalidate("/ppt/Theme/Theme1.xml");
+        assert!(!package.is_cached("/ppt/theme/theme1.xml"));
+        let reparsed = package.part::<OfficeStyleSheet>("/ppt/theme/theme1.xml").unwrap();
+        assert!(!Arc::ptr_eq(&theme, &reparsed));
+        assert_eq!(theme, reparsed);
+
+        assert!(package.part::<Core>("/docProps/core.xml").is_err());
+        assert!(!package.is_cached("/docProps/core.xml"));
+
+        // Replacing the content of a part invalidates its models
+        let replaced_xml = THEME_XML.replace(r#"name="Office Theme""#, r#"name="Replaced Theme""#);
+        package
+            .replace_part("/ppt/theme/theme1.xml", replaced_xml.into_bytes())
+            .unwrap();
+        assert!(!package.is_cached("/ppt/theme/theme1.xml"));
+        let replaced = package.theme().unwrap().unwrap();
+        assert_eq!(replaced.name.as_deref(), Some("Replaced Theme"));
+        assert_eq!(
+            package.part_xml("ppt/theme/theme1.xml").unwrap().attributes.get("name"),
+            Some(&String::from("Replaced Theme"))
+        );
+        assert!(package.replace_part("/ppt/theme/theme2.xml", Vec::new()).is_err());
+
+        let relationships_xml = PRESENTATION_RELS_XML.replace("theme/theme1.xml", "theme/theme2.xml");
+        package
+            .replace_part("/ppt/_rels/presentation.xml.rels", relationships_xml.into_bytes())
+            .unwrap();
+        assert!(package.theme().is_err());
+    }
+
+    #[test]
+    fn test_part_parse_context() {
+        let theme_xml = THEME_XML.replace(r#"lastClr="000000""#, r#"lastClr="00000""#);
+        let reader = build_package(&[
+            ("[Content_Types].xml", CONTENT_TYPES_XML.as_bytes()),
+            ("ppt/theme/theme1.xml", theme_xml.as_bytes()),
+        ]);
+        let mut package = Package::open(reader).unwrap();
+        assert!(package.part::<OfficeStyleSheet>("ppt/theme/theme1.xml").is_err());
+
+        package.set_parse_context(ParseContext::lenient());
+        let theme = package.part::<OfficeStyleSheet>("ppt/theme/theme1.xml").unwrap();
+        assert_eq!(theme.name.as_deref(), Some("Office Theme"));
+        assert_eq!(package.parse_context_mut().take_diagnostics().len(), 1);
+
+        // Processing markup compatibility needs the tree of the whole part, which is parsed with the same context
+        package.set_markup_compatibility(Some(MarkupCompatibility::default()));
+        package.part::<OfficeStyleSheet>("ppt/theme/theme1.xml").unwrap();
+        assert_eq!(package.parse_context().diagnostics().len(), 1);
+    }
+
+    #[test]
+    fn test_markup_compatibility() {
+        let slide_xml = r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+            xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+            xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">
+            <mc:AlternateContent>
+                <mc:Choice Requires="p14"><p14:creationId val="1"/></mc:Choice>
+                <mc:Fallback><p:extLst/></mc:Fallback>
+            </mc:AlternateContent>
+        </p:sld>"#;
+        let reader = build_package(&[
+            ("[Content_Types].xml", CONTENT_TYPES_XML.as_bytes()),
+            ("ppt/slides/slide1.xml", slide_xml.as_bytes()),
+            ("ppt/theme/theme1.xml", THEME_XML.as_bytes()),
+        ]);
+        let mut package = Package::open(reader).unwrap();
+        assert!(package.markup_compatibility().is_none());
+        let slide = package.part_xml("ppt/slides/slide1.xml").unwrap();
+        assert!(slide.child_nodes[0].is(ns::MARKUP_COMPATIBILITY, "AlternateContent"));
+
+        package.part::<OfficeStyleSheet>("ppt/theme/theme1.xml").unwrap();
+        package.set_markup_compatibility(Some(MarkupCompatibility::default()));
+        assert!(!package.is_cached("ppt/theme/theme1.xml"));
+        let slide = package.part_xml("ppt/slides/slide1.xml").unwrap();
+        assert_eq!(slide.child_nodes.len(), 1);
+        assert_eq!(slide.child_nodes[0].name, "p:extLst");
+    }
+}
@@ -0,0 +1,500 @@
+//! Creating packages.
+
+use super::{
+    content_types::{
+        ContentTypes, CONTENT_TYPES_PART_NAME, CORE_PROPERTIES_CONTENT_TYPE, CUSTOM_PROPERTIES_CONTENT_TYPE,
+        EXTENDED_PROPERTIES_CONTENT_TYPE, RELATIONSHIPS_CONTENT_TYPE,
+    },
+    entry_name, validate_part_name, PACKAGE_RELATIONSHIPS_PART_NAME,
+};
+use crate::{
+    docprops::{AppInfo, Core, CustomProperties},
+    error::{InvalidPartNameError, InvalidPartNameReason, Result},
+    relationship::{relationships_part_name, RelationshipType, Relationships},
+    xml::{writer::WriteOptions, XmlNode},
+    xsdtypes::ToXmlElement,
+};
+use std::{
+    collections::BTreeMap,
+    io::{Seek, Write},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+const CORE_PROPERTIES_PART_NAME: &str = "/docProps/core.xml";
+const EXTENDED_PROPERTIES_PART_NAME: &str = "/docProps/app.xml";
+const CUSTOM_PROPERTIES_PART_NAME: &str = "/docProps/custom.xml";
+
+/// Content types of parts that are already compressed, so deflating them again would only waste time
+const PRE_COMPRESSED_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/vnd.ms-photo",
+    "application/zip",
+];
+
+#[derive(Debug, Clone)]
+struct Part {
+    name: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+/// Builds a package from parts, their relationships and the document properties, and writes it as a zip archive.
+///
+/// The archive starts with `[Content_Types].xml`, which is generated from the content types of the parts. XML parts
+/// are deflated, while media that's already compressed (e.g. PNG and JPEG images, audio and video) is stored.
+///
+/// # Example
+///
+/// ```
+/// use msoffice_shared::{
+///     opc::{writer::PackageWriter, Package},
+///     relationship::RelationshipType,
+///     xml::XmlNode,
+/// };
+/// use std::io::Cursor;
+///
+/// let mut writer = PackageWriter::new();
+/// writer
+///     .add_xml_part("/word/document.xml", "application/xml", &XmlNode::new("document"))
+///     .unwrap();
+/// writer
+///     .relationships_mut()
+///     .add(&RelationshipType::OfficeDocument, "word/document.xml");
+///
+/// let cursor = writer.write(Cursor::new(Vec::new())).unwrap();
+/// let package = Package::open(cursor).unwrap();
+/// assert_eq!(package.main_part_name().as_deref(), Some("/word/document.xml"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PackageWriter {
+    parts: Vec<Part>,
+    relationships: Relationships,
+    // Keyed by the lowercase name of the source part
+    part_relationships: BTreeMap<String, Relationships>,
+    core_properties: Option<Core>,
+    app_properties: Option<AppInfo>,
+    custom_properties: Option<CustomProperties>,
+}
+
+impl PackageWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the part `part_name` with the content `data`.
+    ///
+    /// Fails if the part name is invalid, reserved for a part managed by the writer (the content types stream and
+    /// the relationship parts), or clashes with the name of a part that's already added.
+    pub fn add_part<T, U>(&mut self, part_name: T, content_type: U, data: Vec<u8>) -> Result<()>
+    where
+        T: AsRef<str>,
+        U: Into<String>,
+    {
+        let name = validate_part_name(part_name.as_ref())?;
+        if is_relationships_part_name(&name) {
+            return Err(InvalidPartNameError::new(name, InvalidPartNameReason::Reserved).into());
+        }
+
+        if let Some(reason) = self.parts.iter().find_map(|part| name_clash(&part.name, &name)) {
+            return Err(InvalidPartNameError::new(name, reason).into());
+        }
+
+        self.parts.push(Part {
+            name,
+            content_type: content_type.into(),
+            data,
+        });
+
+        Ok(())
+    }
+
+    /// Adds the part `part_name` with `root` written as a complete xml document
+    pub fn add_xml_part<T, U>(&mut self, part_name: T, content_type: U, root: &XmlNode) -> Result<()>
+    where
+        T: AsRef<str>,
+        U: Into<String>,
+    {
+        let mut data = Vec::new();
+        root.write_to(&mut data, &WriteOptions::compact())?;
+        self.add_part(part_name, content_type, data)
+    }
+
+    /// Relationships of the package, written to `_rels/.rels`
+    pub fn relationships_mut(&mut self) -> &mut Relationships {
+        &mut self.relationships
+    }
+
+    /// Relationships of the part `part_name`, written next to the part. The part doesn't have to be added yet.
+    pub fn part_relationships_mut(&mut self, part_name: &str) -> Result<&mut Relationships> {
+        let name = validate_part_name(part_name)?;
+        Ok(self.part_relationships.entry(name.to_ascii_lowercase()).or_default())
+    }
+
+    /// Sets the core properties. If the package already has a core properties relationship, they're written to its
+    /// target, otherwise to `docProps/core.xml` along with a new relationship.
+    pub fn set_core_properties(&mut self, core_properties: Core) {
+        self.core_properties = Some(core_properties);
+    }
+
+    /// Sets the extended properties. If the package already has an extended properties relationship, they're written
+    /// to its target, otherwise to `docProps/app.xml` along with a new relationship.
+    pub fn set_app_properties(&mut self, app_properties: AppInfo) {
+        self.app_properties = Some(app_properties);
+    }
+
+    /// Sets the custom properties. If the package already has a custom properties relationship, they're written to
+    /// its target, otherwise to `docProps/custom.xml` along with a new relationship.
+    pub fn set_custom_properties(&mut self, custom_properties: CustomProperties) {
+        self.custom_properties = Some(custom_properties);
+    }
+
+    /// Writes the package to `writer` and returns the writer.
+    ///
+    /// Fails if a relationship part belongs to a part that's not in the package, or if the relationship to a document
+    /// properties part doesn't target a part.
+    pub fn write<W: Write + Seek>(&self, writer: W) -> Result<W> {
+        let mut parts: Vec<&Part> = Vec::new();
+        let mut relationships = self.relationships.clone();
+        let mut doc_props_parts = Vec::new();
+
+        if let Some(core_properties) = &self.core_properties {
+            doc_props_parts.push(self.doc_props_part(
+                &mut relationships,
+                CORE_PROPERTIES_PART_NAME,
+                CORE_PROPERTIES_CONTENT_TYPE,
+                &RelationshipType::CoreProperties,
+                &core_properties.to_xml_element("cp:coreProperties"),
+            )?);
+        }
+
+        if let Some(app_properties) = &self.app_properties {
+            doc_props_parts.push(self.doc_props_part(
+                &mut relationships,
+                EXTENDED_PROPERTIES_PART_NAME,
+                EXTENDED_PROPERTIES_CONTENT_TYPE,
+                &RelationshipType::ExtendedProperties,
+                &app_properties.to_xml_element("Properties"),
+            )?);
+        }
+
+        if let Some(custom_properties) = &self.custom_properties {
+            doc_props_parts.push(self.doc_props_part(
+                &mut relationships,
+                CUSTOM_PROPERTIES_PART_NAME,
+                CUSTOM_PROPERTIES_CONTENT_TYPE,
+                &RelationshipType::CustomProperties,
+                &custom_properties.to_xml_element("Properties"),
+            )?);
+        }
+
+        parts.extend(doc_props_parts.iter());
+        parts.extend(self.parts.iter());
+
+        for source_part_name in self.part_relationships.keys() {
+            if !parts
+                .iter()
+                .any(|part| part.name.to_ascii_lowercase() == *source_part_name)
+            {
+                return Err(InvalidPartNameError::new(
+                    relationships_part_name(source_part_name),
+                    InvalidPartNameReason::MissingSourcePart,
+                )
+                .into());
+            }
+        }
+
+        let mut relationship_parts = Vec::new();
+        if !relationships.is_empty() {
+            relationship_parts.push(relationships_part(PACKAGE_RELATIONSHIPS_PART_NAME, &relationships)?);
+        }
+
+        for part in &parts {
+            match self.part_relationships.get(&part.name.to_ascii_lowercase()) {
+                Some(part_relationships) if !part_relationships.is_empty() => relationship_parts.push(
+                    relationships_part(&relationships_part_name(&part.name), part_relationships)?,
+                ),
+                _ => (),
+            }
+        }
+
+        let content_types = ContentTypes::from_parts(
+            relationship_parts
+                .iter()
+                .chain(parts.iter().cloned())
+                .map(|part| (part.name.as_str(), part.content_type.as_str())),
+        );
+        let mut content_types_data = Vec::new();
+        content_types.write_xml(&mut content_types_data, &WriteOptions::compact())?;
+
+        let mut zip_writer = ZipWriter::new(writer);
+        zip_writer.start_file(
+            CONTENT_TYPES_PART_NAME,
+            FileOptions::default().compression_method(CompressionMethod::Deflated),
+        )?;
+        zip_writer.write_all(&content_types_data)?;
+
+        for part in relationship_parts.iter().chain(parts.iter().cloned()) {
+            let options = FileOptions::default().compression_method(compression_method(&part.content_type));
+            zip_writer.start_file(entry_name(&part.name), options)?;
+            zip_writer.write_all(&part.data)?;
+        }
+
+        Ok(zip_writer.finish()?)
+    }
+
+    // Creates a document properties part, written to the target of the package relationship of type `rel_type`, or
+    // to `default_part_name` if there's no such relationship yet
+    fn doc_props_part(
+        &self,
+        relationships: &mut Relationships,
+        default_part_name: &str,
+        content_type: &str,
+        rel_type: &RelationshipType,
+        root: &XmlNode,
+    ) -> Result<Part> {
+        let part_name = match relationships.get_by_type(rel_type) {
+            Some(relationship) => relationship.target_part_name("/").ok_or_else(|| {
+                InvalidPartNameError::new(
+                    relationship.target.as_str(),
+                    InvalidPartNameReason::InvalidRelationshipTarget,
+                )
+            })?,
+            None => {
+                relationships.add(rel_type, entry_name(default_part_name));
+                String::from(default_part_name)
+            }
+        };
+
+        if let Some(reason) = self.parts.iter().find_map(|part| name_clash(&part.name, &part_name)) {
+            return Err(InvalidPartNameError::new(part_name, reason).into());
+        }
+
+        let mut data = Vec::new();
+        root.write_to(&mut data, &WriteOptions::compact())?;
+        Ok(Part {
+            name: part_name,
+            content_type: String::from(content_type),
+            data,
+        })
+    }
+}
+
+fn relationships_part(part_name: &str, relationships: &Relationships) -> Result<Part> {
+    let mut data = Vec::new();
+    relationships.write_xml(&mut data, &WriteOptions::compact())?;
+    Ok(Part {
+        name: format!("/{}", entry_name(part_name)),
+        content_type: String::from(RELATIONSHIPS_CONTENT_TYPE),
+        data,
+    })
+}
+
+fn is_relationships_part_name(part_name: &str) -> bool {
+    let part_name = part_name.to_ascii_lowercase();
+    part_name.ends_with(".rels") && part_name.contains("/_rels/")
+}
+
+// Part names are compared case-insensitively, and a part name can't be a "folder" of another one
+fn name_clash(existing: &str, new: &str) -> Option<InvalidPartNameReason> {
+    let (existing, new) = (existing.to_ascii_lowercase(), new.to_ascii_lowercase());
+    if existing == new {
+        Some(InvalidPartNameReason::Duplicate)
+    } else if existing.starts_with(&format!("{}/", new)) || new.starts_with(&format!("{}/", existing)) {
+        Some(InvalidPartNameReason::Prefix)
+    } else {
+        None
+    }
+}
+
+fn compression_method(content_type: &str) -> CompressionMethod {
+    let content_type = content_type.to_ascii_lowercase();
+    let pre_compressed = PRE_COMPRESSED_CONTENT_TYPES.contains(&content_type.as_str())
+        || content_type.starts_with("audio/")
+        || content_type.starts_with("video/");
+
+    if pre_compressed {
+        CompressionMethod::Stored
+    } else {
+        CompressionMethod::Deflated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        drawingml::sharedstylesheet::{tests::THEME_XML, OfficeStyleSheet},
+        error::Error,
+        opc::{content_types::THEME_CONTENT_TYPE, Package},
+        xsdtypes::XsdType,
+    };
+    use std::{io::Cursor, str::FromStr};
+    use zip::ZipArchive;
+
+    const PRESENTATION_CONTENT_TYPE: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml";
+
+    #[test]
+    fn test_package_writer() {
+        let theme = OfficeStyleSheet::from_xml_element(&XmlNode::from_str(THEME_XML).unwrap()).unwrap();
+
+        let mut writer = PackageWriter::new();
+        writer
+            .add_xml_part(
+                "/ppt/presentation.xml",
+                PRESENTATION_CONTENT_TYPE,
+                &XmlNode::new("p:presentation"),
+            )
+            .unwrap();
+        writer
+            .add_xml_part(
+                "ppt/theme/theme1.xml",
+                THEME_CONTENT_TYPE,
+                &theme.to_xml_element("a:theme"),
+            )
+            .unwrap();
+        writer
+            .add_part("/ppt/media/image1.png", "image/png", vec![0x89, 0x50, 0x4e, 0x47])
+            .unwrap();
+        writer
+            .relationships_mut()
+            .add(&RelationshipType::OfficeDocument, "ppt/presentation.xml");
+        {
+            let presentation_relationships = writer.part_relationships_mut("/ppt/presentation.xml").unwrap();
+            presentation_relationships.add(&RelationshipType::Theme, "theme/theme1.xml");
+            presentation_relationships.add(&RelationshipType::Image, "media/image1.png");
+        }
+        writer.set_core_properties(Core {
+            title: Some(String::from("Title")),
+            ..Default::default()
+        });
+        let mut custom_properties = CustomProperties::new();
+        custom_properties.set("Classification", "Internal");
+        writer.set_custom_properties(custom_properties.clone());
+
+        let mut cursor = writer.write(Cursor::new(Vec::new())).unwrap();
+        cursor.set_position(0);
+
+        {
+            let mut archive = ZipArchive::new(&mut cursor).unwrap();
+            assert_eq!(archive.by_index(0).unwrap().name(), CONTENT_TYPES_PART_NAME);
+            assert_eq!(archive.by_index(0).unwrap().compression(), CompressionMethod::Deflated);
+            assert_eq!(
+                archive.by_name("ppt/presentation.xml").unwrap().compression(),
+                CompressionMethod::Deflated
+            );
+            assert_eq!(
+                archive.by_name("ppt/media/image1.png").unwrap().compression(),
+                CompressionMethod::Stored
+            );
+        }
+
+        cursor.set_position(0);
+        let mut package = Package::open(cursor).unwrap();
+        assert_eq!(package.relationships().len(), 3);
+        assert_eq!(package.content_type("/ppt/theme/theme1.xml"), Some(THEME_CONTENT_TYPE));
+        assert_eq!(
+            package.content_type("/docProps/core.xml"),
+            Some(CORE_PROPERTIES_CONTENT_TYPE)
+        );
+        assert_eq!(
+            package.part_bytes("ppt/media/image1.png").unwrap(),
+            vec![0x89, 0x50, 0x4e, 0x47]
+        );
+        assert_eq!(
+            package.core_properties().unwrap().unwrap().title.as_deref(),
+            Some("Title")
+        );
+        assert_eq!(*package.theme().unwrap().unwrap(), theme);
+    }
+
+    #[test]
+    fn test_invalid_part_names() {
+        let mut writer = PackageWriter::new();
+        writer
+            .add_part("/ppt/slides/slide1.xml", "application/xml", Vec::new())
+            .unwrap();
+
+        let reason = |result: Result<()>| match result {
+            Err(Error::InvalidPartName(err)) => err.reason,
+            _ => panic!("part name should be rejected"),
+        };
+
+        assert_eq!(
+            reason(writer.add_part("/PPT/Slides/slide1.xml", "application/xml", Vec::new())),
+            InvalidPartNameReason::Duplicate
+        );
+        // Like in Package, only ASCII letters are compared case-insensitively
+        writer.add_part("/ppt/media/Ä.png", "image/png", Vec::new()).unwrap();
+        writer.add_part("/ppt/media/ä.png", "image/png", Vec::new()).unwrap();
+        assert_eq!(
+            reason(writer.add_part("/ppt/slides", "application/xml", Vec::new())),
+            InvalidPartNameReason::Prefix
+        );
+        assert_eq!(
+            reason(writer.add_part(
+                "/ppt/_rels/presentation.xml.rels",
+                RELATIONSHIPS_CONTENT_TYPE,
+                Vec::new()
+            )),
+            InvalidPartNameReason::Reserved
+        );
+        assert_eq!(
+            reason(writer.add_part("/ppt/../slide.xml", "application/xml", Vec::new())),
+            InvalidPartNameReason::SegmentEndsWithDot
+        );
+        assert_eq!(
+            reason(writer.add_part("/[Content_Types].xml", "application/xml", Vec::new())),
+            InvalidPartNameReason::InvalidCharacter('[')
+        );
+
+        writer
+            .part_relationships_mut("/ppt/presentation.xml")
+            .unwrap()
+            .add(&RelationshipType::Theme, "theme/theme1.xml");
+        match writer.write(Cursor::new(Vec::new())) {
+            Err(Error::InvalidPartName(err)) => assert_eq!(err.reason, InvalidPartNameReason::MissingSourcePart),
+            _ => panic!("relationships of a missing part should be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_doc_props_part_follows_existing_relationship() {
+        let mut writer = PackageWriter::new();
+        writer
+            .relationships_mut()
+            .add(&RelationshipType::CoreProperties, "meta/core.xml");
+        writer.set_core_properties(Core {
+            title: Some(String::from("Title")),
+            ..Default::default()
+        });
+
+        let mut cursor = writer.write(Cursor::new(Vec::new())).unwrap();
+        cursor.set_position(0);
+        let mut package = Package::open(cursor).unwrap();
+        assert_eq!(package.relationships().len(), 1);
+        assert_eq!(
+            package.content_type("/meta/core.xml"),
+            Some(CORE_PROPERTIES_CONTENT_TYPE)
+        );
+        assert_eq!(package.content_type("/docProps/core.xml"), None);
+        assert_eq!(
+            package.core_properties().unwrap().unwrap().title.as_deref(),
+            Some("Title")
+        );
+
+        let mut writer = PackageWriter::new();
+        writer
+            .relationships_mut()
+            .add_external(&RelationshipType::CoreProperties, "http://example.com/core.xml");
+        writer.set_core_properties(Core::default());
+        match writer.write(Cursor::new(Vec::new())) {
+            Err(Error::InvalidPartName(err)) => {
+                assert_eq!(err.reason, InvalidPartNameReason::InvalidRelationshipTarget)
+            }
+            _ => panic!("an external core properties relationship should be rejected"),
+        }
+    }
+}
@@ -1,19 +1,223 @@
 use crate::error::MissingAttributeError;
-use crate::xml::XmlNode;
-use std::{io::Read, str::FromStr};
+use crate::xml::{limits::ParseLimits, ns, reader::XmlReader, writer::WriteOptions, zip_file_to_xml_reader, XmlNode};
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
 use zip::read::ZipFile;
 
 pub type RelationshipId = String;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+pub use crate::error::Result;
 
 pub const THEME_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
 
+const TRANSITIONAL_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/";
+const STRICT_RELATIONSHIPS: &str = "http://purl.oclc.org/ooxml/officeDocument/relationships/";
+const PACKAGE_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/package/2006/relationships/";
+
+// Selects the strict name of a relationship type, which only differs from the transitional one for a few types
+macro_rules! strict_name {
+    ($transitional:literal) => {
+        $transitional
+    };
+    ($transitional:literal, $strict:literal) => {
+        $strict
+    };
+}
+
+macro_rules! relationship_types {
+    (
+        office {
+            $($(#[$office_doc:meta])* $office_variant:ident => $transitional:literal $(, $strict:literal)?;)*
+        }
+        package {
+            $($(#[$package_doc:meta])* $package_variant:ident => $package:literal;)*
+        }
+    ) => {
+        /// The type of a relationship, identified by a URI.
+        ///
+        /// Office document relationship types have different URIs in transitional and strict documents (e.g.
+        /// `http://schemas.openxmlformats.org/officeDocument/2006/relationships/image` and
+        /// `http://purl.oclc.org/ooxml/officeDocument/relationships/image`), both of them are parsed to the same
+        /// variant. Package relationship types are the same in both. Types unknown to this crate are kept as `Other`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum RelationshipType {
+            $($(#[$office_doc])* $office_variant,)*
+            $($(#[$package_doc])* $package_variant,)*
+            Other(String),
+        }
+
+        impl RelationshipType {
+            pub fn from_uri(uri: &str) -> Self {
+                if let Some(name) = uri.strip_prefix(TRANSITIONAL_RELATIONSHIPS) {
+                    match name {
+                        $($transitional => return RelationshipType::$office_variant,)*
+                        _ => (),
+                    }
+                } else if let Some(name) = uri.strip_prefix(STRICT_RELATIONSHIPS) {
+                    match name {
+                        $(strict_name!($transitional $(, $strict)?) => return RelationshipType::$office_variant,)*
+                        _ => (),
+                    }
+                } else if let Some(name) = uri.strip_prefix(PACKAGE_RELATIONSHIPS) {
+                    match name {
+                        $($package => return RelationshipType::$package_variant,)*
+                        _ => (),
+                    }
+                }
+
+                RelationshipType::Other(String::from(uri))
+            }
+
+            /// URI of the type in transitional documents. This is the URI written by Office.
+            pub fn transitional_uri(&self) -> &str {
+                match self {
+                    $(RelationshipType::$office_variant => concat!(
+                        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/",
+                        $transitional
+                    ),)*
+                    $(RelationshipType::$package_variant => concat!(
+                        "http://schemas.openxmlformats.org/package/2006/relationships/",
+                        $package
+                    ),)*
+                    RelationshipType::Other(uri) => uri,
+                }
+            }
+
+            /// URI of the type in strict documents
+            pub fn strict_uri(&self) -> &str {
+                match self {
+                    $(RelationshipType::$office_variant => concat!(
+                        "http://purl.oclc.org/ooxml/officeDocument/relationships/",
+                        strict_name!($transitional $(, $strict)?)
+                    ),)*
+                    RelationshipType::Other(uri) => uri,
+                    _ => self.transitional_uri(),
+                }
+            }
+        }
+    };
+}
+
+relationship_types! {
+    office {
+        OfficeDocument => "officeDocument";
+        ExtendedProperties => "extended-properties", "extendedProperties";
+        CustomProperties => "custom-properties", "customProperties";
+        Image => "image";
+        Hyperlink => "hyperlink";
+        Theme => "theme";
+        ThemeOverride => "themeOverride";
+        Chart => "chart";
+        ChartUserShapes => "chartUserShapes";
+        DiagramData => "diagramData";
+        DiagramLayout => "diagramLayout";
+        DiagramQuickStyle => "diagramQuickStyle";
+        DiagramColors => "diagramColors";
+        OleObject => "oleObject";
+        /// An embedded package, e.g. the workbook of a chart
+        Package => "package";
+        CustomXml => "customXml";
+        CustomXmlProps => "customXmlProps";
+        Control => "control";
+        Audio => "audio";
+        Video => "video";
+        Font => "font";
+        Tags => "tags";
+        PrinterSettings => "printerSettings";
+        AttachedTemplate => "attachedTemplate";
+        Comments => "comments";
+        Styles => "styles";
+        Settings => "settings";
+        WebSettings => "webSettings";
+        FontTable => "fontTable";
+        Numbering => "numbering";
+        Footnotes => "footnotes";
+        Endnotes => "endnotes";
+        Header => "header";
+        Footer => "footer";
+        GlossaryDocument => "glossaryDocument";
+        Slide => "slide";
+        SlideLayout => "slideLayout";
+        SlideMaster => "slideMaster";
+        NotesSlide => "notesSlide";
+        NotesMaster => "notesMaster";
+        HandoutMaster => "handoutMaster";
+        PresentationProperties => "presProps";
+        ViewProperties => "viewProps";
+        TableStyles => "tableStyles";
+        CommentAuthors => "commentAuthors";
+        Worksheet => "worksheet";
+        Chartsheet => "chartsheet";
+        SharedStrings => "sharedStrings";
+        CalcChain => "calcChain";
+        Drawing => "drawing";
+        VmlDrawing => "vmlDrawing";
+        Table => "table";
+        PivotTable => "pivotTable";
+        PivotCacheDefinition => "pivotCacheDefinition";
+        PivotCacheRecords => "pivotCacheRecords";
+        QueryTable => "queryTable";
+        Connections => "connections";
+        ExternalLink => "externalLink";
+    }
+    package {
+        CoreProperties => "metadata/core-properties";
+        Thumbnail => "metadata/thumbnail";
+        DigitalSignatureOrigin => "digital-signature/origin";
+        DigitalSignature => "digital-signature/signature";
+        DigitalSignatureCertificate => "digital-signature/certificate";
+    }
+}
+
+impl From<&str> for RelationshipType {
+    fn from(uri: &str) -> Self {
+        Self::from_uri(uri)
+    }
+}
+
+impl FromStr for RelationshipType {
+    type Err = Infallible;
+
+    fn from_str(uri: &str) -> ::std::result::Result<Self, Self::Err> {
+        Ok(Self::from_uri(uri))
+    }
+}
+
+impl Display for RelationshipType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.transitional_uri())
+    }
+}
+
+/// Specifies whether the target of a relationship is a part of the package or a resource outside of it.
+#[derive(Debug, Clone, Copy, Default, EnumString, Display, PartialEq)]
+pub enum TargetMode {
+    /// The target is a part of the package, referenced by a part name relative to the source part
+    #[default]
+    Internal,
+    /// The target is a resource outside of the package (e.g. a hyperlink), referenced by a URI
+    External,
+}
+
+/// The resolved target of a relationship.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelationshipTarget {
+    /// Absolute, normalised name of the target part, e.g. `/ppt/media/image1.png`
+    Part(String),
+    /// URI of an external resource, exactly as it's specified by the relationship
+    External(String),
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Relationship {
     pub id: String,
     pub rel_type: String,
     pub target: String,
+    pub target_mode: TargetMode,
 }
 
 impl Relationship {
@@ -21,33 +225,581 @@ impl Relationship {
         let mut id = None;
         let mut rel_type = None;
         let mut target = None;
+        let mut target_mode = None;
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "Id" => id = Some(value.clone()),
                 "Type" => rel_type = Some(value.clone()),
                 "Target" => target = Some(value.clone()),
+                "TargetMode" => target_mode = Some(xml_node.parse_attribute_value(attr, value)?),
                 _ => (),
             }
         }
 
-        let id = id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "Id"))?;
-        let rel_type = rel_type.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "Type"))?;
-        let target = target.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "Target"))?;
+        let id = id.ok_or_else(|| MissingAttributeError::at(xml_node, "Id"))?;
+        let rel_type = rel_type.ok_or_else(|| MissingAttributeError::at(xml_node, "Type"))?;
+        let target = target.ok_or_else(|| MissingAttributeError::at(xml_node, "Target"))?;
+        let target_mode = target_mode.unwrap_or_default();
+
+        Ok(Self {
+            id,
+            rel_type,
+            target,
+            target_mode,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace("Relationship", ns::PACKAGE_RELATIONSHIPS);
+        node.attributes.insert(String::from("Id"), self.id.clone());
+        node.attributes.insert(String::from("Type"), self.rel_type.clone());
+        node.attributes.insert(String::from("Target"), self.target.clone());
+        if self.is_external() {
+            node.attributes
+                .insert(String::from("TargetMode"), self.target_mode.to_string());
+        }
+
+        node
+    }
+
+    pub fn relationship_type(&self) -> RelationshipType {
+        RelationshipType::from_uri(&self.rel_type)
+    }
+
+    /// Returns true if the relationship is of type `rel_type`, in either the transitional or the strict form
+    pub fn has_type(&self, rel_type: &RelationshipType) -> bool {
+        self.relationship_type() == *rel_type
+    }
+
+    pub fn is_external(&self) -> bool {
+        self.target_mode == TargetMode::External
+    }
+
+    /// Resolves the target of this relationship of the part `source_part_name`. Use `/` as the source part name of the
+    /// relationships of the package, or `source_part_name` to get it from the name of the relationship part.
+    ///
+    /// Returns None if the target of an internal relationship isn't a valid part name, see `resolve_part_name`.
+    pub fn resolve_target(&self, source_part_name: &str) -> Option<RelationshipTarget> {
+        match self.target_mode {
+            TargetMode::Internal => resolve_part_name(source_part_name, &self.target).map(RelationshipTarget::Part),
+            TargetMode::External => Some(RelationshipTarget::External(self.target.clone())),
+        }
+    }
+
+    /// Resolves the target of this internal relationship of the part `source_part_name` to a part name. Returns None
+    /// for external relationships and for targets that aren't valid part names.
+    pub fn target_part_name(&self, source_part_name: &str) -> Option<String> {
+        match self.resolve_target(source_part_name)? {
+            RelationshipTarget::Part(part_name) => Some(part_name),
+            RelationshipTarget::External(_) => None,
+        }
+    }
+}
+
+/// The relationships of a part, or of the package, stored in a relationship part (`.rels`).
+///
+/// Relationships are kept in the order they were read or added. Ids of added relationships are allocated in the `rIdN`
+/// form used by Office, never clashing with the existing ones.
+#[derive(Debug, Clone, Default)]
+pub struct Relationships {
+    relationships: Vec<Relationship>,
+    // Largest N of the removed `rIdN` ids, so they aren't allocated again while part xml may still refer to them
+    max_removed_id: u32,
+}
+
+impl Relationships {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let relationships = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "Relationship")
+            .map(Relationship::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(relationships.into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.relationships.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.relationships.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Relationship> {
+        self.relationships.iter()
+    }
+
+    pub fn as_slice(&self) -> &[Relationship] {
+        &self.relationships
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Relationship> {
+        self.relationships.iter().find(|relationship| relationship.id == id)
+    }
+
+    /// Returns the first relationship of type `rel_type`
+    pub fn get_by_type(&self, rel_type: &RelationshipType) -> Option<&Relationship> {
+        find_by_type(&self.relationships, rel_type)
+    }
+
+    pub fn iter_by_type<'a>(&'a self, rel_type: &'a RelationshipType) -> impl Iterator<Item = &'a Relationship> {
+        self.relationships
+            .iter()
+            .filter(move |relationship| relationship.has_type(rel_type))
+    }
+
+    /// Returns the id the next added relationship will get: `rIdN`, where N is one more than the largest N in use or
+    /// removed from this collection. Ids of removed relationships are only reused if N would overflow, in which case
+    /// the smallest N not in use is returned.
+    ///
+    /// Removed ids are only remembered by this collection, so they can be reused after it's written and read back.
+    pub fn next_id(&self) -> RelationshipId {
+        let mut numbers: Vec<u32> = self
+            .relationships
+            .iter()
+            .filter_map(|relationship| id_number(&relationship.id))
+            .collect();
+        let max_id = numbers
+            .iter()
+            .copied()
+            .chain(Some(self.max_removed_id))
+            .max()
+            .unwrap_or(0);
+
+        let number = max_id.checked_add(1).unwrap_or_else(|| {
+            // There can't be as many relationships as numbers, so there's always one that's free
+            numbers.sort_unstable();
+            let mut first_free = 1;
+            for number in numbers {
+                if number == first_free {
+                    first_free += 1;
+                } else if number > first_free {
+                    break;
+                }
+            }
+
+            first_free
+        });
+
+        format!("rId{}", number)
+    }
+
+    /// Adds an internal relationship of type `rel_type` to `target`, a part name relative to the source part, and
+    /// returns its id. The type is written in its transitional form.
+    pub fn add<T: Into<String>>(&mut self, rel_type: &RelationshipType, target: T) -> RelationshipId {
+        self.add_with_target_mode(rel_type, target, TargetMode::Internal)
+    }
+
+    /// Adds an external relationship of type `rel_type` to the URI `target` and returns its id
+    pub fn add_external<T: Into<String>>(&mut self, rel_type: &RelationshipType, target: T) -> RelationshipId {
+        self.add_with_target_mode(rel_type, target, TargetMode::External)
+    }
+
+    fn add_with_target_mode<T: Into<String>>(
+        &mut self,
+        rel_type: &RelationshipType,
+        target: T,
+        target_mode: TargetMode,
+    ) -> RelationshipId {
+        let id = self.next_id();
+        self.relationships.push(Relationship {
+            id: id.clone(),
+            rel_type: String::from(rel_type.transitional_uri()),
+            target: target.into(),
+            target_mode,
+        });
+
+        id
+    }
+
+    /// Inserts `relationship` with its own id, replacing and returning the relationship with the same id
+    pub fn insert(&mut self, relationship: Relationship) -> Option<Relationship> {
+        match self
+            .relationships
+            .iter_mut()
+            .find(|existing| existing.id == relationship.id)
+        {
+            Some(existing) => Some(::std::mem::replace(existing, relationship)),
+            None => {
+                self.relationships.push(relationship);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Relationship> {
+        let idx = self
+            .relationships
+            .iter()
+            .position(|relationship| relationship.id == id)?;
+        let relationship = self.relationships.remove(idx);
+        if let Some(number) = id_number(&relationship.id) {
+            self.max_removed_id = self.max_removed_id.max(number);
+        }
+
+        Some(relationship)
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let mut node = XmlNode::new_with_namespace("Relationships", ns::PACKAGE_RELATIONSHIPS);
+        node.child_nodes
+            .extend(self.relationships.iter().map(Relationship::to_xml_element));
+        node
+    }
 
-        Ok(Self { id, rel_type, target })
+    pub fn write_xml<W: Write>(&self, writer: W, options: &WriteOptions) -> io::Result<()> {
+        self.to_xml_element().write_to(writer, options)
     }
 }
 
+impl PartialEq for Relationships {
+    fn eq(&self, other: &Self) -> bool {
+        self.relationships == other.relationships
+    }
+}
+
+impl From<Vec<Relationship>> for Relationships {
+    fn from(relationships: Vec<Relationship>) -> Self {
+        Self {
+            relationships,
+            max_removed_id: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Relationships {
+    type Item = &'a Relationship;
+    type IntoIter = ::std::slice::Iter<'a, Relationship>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.relationships.iter()
+    }
+}
+
+// Returns N of an id in the `rIdN` form
+fn id_number(id: &str) -> Option<u32> {
+    id.strip_prefix("rId").and_then(|number| number.parse().ok())
+}
+
+/// Reads the relationships stored in a relationship part in a zip archive. The default `ParseLimits` are enforced.
 pub fn relationships_from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Vec<Relationship>> {
-    let mut xml_string = String::new();
-    zip_file.read_to_string(&mut xml_string)?;
-    let xml_node = XmlNode::from_str(xml_string.as_str())?;
-    let mut relationships = Vec::new();
+    relationships_from_zip_file_with_limits(zip_file, &Default::default())
+}
 
-    for child_node in &xml_node.child_nodes {
-        relationships.push(Relationship::from_xml_element(child_node)?);
+/// Same as `relationships_from_zip_file`, but enforces the given limits.
+pub fn relationships_from_zip_file_with_limits(
+    zip_file: &mut ZipFile<'_>,
+    limits: &ParseLimits,
+) -> Result<Vec<Relationship>> {
+    relationships_from_xml_reader(&mut zip_file_to_xml_reader(zip_file, limits)?)
+}
+
+// Reads the relationships of the relationship part read by `reader`
+pub(crate) fn relationships_from_xml_reader<B: BufRead>(reader: &mut XmlReader<B>) -> Result<Vec<Relationship>> {
+    // Relationship parts may list thousands of relationships, so they're read one at a time
+    let root = reader.read_root_start()?;
+    let mut relationships = Vec::new();
+    while let Some(child) = reader.next_child(&root)? {
+        if child.node.local_name() == "Relationship" {
+            relationships.push(Relationship::from_xml_element(&reader.read_node(child)?)?);
+        } else {
+            reader.skip_node(&child)?;
+        }
     }
 
+    reader.read_document_end()?;
     Ok(relationships)
 }
+
+/// Returns the first relationship of type `rel_type` in `relationships`
+pub fn find_by_type<'a>(relationships: &'a [Relationship], rel_type: &RelationshipType) -> Option<&'a Relationship> {
+    relationships
+        .iter()
+        .find(|relationship| relationship.has_type(rel_type))
+}
+
+/// Resolves `target`, relative to the part `source_part_name`, to an absolute, normalised part name. The source part
+/// name can be given with or without the leading `/`.
+///
+/// The fragment of the target is dropped and percent-encoded characters are decoded, so the part name can be looked
+/// up in the package. Returns None if the target goes above the root of the package, or if it has an invalid or a
+/// forbidden (`%2F`, `%5C`) percent-encoding.
+///
+/// ```
+/// use msoffice_shared::relationship::resolve_part_name;
+///
+/// assert_eq!(
+///     resolve_part_name("/ppt/slides/slide1.xml", "../media/image1.png").as_deref(),
+///     Some("/ppt/media/image1.png")
+/// );
+/// assert_eq!(
+///     resolve_part_name("/", "ppt/presentation.xml").as_deref(),
+///     Some("/ppt/presentation.xml")
+/// );
+/// assert_eq!(resolve_part_name("/", "../docProps/app.xml"), None);
+/// ```
+pub fn resolve_part_name(source_part_name: &str, target: &str) -> Option<String> {
+    let target = target.find('#').map_or(target, |idx| &target[..idx]);
+    let mut segments: Vec<String> = if target.starts_with('/') {
+        Vec::new()
+    } else {
+        let source_part_name = source_part_name.trim_start_matches('/');
+        match source_part_name.rfind('/') {
+            Some(idx) => source_part_name[..idx].split('/').map(String::from).collect(),
+            None => Vec::new(),
+        }
+    };
+
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(percent_decode_segment(segment)?),
+        }
+    }
+
+    Some(format!("/{}", segments.join("/")))
+}
+
+// Decodes the percent-encoded UTF-8 characters of a part name segment. Returns None for invalid encodings and for
+// encoded `/` and `\` characters, which are forbidden in part names
+fn percent_decode_segment(segment: &str) -> Option<String> {
+    if !segment.contains('%') {
+        return Some(String::from(segment));
+    }
+
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut iter = segment.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = char::from(iter.next()?).to_digit(16)?;
+            let low = char::from(iter.next()?).to_digit(16)?;
+            match (high * 16 + low) as u8 {
+                b'/' | b'\\' => return None,
+                decoded => bytes.push(decoded),
+            }
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Name of the relationship part holding the relationships of the part `part_name`, e.g.
+/// `/ppt/_rels/presentation.xml.rels` for `/ppt/presentation.xml`. The relationships of the package (`/`) are held by
+/// `/_rels/.rels`.
+pub fn relationships_part_name(part_name: &str) -> String {
+    let part_name = part_name.trim_start_matches('/');
+    match part_name.rfind('/') {
+        Some(idx) => format!("/{}/_rels/{}.rels", &part_name[..idx], &part_name[idx + 1..]),
+        None => format!("/_rels/{}.rels", part_name),
+    }
+}
+
+/// Name of the part whose relationships are held by the relationship part `relationships_part_name`, or None if it's
+/// not the name of a relationship part. Returns `/` for `/_rels/.rels`.
+pub fn source_part_name(relationships_part_name: &str) -> Option<String> {
+    let relationships_part_name = relationships_part_name.trim_start_matches('/');
+    let (dir, file_name) = match relationships_part_name.rfind('/') {
+        Some(idx) => (&relationships_part_name[..idx], &relationships_part_name[idx + 1..]),
+        None => return None,
+    };
+
+    let file_name = file_name.strip_suffix(".rels")?;
+    match dir {
+        "_rels" => Some(format!("/{}", file_name)),
+        dir => dir
+            .strip_suffix("/_rels")
+            .map(|source_dir| format!("/{}/{}", source_dir, file_name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_target_resolution() {
+        let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/./image1.png"/>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/a/../b" TargetMode="External"/>
+        </Relationships>"#;
+        let xml_node = XmlNode::from_str(xml).unwrap();
+        let image = Relationship::from_xml_element(&xml_node.child_nodes[0]).unwrap();
+        let hyperlink = Relationship::from_xml_element(&xml_node.child_nodes[1]).unwrap();
+
+        assert_eq!(image.target_mode, TargetMode::Internal);
+        assert_eq!(hyperlink.target_mode, TargetMode::External);
+
+        let slide_part_name = source_part_name("/ppt/slides/_rels/slide1.xml.rels").unwrap();
+        assert_eq!(slide_part_name, "/ppt/slides/slide1.xml");
+        assert_eq!(
+            image.resolve_target(&slide_part_name),
+            Some(RelationshipTarget::Part(String::from("/ppt/media/image1.png")))
+        );
+        assert_eq!(
+            hyperlink.resolve_target(&slide_part_name),
+            Some(RelationshipTarget::External(String::from("https://example.com/a/../b")))
+        );
+        assert_eq!(hyperlink.target_part_name(&slide_part_name), None);
+
+        assert_eq!(
+            resolve_part_name("ppt/presentation.xml", "/customXml/item1.xml").as_deref(),
+            Some("/customXml/item1.xml")
+        );
+        assert_eq!(resolve_part_name("/", "../docProps/app.xml"), None);
+        assert_eq!(
+            resolve_part_name("/ppt/presentation.xml", "slides/../../../a.xml"),
+            None
+        );
+        assert_eq!(
+            resolve_part_name("/ppt/presentation.xml", "slides/slide%201.xml#slide").as_deref(),
+            Some("/ppt/slides/slide 1.xml")
+        );
+        assert_eq!(
+            resolve_part_name("/", "media/%C3%A1rv%C3%ADz.png").as_deref(),
+            Some("/media/árvíz.png")
+        );
+        for invalid_target in &["a%2Fb.xml", "a%5cb.xml", "a%2.xml", "a%C3.xml"] {
+            assert_eq!(resolve_part_name("/", invalid_target), None, "{}", invalid_target);
+        }
+        assert_eq!(
+            relationships_part_name("ppt/presentation.xml"),
+            "/ppt/_rels/presentation.xml.rels"
+        );
+        assert_eq!(relationships_part_name("/"), "/_rels/.rels");
+        assert_eq!(source_part_name("/_rels/.rels").as_deref(), Some("/"));
+        assert_eq!(source_part_name("/ppt/slides/slide1.xml"), None);
+
+        let mut invalid_mode = xml_node.child_nodes[1].clone();
+        invalid_mode
+            .attributes
+            .insert(String::from("TargetMode"), String::from("Remote"));
+        assert!(Relationship::from_xml_element(&invalid_mode).is_err());
+    }
+
+    #[test]
+    fn test_relationship_type() {
+        let strict =
+            RelationshipType::from_uri("http://purl.oclc.org/ooxml/officeDocument/relationships/extendedProperties");
+        assert_eq!(strict, RelationshipType::ExtendedProperties);
+        assert_eq!(
+            strict.transitional_uri(),
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties"
+        );
+        assert_eq!(
+            RelationshipType::DiagramData.strict_uri(),
+            "http://purl.oclc.org/ooxml/officeDocument/relationships/diagramData"
+        );
+        assert_eq!(RelationshipType::from_uri(THEME_RELATION_TYPE), RelationshipType::Theme);
+
+        let core_properties = "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties";
+        assert_eq!(
+            RelationshipType::from_uri(core_properties),
+            RelationshipType::CoreProperties
+        );
+        assert_eq!(RelationshipType::CoreProperties.strict_uri(), core_properties);
+
+        let unknown = "http://schemas.microsoft.com/office/2007/relationships/hdphoto";
+        assert_eq!(
+            RelationshipType::from_uri(unknown),
+            RelationshipType::Other(String::from(unknown))
+        );
+        assert_eq!(RelationshipType::from_uri(unknown).to_string(), unknown);
+        assert_eq!(
+            RelationshipType::from_uri("http://schemas.openxmlformats.org/officeDocument/2006/relationships/nope"),
+            RelationshipType::Other(String::from(
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships/nope"
+            ))
+        );
+
+        let relationships = vec![
+            Relationship {
+                id: String::from("rId1"),
+                rel_type: String::from("http://purl.oclc.org/ooxml/officeDocument/relationships/image"),
+                target: String::from("media/image1.png"),
+                ..Default::default()
+            },
+            Relationship {
+                id: String::from("rId2"),
+                rel_type: String::from(THEME_RELATION_TYPE),
+                target: String::from("theme/theme1.xml"),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            find_by_type(&relationships, &RelationshipType::Theme).unwrap().id,
+            "rId2"
+        );
+        assert!(relationships[0].has_type(&RelationshipType::Image));
+        assert!(find_by_type(&relationships, &RelationshipType::Chart).is_none());
+    }
+
+    #[test]
+    fn test_relationships() {
+        let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>
+            <Relationship Id="rId7" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/>
+            <Relationship Id="link" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com" TargetMode="External"/>
+        </Relationships>"#;
+        let mut relationships = Relationships::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(relationships.len(), 3);
+        assert_eq!(relationships.next_id(), "rId8");
+        assert_eq!(relationships.get("link").unwrap().target_mode, TargetMode::External);
+        assert_eq!(relationships.get_by_type(&RelationshipType::Image).unwrap().id, "rId7");
+
+        let image_id = relationships.add(&RelationshipType::Image, "../media/image2.png");
+        let hyperlink_id = relationships.add_external(&RelationshipType::Hyperlink, "https://example.org");
+        assert_eq!((image_id.as_str(), hyperlink_id.as_str()), ("rId8", "rId9"));
+        assert_eq!(relationships.iter_by_type(&RelationshipType::Image).count(), 2);
+
+        assert_eq!(relationships.remove("rId9").unwrap().target, "https://example.org");
+        assert!(relationships.remove("rId9").is_none());
+        assert_eq!(relationships.next_id(), "rId10");
+
+        let written = relationships.to_xml_element();
+        assert_eq!(written.child_nodes[2].attributes["TargetMode"], "External");
+        assert!(!written.child_nodes[3].attributes.contains_key("TargetMode"));
+        let reparsed = XmlNode::from_str(&written.to_xml_string(&WriteOptions::compact()).unwrap()).unwrap();
+        assert_eq!(Relationships::from_xml_element(&reparsed).unwrap(), relationships);
+
+        let image = |id: &str| Relationship {
+            id: String::from(id),
+            rel_type: String::from(RelationshipType::Image.transitional_uri()),
+            target: String::from("../media/image.png"),
+            target_mode: TargetMode::Internal,
+        };
+        let mut relationships = Relationships::from(vec![image("rId1"), image("rId3"), image("rId4294967295")]);
+        assert_eq!(relationships.next_id(), "rId2");
+        relationships.add(&RelationshipType::Image, "../media/image2.png");
+        assert_eq!(relationships.next_id(), "rId4");
+    }
+
+    #[test]
+    fn test_relationships_from_zip_file() {
+        let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships" xmlns:x="urn:x">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>
+            <x:note><x:text>Not a relationship</x:text></x:note>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/>
+        </Relationships>"#;
+        let mut archive =
+            zip::ZipArchive::new(crate::opc::tests::build_package(&[("slide1.xml.rels", xml.as_bytes())])).unwrap();
+        let relationships = relationships_from_zip_file(&mut archive.by_index(0).unwrap()).unwrap();
+        let ids: Vec<&str> = relationships
+            .iter()
+            .map(|relationship| relationship.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["rId1", "rId2"]);
+    }
+}
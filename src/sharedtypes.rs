@@ -142,7 +142,7 @@ impl<T> FromStr for UniversalMeasure<T>
 where
     UniversalMeasure<T>: PatternRestricted,
 {
-    type Err = Box<dyn std::error::Error>;
+    type Err = Box<dyn std::error::Error + Send + Sync>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let re = Regex::new(Self::restriction_pattern()).expect("valid regexp should be provided");
@@ -164,7 +164,7 @@ pub enum TwipsMeasure {
 
 impl FromStr for TwipsMeasure {
     // TODO custom error type
-    type Err = Box<dyn std::error::Error>;
+    type Err = Box<dyn std::error::Error + Send + Sync>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(value) = s.parse::<u64>() {
@@ -185,7 +185,7 @@ impl PatternRestricted for Percentage {
 }
 
 impl FromStr for Percentage {
-    type Err = Box<dyn std::error::Error>;
+    type Err = Box<dyn std::error::Error + Send + Sync>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let re = Regex::new(Self::restriction_pattern()).expect("valid regexp should be provided");
@@ -0,0 +1,132 @@
+//! Resource limits applied while parsing documents from untrusted sources.
+
+use std::fmt::{Display, Formatter};
+
+/// Depth no document may exceed, whatever its `ParseLimits` say. Writing, cloning, comparing and dropping an `XmlNode`
+/// recurse once per level, and so do the typed parsers, so documents are never nested deeper than what these can
+/// handle on the stack of a spawned thread.
+pub const MAX_DEPTH: usize = 1024;
+
+/// Limits enforced by `XmlReader` and the functions building `XmlNode` trees on top of it. A document exceeding any of
+/// them fails to parse with an `InvalidXmlError` of kind `InvalidXmlErrorKind::LimitExceeded`, before it can overflow
+/// the stack or exhaust the memory.
+///
+/// The default limits are generous enough for any document produced by an Office application, but keep a zip bomb or
+/// a maliciously nested document in check. None disables the limit, apart from the depth, which is capped at
+/// `MAX_DEPTH` even then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum number of nested elements, including the root element. None and values above `MAX_DEPTH` mean
+    /// `MAX_DEPTH`.
+    ///
+    /// Defaults to 256
+    pub max_depth: Option<usize>,
+    /// Maximum size of a part in bytes. For parts stored in a zip archive it's the uncompressed size.
+    ///
+    /// Defaults to 256 MiB
+    pub max_part_size: Option<u64>,
+    /// Maximum number of elements in a part.
+    ///
+    /// Defaults to 10 000 000
+    pub max_node_count: Option<usize>,
+    /// Maximum length of an attribute value in bytes, measured before unescaping.
+    ///
+    /// Defaults to 1 MiB
+    pub max_attribute_length: Option<usize>,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: Some(256),
+            max_part_size: Some(256 * 1024 * 1024),
+            max_node_count: Some(10_000_000),
+            max_attribute_length: Some(1024 * 1024),
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Limits that are never exceeded, apart from the depth, which is capped at `MAX_DEPTH`. Only use them for trusted
+    /// documents.
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: None,
+            max_part_size: None,
+            max_node_count: None,
+            max_attribute_length: None,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_max_part_size(mut self, max_part_size: Option<u64>) -> Self {
+        self.max_part_size = max_part_size;
+        self
+    }
+
+    pub fn with_max_node_count(mut self, max_node_count: Option<usize>) -> Self {
+        self.max_node_count = max_node_count;
+        self
+    }
+
+    pub fn with_max_attribute_length(mut self, max_attribute_length: Option<usize>) -> Self {
+        self.max_attribute_length = max_attribute_length;
+        self
+    }
+
+    pub(crate) fn check_depth(&self, depth: usize) -> Result<(), ParseLimit> {
+        let max = self.max_depth.map_or(MAX_DEPTH, |max| max.min(MAX_DEPTH));
+        if depth > max {
+            return Err(ParseLimit::Depth(max));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_part_size(&self, part_size: u64) -> Result<(), ParseLimit> {
+        match self.max_part_size {
+            Some(max) if part_size > max => Err(ParseLimit::PartSize(max)),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_node_count(&self, node_count: usize) -> Result<(), ParseLimit> {
+        match self.max_node_count {
+            Some(max) if node_count > max => Err(ParseLimit::NodeCount(max)),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_attribute_length(&self, attribute_length: usize) -> Result<(), ParseLimit> {
+        match self.max_attribute_length {
+            Some(max) if attribute_length > max => Err(ParseLimit::AttributeLength(max)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A limit of `ParseLimits` that was exceeded, along with its configured maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseLimit {
+    Depth(usize),
+    PartSize(u64),
+    NodeCount(usize),
+    AttributeLength(usize),
+}
+
+impl Display for ParseLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            ParseLimit::Depth(max) => write!(f, "element depth exceeds the maximum of {}", max),
+            ParseLimit::PartSize(max) => write!(f, "part size exceeds the maximum of {} bytes", max),
+            ParseLimit::NodeCount(max) => write!(f, "number of elements exceeds the maximum of {}", max),
+            ParseLimit::AttributeLength(max) => {
+                write!(f, "attribute value length exceeds the maximum of {} bytes", max)
+            }
+        }
+    }
+}
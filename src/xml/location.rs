@@ -0,0 +1,98 @@
+//! Source positions of parsed elements.
+
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+/// The ancestry path of an element, e.g. `a:theme/a:themeElements/a:fmtScheme/a:fillStyleLst`.
+///
+/// Like in XPath, the 1-based position of the element among its siblings of the same name is appended in brackets,
+/// except for the first one. Paths of sibling elements share the path of their parent, so keeping one for every
+/// parsed element is cheap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementPath(Arc<PathSegment>);
+
+#[derive(Debug, PartialEq, Eq)]
+struct PathSegment {
+    parent: Option<ElementPath>,
+    name: String,
+    position: usize,
+}
+
+impl ElementPath {
+    /// Creates the path of a root element
+    pub fn root<T: Into<String>>(name: T) -> Self {
+        Self::new(None, name.into(), 1)
+    }
+
+    /// Creates the path of a child element, where `position` is the 1-based position of the child among its siblings
+    /// of the same name.
+    pub fn child<T: Into<String>>(&self, name: T, position: usize) -> Self {
+        Self::new(Some(self.clone()), name.into(), position)
+    }
+
+    fn new(parent: Option<ElementPath>, name: String, position: usize) -> Self {
+        ElementPath(Arc::new(PathSegment { parent, name, position }))
+    }
+
+    pub fn parent(&self) -> Option<&ElementPath> {
+        self.0.parent.as_ref()
+    }
+
+    /// Qualified name of the element the path points to
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// 1-based position of the element among its siblings of the same name
+    pub fn position(&self) -> usize {
+        self.0.position
+    }
+
+    /// Number of elements in the path
+    pub fn depth(&self) -> usize {
+        1 + self.parent().map(ElementPath::depth).unwrap_or_default()
+    }
+}
+
+impl Display for ElementPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        if let Some(parent) = self.parent() {
+            write!(f, "{}/", parent)?;
+        }
+
+        match self.position() {
+            1 => write!(f, "{}", self.name()),
+            position => write!(f, "{}[{}]", self.name(), position),
+        }
+    }
+}
+
+/// Location of an element (or of an error) in the document it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// 1-based line number of the start tag
+    pub line: usize,
+    /// 1-based column of the start tag, counted in bytes
+    pub column: usize,
+    /// Byte offset of the start tag from the beginning of the document
+    pub offset: usize,
+    /// Path of the element. Only None for locations outside of the root element, e.g. in errors reported for
+    /// malformed documents.
+    pub path: Option<ElementPath>,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{} ", path)?;
+        }
+
+        write!(
+            f,
+            "(line {}, column {}, byte offset {})",
+            self.line, self.column, self.offset
+        )
+    }
+}
@@ -0,0 +1,603 @@
+//! Markup Compatibility and Extensibility (ECMA-376 Part 3) preprocessing.
+//!
+//! Office applications write markup introduced by newer versions of the file formats in a way that older consumers
+//! can still process: alternatives are wrapped in `mc:AlternateContent`, and namespaces that can safely be skipped
+//! are listed in `mc:Ignorable`. `MarkupCompatibility` rewrites an `XmlNode` tree into the markup a consumer
+//! understanding a given set of namespaces should see, so the typed parsers never encounter any of these constructs.
+//!
+//! # Examples
+//!
+//! ```
+//! use msoffice_shared::xml::{mce::MarkupCompatibility, ns, XmlNode};
+//! use std::str::FromStr;
+//!
+//! let xml = r#"<a:graphicData xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+//!     xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+//!     xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main">
+//!     <mc:AlternateContent>
+//!         <mc:Choice Requires="a14"><a14:m/></mc:Choice>
+//!         <mc:Fallback><a:pic/></mc:Fallback>
+//!     </mc:AlternateContent>
+//! </a:graphicData>"#;
+//!
+//! let node = XmlNode::from_str(xml).unwrap();
+//! let processed = MarkupCompatibility::default().process(node.clone()).unwrap();
+//! assert!(processed.child_nodes[0].is(ns::DRAWINGML, "pic"));
+//!
+//! let processed = MarkupCompatibility::default()
+//!     .understand(ns::DRAWINGML_2010)
+//!     .process(node)
+//!     .unwrap();
+//! assert!(processed.child_nodes[0].is(ns::DRAWINGML_2010, "m"));
+//! ```
+
+use super::{ns, ns::NamespaceScope, XmlContent, XmlNode};
+use crate::error::{MarkupCompatibilityError, MarkupCompatibilityErrorKind};
+use std::vec;
+
+/// Namespaces of the vocabularies defined by ECMA-376, which are understood by default.
+const ECMA_376_NAMESPACES: &[&str] = &[
+    ns::DRAWINGML,
+    ns::DRAWINGML_PICTURE,
+    ns::DRAWINGML_CHART,
+    ns::DRAWINGML_CHART_DRAWING,
+    ns::DRAWINGML_DIAGRAM,
+    ns::DRAWINGML_LOCKED_CANVAS,
+    ns::DRAWINGML_WORDPROCESSING,
+    ns::DRAWINGML_SPREADSHEET,
+    ns::PRESENTATIONML,
+    ns::WORDPROCESSINGML,
+    ns::SPREADSHEETML,
+    ns::RELATIONSHIPS,
+    ns::EXTENDED_PROPERTIES,
+    ns::CUSTOM_PROPERTIES,
+    ns::DOC_PROPS_VTYPES,
+    ns::PACKAGE_RELATIONSHIPS,
+    ns::CONTENT_TYPES,
+    ns::CORE_PROPERTIES,
+    ns::DUBLIN_CORE,
+    ns::DUBLIN_CORE_TERMS,
+    ns::DUBLIN_CORE_TYPE,
+];
+
+/// Markup compatibility processor configured with the set of namespaces the consumer understands.
+///
+/// Processing a tree
+/// * replaces every `mc:AlternateContent` with the content of its first `mc:Choice` whose `Requires` namespaces are
+///   all understood, or with the content of its `mc:Fallback` if there's no such choice,
+/// * removes the elements and attributes that are in a namespace marked as `mc:Ignorable` but not understood. Ignored
+///   elements listed in `mc:ProcessContent` are replaced by their content instead,
+/// * fails if a namespace listed in `mc:MustUnderstand` is not understood,
+/// * removes the markup compatibility attributes themselves.
+///
+/// Elements in namespaces that are neither understood nor ignorable are kept, so the typed parsers can decide how to
+/// handle them. `mc:PreserveElements` and `mc:PreserveAttributes` are only meaningful to editors and are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupCompatibility {
+    understood_namespaces: Vec<String>,
+}
+
+impl Default for MarkupCompatibility {
+    /// Understands the namespaces defined by ECMA-376, but none of the extensions added by later Office versions.
+    fn default() -> Self {
+        Self {
+            understood_namespaces: ECMA_376_NAMESPACES.iter().map(|uri| String::from(*uri)).collect(),
+        }
+    }
+}
+
+// Markup compatibility attributes in effect for an element, inherited from its ancestors
+#[derive(Debug, Clone, Default)]
+struct CompatibilityContext {
+    ignorable_namespaces: Vec<String>,
+    // Pairs of namespace URI and local name, where the local name can be "*"
+    process_content: Vec<(String, String)>,
+}
+
+impl MarkupCompatibility {
+    /// Creates a processor that doesn't understand any namespace apart from the markup compatibility namespace.
+    pub fn new() -> Self {
+        Self {
+            understood_namespaces: Vec::new(),
+        }
+    }
+
+    /// Adds a namespace to the set of understood namespaces. The strict and transitional URIs of a vocabulary are
+    /// interchangeable.
+    pub fn understand<T: Into<String>>(mut self, namespace: T) -> Self {
+        self.understood_namespaces.push(namespace.into());
+        self
+    }
+
+    pub fn understands(&self, namespace: &str) -> bool {
+        ns::is_same_namespace(namespace, ns::MARKUP_COMPATIBILITY)
+            || namespace == ns::XML
+            || namespace == ns::XMLNS
+            || self
+                .understood_namespaces
+                .iter()
+                .any(|understood| ns::is_same_namespace(understood, namespace))
+    }
+
+    /// Processes `root` and its descendants. See the type level documentation for the rules applied.
+    pub fn process(&self, root: XmlNode) -> Result<XmlNode, MarkupCompatibilityError> {
+        // The tree is walked without recursion, so deeply nested documents can't overflow the stack
+        let mut scope = NamespaceScope::new();
+        let mut open_elements: Vec<OpenElement> = Vec::new();
+        let mut root_nodes = Vec::new();
+        let mut opened = self.open_node(root, &mut scope, &CompatibilityContext::default())?;
+        loop {
+            match opened {
+                Opened::Open(element) => open_elements.push(*element),
+                Opened::Done(nodes) => match open_elements.last_mut() {
+                    Some(parent) => parent.push_processed(nodes),
+                    None => root_nodes = nodes,
+                },
+            }
+
+            let element = match open_elements.last_mut() {
+                Some(element) => element,
+                None => break,
+            };
+
+            opened = match element.next_child_node() {
+                Some(child_node) => self.open_node(child_node, &mut scope, &element.context)?,
+                None => {
+                    let element = open_elements.pop().expect("The element was just looked at");
+                    scope.leave(element.namespace_marker);
+                    Opened::Done(element.finish())
+                }
+            };
+        }
+
+        match root_nodes.len() {
+            1 => Ok(root_nodes.remove(0)),
+            _ => Err(MarkupCompatibilityError::new(
+                MarkupCompatibilityErrorKind::InvalidRootElement,
+                None,
+            )),
+        }
+    }
+
+    // Processes `node` as far as it's possible without processing its content. The namespace declarations of the node
+    // stay in scope while its content is processed.
+    fn open_node(
+        &self,
+        node: XmlNode,
+        scope: &mut NamespaceScope,
+        context: &CompatibilityContext,
+    ) -> Result<Opened, MarkupCompatibilityError> {
+        let marker = scope.enter(node.attributes.iter());
+        let opened = self.open_node_in_scope(node, marker, scope, context);
+        if !matches!(opened, Ok(Opened::Open(_))) {
+            scope.leave(marker);
+        }
+
+        opened
+    }
+
+    fn open_node_in_scope(
+        &self,
+        node: XmlNode,
+        namespace_marker: usize,
+        scope: &mut NamespaceScope,
+        context: &CompatibilityContext,
+    ) -> Result<Opened, MarkupCompatibilityError> {
+        let namespace = node
+            .namespace
+            .clone()
+            .or_else(|| scope.resolve_element(&node.name).map(String::from));
+
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => return self.open_kept_node(node, namespace_marker, scope, context),
+        };
+
+        if ns::is_same_namespace(&namespace, ns::MARKUP_COMPATIBILITY) {
+            return match node.local_name() {
+                "AlternateContent" => self.open_alternate_content(node, namespace_marker, scope, context),
+                _ => Err(MarkupCompatibilityError::new(
+                    MarkupCompatibilityErrorKind::UnexpectedElement,
+                    Some(&node),
+                )),
+            };
+        }
+
+        if self.understands(&namespace) || !context.is_ignorable(&namespace) {
+            return self.open_kept_node(node, namespace_marker, scope, context);
+        }
+
+        if !context.processes_content(&namespace, node.local_name()) {
+            return Ok(Opened::Done(Vec::new()));
+        }
+
+        // The content of an ignored element listed in mc:ProcessContent is processed as if it was the content of the
+        // ignored element's parent, but the compatibility attributes of the ignored element still apply to it
+        let context = self.element_context(&node, scope, context)?;
+        Ok(Opened::Open(Box::new(OpenElement::replaced(
+            node,
+            context,
+            namespace_marker,
+        ))))
+    }
+
+    fn open_kept_node(
+        &self,
+        mut node: XmlNode,
+        namespace_marker: usize,
+        scope: &mut NamespaceScope,
+        context: &CompatibilityContext,
+    ) -> Result<Opened, MarkupCompatibilityError> {
+        let context = self.element_context(&node, scope, context)?;
+
+        let removed_attributes: Vec<String> = node
+            .attributes
+            .keys()
+            .filter(|attr| match attribute_namespace(&node, scope, attr) {
+                Some(namespace) if ns::is_same_namespace(namespace, ns::MARKUP_COMPATIBILITY) => true,
+                Some(namespace) => !self.understands(namespace) && context.is_ignorable(namespace),
+                None => false,
+            })
+            .cloned()
+            .collect();
+
+        for attr in &removed_attributes {
+            node.attributes.remove(attr);
+            node.attribute_namespaces.remove(attr);
+        }
+
+        if node.child_nodes.is_empty() {
+            return Ok(Opened::Done(vec![node]));
+        }
+
+        Ok(Opened::Open(Box::new(OpenElement::kept(
+            node,
+            context,
+            namespace_marker,
+        ))))
+    }
+
+    fn open_alternate_content(
+        &self,
+        mut node: XmlNode,
+        namespace_marker: usize,
+        scope: &mut NamespaceScope,
+        context: &CompatibilityContext,
+    ) -> Result<Opened, MarkupCompatibilityError> {
+        let context = self.element_context(&node, scope, context)?;
+        let (branches, _) = node.take_ordered_content();
+
+        let mut selected = None;
+        let mut fallback_seen = false;
+        for branch in branches {
+            let is_choice = match (branch.local_name_in(ns::MARKUP_COMPATIBILITY), fallback_seen) {
+                (Some("Choice"), false) => true,
+                (Some("Fallback"), false) => false,
+                _ => {
+                    return Err(MarkupCompatibilityError::new(
+                        MarkupCompatibilityErrorKind::InvalidAlternateContent,
+                        Some(&branch),
+                    ))
+                }
+            };
+
+            fallback_seen = !is_choice;
+            if selected.is_some() {
+                continue;
+            }
+
+            let marker = scope.enter(branch.attributes.iter());
+            let is_selected = if is_choice {
+                self.is_choice_selectable(&branch, scope)
+            } else {
+                Ok(true)
+            };
+            scope.leave(marker);
+
+            if is_selected? {
+                selected = Some(branch);
+            }
+        }
+
+        let selected = match selected {
+            Some(selected) => selected,
+            None => return Ok(Opened::Done(Vec::new())),
+        };
+
+        // The declarations of the selected branch are left together with the ones of mc:AlternateContent
+        scope.enter(selected.attributes.iter());
+        let context = self.element_context(&selected, scope, &context)?;
+        Ok(Opened::Open(Box::new(OpenElement::replaced(
+            selected,
+            context,
+            namespace_marker,
+        ))))
+    }
+
+    fn is_choice_selectable(&self, choice: &XmlNode, scope: &NamespaceScope) -> Result<bool, MarkupCompatibilityError> {
+        let requires = choice.attributes.get("Requires").ok_or_else(|| {
+            MarkupCompatibilityError::new(MarkupCompatibilityErrorKind::InvalidAlternateContent, Some(choice))
+        })?;
+
+        for prefix in requires.split_whitespace() {
+            if !self.understands(resolve_prefix(choice, scope, prefix)?) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    // Checks mc:MustUnderstand and extends the inherited context with the mc:Ignorable and mc:ProcessContent
+    // attributes of `node`
+    fn element_context(
+        &self,
+        node: &XmlNode,
+        scope: &NamespaceScope,
+        context: &CompatibilityContext,
+    ) -> Result<CompatibilityContext, MarkupCompatibilityError> {
+        let mut context = context.clone();
+        for (attr, value) in &node.attributes {
+            match attribute_namespace(node, scope, attr) {
+                Some(namespace) if ns::is_same_namespace(namespace, ns::MARKUP_COMPATIBILITY) => (),
+                _ => continue,
+            }
+
+            match attr.rsplit(':').next().unwrap_or_default() {
+                "MustUnderstand" => {
+                    for prefix in value.split_whitespace() {
+                        let namespace = resolve_prefix(node, scope, prefix)?;
+                        if !self.understands(namespace) {
+                            return Err(MarkupCompatibilityError::new(
+                                MarkupCompatibilityErrorKind::NotUnderstood(String::from(namespace)),
+                                Some(node),
+                            ));
+                        }
+                    }
+                }
+                "Ignorable" => {
+                    for prefix in value.split_whitespace() {
+                        let namespace = resolve_prefix(node, scope, prefix)?;
+                        context.ignorable_namespaces.push(String::from(namespace));
+                    }
+                }
+                "ProcessContent" => {
+                    for name in value.split_whitespace() {
+                        let (prefix, local_name) = match name.find(':') {
+                            Some(idx) => (&name[..idx], &name[idx + 1..]),
+                            None => ("", name),
+                        };
+
+                        let namespace = resolve_prefix(node, scope, prefix)?;
+                        context
+                            .process_content
+                            .push((String::from(namespace), String::from(local_name)));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(context)
+    }
+}
+
+// The result of processing a node as far as it's possible without processing its content
+enum Opened {
+    // The nodes the node is replaced with, which is the node itself if it's kept
+    Done(Vec<XmlNode>),
+    // The content of the node has to be processed first
+    Open(Box<OpenElement>),
+}
+
+// An element whose content is being processed
+struct OpenElement {
+    // The element rebuilt from its processed content, or None if it's replaced by its processed child elements
+    node: Option<XmlNode>,
+    context: CompatibilityContext,
+    namespace_marker: usize,
+    child_nodes: Vec<Option<XmlNode>>,
+    content: vec::IntoIter<XmlContent>,
+    processed_child_nodes: Vec<XmlNode>,
+    processed_content: Vec<XmlContent>,
+}
+
+impl OpenElement {
+    fn kept(node: XmlNode, context: CompatibilityContext, namespace_marker: usize) -> Self {
+        Self::new(node, true, context, namespace_marker)
+    }
+
+    fn replaced(node: XmlNode, context: CompatibilityContext, namespace_marker: usize) -> Self {
+        Self::new(node, false, context, namespace_marker)
+    }
+
+    fn new(mut node: XmlNode, is_kept: bool, context: CompatibilityContext, namespace_marker: usize) -> Self {
+        let (child_nodes, content) = node.take_ordered_content();
+        Self {
+            node: if is_kept { Some(node) } else { None },
+            context,
+            namespace_marker,
+            child_nodes: child_nodes.into_iter().map(Some).collect(),
+            content: content.into_iter(),
+            processed_child_nodes: Vec::new(),
+            processed_content: Vec::new(),
+        }
+    }
+
+    // Returns the next child element to process. The text before it is kept in place.
+    fn next_child_node(&mut self) -> Option<XmlNode> {
+        for content in &mut self.content {
+            match content {
+                XmlContent::Element(idx) => {
+                    return Some(self.child_nodes[idx].take().expect("Elements are only referenced once"))
+                }
+                content => self.processed_content.push(content),
+            }
+        }
+
+        None
+    }
+
+    fn push_processed(&mut self, nodes: Vec<XmlNode>) {
+        for node in nodes {
+            self.processed_content
+                .push(XmlContent::Element(self.processed_child_nodes.len()));
+            self.processed_child_nodes.push(node);
+        }
+    }
+
+    // Returns the nodes the element is replaced with
+    fn finish(self) -> Vec<XmlNode> {
+        match self.node {
+            Some(mut node) => {
+                // Whitespace was already handled when the node was parsed, so everything that's left is kept
+                node.set_content(self.processed_child_nodes, self.processed_content, true);
+                vec![node]
+            }
+            None => self.processed_child_nodes,
+        }
+    }
+}
+
+impl CompatibilityContext {
+    fn is_ignorable(&self, namespace: &str) -> bool {
+        self.ignorable_namespaces
+            .iter()
+            .any(|ignorable| ns::is_same_namespace(ignorable, namespace))
+    }
+
+    fn processes_content(&self, namespace: &str, local_name: &str) -> bool {
+        self.process_content.iter().any(|(process_namespace, process_name)| {
+            ns::is_same_namespace(process_namespace, namespace) && (process_name == "*" || process_name == local_name)
+        })
+    }
+}
+
+fn attribute_namespace<'a>(node: &'a XmlNode, scope: &'a NamespaceScope, attr: &str) -> Option<&'a str> {
+    node.attribute_namespace(attr).or_else(|| scope.resolve_attribute(attr))
+}
+
+// Prefixes listed in the compatibility attributes must be declared, even the ones that are ignored
+fn resolve_prefix<'a>(
+    node: &XmlNode,
+    scope: &'a NamespaceScope,
+    prefix: &str,
+) -> Result<&'a str, MarkupCompatibilityError> {
+    scope.resolve(prefix).ok_or_else(|| {
+        MarkupCompatibilityError::new(
+            MarkupCompatibilityErrorKind::UndeclaredPrefix(String::from(prefix)),
+            Some(node),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::limits::{ParseLimits, MAX_DEPTH};
+    use std::str::FromStr;
+
+    const SLIDE_XML: &str = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+        xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+        xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+        xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main"
+        xmlns:v="urn:schemas-microsoft-com:vml"
+        mc:Ignorable="p14 v" mc:ProcessContent="v:wrapper" v:ext="edit">
+        <p:cSld p14:attr="1">
+            <p14:creationId val="1"/>
+            <v:wrapper><p:spTree/></v:wrapper>
+            <mc:AlternateContent>
+                <mc:Choice xmlns:p15="http://schemas.microsoft.com/office/powerpoint/2012/main" Requires="p15">
+                    <p15:guide/>
+                </mc:Choice>
+                <mc:Choice Requires="p14"><p14:timing/></mc:Choice>
+                <mc:Fallback><p:timing/></mc:Fallback>
+            </mc:AlternateContent>
+        </p:cSld>
+    </p:sld>"#;
+
+    #[test]
+    fn test_ignorable_and_alternate_content() {
+        let node = XmlNode::from_str(SLIDE_XML).unwrap();
+        let processed = MarkupCompatibility::default().process(node.clone()).unwrap();
+        assert!(!processed.attributes.contains_key("mc:Ignorable"));
+        assert!(!processed.attributes.contains_key("v:ext"));
+
+        let common_slide_data = &processed.child_nodes[0];
+        assert!(common_slide_data.attributes.is_empty());
+        let names: Vec<&str> = common_slide_data
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["p:spTree", "p:timing"]);
+
+        let processed = MarkupCompatibility::default()
+            .understand(ns::PRESENTATIONML_2010)
+            .process(node)
+            .unwrap();
+        let common_slide_data = &processed.child_nodes[0];
+        assert_eq!(
+            common_slide_data.attributes.get("p14:attr").map(String::as_str),
+            Some("1")
+        );
+        let names: Vec<&str> = common_slide_data
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["p14:creationId", "p:spTree", "p14:timing"]);
+    }
+
+    #[test]
+    fn test_must_understand() {
+        let xml = r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+            xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+            xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" mc:MustUnderstand="p14"/>"#;
+        let node = XmlNode::from_str(xml).unwrap();
+
+        let err = MarkupCompatibility::default().process(node.clone()).unwrap_err();
+        assert_eq!(
+            err.kind,
+            MarkupCompatibilityErrorKind::NotUnderstood(String::from(ns::PRESENTATIONML_2010))
+        );
+        assert!(MarkupCompatibility::default()
+            .understand(ns::PRESENTATIONML_2010)
+            .process(node)
+            .is_ok());
+
+        let xml = r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+            xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" mc:Ignorable="x"/>"#;
+        let err = MarkupCompatibility::default()
+            .process(XmlNode::from_str(xml).unwrap())
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            MarkupCompatibilityErrorKind::UndeclaredPrefix(String::from("x"))
+        );
+    }
+
+    #[test]
+    fn test_deeply_nested() {
+        let levels = (MAX_DEPTH - 1) / 2;
+        let xml = format!(
+            r#"<a:r xmlns:a="{}" xmlns:mc="{}" xmlns:x="urn:x" mc:Ignorable="x" mc:ProcessContent="x:w">{}{}</a:r>"#,
+            ns::DRAWINGML,
+            ns::MARKUP_COMPATIBILITY,
+            "<x:w><a:p>".repeat(levels),
+            "</a:p></x:w>".repeat(levels),
+        );
+        let node = XmlNode::from_str_with_limits(&xml, &ParseLimits::unlimited()).unwrap();
+        let processed = MarkupCompatibility::default().process(node).unwrap();
+
+        let mut depth = 1;
+        let mut node = &processed;
+        while let Some(child_node) = node.child_nodes.first() {
+            assert!(child_node.is(ns::DRAWINGML, "p"));
+            depth += 1;
+            node = child_node;
+        }
+
+        assert_eq!(depth, levels + 1);
+    }
+}
@@ -0,0 +1,758 @@
+pub mod limits;
+pub mod location;
+pub mod mce;
+pub mod ns;
+pub mod reader;
+pub mod unknown;
+pub mod writer;
+
+use self::{
+    limits::ParseLimits,
+    location::SourceLocation,
+    mce::MarkupCompatibility,
+    ns::NamespaceScope,
+    reader::XmlReader,
+    writer::{WriteOptions, XmlWriter},
+};
+use crate::error::{
+    self, InvalidAttributeValueError, InvalidTextValueError, InvalidXmlError, InvalidXmlErrorKind,
+    MissingAttributeError, ParseBoolError,
+};
+use crate::{
+    diagnostics::recover_attribute,
+    xsdtypes::{ParseContext, XsdType},
+};
+use quick_xml::events::BytesStart;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{self, BufRead, BufReader, Read, Take, Write},
+    str::FromStr,
+};
+use zip::read::ZipFile;
+
+/// A piece of the content of an element in document order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlContent {
+    /// Character data, stored unescaped
+    Text(String),
+    /// Content of a CDATA section
+    CData(String),
+    /// A child element, referenced by its index in `child_nodes`
+    Element(usize),
+}
+
+/// A piece of the content of an element in document order, as returned by `XmlNode::ordered_content`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum XmlContentRef<'a> {
+    Text(&'a str),
+    CData(&'a str),
+    Element(&'a XmlNode),
+}
+
+/// Represents an implementation independent xml node
+#[derive(Debug, Clone)]
+pub struct XmlNode {
+    pub name: String,
+    pub child_nodes: Vec<XmlNode>,
+    pub attributes: HashMap<String, String>,
+    /// Character data of the element. If the text is split by child elements, CDATA sections, comments or processing
+    /// instructions, this is the concatenation of all the pieces.
+    ///
+    /// Whitespace only text between child elements is considered formatting and is dropped, unless
+    /// `xml:space="preserve"` is in effect for the element.
+    pub text: Option<String>,
+    /// Ordered content of a mixed content element, i.e. where the text is interleaved with child elements or
+    /// contains CDATA sections. Empty when the content is fully described by `text` followed by `child_nodes`.
+    ///
+    /// The content is only used if it's still consistent with `text` and `child_nodes`. If either of them is
+    /// modified, the node is handled as if it had no ordered content.
+    pub content: Vec<XmlContent>,
+    /// Namespace URI the element's prefix (or the default namespace) resolved to while parsing. None if the element
+    /// isn't bound to any namespace, e.g. when it's created in code or parsed from a fragment without declarations.
+    pub namespace: Option<String>,
+    /// Resolved namespace URIs of the prefixed attributes, keyed by the attribute's qualified name.
+    pub attribute_namespaces: HashMap<String, String>,
+    /// Where the element's start tag was found while parsing. None for nodes created in code.
+    ///
+    /// The location isn't part of the node's value, so it's ignored when nodes are compared.
+    pub location: Option<SourceLocation>,
+}
+
+impl Eq for XmlNode {}
+
+impl PartialEq for XmlNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.child_nodes == other.child_nodes
+            && self.attributes == other.attributes
+            && self.text == other.text
+            && self.content == other.content
+            && self.namespace == other.namespace
+            && self.attribute_namespaces == other.attribute_namespaces
+    }
+}
+
+impl Display for XmlNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+        write!(f, "name: {}", self.name)
+    }
+}
+
+impl XmlNode {
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            child_nodes: Vec::new(),
+            attributes: HashMap::new(),
+            text: None,
+            content: Vec::new(),
+            namespace: None,
+            attribute_namespaces: HashMap::new(),
+            location: None,
+        }
+    }
+
+    /// Creates a node that's bound to the namespace conventionally used with the prefix of `name` (see
+    /// `ns::conventional_namespace`). Unprefixed and unknown names create an unbound node.
+    pub fn new_with_conventional_namespace<T: Into<String>>(name: T) -> Self {
+        let mut node = Self::new(name);
+        node.namespace = node.prefix().and_then(ns::conventional_namespace).map(String::from);
+        node
+    }
+
+    /// Creates a node that's bound to the given namespace URI.
+    pub fn new_with_namespace<T, U>(name: T, namespace: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        Self {
+            namespace: Some(namespace.into()),
+            ..Self::new(name)
+        }
+    }
+
+    pub fn local_name(&self) -> &str {
+        match self.name.find(':') {
+            Some(idx) => self.name.split_at(idx + 1).1,
+            None => self.name.as_str(),
+        }
+    }
+
+    /// Returns the prefix of the element's qualified name if it has any
+    pub fn prefix(&self) -> Option<&str> {
+        self.name.find(':').map(|idx| &self.name[..idx])
+    }
+
+    /// Returns the resolved namespace URI of the element
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Checks whether the element is in `namespace` (strict and transitional URIs are considered equal). Elements
+    /// that aren't bound to any namespace are in no namespace, so trees built in code should create their nodes with
+    /// `new_with_namespace` or `new_with_conventional_namespace`.
+    pub fn is_in_namespace(&self, namespace: &str) -> bool {
+        self.namespace()
+            .map(|uri| ns::is_same_namespace(uri, namespace))
+            .unwrap_or(false)
+    }
+
+    /// Checks whether the element is `local_name` in `namespace`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use msoffice_shared::xml::{ns, XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<d:solidFill xmlns:d="http://schemas.openxmlformats.org/drawingml/2006/main"/>"#;
+    /// let node = XmlNode::from_str(xml).unwrap();
+    /// assert!(node.is(ns::DRAWINGML, "solidFill"));
+    /// assert!(!node.is(ns::PRESENTATIONML, "solidFill"));
+    /// ```
+    pub fn is(&self, namespace: &str, local_name: &str) -> bool {
+        self.local_name() == local_name && self.is_in_namespace(namespace)
+    }
+
+    /// Returns the local name of the element if it's in `namespace`, otherwise None.
+    pub fn local_name_in(&self, namespace: &str) -> Option<&str> {
+        if self.is_in_namespace(namespace) {
+            Some(self.local_name())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the resolved namespace URI of an attribute of this element.
+    pub fn attribute_namespace(&self, attr: &str) -> Option<&str> {
+        self.attribute_namespaces.get(attr).map(String::as_str)
+    }
+
+    /// Looks up an attribute by its namespace and local name regardless of the prefix used in the document.
+    /// Prefixed attributes whose prefix couldn't be resolved are matched by their local name only.
+    pub fn attribute_ns(&self, namespace: &str, local_name: &str) -> Option<&String> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| match attr.find(':') {
+                Some(idx) if &attr[idx + 1..] == local_name => self
+                    .attribute_namespace(attr)
+                    .map(|uri| ns::is_same_namespace(uri, namespace))
+                    .unwrap_or(true),
+                _ => false,
+            })
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the namespace declarations made on this element as (prefix, uri) pairs. The default namespace
+    /// declaration is returned with an empty prefix.
+    pub fn namespace_declarations(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes.iter().filter_map(|(attr, value)| {
+            if attr == "xmlns" {
+                Some(("", value.as_str()))
+            } else {
+                attr.strip_prefix("xmlns:").map(|prefix| (prefix, value.as_str()))
+            }
+        })
+    }
+
+    /// Returns the content of the element in document order. If the element has no (consistent) ordered content,
+    /// the text is returned first followed by the child elements.
+    pub fn ordered_content(&self) -> Vec<XmlContentRef<'_>> {
+        if self.has_consistent_content() {
+            return self
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    XmlContent::Text(text) if text.is_empty() => None,
+                    XmlContent::Text(text) => Some(XmlContentRef::Text(text)),
+                    XmlContent::CData(data) => Some(XmlContentRef::CData(data)),
+                    XmlContent::Element(idx) => Some(XmlContentRef::Element(&self.child_nodes[*idx])),
+                })
+                .collect();
+        }
+
+        self.text
+            .iter()
+            .filter(|text| !text.is_empty())
+            .map(|text| XmlContentRef::Text(text))
+            .chain(self.child_nodes.iter().map(XmlContentRef::Element))
+            .collect()
+    }
+
+    /// Checks whether `xml:space="preserve"` is set on this element
+    pub fn preserves_space(&self) -> bool {
+        self.attributes.get("xml:space").map(String::as_str) == Some("preserve")
+    }
+
+    /// Parses the text content of this node. Returns None if the node has no text. On failure the error refers to the
+    /// location of the node.
+    pub fn parse_text<T>(&self) -> error::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.text
+            .as_ref()
+            .map(|text| {
+                text.parse()
+                    .map_err(|err| InvalidTextValueError::new(self, text.as_str(), err).into())
+            })
+            .transpose()
+    }
+
+    /// Parses `value` of the attribute `attr` of this node. On failure the error refers to the attribute and the
+    /// location of the node.
+    pub fn parse_attribute_value<T>(&self, attr: &str, value: &str) -> error::Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        value
+            .parse()
+            .map_err(|err| InvalidAttributeValueError::new(self, attr, value, err).into())
+    }
+
+    /// Parses `value` of the attribute `attr` of this node as an xml bool.
+    pub fn parse_bool_attribute_value(&self, attr: &str, value: &str) -> error::Result<bool> {
+        parse_xml_bool(value).map_err(|err| InvalidAttributeValueError::new(self, attr, value, err).into())
+    }
+
+    /// Parses the attribute `attr` if it's present. In lenient mode an invalid value is reported and treated as if
+    /// the attribute was missing.
+    pub fn parse_attribute<T>(&self, attr: &str, context: &mut ParseContext) -> error::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        match self.attributes.get(attr) {
+            Some(value) => recover_attribute(self.parse_attribute_value(attr, value), context),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses the attribute `attr`, which is required by the schema, so it's never skipped in lenient mode.
+    pub fn parse_required_attribute<T>(&self, attr: &'static str) -> error::Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let value = self
+            .attributes
+            .get(attr)
+            .ok_or_else(|| MissingAttributeError::at(self, attr))?;
+        self.parse_attribute_value(attr, value)
+    }
+
+    /// Parses the attribute `attr` as an xml bool if it's present. In lenient mode an invalid value is reported and
+    /// treated as if the attribute was missing.
+    pub fn parse_bool_attribute(&self, attr: &str, context: &mut ParseContext) -> error::Result<Option<bool>> {
+        match self.attributes.get(attr) {
+            Some(value) => recover_attribute(self.parse_bool_attribute_value(attr, value), context),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses the root element of an xml document read from `reader`, without reading the whole document to a string
+    /// first. The default `ParseLimits` are enforced.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, InvalidXmlError> {
+        Self::from_reader_with_limits(reader, &Default::default())
+    }
+
+    /// Parses the root element of an xml document read from `reader`, enforcing the given limits.
+    pub fn from_reader_with_limits<R: BufRead>(reader: R, limits: &ParseLimits) -> Result<Self, InvalidXmlError> {
+        limited_xml_reader(reader, limits).read_root_node()
+    }
+
+    /// Parses an xml document string, enforcing the given limits. `FromStr` enforces the default `ParseLimits`.
+    pub fn from_str_with_limits(xml_string: &str, limits: &ParseLimits) -> Result<Self, InvalidXmlError> {
+        XmlReader::from_str(xml_string)
+            .with_limits(limits.clone())
+            .read_root_node()
+    }
+
+    /// Writes the node and its descendants as an xml document to `writer`. Fails if a prefix used in the tree can't
+    /// be bound to a namespace, see `XmlWriter` for details.
+    pub fn write_to<W: Write>(&self, writer: W, options: &WriteOptions) -> io::Result<()> {
+        XmlWriter::new(writer, options.clone()).write_document(self)
+    }
+
+    /// Serializes the node and its descendants to an xml document string. Fails the same way as `write_to`.
+    pub fn to_xml_string(&self, options: &WriteOptions) -> io::Result<String> {
+        let mut buffer = Vec::new();
+        self.write_to(&mut buffer, options)?;
+
+        // Everything written is either valid UTF-8 from the tree or ASCII markup
+        Ok(String::from_utf8(buffer).expect("xml writer produced invalid UTF-8"))
+    }
+
+    fn has_consistent_content(&self) -> bool {
+        if self.content.is_empty() {
+            return false;
+        }
+
+        let mut next_child_idx = 0;
+        let mut text = String::new();
+        for content in &self.content {
+            match content {
+                XmlContent::Text(value) | XmlContent::CData(value) => text.push_str(value),
+                XmlContent::Element(idx) if *idx == next_child_idx => next_child_idx += 1,
+                XmlContent::Element(_) => return false,
+            }
+        }
+
+        next_child_idx == self.child_nodes.len() && self.text.as_deref().unwrap_or_default() == text
+    }
+
+    // Takes the child nodes along with the ordered content referencing them, leaving the node without any content
+    fn take_ordered_content(&mut self) -> (Vec<Self>, Vec<XmlContent>) {
+        let content = if self.has_consistent_content() {
+            ::std::mem::take(&mut self.content)
+        } else {
+            self.text
+                .take()
+                .map(XmlContent::Text)
+                .into_iter()
+                .chain((0..self.child_nodes.len()).map(XmlContent::Element))
+                .collect()
+        };
+
+        self.text = None;
+        self.content = Vec::new();
+        (::std::mem::take(&mut self.child_nodes), content)
+    }
+
+    // Sets `text`, `child_nodes` and `content` from the content read by the parser
+    fn set_content(&mut self, child_nodes: Vec<Self>, mut content: Vec<XmlContent>, preserve_space: bool) {
+        if !preserve_space && !child_nodes.is_empty() {
+            content.retain(|content| match content {
+                XmlContent::Text(text) => !text.chars().all(char::is_whitespace),
+                _ => true,
+            });
+        }
+
+        // Merge the text that was split by comments or processing instructions
+        content.dedup_by(|next, prev| match (prev, next) {
+            (XmlContent::Text(prev), XmlContent::Text(next)) => {
+                prev.push_str(next);
+                true
+            }
+            _ => false,
+        });
+
+        let text: String = content
+            .iter()
+            .filter_map(|content| match content {
+                XmlContent::Text(value) | XmlContent::CData(value) => Some(value.as_str()),
+                XmlContent::Element(_) => None,
+            })
+            .collect();
+
+        // The ordered content is only kept if it carries any information over text followed by the child elements
+        let is_simple = content.iter().enumerate().all(|(idx, content)| match content {
+            XmlContent::Text(_) => idx == 0,
+            XmlContent::CData(_) => false,
+            XmlContent::Element(_) => true,
+        });
+
+        self.text = if text.is_empty() { None } else { Some(text) };
+        self.child_nodes = child_nodes;
+        self.content = if is_simple { Vec::new() } else { content };
+    }
+
+    fn from_quick_xml_element(
+        xml_element: &BytesStart<'_>,
+        scope: &mut NamespaceScope,
+        limits: &ParseLimits,
+    ) -> Result<(Self, usize), InvalidXmlErrorKind> {
+        let name = ::std::str::from_utf8(xml_element.name()).map_err(|_| InvalidXmlErrorKind::InvalidUtf8)?;
+        let mut node = Self::new(name);
+
+        for attr in xml_element.attributes() {
+            let attr = attr?;
+            limits
+                .check_attribute_length(attr.value.len())
+                .map_err(InvalidXmlErrorKind::LimitExceeded)?;
+
+            let key_str = ::std::str::from_utf8(attr.key).map_err(|_| InvalidXmlErrorKind::InvalidUtf8)?;
+            let value = attr.unescaped_value()?;
+            let value_str = ::std::str::from_utf8(&value).map_err(|_| InvalidXmlErrorKind::InvalidUtf8)?;
+            node.attributes.insert(String::from(key_str), String::from(value_str));
+        }
+
+        let marker = scope.enter(&node.attributes);
+        node.namespace = scope.resolve_element(&node.name).map(String::from);
+        for attr in node.attributes.keys() {
+            if !attr.contains(':') || attr.starts_with("xmlns:") {
+                continue;
+            }
+
+            if let Some(uri) = scope.resolve_attribute(attr) {
+                node.attribute_namespaces.insert(attr.clone(), String::from(uri));
+            }
+        }
+
+        Ok((node, marker))
+    }
+}
+
+impl FromStr for XmlNode {
+    type Err = InvalidXmlError;
+
+    fn from_str(xml_string: &str) -> Result<Self, Self::Err> {
+        XmlReader::from_str(xml_string).read_root_node()
+    }
+}
+
+pub fn parse_xml_bool<T: AsRef<str>>(value: T) -> Result<bool, ParseBoolError> {
+    match value.as_ref() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(ParseBoolError::new(String::from(value.as_ref()))),
+    }
+}
+
+/// Formats a bool the way Office applications write them.
+pub fn format_xml_bool(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+/// Parses the root element of a part stored in a zip archive. Markup compatibility constructs are kept as they are, so
+/// they're written back unchanged. Use `zip_file_to_xml_node_with_options` to process them.
+///
+/// The default `ParseLimits` are enforced.
+pub fn zip_file_to_xml_node(zip_file: &mut ZipFile) -> error::Result<XmlNode> {
+    zip_file_to_xml_node_with_limits(zip_file, &Default::default())
+}
+
+/// Same as `zip_file_to_xml_node`, but enforces the given limits. A part whose uncompressed size stored in the archive
+/// exceeds the maximum part size is rejected without decompressing it, and decompression stops as soon as the limit is
+/// exceeded if the stored size is lying.
+pub fn zip_file_to_xml_node_with_limits(zip_file: &mut ZipFile, limits: &ParseLimits) -> error::Result<XmlNode> {
+    limits
+        .check_part_size(zip_file.size())
+        .map_err(|limit| InvalidXmlError::new(InvalidXmlErrorKind::LimitExceeded(limit), None))?;
+
+    Ok(XmlNode::from_reader_with_limits(BufReader::new(zip_file), limits)?)
+}
+
+/// Parses a part stored in a zip archive directly to `T` with `XsdType::from_xml_reader_with_context`, so the part is
+/// never read to memory as a whole. Types that override `from_xml_reader_with_context` don't build a tree of the whole
+/// part either. Markup compatibility constructs are kept as they are.
+///
+/// The default `ParseLimits` are enforced.
+pub fn zip_file_to_xsd_type<T: XsdType>(zip_file: &mut ZipFile, context: &mut ParseContext) -> error::Result<T> {
+    zip_file_to_xsd_type_with_limits(zip_file, &Default::default(), context)
+}
+
+/// Same as `zip_file_to_xsd_type`, but enforces the given limits.
+pub fn zip_file_to_xsd_type_with_limits<T: XsdType>(
+    zip_file: &mut ZipFile,
+    limits: &ParseLimits,
+    context: &mut ParseContext,
+) -> error::Result<T> {
+    xml_reader_to_xsd_type(&mut zip_file_to_xml_reader(zip_file, limits)?, context)
+}
+
+// Parses the document read by `reader` to `T` with `XsdType::from_xml_reader_with_context`
+pub(crate) fn xml_reader_to_xsd_type<T: XsdType, B: BufRead>(
+    reader: &mut XmlReader<B>,
+    context: &mut ParseContext,
+) -> error::Result<T> {
+    let start = reader.read_root_start()?;
+    let value = T::from_xml_reader_with_context(reader, start, context)?;
+    reader.read_document_end()?;
+    Ok(value)
+}
+
+/// Creates a reader streaming a part stored in a zip archive, for parts read one element at a time. The limits are
+/// enforced the same way as in `zip_file_to_xml_node_with_limits`.
+pub fn zip_file_to_xml_reader<'a, 'b>(
+    zip_file: &'a mut ZipFile<'b>,
+    limits: &ParseLimits,
+) -> error::Result<XmlReader<Take<BufReader<&'a mut ZipFile<'b>>>>> {
+    let size = zip_file.size();
+    sized_xml_reader(zip_file, size, limits)
+}
+
+// Creates a reader streaming a part of `size` bytes, enforcing the limits the same way as
+// `zip_file_to_xml_node_with_limits`
+pub(crate) fn sized_xml_reader<R: Read>(
+    reader: R,
+    size: u64,
+    limits: &ParseLimits,
+) -> error::Result<XmlReader<Take<BufReader<R>>>> {
+    limits
+        .check_part_size(size)
+        .map_err(|limit| InvalidXmlError::new(InvalidXmlErrorKind::LimitExceeded(limit), None))?;
+
+    Ok(limited_xml_reader(BufReader::new(reader), limits))
+}
+
+// Creates a reader enforcing `limits`. Reading stops right after the maximum part size, so an oversized part is never
+// buffered as a whole
+fn limited_xml_reader<R: BufRead>(reader: R, limits: &ParseLimits) -> XmlReader<Take<R>> {
+    let max_read = limits
+        .max_part_size
+        .map(|max| max.saturating_add(1))
+        .unwrap_or(u64::MAX);
+
+    XmlReader::new(reader.take(max_read)).with_limits(limits.clone())
+}
+
+/// Same as `zip_file_to_xml_node_with_limits`, but processes the markup compatibility constructs with
+/// `markup_compatibility` if it's given. Content in namespaces that aren't understood by it is dropped from the tree.
+pub fn zip_file_to_xml_node_with_options(
+    zip_file: &mut ZipFile,
+    limits: &ParseLimits,
+    markup_compatibility: Option<&MarkupCompatibility>,
+) -> error::Result<XmlNode> {
+    let xml_node = zip_file_to_xml_node_with_limits(zip_file, limits)?;
+    match markup_compatibility {
+        Some(markup_compatibility) => Ok(markup_compatibility.process(xml_node)?),
+        None => Ok(xml_node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ns, writer::WriteOptions, XmlContent, XmlContentRef, XmlNode};
+    use crate::error::{Error, MissingAttributeError};
+    use crate::xsdtypes::ParseContext;
+    use std::{error::Error as StdError, str::FromStr};
+
+    #[test]
+    fn test_xml_parser() {
+        use std::fs::File;
+        use std::io::Read;
+        use std::path::PathBuf;
+
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_xml_file = test_dir.join("tests/presentation.xml");
+        let mut file = File::open(sample_xml_file).expect("Sample xml file not found");
+
+        let mut file_content = String::new();
+        file.read_to_string(&mut file_content)
+            .expect("Failed to read sample xml file to string");
+
+        let root_node = XmlNode::from_str(file_content.as_str()).expect("Couldn't create XmlNode from string");
+        assert_eq!(root_node.name, "p:presentation");
+        assert_eq!(
+            root_node.attributes.get("xmlns:a").unwrap(),
+            "http://schemas.openxmlformats.org/drawingml/2006/main"
+        );
+
+        assert_eq!(root_node.child_nodes[0].name, "p:sldMasterIdLst");
+        assert_eq!(root_node.child_nodes[1].name, "p:sldIdLst");
+        assert_eq!(root_node.child_nodes[2].name, "p:sldSz");
+        assert_eq!(root_node.child_nodes[3].name, "p:notesSz");
+        assert_eq!(root_node.child_nodes[4].name, "p:custDataLst");
+        assert_eq!(root_node.child_nodes[5].name, "p:defaultTextStyle");
+        assert_eq!(root_node.child_nodes[0].child_nodes[0].name, "p:sldMasterId");
+
+        let slide_id_0_node = &root_node.child_nodes[1].child_nodes[0];
+        assert_eq!(slide_id_0_node.name, "p:sldId");
+        assert_eq!(slide_id_0_node.attributes.get("id").unwrap(), "256");
+        assert_eq!(slide_id_0_node.attributes.get("r:id").unwrap(), "rId2");
+
+        assert_eq!(root_node.child_nodes[1].child_nodes[1].name, "p:sldId");
+
+        let lvl1_ppr_defrpr_node = &root_node.child_nodes[5].child_nodes[1].child_nodes[0];
+        assert_eq!(lvl1_ppr_defrpr_node.attributes.get("sz").unwrap(), "1800");
+        assert_eq!(lvl1_ppr_defrpr_node.attributes.get("kern").unwrap(), "1200");
+    }
+
+    #[test]
+    fn test_xml_namespaces() {
+        let xml = r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:x="http://schemas.openxmlformats.org/drawingml/2006/main"
+                xmlns:rel="http://purl.oclc.org/ooxml/officeDocument/relationships">
+            <x:blip rel:embed="rId1"/>
+            <inner xmlns="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <solidFill xmlns:p="http://example.com"><p:srgbClr/></solidFill>
+            </inner>
+        </p:sld>"#;
+
+        let root_node = XmlNode::from_str(xml).unwrap();
+        assert_eq!(root_node.namespace(), Some(ns::PRESENTATIONML));
+        assert!(root_node.is(ns::PRESENTATIONML, "sld"));
+
+        let blip_node = &root_node.child_nodes[0];
+        assert!(blip_node.is(ns::DRAWINGML, "blip"));
+        assert!(!blip_node.is(ns::PRESENTATIONML, "blip"));
+        assert_eq!(blip_node.attribute_ns(ns::RELATIONSHIPS, "embed").unwrap(), "rId1");
+
+        let solid_fill_node = &root_node.child_nodes[1].child_nodes[0];
+        assert!(solid_fill_node.is(ns::DRAWINGML, "solidFill"));
+        assert_eq!(solid_fill_node.child_nodes[0].namespace(), Some("http://example.com"));
+
+        let unbound_node = XmlNode::from_str("<a:solidFill/>").unwrap();
+        assert_eq!(unbound_node.namespace(), None);
+        assert!(!unbound_node.is(ns::DRAWINGML, "solidFill"));
+        assert!(XmlNode::new_with_conventional_namespace("a:solidFill").is(ns::DRAWINGML, "solidFill"));
+    }
+
+    #[test]
+    fn test_xml_mixed_content() {
+        let xml = r#"<root xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+            <a:t> leading and trailing </a:t>
+            <p xml:space="preserve">Hello <b>bold</b> <i>world</i>!<![CDATA[<raw> & ]]></p>
+            <q>Before <b/> after</q>
+        </root>"#;
+
+        let root_node = XmlNode::from_str(xml).unwrap();
+        assert_eq!(root_node.text, None);
+        assert!(root_node.content.is_empty());
+        assert_eq!(root_node.child_nodes[0].text.as_deref(), Some(" leading and trailing "));
+
+        let p_node = &root_node.child_nodes[1];
+        assert_eq!(p_node.text.as_deref(), Some("Hello  !<raw> & "));
+        assert_eq!(
+            p_node.content,
+            vec![
+                XmlContent::Text(String::from("Hello ")),
+                XmlContent::Element(0),
+                XmlContent::Text(String::from(" ")),
+                XmlContent::Element(1),
+                XmlContent::Text(String::from("!")),
+                XmlContent::CData(String::from("<raw> & ")),
+            ]
+        );
+
+        let q_node = &root_node.child_nodes[2];
+        assert_eq!(
+            q_node.ordered_content(),
+            vec![
+                XmlContentRef::Text("Before "),
+                XmlContentRef::Element(&q_node.child_nodes[0]),
+                XmlContentRef::Text(" after"),
+            ]
+        );
+
+        let written = root_node.to_xml_string(&WriteOptions::pretty()).unwrap();
+        assert!(written.contains(r#"<p xml:space="preserve">Hello <b>bold</b> <i>world</i>!<![CDATA[<raw> & ]]></p>"#));
+        assert_eq!(XmlNode::from_str(&written).unwrap(), root_node);
+
+        // Modified nodes fall back to text followed by the child elements
+        let mut modified_node = q_node.clone();
+        modified_node.text = Some(String::from("Replaced"));
+        assert_eq!(
+            modified_node.ordered_content(),
+            vec![
+                XmlContentRef::Text("Replaced"),
+                XmlContentRef::Element(&q_node.child_nodes[0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attribute_value_errors() {
+        let xml = "<a:srgbClr val=\"FFFFFF\">\n  <a:alpha val=\"half\"/>\n  <a:shade/>\n</a:srgbClr>";
+        let node = XmlNode::from_str(xml).unwrap();
+
+        let alpha = &node.child_nodes[0];
+        assert_eq!(
+            alpha
+                .parse_attribute::<i32>("missing", &mut ParseContext::new())
+                .unwrap(),
+            None
+        );
+        let err = alpha
+            .parse_attribute::<i32>("val", &mut ParseContext::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Xml element 'a:alpha' has an invalid value for attribute val: 'half' (invalid digit found in string) \
+             at a:srgbClr/a:alpha (line 2, column 3, byte offset 27)"
+        );
+        assert!(StdError::source(&err).is_some());
+        match &err {
+            Error::InvalidAttributeValue(err) => assert_eq!(err.value, "half"),
+            err => panic!("unexpected error: {:?}", err),
+        }
+        assert_eq!(
+            err.path().map(ToString::to_string).as_deref(),
+            Some("a:srgbClr/a:alpha")
+        );
+
+        let mut revision = XmlNode::new("cp:revision");
+        revision.text = Some(String::from("1.5"));
+        match revision.parse_text::<i32>() {
+            Err(Error::InvalidTextValue(err)) => assert_eq!(err.value, "1.5"),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let err = MissingAttributeError::at(&node.child_nodes[1], "val");
+        assert_eq!(
+            err.to_string(),
+            "Xml element 'a:shade' is missing a required attribute: val at a:srgbClr/a:shade (line 3, column 3, byte \
+             offset 51)"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Catalogue of the namespace URIs used throughout the Office Open XML file formats.
+//!
+//! Constants always hold the transitional (ECMA-376 1st edition compatible) URI. Documents saved in strict
+//! conformance mode use a different set of URIs for the same vocabularies, use `to_transitional` to map such a URI
+//! to its transitional counterpart before comparing it against these constants.
+
+/// The namespace implicitly bound to the `xml` prefix.
+pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The namespace implicitly bound to the `xmlns` prefix.
+pub const XMLNS: &str = "http://www.w3.org/2000/xmlns/";
+
+/// XML Schema instance namespace (`xsi`).
+pub const XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// DrawingML main namespace (`a`).
+pub const DRAWINGML: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+
+/// DrawingML picture namespace (`pic`).
+pub const DRAWINGML_PICTURE: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
+
+/// DrawingML chart namespace (`c`).
+pub const DRAWINGML_CHART: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+
+/// DrawingML chart drawing namespace (`cdr`).
+pub const DRAWINGML_CHART_DRAWING: &str = "http://schemas.openxmlformats.org/drawingml/2006/chartDrawing";
+
+/// DrawingML diagram namespace (`dgm`).
+pub const DRAWINGML_DIAGRAM: &str = "http://schemas.openxmlformats.org/drawingml/2006/diagram";
+
+/// DrawingML locked canvas namespace (`lc`).
+pub const DRAWINGML_LOCKED_CANVAS: &str = "http://schemas.openxmlformats.org/drawingml/2006/lockedCanvas";
+
+/// DrawingML WordprocessingML drawing namespace (`wp`).
+pub const DRAWINGML_WORDPROCESSING: &str = "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+
+/// DrawingML SpreadsheetML drawing namespace (`xdr`).
+pub const DRAWINGML_SPREADSHEET: &str = "http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing";
+
+/// PresentationML main namespace (`p`).
+pub const PRESENTATIONML: &str = "http://schemas.openxmlformats.org/presentationml/2006/main";
+
+/// WordprocessingML main namespace (`w`).
+pub const WORDPROCESSINGML: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+
+/// SpreadsheetML main namespace (`x`).
+pub const SPREADSHEETML: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+
+/// Namespace of relationship reference attributes, e.g. `r:id` or `r:embed` (`r`).
+pub const RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Namespace of the extended (application specific) document properties part (`ep`).
+pub const EXTENDED_PROPERTIES: &str = "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+
+/// Namespace of the custom document properties part.
+pub const CUSTOM_PROPERTIES: &str = "http://schemas.openxmlformats.org/officeDocument/2006/custom-properties";
+
+/// Namespace of the variant types used by the extended and custom document properties (`vt`).
+pub const DOC_PROPS_VTYPES: &str = "http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes";
+
+/// Markup compatibility namespace (`mc`).
+pub const MARKUP_COMPATIBILITY: &str = "http://schemas.openxmlformats.org/markup-compatibility/2006";
+
+/// Namespace of the package relationships parts (`.rels`).
+pub const PACKAGE_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+
+/// Namespace of the `[Content_Types].xml` part.
+pub const CONTENT_TYPES: &str = "http://schemas.openxmlformats.org/package/2006/content-types";
+
+/// Namespace of the core properties part (`cp`).
+pub const CORE_PROPERTIES: &str = "http://schemas.openxmlformats.org/package/2006/metadata/core-properties";
+
+/// Dublin Core elements namespace (`dc`).
+pub const DUBLIN_CORE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// Dublin Core terms namespace (`dcterms`).
+pub const DUBLIN_CORE_TERMS: &str = "http://purl.org/dc/terms/";
+
+/// Dublin Core type vocabulary namespace (`dcmitype`).
+pub const DUBLIN_CORE_TYPE: &str = "http://purl.org/dc/dcmitype/";
+
+/// Office 2010 DrawingML extensions (`a14`).
+pub const DRAWINGML_2010: &str = "http://schemas.microsoft.com/office/drawing/2010/main";
+
+/// Office 2016 DrawingML extensions (`a16`).
+pub const DRAWINGML_2014: &str = "http://schemas.microsoft.com/office/drawing/2014/main";
+
+/// Office 2019 DrawingML decorative extension (`adec`).
+pub const DRAWINGML_DECORATIVE: &str = "http://schemas.microsoft.com/office/drawing/2017/decorative";
+
+/// Office 2010 PresentationML extensions (`p14`).
+pub const PRESENTATIONML_2010: &str = "http://schemas.microsoft.com/office/powerpoint/2010/main";
+
+/// Office 2013 PresentationML extensions (`p15`).
+pub const PRESENTATIONML_2012: &str = "http://schemas.microsoft.com/office/powerpoint/2012/main";
+
+/// Office 2010 WordprocessingML extensions (`w14`).
+pub const WORDPROCESSINGML_2010: &str = "http://schemas.microsoft.com/office/word/2010/wordml";
+
+/// Office 2010 SpreadsheetML extensions (`x14`).
+pub const SPREADSHEETML_2010: &str = "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main";
+
+/// Pairs of strict and transitional namespace URIs of the same vocabulary.
+const STRICT_TO_TRANSITIONAL: &[(&str, &str)] = &[
+    ("http://purl.oclc.org/ooxml/drawingml/main", DRAWINGML),
+    ("http://purl.oclc.org/ooxml/drawingml/picture", DRAWINGML_PICTURE),
+    ("http://purl.oclc.org/ooxml/drawingml/chart", DRAWINGML_CHART),
+    (
+        "http://purl.oclc.org/ooxml/drawingml/chartDrawing",
+        DRAWINGML_CHART_DRAWING,
+    ),
+    ("http://purl.oclc.org/ooxml/drawingml/diagram", DRAWINGML_DIAGRAM),
+    (
+        "http://purl.oclc.org/ooxml/drawingml/lockedCanvas",
+        DRAWINGML_LOCKED_CANVAS,
+    ),
+    (
+        "http://purl.oclc.org/ooxml/drawingml/wordprocessingDrawing",
+        DRAWINGML_WORDPROCESSING,
+    ),
+    (
+        "http://purl.oclc.org/ooxml/drawingml/spreadsheetDrawing",
+        DRAWINGML_SPREADSHEET,
+    ),
+    ("http://purl.oclc.org/ooxml/presentationml/main", PRESENTATIONML),
+    ("http://purl.oclc.org/ooxml/wordprocessingml/main", WORDPROCESSINGML),
+    ("http://purl.oclc.org/ooxml/spreadsheetml/main", SPREADSHEETML),
+    ("http://purl.oclc.org/ooxml/officeDocument/relationships", RELATIONSHIPS),
+    (
+        "http://purl.oclc.org/ooxml/officeDocument/extendedProperties",
+        EXTENDED_PROPERTIES,
+    ),
+    (
+        "http://purl.oclc.org/ooxml/officeDocument/customProperties",
+        CUSTOM_PROPERTIES,
+    ),
+    (
+        "http://purl.oclc.org/ooxml/officeDocument/docPropsVTypes",
+        DOC_PROPS_VTYPES,
+    ),
+];
+
+/// Conventional prefixes of the namespaces, as used by Office applications when saving a document.
+const CONVENTIONAL_PREFIXES: &[(&str, &str)] = &[
+    ("xml", XML),
+    ("xsi", XSI),
+    ("a", DRAWINGML),
+    ("pic", DRAWINGML_PICTURE),
+    ("c", DRAWINGML_CHART),
+    ("cdr", DRAWINGML_CHART_DRAWING),
+    ("dgm", DRAWINGML_DIAGRAM),
+    ("lc", DRAWINGML_LOCKED_CANVAS),
+    ("wp", DRAWINGML_WORDPROCESSING),
+    ("xdr", DRAWINGML_SPREADSHEET),
+    ("p", PRESENTATIONML),
+    ("w", WORDPROCESSINGML),
+    ("x", SPREADSHEETML),
+    ("r", RELATIONSHIPS),
+    ("ep", EXTENDED_PROPERTIES),
+    ("vt", DOC_PROPS_VTYPES),
+    ("mc", MARKUP_COMPATIBILITY),
+    ("cp", CORE_PROPERTIES),
+    ("dc", DUBLIN_CORE),
+    ("dcterms", DUBLIN_CORE_TERMS),
+    ("dcmitype", DUBLIN_CORE_TYPE),
+    ("a14", DRAWINGML_2010),
+    ("a16", DRAWINGML_2014),
+    ("adec", DRAWINGML_DECORATIVE),
+    ("p14", PRESENTATIONML_2010),
+    ("p15", PRESENTATIONML_2012),
+    ("w14", WORDPROCESSINGML_2010),
+    ("x14", SPREADSHEETML_2010),
+];
+
+/// Returns the namespace URI conventionally bound to `prefix`, e.g. `a` for DrawingML.
+pub fn conventional_namespace(prefix: &str) -> Option<&'static str> {
+    CONVENTIONAL_PREFIXES
+        .iter()
+        .find(|(conventional_prefix, _)| *conventional_prefix == prefix)
+        .map(|(_, uri)| *uri)
+}
+
+/// Maps a strict namespace URI to its transitional counterpart. Any other URI is returned as is.
+pub fn to_transitional(uri: &str) -> &str {
+    STRICT_TO_TRANSITIONAL
+        .iter()
+        .find(|(strict, _)| *strict == uri)
+        .map(|(_, transitional)| *transitional)
+        .unwrap_or(uri)
+}
+
+/// Returns the strict counterpart of a transitional namespace URI if the vocabulary has one.
+pub fn to_strict(uri: &str) -> Option<&'static str> {
+    STRICT_TO_TRANSITIONAL
+        .iter()
+        .find(|(_, transitional)| *transitional == uri)
+        .map(|(strict, _)| *strict)
+}
+
+/// Returns true if two namespace URIs name the same vocabulary, either in the strict or the transitional form.
+pub fn is_same_namespace(lhs: &str, rhs: &str) -> bool {
+    to_transitional(lhs) == to_transitional(rhs)
+}
+
+/// Stack of the namespace declarations in scope while walking an xml document.
+///
+/// Declarations of an element are pushed when the element is entered and popped when it's left, so a prefix always
+/// resolves to the innermost declaration. The `xml` prefix is implicitly bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespaceScope {
+    bindings: Vec<(String, String)>,
+}
+
+impl Default for NamespaceScope {
+    fn default() -> Self {
+        Self {
+            bindings: vec![(String::from("xml"), String::from(XML))],
+        }
+    }
+}
+
+impl NamespaceScope {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Pushes the `xmlns` and `xmlns:prefix` declarations found in `attributes` and returns a marker that can be
+    /// passed to `leave` to pop them again.
+    pub fn enter<I, K, V>(&mut self, attributes: I) -> usize
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let marker = self.bindings.len();
+        for (attr, value) in attributes {
+            let (attr, value) = (attr.as_ref(), value.as_ref());
+            if attr == "xmlns" {
+                self.bindings.push((String::new(), String::from(value)));
+            } else if let Some(prefix) = attr.strip_prefix("xmlns:") {
+                self.bindings.push((String::from(prefix), String::from(value)));
+            }
+        }
+
+        marker
+    }
+
+    /// Pops every declaration pushed since `enter` returned `marker`.
+    pub fn leave(&mut self, marker: usize) {
+        self.bindings.truncate(marker);
+    }
+
+    /// Resolves a prefix to a namespace URI. An empty prefix resolves to the default namespace. An empty default
+    /// namespace declaration (`xmlns=""`) undeclares the default namespace.
+    pub fn resolve(&self, prefix: &str) -> Option<&str> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(bound_prefix, _)| bound_prefix == prefix)
+            .map(|(_, uri)| uri.as_str())
+            .filter(|uri| !uri.is_empty())
+    }
+
+    /// Resolves the namespace of a possibly prefixed element name.
+    pub fn resolve_element(&self, name: &str) -> Option<&str> {
+        match name.find(':') {
+            Some(idx) => self.resolve(&name[..idx]),
+            None => self.resolve(""),
+        }
+    }
+
+    /// Resolves the namespace of a possibly prefixed attribute name. Unprefixed attributes are never in a namespace.
+    pub fn resolve_attribute(&self, name: &str) -> Option<&str> {
+        match name.find(':') {
+            Some(idx) if &name[..idx] == "xmlns" => Some(XMLNS),
+            Some(idx) => self.resolve(&name[..idx]),
+            None if name == "xmlns" => Some(XMLNS),
+            None => None,
+        }
+    }
+
+    /// Returns the innermost prefix bound to `uri`. The default namespace is returned as an empty prefix.
+    pub fn prefix_of(&self, uri: &str) -> Option<&str> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(prefix, bound_uri)| bound_uri == uri && self.resolve(prefix) == Some(uri))
+            .map(|(prefix, _)| prefix.as_str())
+    }
+}
@@ -0,0 +1,642 @@
+//! Event-driven parsing of xml documents.
+//!
+//! `XmlReader` wraps a quick-xml reader and keeps track of the namespaces and `xml:space` in scope, so elements can
+//! be parsed one at a time without building a DOM of the whole document. A subtree can still be materialized as an
+//! `XmlNode` with `read_node`, which is how `XmlNode::from_str` and `XsdType::from_xml_reader` are implemented.
+//!
+//! # Examples
+//!
+//! ```
+//! use msoffice_shared::{
+//!     drawingml::colors::Color,
+//!     xml::reader::{XmlEvent, XmlReader},
+//!     xsdtypes::{XsdChoice, XsdType},
+//! };
+//!
+//! let xml = r#"<a:clrScheme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office">
+//!     <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+//!     <a:lt1><a:srgbClr val="FFFFFF"/></a:lt1>
+//! </a:clrScheme>"#;
+//!
+//! let mut reader = XmlReader::from_str(xml);
+//! let mut colors = Vec::new();
+//! loop {
+//!     match reader.next_event().unwrap() {
+//!         XmlEvent::Start(start) if Color::is_choice_member(start.node.local_name()) => {
+//!             colors.push(Color::from_xml_reader(&mut reader, start).unwrap());
+//!         }
+//!         XmlEvent::Eof => break,
+//!         _ => (),
+//!     }
+//! }
+//!
+//! assert_eq!(colors.len(), 2);
+//! ```
+
+use super::{
+    limits::ParseLimits,
+    location::{ElementPath, SourceLocation},
+    ns::NamespaceScope,
+    XmlContent, XmlNode,
+};
+use crate::error::{InvalidXmlError, InvalidXmlErrorKind};
+use quick_xml::{events::Event, Reader};
+use std::{collections::HashMap, io::BufRead, str};
+
+/// The start tag of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartElement {
+    /// The element with its attributes and resolved namespaces, but without any content
+    pub node: XmlNode,
+    /// True for empty-element tags (e.g. `<a:noFill/>`), which don't have a matching `XmlEvent::End`
+    pub is_empty: bool,
+}
+
+/// An event produced by `XmlReader`. Comments, processing instructions and the xml declaration are skipped.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum XmlEvent {
+    Start(StartElement),
+    /// End of the innermost open element
+    End,
+    /// Character data, unescaped. Empty text is never reported.
+    Text(String),
+    /// Content of a CDATA section
+    CData(String),
+    Eof,
+}
+
+struct OpenElement {
+    namespace_marker: usize,
+    preserve_space: bool,
+    path: ElementPath,
+    // Number of child elements seen so far by name, used to compute the position of the next child in its path
+    child_counts: HashMap<String, usize>,
+}
+
+// An event read from quick-xml that's converted to owned data, so the position can be updated from the buffer
+enum PendingEvent {
+    Start(XmlNode, usize),
+    Empty(XmlNode, usize),
+    End,
+    Text(String),
+    CData(String),
+    Eof,
+    Skipped,
+}
+
+/// Pull parser producing `XmlEvent`s from any `BufRead` implementor.
+///
+/// The reader enforces the `ParseLimits` it's configured with, which are the default limits unless `with_limits` is
+/// used. The part size limit is checked against the number of bytes consumed, so the underlying reader should be
+/// limited as well (e.g. with `Read::take`) to keep a single oversized event from being buffered.
+pub struct XmlReader<B: BufRead> {
+    reader: Reader<B>,
+    scope: NamespaceScope,
+    open_elements: Vec<OpenElement>,
+    buffer: Vec<u8>,
+    line: usize,
+    line_start: usize,
+    limits: ParseLimits,
+    node_count: usize,
+}
+
+impl<'a> XmlReader<&'a [u8]> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(xml_string: &'a str) -> Self {
+        Self::from_quick_xml(Reader::from_str(xml_string))
+    }
+}
+
+impl<B: BufRead> XmlReader<B> {
+    pub fn new(reader: B) -> Self {
+        Self::from_quick_xml(Reader::from_reader(reader))
+    }
+
+    /// Wraps an already configured quick-xml reader. The reader is expected to be positioned at the beginning of the
+    /// document, otherwise the reported lines and columns are relative to its current position.
+    pub fn from_quick_xml(reader: Reader<B>) -> Self {
+        Self {
+            reader,
+            scope: NamespaceScope::new(),
+            open_elements: Vec::new(),
+            buffer: Vec::new(),
+            line: 1,
+            line_start: 0,
+            limits: Default::default(),
+            node_count: 0,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn limits(&self) -> &ParseLimits {
+        &self.limits
+    }
+
+    pub fn into_inner(self) -> Reader<B> {
+        self.reader
+    }
+
+    /// Returns the number of elements that are currently open
+    pub fn depth(&self) -> usize {
+        self.open_elements.len()
+    }
+
+    /// Checks whether `xml:space="preserve"` is in effect for the innermost open element
+    pub fn preserves_space(&self) -> bool {
+        self.open_elements
+            .last()
+            .map(|element| element.preserve_space)
+            .unwrap_or_default()
+    }
+
+    /// Returns the path of the innermost open element
+    pub fn path(&self) -> Option<&ElementPath> {
+        self.open_elements.last().map(|element| &element.path)
+    }
+
+    /// Returns the current position of the reader
+    pub fn location(&self) -> SourceLocation {
+        let offset = self.reader.buffer_position();
+        SourceLocation {
+            line: self.line,
+            column: offset.saturating_sub(self.line_start) + 1,
+            offset,
+            path: self.path().cloned(),
+        }
+    }
+
+    pub fn next_event(&mut self) -> Result<XmlEvent, InvalidXmlError> {
+        loop {
+            let location = self.location();
+
+            self.buffer.clear();
+            let pending_event = match self.reader.read_event(&mut self.buffer) {
+                Ok(Event::Start(ref element)) => {
+                    XmlNode::from_quick_xml_element(element, &mut self.scope, &self.limits)
+                        .map(|(node, marker)| PendingEvent::Start(node, marker))
+                }
+                Ok(Event::Empty(ref element)) => {
+                    XmlNode::from_quick_xml_element(element, &mut self.scope, &self.limits)
+                        .map(|(node, marker)| PendingEvent::Empty(node, marker))
+                }
+                Ok(Event::End(_)) => Ok(PendingEvent::End),
+                Ok(Event::Text(ref text)) => text
+                    .unescaped()
+                    .map_err(InvalidXmlErrorKind::from)
+                    .and_then(|text| utf8_string(&text))
+                    .map(PendingEvent::Text),
+                Ok(Event::CData(ref data)) => utf8_string(data).map(PendingEvent::CData),
+                Ok(Event::Eof) => Ok(PendingEvent::Eof),
+                Ok(_) => Ok(PendingEvent::Skipped),
+                Err(err) => Err(InvalidXmlErrorKind::from(err)),
+            };
+
+            // An exceeded part size takes precedence, since the event may be truncated by a limited underlying reader
+            let pending_event = self
+                .limits
+                .check_part_size(self.reader.buffer_position() as u64)
+                .map_err(InvalidXmlErrorKind::LimitExceeded)
+                .and(pending_event);
+
+            // Markup is reported after the opening '<', which is not part of the buffer
+            let is_markup = !matches!(pending_event, Ok(PendingEvent::Text(_)));
+            self.advance_lines(location.offset + is_markup as usize);
+
+            let pending_event = pending_event.map_err(|kind| self.error(kind, location.clone()))?;
+            match pending_event {
+                PendingEvent::Start(mut node, namespace_marker) => {
+                    let path = self.child_path(&node.name);
+                    let location = SourceLocation {
+                        path: Some(path.clone()),
+                        ..location
+                    };
+                    self.check_element_limits(self.depth() + 1)
+                        .map_err(|kind| self.error(kind, location.clone()))?;
+                    node.location = Some(location);
+
+                    // xml:space is inherited by the descendants until it's overridden
+                    let preserve_space = match node.attributes.get("xml:space").map(String::as_str) {
+                        Some("preserve") => true,
+                        Some("default") => false,
+                        _ => self.preserves_space(),
+                    };
+
+                    self.open_elements.push(OpenElement {
+                        namespace_marker,
+                        preserve_space,
+                        path,
+                        child_counts: HashMap::new(),
+                    });
+
+                    return Ok(XmlEvent::Start(StartElement { node, is_empty: false }));
+                }
+                PendingEvent::Empty(mut node, namespace_marker) => {
+                    self.scope.leave(namespace_marker);
+                    let location = SourceLocation {
+                        path: Some(self.child_path(&node.name)),
+                        ..location
+                    };
+                    self.check_element_limits(self.depth() + 1)
+                        .map_err(|kind| self.error(kind, location.clone()))?;
+                    node.location = Some(location);
+
+                    return Ok(XmlEvent::Start(StartElement { node, is_empty: true }));
+                }
+                PendingEvent::End => {
+                    let element = self
+                        .open_elements
+                        .pop()
+                        .ok_or_else(|| self.error(InvalidXmlErrorKind::UnexpectedEndTag, location))?;
+                    self.scope.leave(element.namespace_marker);
+                    return Ok(XmlEvent::End);
+                }
+                PendingEvent::Text(text) if !text.is_empty() => return Ok(XmlEvent::Text(text)),
+                PendingEvent::CData(data) => return Ok(XmlEvent::CData(data)),
+                PendingEvent::Eof => return Ok(XmlEvent::Eof),
+                PendingEvent::Text(_) | PendingEvent::Skipped => (),
+            }
+        }
+    }
+
+    /// Reads the content of the element `start` was returned for and returns the whole subtree as an `XmlNode`.
+    /// Must be called right after `start` is returned by `next_event`.
+    pub fn read_node(&mut self, start: StartElement) -> Result<XmlNode, InvalidXmlError> {
+        let StartElement { node, is_empty } = start;
+        if is_empty {
+            return Ok(node);
+        }
+
+        // The subtree is built with an explicit stack instead of recursion, so deeply nested documents can't overflow
+        // the call stack
+        let mut open_nodes = vec![PartialNode::new(node, self.preserves_space())];
+        loop {
+            match self.next_event()? {
+                XmlEvent::Start(StartElement { node, is_empty: true }) => {
+                    open_nodes.last_mut().expect("an element is open").push_child(node)
+                }
+                XmlEvent::Start(StartElement { node, .. }) => {
+                    open_nodes.push(PartialNode::new(node, self.preserves_space()))
+                }
+                XmlEvent::Text(text) => open_nodes
+                    .last_mut()
+                    .expect("an element is open")
+                    .content
+                    .push(XmlContent::Text(text)),
+                XmlEvent::CData(data) => open_nodes
+                    .last_mut()
+                    .expect("an element is open")
+                    .content
+                    .push(XmlContent::CData(data)),
+                XmlEvent::End => {
+                    let node = open_nodes.pop().expect("an element is open").finish();
+                    match open_nodes.last_mut() {
+                        Some(parent) => parent.push_child(node),
+                        None => return Ok(node),
+                    }
+                }
+                XmlEvent::Eof => return Err(self.error(InvalidXmlErrorKind::UnexpectedEof, self.location())),
+            }
+        }
+    }
+
+    /// Skips the content of the element `start` was returned for. Must be called right after `start` is returned by
+    /// `next_event`.
+    pub fn skip_node(&mut self, start: &StartElement) -> Result<(), InvalidXmlError> {
+        if start.is_empty {
+            return Ok(());
+        }
+
+        let depth = self.depth();
+        while self.depth() >= depth {
+            if let XmlEvent::Eof = self.next_event()? {
+                return Err(self.error(InvalidXmlErrorKind::UnexpectedEof, self.location()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the start tag of the next child element of the element `parent` was returned for, or None once its end
+    /// tag is reached. Text content is skipped. The returned child has to be consumed (e.g. with `read_node`,
+    /// `skip_node` or `XsdType::from_xml_reader`) before the next one is requested.
+    pub fn next_child(&mut self, parent: &StartElement) -> Result<Option<StartElement>, InvalidXmlError> {
+        if parent.is_empty {
+            return Ok(None);
+        }
+
+        loop {
+            match self.next_event()? {
+                XmlEvent::Start(start) => return Ok(Some(start)),
+                XmlEvent::End => return Ok(None),
+                XmlEvent::Eof => return Err(self.error(InvalidXmlErrorKind::UnexpectedEof, self.location())),
+                XmlEvent::Text(_) | XmlEvent::CData(_) => (),
+            }
+        }
+    }
+
+    /// Skips everything up to the root element and returns its start tag
+    pub fn read_root_start(&mut self) -> Result<StartElement, InvalidXmlError> {
+        loop {
+            match self.next_event()? {
+                XmlEvent::Start(start) => return Ok(start),
+                XmlEvent::Eof => return Err(self.error(InvalidXmlErrorKind::UnexpectedEof, self.location())),
+                _ => (),
+            }
+        }
+    }
+
+    /// Skips everything up to the root element and reads it as an `XmlNode`. Fails if anything but whitespace,
+    /// comments and processing instructions follow the root element.
+    pub fn read_root_node(&mut self) -> Result<XmlNode, InvalidXmlError> {
+        let start = self.read_root_start()?;
+        let root = self.read_node(start)?;
+        self.read_document_end()?;
+        Ok(root)
+    }
+
+    /// Reads the rest of the document once the root element is read. Fails if anything but whitespace, comments and
+    /// processing instructions follow the root element.
+    pub fn read_document_end(&mut self) -> Result<(), InvalidXmlError> {
+        loop {
+            let location = self.location();
+            match self.next_event()? {
+                XmlEvent::Eof => return Ok(()),
+                XmlEvent::Text(text) if text.chars().all(char::is_whitespace) => (),
+                XmlEvent::Start(start) => {
+                    let location = start.node.location.unwrap_or(location);
+                    return Err(self.error(InvalidXmlErrorKind::ContentAfterRoot, location));
+                }
+                _ => return Err(self.error(InvalidXmlErrorKind::ContentAfterRoot, location)),
+            }
+        }
+    }
+
+    fn error(&self, kind: InvalidXmlErrorKind, location: SourceLocation) -> InvalidXmlError {
+        InvalidXmlError::new(kind, Some(location))
+    }
+
+    fn check_element_limits(&mut self, depth: usize) -> Result<(), InvalidXmlErrorKind> {
+        self.node_count += 1;
+        self.limits
+            .check_depth(depth)
+            .and_then(|_| self.limits.check_node_count(self.node_count))
+            .map_err(InvalidXmlErrorKind::LimitExceeded)
+    }
+
+    fn child_path(&mut self, name: &str) -> ElementPath {
+        match self.open_elements.last_mut() {
+            Some(parent) => {
+                let position = parent.child_counts.entry(String::from(name)).or_default();
+                *position += 1;
+                parent.path.child(name, *position)
+            }
+            None => ElementPath::root(name),
+        }
+    }
+
+    // Updates the current line from the newlines in the buffer, whose first byte is at `buffer_offset`
+    fn advance_lines(&mut self, buffer_offset: usize) {
+        for (idx, _) in self.buffer.iter().enumerate().filter(|(_, byte)| **byte == b'\n') {
+            self.line += 1;
+            self.line_start = buffer_offset + idx + 1;
+        }
+    }
+}
+
+// An element whose content is still being read by `XmlReader::read_node`
+struct PartialNode {
+    node: XmlNode,
+    preserve_space: bool,
+    child_nodes: Vec<XmlNode>,
+    content: Vec<XmlContent>,
+}
+
+impl PartialNode {
+    fn new(node: XmlNode, preserve_space: bool) -> Self {
+        Self {
+            node,
+            preserve_space,
+            child_nodes: Vec::new(),
+            content: Vec::new(),
+        }
+    }
+
+    fn push_child(&mut self, child_node: XmlNode) {
+        self.content.push(XmlContent::Element(self.child_nodes.len()));
+        self.child_nodes.push(child_node);
+    }
+
+    fn finish(self) -> XmlNode {
+        let PartialNode {
+            mut node,
+            preserve_space,
+            child_nodes,
+            content,
+        } = self;
+        node.set_content(child_nodes, content, preserve_space);
+        node
+    }
+}
+
+fn utf8_string(bytes: &[u8]) -> Result<String, InvalidXmlErrorKind> {
+    str::from_utf8(bytes)
+        .map(String::from)
+        .map_err(|_| InvalidXmlErrorKind::InvalidUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::{
+        limits::{ParseLimit, MAX_DEPTH},
+        ns,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_xml_reader_events() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xml:space="preserve">
+                <a:themeElements><a:clrScheme name="Office"><a:dk1/></a:clrScheme></a:themeElements>
+                <x:objectDefaults xmlns:x="http://schemas.openxmlformats.org/drawingml/2006/main"> <a:spDef/> </x:objectDefaults>
+            </a:theme>"#;
+
+        let mut reader = XmlReader::from_str(xml);
+        let root = loop {
+            if let XmlEvent::Start(start) = reader.next_event().unwrap() {
+                break start;
+            }
+        };
+        assert!(root.node.is(ns::DRAWINGML, "theme"));
+        assert!(root.node.child_nodes.is_empty());
+        assert_eq!(reader.depth(), 1);
+        assert!(reader.preserves_space());
+
+        let mut visited = Vec::new();
+        loop {
+            match reader.next_event().unwrap() {
+                XmlEvent::Start(start) if start.node.local_name() == "themeElements" => {
+                    reader.skip_node(&start).unwrap()
+                }
+                XmlEvent::Start(start) if start.node.local_name() == "objectDefaults" => {
+                    let node = reader.read_node(start).unwrap();
+                    assert_eq!(node.namespace(), Some(ns::DRAWINGML));
+                    assert_eq!(node.text.as_deref(), Some("  "));
+                    visited.push(node);
+                }
+                XmlEvent::Start(start) => panic!("unexpected element {}", start.node.name),
+                XmlEvent::End => assert_eq!(reader.depth(), 0),
+                XmlEvent::Text(_) => (),
+                event => {
+                    assert_eq!(event, XmlEvent::Eof);
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(visited.len(), 1);
+        assert_eq!(
+            XmlNode::from_reader(xml.as_bytes()).unwrap(),
+            XmlNode::from_str(xml).unwrap()
+        );
+        assert!(XmlNode::from_str("<a:theme><a:themeElements></a:theme>").is_err());
+
+        let err = XmlNode::from_str("<a></a>\n<b/>").unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::ContentAfterRoot);
+        assert_eq!(err.location.unwrap().line, 2);
+        assert!(XmlNode::from_str("<a/>text").is_err());
+        assert!(XmlNode::from_str("<a/>\n<!-- comment --> <?pi?>\n").is_ok());
+    }
+
+    #[test]
+    fn test_next_child() {
+        let xml = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <a:themeElements><a:clrScheme/></a:themeElements> text <a:objectDefaults/>
+            </a:theme>"#;
+
+        let mut reader = XmlReader::from_str(xml);
+        let root = reader.read_root_start().unwrap();
+        let theme_elements = reader.next_child(&root).unwrap().unwrap();
+        assert_eq!(theme_elements.node.local_name(), "themeElements");
+        let color_scheme = reader.next_child(&theme_elements).unwrap().unwrap();
+        assert!(reader.next_child(&color_scheme).unwrap().is_none());
+        assert!(reader.next_child(&theme_elements).unwrap().is_none());
+        let object_defaults = reader.next_child(&root).unwrap().unwrap();
+        assert_eq!(
+            reader.read_node(object_defaults).unwrap().local_name(),
+            "objectDefaults"
+        );
+        assert!(reader.next_child(&root).unwrap().is_none());
+
+        let mut reader = XmlReader::from_str("<a:theme><a:themeElements/>");
+        let root = reader.read_root_start().unwrap();
+        assert!(reader.next_child(&root).unwrap().is_some());
+        assert!(reader.next_child(&root).is_err());
+    }
+
+    #[test]
+    fn test_source_locations() {
+        let xml = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+            "\n",
+            "  <a:themeElements><a:fmtScheme>\n",
+            "    <a:fillStyleLst/>\n",
+            "    <a:fillStyleLst><a:noFill/></a:fillStyleLst>\n",
+            "  </a:fmtScheme></a:themeElements>\n",
+            "</a:theme>",
+        );
+
+        let root = XmlNode::from_str(xml).unwrap();
+        let location = root.location.as_ref().unwrap();
+        assert_eq!((location.line, location.column, location.offset), (2, 1, 56));
+        assert_eq!(location.path.as_ref().unwrap().to_string(), "a:theme");
+
+        let fmt_scheme = &root.child_nodes[0].child_nodes[0];
+        let location = fmt_scheme.location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (3, 20));
+        assert_eq!(location.offset, xml.find("<a:fmtScheme>").unwrap());
+
+        let no_fill = &fmt_scheme.child_nodes[1].child_nodes[0];
+        let location = no_fill.location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (5, 21));
+        assert_eq!(
+            location.to_string(),
+            "a:theme/a:themeElements/a:fmtScheme/a:fillStyleLst[2]/a:noFill (line 5, column 21, byte offset 205)"
+        );
+
+        let err = XmlNode::from_str("<a:theme>\n  <a:themeElements>\n</a:theme>").unwrap_err();
+        let location = err.location.unwrap();
+        assert_eq!(location.line, 3);
+        assert_eq!(location.path.unwrap().to_string(), "a:theme/a:themeElements");
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let nested = format!("{}{}", "<a>".repeat(100_000), "</a>".repeat(100_000));
+        let err = XmlNode::from_str(&nested).unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::LimitExceeded(ParseLimit::Depth(256)));
+        assert_eq!(err.location.unwrap().path.unwrap().depth(), 257);
+
+        let nested = format!("{}{}", "<a>".repeat(300), "</a>".repeat(300));
+        let limits = ParseLimits::default().with_max_depth(None);
+        let node = XmlNode::from_str_with_limits(&nested, &limits).unwrap();
+        assert_eq!(node.child_nodes.len(), 1);
+
+        // The depth is capped even without a limit
+        let nested = format!("{}{}", "<a>".repeat(MAX_DEPTH + 1), "</a>".repeat(MAX_DEPTH + 1));
+        let err = XmlNode::from_str_with_limits(&nested, &ParseLimits::unlimited()).unwrap_err();
+        assert_eq!(
+            err.kind,
+            InvalidXmlErrorKind::LimitExceeded(ParseLimit::Depth(MAX_DEPTH))
+        );
+        let limits = ParseLimits::default().with_max_depth(Some(MAX_DEPTH * 2));
+        assert!(XmlNode::from_str_with_limits(&nested, &limits).is_err());
+
+        let xml = r#"<root><a val="0123456789"/><b/><c/></root>"#;
+        let limits = ParseLimits::default().with_max_attribute_length(Some(9));
+        let err = XmlNode::from_str_with_limits(xml, &limits).unwrap_err();
+        assert_eq!(
+            err.kind,
+            InvalidXmlErrorKind::LimitExceeded(ParseLimit::AttributeLength(9))
+        );
+
+        let limits = ParseLimits::default().with_max_node_count(Some(3));
+        let err = XmlNode::from_str_with_limits(xml, &limits).unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::LimitExceeded(ParseLimit::NodeCount(3)));
+        assert_eq!(err.location.unwrap().path.unwrap().to_string(), "root/c");
+
+        let limits = ParseLimits::default().with_max_part_size(Some(16));
+        let err = XmlNode::from_reader_with_limits(xml.as_bytes(), &limits).unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::LimitExceeded(ParseLimit::PartSize(16)));
+        let limits = ParseLimits::default().with_max_part_size(Some(xml.len() as u64));
+        assert!(XmlNode::from_reader_with_limits(xml.as_bytes(), &limits).is_ok());
+    }
+
+    #[test]
+    fn test_strict_errors() {
+        let err = XmlNode::from_str("<a:t>Fish &amp chips</a:t>").unwrap_err();
+        match err.kind {
+            InvalidXmlErrorKind::InvalidEscape(_) => (),
+            kind => panic!("unexpected error kind {:?}", kind),
+        }
+
+        let err = XmlNode::from_str(r#"<a:t val="1" val="2"/>"#).unwrap_err();
+        match err.kind {
+            InvalidXmlErrorKind::Syntax(_) => (),
+            kind => panic!("unexpected error kind {:?}", kind),
+        }
+
+        let err = XmlNode::from_reader(&b"<a:t>\xC3\x28</a:t>"[..]).unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::InvalidUtf8);
+
+        let err = XmlNode::from_str("<a:theme><a:themeElements>").unwrap_err();
+        assert_eq!(err.kind, InvalidXmlErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,214 @@
+//! Content of parsed elements that isn't described by the typed models.
+//!
+//! The typed models only hold what the schema of the element describes. Everything else an element contains, most
+//! notably vendor extensions in `a:extLst` and attributes of newer schema versions, is recorded to an `UnknownContent`
+//! while parsing and stored next to the model, so a document can be parsed, modified and written back without losing any
+//! of it.
+
+use super::XmlNode;
+use crate::{
+    diagnostics,
+    error::{Error, Result},
+    xsdtypes::{ParseContext, XsdChoice},
+};
+use std::collections::HashMap;
+
+/// An unrecognised child element along with its position in the parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownElement {
+    /// Index of the element among all the child elements of its parent in the parsed document
+    pub position: usize,
+    pub node: XmlNode,
+}
+
+/// Attributes and child elements of an element that weren't recognised by the parser of its typed model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownContent {
+    /// Unrecognised attributes keyed by their qualified name, including the namespace declarations of the element
+    pub attributes: HashMap<String, String>,
+    /// Resolved namespace URIs of the prefixed attributes in `attributes`
+    pub attribute_namespaces: HashMap<String, String>,
+    /// Unrecognised child elements in document order
+    pub elements: Vec<UnknownElement>,
+}
+
+impl UnknownContent {
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.elements.is_empty()
+    }
+
+    /// Records the attribute `attr` of `xml_node`, along with its namespace.
+    pub fn push_attribute(&mut self, xml_node: &XmlNode, attr: &str) {
+        if let Some(value) = xml_node.attributes.get(attr) {
+            self.attributes.insert(String::from(attr), value.clone());
+            if let Some(namespace) = xml_node.attribute_namespace(attr) {
+                self.attribute_namespaces
+                    .insert(String::from(attr), String::from(namespace));
+            }
+        }
+    }
+
+    /// Records `node`, which is the child element of the parsed element at `position`.
+    pub fn push_element(&mut self, position: usize, node: &XmlNode) {
+        self.elements.push(UnknownElement {
+            position,
+            node: node.clone(),
+        });
+    }
+
+    /// Records every attribute of `xml_node`. Used for elements that have no attributes in the schema.
+    pub fn push_all_attributes(&mut self, xml_node: &XmlNode) {
+        for attr in xml_node.attributes.keys() {
+            self.push_attribute(xml_node, attr);
+        }
+    }
+
+    /// Records every child element of `xml_node`. Used for elements that have no child elements in the schema.
+    pub fn push_all_elements(&mut self, xml_node: &XmlNode) {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            self.push_element(position, child_node);
+        }
+    }
+
+    /// Like `diagnostics::recover_attribute`, but an attribute skipped in lenient mode is recorded, so it's written
+    /// back unchanged.
+    pub(crate) fn recover_attribute<T>(
+        &mut self,
+        result: Result<T>,
+        xml_node: &XmlNode,
+        attr: &str,
+        context: &mut ParseContext,
+    ) -> Result<Option<T>> {
+        let value = diagnostics::recover_attribute(result, context)?;
+        if value.is_none() {
+            self.push_attribute(xml_node, attr);
+        }
+
+        Ok(value)
+    }
+
+    /// Like `diagnostics::recover_element`, but an element skipped in lenient mode is recorded, so it's written back
+    /// unchanged.
+    pub(crate) fn recover_element<T>(
+        &mut self,
+        result: Result<T>,
+        position: usize,
+        node: &XmlNode,
+        context: &mut ParseContext,
+    ) -> Result<Option<T>> {
+        let value = diagnostics::recover_element(result, context)?;
+        if value.is_none() {
+            self.push_element(position, node);
+        }
+
+        Ok(value)
+    }
+
+    /// Parses `node`, the child element of the parsed element at `position`, as a member of the choice `T`. Elements
+    /// that aren't members of the choice and members skipped in lenient mode are recorded and None is returned for
+    /// them.
+    pub(crate) fn parse_choice_element<T: XsdChoice>(
+        &mut self,
+        position: usize,
+        node: &XmlNode,
+        context: &mut ParseContext,
+    ) -> Result<Option<T>> {
+        match T::from_xml_element_with_context(node, context) {
+            Err(Error::NotGroupMember(_)) => {
+                self.push_element(position, node);
+                Ok(None)
+            }
+            result => self.recover_element(result, position, node, context),
+        }
+    }
+
+    /// Parses the items of the list element `xml_node`, i.e. its `local_name` child elements in `namespace`. Other
+    /// child elements, items skipped in lenient mode and the attributes of the list are recorded, so the list is
+    /// written back unchanged.
+    pub(crate) fn parse_list_items<T, F>(
+        &mut self,
+        xml_node: &XmlNode,
+        namespace: &str,
+        local_name: &str,
+        context: &mut ParseContext,
+        mut parse_item: F,
+    ) -> Result<Vec<T>>
+    where
+        F: FnMut(&XmlNode, &mut ParseContext) -> Result<T>,
+    {
+        self.push_all_attributes(xml_node);
+
+        let mut items = Vec::new();
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if !child_node.is(namespace, local_name) {
+                self.push_element(position, child_node);
+                continue;
+            }
+
+            let result = parse_item(child_node, context);
+            items.extend(self.recover_element(result, position, child_node, context)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Adds the unknown content to `node`. Elements are inserted at their original position, which is exact as long as
+    /// the known child elements are the same as in the parsed document. Attributes already set on `node` aren't
+    /// overwritten.
+    pub fn apply_to(&self, node: &mut XmlNode) {
+        for (attr, value) in &self.attributes {
+            if !node.attributes.contains_key(attr) {
+                node.attributes.insert(attr.clone(), value.clone());
+                if let Some(namespace) = self.attribute_namespaces.get(attr) {
+                    node.attribute_namespaces.insert(attr.clone(), namespace.clone());
+                }
+            }
+        }
+
+        for element in &self.elements {
+            let position = element.position.min(node.child_nodes.len());
+            node.child_nodes.insert(position, element.node.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::ns;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_push_and_apply() {
+        let original = XmlNode::from_str(
+            r#"<a:spPr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:x="urn:x" bwMode="auto" x:y="1">
+                <a:xfrm/><a:ln/><a:extLst><a:ext uri="{1}"/></a:extLst><a:ln/>
+            </a:spPr>"#,
+        )
+        .unwrap();
+
+        let mut unknown = UnknownContent::default();
+        unknown.push_attribute(&original, "x:y");
+        unknown.push_attribute(&original, "missing");
+        unknown.push_element(2, &original.child_nodes[2]);
+        assert_eq!(unknown.attributes.len(), 1);
+        assert_eq!(
+            unknown.attribute_namespaces.get("x:y").map(String::as_str),
+            Some("urn:x")
+        );
+
+        let mut node = XmlNode::new_with_namespace("a:spPr", ns::DRAWINGML);
+        node.attributes.insert(String::from("x:y"), String::from("2"));
+        node.child_nodes.push(original.child_nodes[0].clone());
+        node.child_nodes.push(original.child_nodes[1].clone());
+        node.child_nodes.push(original.child_nodes[3].clone());
+        unknown.apply_to(&mut node);
+        assert_eq!(node.attributes.get("x:y").map(String::as_str), Some("2"));
+        let names: Vec<&str> = node
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["a:xfrm", "a:ln", "a:extLst", "a:ln"]);
+    }
+}
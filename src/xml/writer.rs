@@ -0,0 +1,480 @@
+//! Serialization of `XmlNode` trees to any `io::Write` implementor.
+
+use super::{
+    ns::{self, NamespaceScope},
+    XmlContentRef, XmlNode,
+};
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
+
+/// The xml declaration written in front of every Office Open XML part.
+pub const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+
+/// Options controlling how an `XmlNode` tree is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Write the xml declaration before the root element.
+    ///
+    /// Defaults to true
+    pub xml_declaration: bool,
+    /// String used to indent child elements. None produces compact output without any whitespace between elements.
+    ///
+    /// Defaults to None
+    pub indent: Option<String>,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            xml_declaration: true,
+            indent: None,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Options producing compact output, which is how Office applications save their parts.
+    pub fn compact() -> Self {
+        Default::default()
+    }
+
+    /// Options producing human readable output indented by two spaces.
+    pub fn pretty() -> Self {
+        Self {
+            indent: Some(String::from("  ")),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_xml_declaration(mut self, xml_declaration: bool) -> Self {
+        self.xml_declaration = xml_declaration;
+        self
+    }
+}
+
+/// Writes `XmlNode` trees to an underlying `io::Write`.
+///
+/// Namespace declarations stored on the nodes are written as they are. If a node (or one of its prefixed attributes)
+/// is bound to a namespace that isn't declared by the node itself or any of its ancestors, the missing declaration is
+/// added, so trees built in code are written as well-formed documents. `write_document` declares the missing prefixes
+/// once on the root element, only a prefix that is bound to different namespaces in different subtrees is declared
+/// where it's used. A prefix that isn't bound to any namespace is resolved with `ns::conventional_namespace`, and
+/// writing fails with an `io::ErrorKind::InvalidInput` error if it's unknown.
+pub struct XmlWriter<W: Write> {
+    writer: W,
+    options: WriteOptions,
+    scope: NamespaceScope,
+    // Declarations hoisted to the root element by `write_document`, added to the next node written
+    root_declarations: Vec<(String, String)>,
+    // Whether `xml:space="preserve"` is in effect for the node being written
+    preserve_space: bool,
+}
+
+impl<W: Write> XmlWriter<W> {
+    pub fn new(writer: W, options: WriteOptions) -> Self {
+        Self {
+            writer,
+            options,
+            scope: NamespaceScope::new(),
+            root_declarations: Vec::new(),
+            preserve_space: false,
+        }
+    }
+
+    /// Writes `root` as a complete document, including the xml declaration if it's enabled. The namespace
+    /// declarations missing from the tree are written on `root`.
+    pub fn write_document(&mut self, root: &XmlNode) -> io::Result<()> {
+        self.root_declarations = self.hoisted_declarations(root)?;
+
+        if self.options.xml_declaration {
+            self.writer.write_all(XML_DECLARATION.as_bytes())?;
+            self.writer.write_all(b"\r\n")?;
+        }
+
+        self.write_node(root, 0)?;
+        if self.options.indent.is_some() {
+            self.writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `node` and its descendants without the xml declaration. `depth` is only used for indentation. Elements
+    /// in the scope of `xml:space="preserve"` are never indented, since the whitespace would become significant.
+    pub fn write_node(&mut self, node: &XmlNode, depth: usize) -> io::Result<()> {
+        let marker = self.scope.enter(&node.attributes);
+        let attributes = match self.collect_attributes(node) {
+            Ok(attributes) => attributes,
+            Err(err) => {
+                self.scope.leave(marker);
+                return Err(err);
+            }
+        };
+
+        self.writer.write_all(b"<")?;
+        self.writer.write_all(node.name.as_bytes())?;
+        for (attr, value) in &attributes {
+            write!(self.writer, " {}=\"{}\"", attr, escape_attribute(value))?;
+        }
+
+        let content = node.ordered_content();
+        if content.is_empty() {
+            self.writer.write_all(b"/>")?;
+            self.scope.leave(marker);
+            return Ok(());
+        }
+
+        self.writer.write_all(b">")?;
+
+        // xml:space is inherited by the descendants until it's overridden
+        let parent_preserve_space = self.preserve_space;
+        self.preserve_space = match node.attributes.get("xml:space").map(String::as_str) {
+            Some("preserve") => true,
+            Some("default") => false,
+            _ => parent_preserve_space,
+        };
+
+        // Indenting the content of an element with text would alter the text, so mixed content is written inline
+        let indent_children = !self.preserve_space
+            && content
+                .iter()
+                .all(|content| matches!(content, XmlContentRef::Element(_)));
+
+        for content in &content {
+            match content {
+                XmlContentRef::Text(text) => self.writer.write_all(escape_text(text).as_bytes())?,
+                XmlContentRef::CData(data) => self.write_cdata(data)?,
+                XmlContentRef::Element(child_node) => {
+                    if indent_children {
+                        self.write_indent(depth + 1)?;
+                    }
+
+                    self.write_node(child_node, depth + 1)?;
+                }
+            }
+        }
+
+        if indent_children {
+            self.write_indent(depth)?;
+        }
+
+        write!(self.writer, "</{}>", node.name)?;
+        self.preserve_space = parent_preserve_space;
+        self.scope.leave(marker);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // A CDATA section can't contain its own terminator, so "]]>" is split across two sections
+    fn write_cdata(&mut self, data: &str) -> io::Result<()> {
+        self.writer.write_all(b"<![CDATA[")?;
+        self.writer
+            .write_all(data.replace("]]>", "]]]]><![CDATA[>").as_bytes())?;
+        self.writer.write_all(b"]]>")
+    }
+
+    fn write_indent(&mut self, depth: usize) -> io::Result<()> {
+        if let Some(indent) = &self.options.indent {
+            self.writer.write_all(b"\n")?;
+            for _ in 0..depth {
+                self.writer.write_all(indent.as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns the prefixed declarations missing anywhere in the tree of `root`, with the first namespace each prefix
+    // is used with. The default namespace is never hoisted, since it would change the namespace of the unprefixed
+    // elements in between.
+    fn hoisted_declarations(&self, root: &XmlNode) -> io::Result<Vec<(String, String)>> {
+        enum Step<'a> {
+            Enter(&'a XmlNode),
+            Leave(usize),
+        }
+
+        let mut scope = self.scope.clone();
+        let mut hoisted: Vec<(String, String)> = Vec::new();
+        let mut steps = vec![Step::Enter(root)];
+        while let Some(step) = steps.pop() {
+            let node = match step {
+                Step::Enter(node) => node,
+                Step::Leave(marker) => {
+                    scope.leave(marker);
+                    continue;
+                }
+            };
+
+            let marker = scope.enter(&node.attributes);
+            for (prefix, uri) in missing_declarations(node, &scope)? {
+                if !prefix.is_empty() && !hoisted.iter().any(|(hoisted_prefix, _)| *hoisted_prefix == prefix) {
+                    hoisted.push((prefix, uri));
+                }
+            }
+
+            steps.push(Step::Leave(marker));
+            steps.extend(node.child_nodes.iter().rev().map(Step::Enter));
+        }
+
+        Ok(hoisted)
+    }
+
+    // Returns the attributes to write in a stable order: namespace declarations (including the missing ones) first,
+    // then the rest of the attributes sorted by name. The declarations of the node have to be in scope already, the
+    // missing ones are added to the scope.
+    fn collect_attributes(&mut self, node: &XmlNode) -> io::Result<Vec<(String, String)>> {
+        let mut declarations: Vec<(String, String)> = node
+            .namespace_declarations()
+            .map(|(prefix, uri)| (String::from(prefix), String::from(uri)))
+            .collect();
+
+        let root_declarations = ::std::mem::take(&mut self.root_declarations);
+        self.scope.enter(declaration_attributes(&root_declarations));
+        declarations.extend(root_declarations);
+
+        let missing = missing_declarations(node, &self.scope)?;
+        self.scope.enter(declaration_attributes(&missing));
+        declarations.extend(missing);
+
+        declarations.sort();
+        let mut attributes = declaration_attributes(&declarations);
+
+        let mut other_attributes: Vec<(String, String)> = node
+            .attributes
+            .iter()
+            .filter(|(attr, _)| *attr != "xmlns" && !attr.starts_with("xmlns:"))
+            .map(|(attr, value)| (attr.clone(), value.clone()))
+            .collect();
+
+        other_attributes.sort();
+        attributes.extend(other_attributes);
+        Ok(attributes)
+    }
+}
+
+// Returns the (prefix, uri) declarations `node` needs that aren't in `scope`. Fails if a prefix is neither bound by
+// the node, nor declared in scope, nor has a conventional namespace.
+fn missing_declarations(node: &XmlNode, scope: &NamespaceScope) -> io::Result<Vec<(String, String)>> {
+    let mut missing: Vec<(String, String)> = Vec::new();
+    let mut require = |prefix: &str, uri: Option<&str>| -> io::Result<()> {
+        let uri = match (uri, scope.resolve(prefix)) {
+            (Some(uri), Some(declared)) if uri == declared => return Ok(()),
+            (Some(uri), _) => uri,
+            (None, Some(_)) => return Ok(()),
+            (None, None) => ns::conventional_namespace(prefix).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("prefix '{}' of '{}' is not bound to a namespace", prefix, node.name),
+                )
+            })?,
+        };
+
+        if !missing.iter().any(|(missing_prefix, _)| missing_prefix == prefix) {
+            missing.push((String::from(prefix), String::from(uri)));
+        }
+
+        Ok(())
+    };
+
+    match node.prefix() {
+        Some(prefix) => require(prefix, node.namespace())?,
+        None => {
+            if let Some(uri) = node.namespace() {
+                require("", Some(uri))?;
+            }
+        }
+    }
+
+    for attr in node.attributes.keys() {
+        match attr.find(':') {
+            Some(idx) if &attr[..idx] != "xmlns" => require(&attr[..idx], node.attribute_namespace(attr))?,
+            _ => (),
+        }
+    }
+
+    Ok(missing)
+}
+
+fn declaration_attributes(declarations: &[(String, String)]) -> Vec<(String, String)> {
+    declarations
+        .iter()
+        .map(|(prefix, uri)| match prefix.as_str() {
+            "" => (String::from("xmlns"), uri.clone()),
+            _ => (format!("xmlns:{}", prefix), uri.clone()),
+        })
+        .collect()
+}
+
+/// Escapes the characters that can't appear literally in character data.
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '\r' => Some("&#xD;"),
+        _ => None,
+    })
+}
+
+/// Escapes the characters that can't appear literally in a double quoted attribute value. Whitespace characters other
+/// than space are written as character references so they survive attribute value normalization.
+pub fn escape_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\t' => Some("&#x9;"),
+        '\n' => Some("&#xA;"),
+        '\r' => Some("&#xD;"),
+        _ => None,
+    })
+}
+
+fn escape<F>(value: &str, replacement: F) -> Cow<'_, str>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    if !value.chars().any(|c| replacement(c).is_some()) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 8);
+    for c in value.chars() {
+        match replacement(c) {
+            Some(entity) => escaped.push_str(entity),
+            None => escaped.push(c),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::ns;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_write_compact_round_trip() {
+        let xml = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office &amp; &quot;Co&quot;"><a:themeElements><a:t>1 &lt; 2</a:t><a:ext/></a:themeElements></a:theme>"#;
+        let node = XmlNode::from_str(xml).unwrap();
+
+        let written = node.to_xml_string(&WriteOptions::compact()).unwrap();
+        assert_eq!(written, format!("{}\r\n{}", XML_DECLARATION, xml));
+        assert_eq!(XmlNode::from_str(&written).unwrap(), node);
+    }
+
+    #[test]
+    fn test_write_pretty() {
+        let mut root = XmlNode::new_with_namespace("p:sld", ns::PRESENTATIONML);
+        let mut child = XmlNode::new_with_namespace("a:off", ns::DRAWINGML);
+        child.attributes.insert(String::from("y"), String::from("2"));
+        child.attributes.insert(String::from("x"), String::from("1"));
+        let mut text = XmlNode::new_with_namespace("a:t", ns::DRAWINGML);
+        text.text = Some(String::from("a\tb"));
+        root.child_nodes.push(child);
+        root.child_nodes.push(text);
+
+        let written = root
+            .to_xml_string(&WriteOptions::pretty().with_xml_declaration(false))
+            .unwrap();
+        let expected = concat!(
+            r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" "#,
+            r#"xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">"#,
+            "\n",
+            r#"  <a:off x="1" y="2"/>"#,
+            "\n",
+            "  <a:t>a\tb</a:t>\n",
+            "</p:sld>\n",
+        );
+        assert_eq!(written, expected);
+    }
+
+    #[test]
+    fn test_write_pretty_preserve_space() {
+        let xml = concat!(
+            r#"<w:body xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+            r#"<w:p xml:space="preserve"><w:r><w:t> a </w:t><w:tab/></w:r>"#,
+            r#"<w:r xml:space="default"><w:t>b</w:t><w:tab/></w:r></w:p><w:p/></w:body>"#,
+        );
+        let node = XmlNode::from_str(xml).unwrap();
+
+        let written = node
+            .to_xml_string(&WriteOptions::pretty().with_xml_declaration(false))
+            .unwrap();
+        let expected = concat!(
+            r#"<w:body xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+            "\n",
+            r#"  <w:p xml:space="preserve"><w:r><w:t> a </w:t><w:tab/></w:r><w:r xml:space="default">"#,
+            "\n",
+            "      <w:t>b</w:t>\n",
+            "      <w:tab/>\n",
+            "    </w:r></w:p>\n",
+            "  <w:p/>\n",
+            "</w:body>\n",
+        );
+        assert_eq!(written, expected);
+        assert_eq!(XmlNode::from_str(&written).unwrap(), node);
+    }
+
+    #[test]
+    fn test_write_namespace_declarations() {
+        // Missing declarations are hoisted to the root, even if it's in a foreign namespace
+        let mut root = XmlNode::new_with_namespace("x:root", "urn:x");
+        let mut shape = XmlNode::new_with_namespace("p:sp", ns::PRESENTATIONML);
+        shape.child_nodes.push(XmlNode::new("a:off"));
+        let mut fill = XmlNode::new_with_namespace("a:solidFill", ns::DRAWINGML);
+        fill.attributes.insert(String::from("r:id"), String::from("rId1"));
+        root.child_nodes.push(shape);
+        root.child_nodes.push(fill);
+
+        let written = root
+            .to_xml_string(&WriteOptions::compact().with_xml_declaration(false))
+            .unwrap();
+        assert_eq!(
+            written,
+            format!(
+                r#"<x:root xmlns:a="{}" xmlns:p="{}" xmlns:r="{}" xmlns:x="urn:x"><p:sp><a:off/></p:sp><a:solidFill r:id="rId1"/></x:root>"#,
+                ns::DRAWINGML,
+                ns::PRESENTATIONML,
+                ns::RELATIONSHIPS
+            )
+        );
+        let reparsed = XmlNode::from_str(&written).unwrap();
+        assert!(reparsed.child_nodes[0].child_nodes[0].is(ns::DRAWINGML, "off"));
+
+        // A prefix bound to another namespace in a subtree is declared where it's used
+        let mut root = XmlNode::new_with_namespace("a:root", ns::DRAWINGML);
+        root.child_nodes
+            .push(XmlNode::new_with_namespace("a:other", "urn:other"));
+        let written = root
+            .to_xml_string(&WriteOptions::compact().with_xml_declaration(false))
+            .unwrap();
+        assert_eq!(
+            written,
+            format!(
+                r#"<a:root xmlns:a="{}"><a:other xmlns:a="urn:other"/></a:root>"#,
+                ns::DRAWINGML
+            )
+        );
+
+        let mut root = XmlNode::new_with_namespace("a:root", ns::DRAWINGML);
+        root.child_nodes.push(XmlNode::new("unknown:child"));
+        let err = root.to_xml_string(&WriteOptions::compact()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape_text("plain"), Cow::Borrowed("plain"));
+        assert_eq!(escape_text("<a & b>\"'"), "&lt;a &amp; b&gt;\"'");
+        assert_eq!(escape_attribute("\"x\"\n"), "&quot;x&quot;&#xA;");
+    }
+}
@@ -1,27 +1,191 @@
-use super::{error::NotGroupMemberError, xml::XmlNode};
+use super::{
+    diagnostics::{recover_element, Diagnostic, Parsed},
+    drawingml::extensions::ExtensionRegistry,
+    error::{Error, Result},
+    xml::{
+        reader::{StartElement, XmlReader},
+        writer::WriteOptions,
+        XmlNode,
+    },
+};
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+/// Settings of a parse, passed down to every parser called while parsing a document.
+///
+/// The default context parses strictly: the first invalid value or element fails the whole parse, and values of
+/// `ExtensibleEnum`s unknown to this crate are rejected. A lenient context recovers from invalid content instead and
+/// collects the problems found, see the `diagnostics` module for details. Extensions are parsed with the default
+/// `ExtensionRegistry` unless another one is set.
+#[derive(Debug, Default)]
+pub struct ParseContext {
+    // Some in lenient mode
+    pub(crate) diagnostics: Option<Vec<Diagnostic>>,
+    accept_unknown_values: bool,
+    extension_registry: ExtensionRegistry,
+}
+
+impl ParseContext {
+    /// Creates a context for a strict parse
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a context for a lenient parse
+    pub fn lenient() -> Self {
+        Self {
+            diagnostics: Some(Vec::new()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets whether values of `ExtensibleEnum`s unknown to this crate are parsed as the `Unknown` variant instead of
+    /// being rejected.
+    pub fn with_unknown_values_accepted(mut self, accept_unknown_values: bool) -> Self {
+        self.accept_unknown_values = accept_unknown_values;
+        self
+    }
+
+    /// Sets the registry used to parse the content of `a:ext` elements
+    pub fn with_extension_registry(mut self, extension_registry: ExtensionRegistry) -> Self {
+        self.extension_registry = extension_registry;
+        self
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.diagnostics.is_some()
+    }
+
+    pub fn unknown_values_accepted(&self) -> bool {
+        self.accept_unknown_values
+    }
+
+    pub fn extension_registry(&self) -> &ExtensionRegistry {
+        &self.extension_registry
+    }
+
+    /// Problems recovered from so far in lenient mode, in document order
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics.as_deref().unwrap_or_default()
+    }
+
+    /// Takes the problems recovered from so far, so the context can be reused for another document
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.diagnostics.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+}
 
 pub trait XsdType
 where
     Self: Sized,
 {
-    fn from_xml_element(xml_node: &XmlNode) -> Result<Self>;
+    /// Parses `xml_node` with the settings of `context`
+    fn from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Result<Self>;
+
+    /// Parses `xml_node` strictly
+    fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        Self::from_xml_element_with_context(xml_node, &mut ParseContext::new())
+    }
+
+    /// Parses `xml_node` in lenient mode. See the `diagnostics` module for details.
+    fn from_xml_element_lenient(xml_node: &XmlNode) -> Result<Parsed<Self>> {
+        let mut context = ParseContext::lenient();
+        let value = Self::from_xml_element_with_context(xml_node, &mut context)?;
+        Ok(Parsed {
+            value,
+            diagnostics: context.take_diagnostics(),
+        })
+    }
+
+    /// Parses the element `start` was returned for directly from an `XmlReader`, so only the subtree of the element
+    /// has to be kept in memory instead of the whole document. Must be called right after `start` is returned by
+    /// `XmlReader::next_event`.
+    fn from_xml_reader<B: BufRead>(reader: &mut XmlReader<B>, start: StartElement) -> Result<Self> {
+        Self::from_xml_reader_with_context(reader, start, &mut ParseContext::new())
+    }
+
+    /// Same as `from_xml_reader`, but parses with the settings of `context`.
+    ///
+    /// The default implementation reads the subtree to an `XmlNode` and calls `from_xml_element_with_context`. Types
+    /// of large parts (e.g. `OfficeStyleSheet`) override it to read their children one at a time, so at most the
+    /// subtree of a single child is built.
+    fn from_xml_reader_with_context<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        start: StartElement,
+        context: &mut ParseContext,
+    ) -> Result<Self> {
+        let xml_node = reader.read_node(start)?;
+        Self::from_xml_element_with_context(&xml_node, context)
+    }
 }
 
 pub trait XsdChoice: XsdType {
     fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool;
 
     /// Tries to parse an XmlNode as a choice member.
-    /// None is returned if the XmlNode is not a member of the choice element (implementors should return a boxed
-    /// NotGroupMemberError), otherwise Some is returned with the Result of from_xml_element.
+    /// None is returned if the XmlNode is not a member of the choice element (implementors should return
+    /// `Error::NotGroupMember`), otherwise Some is returned with the Result of from_xml_element.
     fn try_from_xml_element(xml_node: &XmlNode) -> Option<Result<Self>> {
-        match Self::from_xml_element(xml_node) {
-            Ok(val) => Some(Ok(val)),
-            Err(err) => match err.downcast::<NotGroupMemberError>() {
-                Ok(_) => None,
-                Err(err) => Some(Err(err)),
-            },
+        Self::try_from_xml_element_with_context(xml_node, &mut ParseContext::new())
+    }
+
+    /// Same as `try_from_xml_element`, but parses with the settings of `context`. In lenient mode a member that fails
+    /// to parse is reported and skipped, so None is returned for it too.
+    fn try_from_xml_element_with_context(xml_node: &XmlNode, context: &mut ParseContext) -> Option<Result<Self>> {
+        match Self::from_xml_element_with_context(xml_node, context) {
+            Err(Error::NotGroupMember(_)) => None,
+            result => recover_element(result, context).transpose(),
         }
     }
 }
+
+/// An enumeration of the schema that newer versions of Office extend with new values, e.g. `ShapeType`.
+///
+/// `FromStr` rejects values unknown to this crate. With a `ParseContext` that accepts unknown values they're parsed as
+/// the `Unknown` variant instead, which holds the original text and is written back unchanged, so documents created by
+/// a newer version of Office degrade gracefully instead of failing to parse.
+///
+/// Since the `Unknown` variant holds a `String`, these enums implement `Clone` but not `Copy`.
+pub trait ExtensibleEnum: FromStr {
+    /// Creates the `Unknown` variant holding `value`
+    fn unknown(value: String) -> Self;
+
+    /// Returns the original text if this is the `Unknown` variant
+    fn unknown_value(&self) -> Option<&str>;
+
+    fn is_unknown(&self) -> bool {
+        self.unknown_value().is_some()
+    }
+
+    /// Parses `value`, falling back to the `Unknown` variant if `context` accepts unknown values.
+    fn parse_extensible(value: &str, context: &ParseContext) -> ::std::result::Result<Self, Self::Err> {
+        match value.parse() {
+            Err(_) if context.unknown_values_accepted() => Ok(Self::unknown(String::from(value))),
+            result => result,
+        }
+    }
+}
+
+/// Counterpart of `XsdType` that converts a value back to an `XmlNode`, so a parsed document can be modified and
+/// written back.
+///
+/// The name of the element is provided by the caller, since the same complex type can appear under several element
+/// names (e.g. `a:off` and `a:chOff` are both `Point2D`s).
+pub trait ToXmlElement {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode;
+
+    fn write_xml<W: Write>(&self, node_name: &str, writer: W, options: &WriteOptions) -> io::Result<()> {
+        self.to_xml_element(node_name).write_to(writer, options)
+    }
+}
+
+/// Counterpart of `XsdChoice`. The name of the element is determined by the choice member the value holds.
+pub trait ToXmlChoice {
+    fn to_xml_element(&self) -> XmlNode;
+
+    fn write_xml<W: Write>(&self, writer: W, options: &WriteOptions) -> io::Result<()> {
+        self.to_xml_element().write_to(writer, options)
+    }
+}